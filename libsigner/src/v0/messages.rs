@@ -916,6 +916,8 @@ impl From<&RejectReason> for RejectReasonPrefix {
             RejectReason::IrrecoverablePubkeyHash => RejectReasonPrefix::IrrecoverablePubkeyHash,
             RejectReason::NoSignerConsensus => RejectReasonPrefix::NoSignerConsensus,
             RejectReason::ConsensusHashMismatch { .. } => RejectReasonPrefix::ConsensusHashMismatch,
+            RejectReason::OversizedBlock => RejectReasonPrefix::OversizedBlock,
+            RejectReason::NonCanonicalTenure => RejectReasonPrefix::NonCanonicalTenure,
             RejectReason::Unknown(_) => RejectReasonPrefix::Unknown,
             RejectReason::NotRejected => RejectReasonPrefix::NotRejected,
         }
@@ -1001,6 +1003,10 @@ pub enum RejectReason {
         /// The block proposal's corresponding miner's tenure id
         actual: ConsensusHash,
     },
+    /// The block exceeds the signer's configured transaction count or size limit
+    OversizedBlock,
+    /// The block's tenure, or the tenure it builds on, is not on the canonical sortition fork
+    NonCanonicalTenure,
     /// The block was approved, no rejection details needed
     NotRejected,
     /// Handle unknown codes gracefully
@@ -1048,6 +1054,10 @@ pub enum RejectReasonPrefix {
     NoSignerConsensus = 15,
     /// The block consensus hash does not match the active miner's tenure id
     ConsensusHashMismatch = 16,
+    /// The block exceeds the signer's configured transaction count or size limit
+    OversizedBlock = 17,
+    /// The block's tenure, or the tenure it builds on, is not on the canonical sortition fork
+    NonCanonicalTenure = 18,
     /// Unknown reject code, for forward compatibility
     Unknown = 254,
     /// The block was approved, no rejection details needed
@@ -1075,6 +1085,8 @@ impl RejectReasonPrefix {
             Self::IrrecoverablePubkeyHash => 14,
             Self::NoSignerConsensus => 15,
             Self::ConsensusHashMismatch => 16,
+            Self::OversizedBlock => 17,
+            Self::NonCanonicalTenure => 18,
             Self::Unknown => 254,
             Self::NotRejected => 255,
         }
@@ -1101,6 +1113,8 @@ impl From<u8> for RejectReasonPrefix {
             14 => Self::IrrecoverablePubkeyHash,
             15 => Self::NoSignerConsensus,
             16 => Self::ConsensusHashMismatch,
+            17 => Self::OversizedBlock,
+            18 => Self::NonCanonicalTenure,
             255 => Self::NotRejected,
             // For forward compatibility, all other values are unknown
             _ => Self::Unknown,
@@ -1697,6 +1711,8 @@ impl StacksMessageCodec for RejectReason {
             | RejectReason::InvalidTenureExtend
             | RejectReason::IrrecoverablePubkeyHash
             | RejectReason::NoSignerConsensus
+            | RejectReason::OversizedBlock
+            | RejectReason::NonCanonicalTenure
             | RejectReason::Unknown(_)
             | RejectReason::NotRejected => {
                 // No additional data to serialize / deserialize
@@ -1741,6 +1757,8 @@ impl StacksMessageCodec for RejectReason {
                 let actual = read_next::<ConsensusHash, _>(fd)?;
                 RejectReason::ConsensusHashMismatch { expected, actual }
             }
+            RejectReasonPrefix::OversizedBlock => RejectReason::OversizedBlock,
+            RejectReasonPrefix::NonCanonicalTenure => RejectReason::NonCanonicalTenure,
             RejectReasonPrefix::Unknown => RejectReason::Unknown(type_prefix_byte),
             RejectReasonPrefix::NotRejected => RejectReason::NotRejected,
         };
@@ -1856,6 +1874,18 @@ impl std::fmt::Display for RejectReason {
                     "The block's consensus hash ({expected}) does not match the active miner's tenure id ({actual})",
                 )
             }
+            RejectReason::OversizedBlock => {
+                write!(
+                    f,
+                    "The block exceeds the signer's transaction count or size limit."
+                )
+            }
+            RejectReason::NonCanonicalTenure => {
+                write!(
+                    f,
+                    "The block's tenure or its parent tenure is not on the canonical fork."
+                )
+            }
             RejectReason::Unknown(code) => {
                 write!(f, "Unknown reject code: {code}")
             }
@@ -236,6 +236,11 @@ pub enum PeerHost {
 impl fmt::Display for PeerHost {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
+            // an IPv6 literal which isn't a valid `IpAddr` (e.g. it has a zone ID) must still be
+            // bracketed, or its port can't be told apart from its last segment
+            PeerHost::DNS(ref s, ref p) if s.contains(':') && !s.starts_with('[') => {
+                write!(f, "[{s}]:{p}")
+            }
             PeerHost::DNS(ref s, ref p) => write!(f, "{s}:{p}"),
             PeerHost::IP(ref a, ref p) => write!(f, "{}", a.to_socketaddr(*p)),
         }
@@ -281,9 +286,13 @@ impl FromStr for PeerHost {
                 socketaddr.port(),
             )),
             Err(_) => {
-                // maybe missing :port
+                // maybe missing :port, or an unbracketed IPv6 address without a port
                 let hostport = format!("{header}:80");
-                match hostport.parse::<SocketAddr>() {
+                let ipv6_hostport = format!("[{header}]:80");
+                match hostport
+                    .parse::<SocketAddr>()
+                    .or_else(|_| ipv6_hostport.parse::<SocketAddr>())
+                {
                     Ok(socketaddr) => Ok(PeerHost::IP(
                         PeerAddress::from_socketaddr(&socketaddr),
                         socketaddr.port(),
@@ -58,7 +58,7 @@ use stacks::config::{Config as NeonConfig, EventKeyType, EventObserverConfig};
 use stacks::core::mempool::MemPoolWalkStrategy;
 use stacks::core::test_util::{
     insert_tx_in_mempool, make_big_read_count_contract, make_contract_call, make_contract_publish,
-    make_stacks_transfer_serialized, to_addr,
+    make_stacks_transfer_serialized, make_stacks_transfer_tx, to_addr,
 };
 use stacks::core::{StacksEpochId, CHAIN_ID_TESTNET, HELIUM_BLOCK_LIMIT_20};
 use stacks::libstackerdb::StackerDBChunkData;
@@ -11838,6 +11838,189 @@ fn block_proposal_max_age_rejections() {
     signer_test.shutdown();
 }
 
+#[test]
+#[ignore]
+/// Test the max_block_txs signer configuration option. Signers should reject a block proposal with
+/// too many transactions without submitting it to their stacks-node for validation.
+///
+/// Test Setup:
+/// The test spins up five stacks signers, one miner Nakamoto node, and a corresponding bitcoind.
+///
+/// Test Execution:
+/// The stacks node is advanced to epoch 3.0 reward set calculation to ensure the signer set is determined.
+/// A block proposal with more transactions than the signers' max_block_txs is forcibly written to the
+/// miner's slot to simulate the miner proposing a block.
+///
+/// Test Assertion:
+/// - Each signer rejects the block proposal with an OversizedBlock reason.
+/// - The stacks-node never reports a validation response for the block proposal.
+fn block_proposal_oversized_rejections() {
+    if env::var("BITCOIND_TEST") != Ok("1".into()) {
+        return;
+    }
+
+    tracing_subscriber::registry()
+        .with(fmt::layer())
+        .with(EnvFilter::from_default_env())
+        .init();
+
+    info!("------------------------- Test Setup -------------------------");
+    let num_signers = 5;
+    let max_block_txs = 5;
+    let signer_test: SignerTest<SpawnedSigner> = SignerTest::new_with_config_modifications(
+        num_signers,
+        vec![],
+        |config| {
+            config.max_block_txs = Some(max_block_txs);
+        },
+        |_| {},
+        None,
+        None,
+    );
+    let all_signers = signer_test.signer_test_pks();
+    signer_test.boot_to_epoch_3();
+    let short_timeout = Duration::from_secs(30);
+
+    info!("------------------------- Send Block Proposal To Signers -------------------------");
+    let sender_sk = Secp256k1PrivateKey::random();
+    let recipient = PrincipalData::from(StacksAddress::burn_address(false));
+    let txs = (0..=max_block_txs)
+        .map(|nonce| {
+            make_stacks_transfer_tx(
+                &sender_sk,
+                nonce,
+                180,
+                signer_test.running_nodes.conf.burnchain.chain_id,
+                &recipient,
+                100,
+            )
+        })
+        .collect();
+    let mut block = NakamotoBlock {
+        header: NakamotoBlockHeader::empty(),
+        txs,
+    };
+    block.header.timestamp = get_epoch_time_secs();
+    block
+        .header
+        .sign_miner(signer_test.get_miner_key())
+        .unwrap();
+    let block_signer_signature_hash = block.header.signer_signature_hash();
+    signer_test.propose_block(block, short_timeout);
+
+    info!("------------------------- Test Block Proposal Rejected -------------------------");
+    let rejections =
+        wait_for_block_rejections_from_signers(30, &block_signer_signature_hash, &all_signers)
+            .expect("Timed out waiting for block rejections");
+    for rejection in rejections {
+        assert_eq!(
+            rejection.response_data.reject_reason,
+            RejectReason::OversizedBlock
+        );
+    }
+
+    // The signers never asked the node to validate the block
+    assert!(!test_observer::get_proposal_responses()
+        .iter()
+        .any(|response| response.signer_signature_hash() == &block_signer_signature_hash));
+
+    info!("------------------------- Test Shutdown-------------------------");
+    signer_test.shutdown();
+}
+
+#[test]
+#[ignore]
+/// Test that signers reject a block proposal whose tenure was orphaned by a Bitcoin fork, without
+/// submitting it to their stacks-node for validation.
+///
+/// Test Setup:
+/// The test spins up five stacks signers, one miner Nakamoto node, and a corresponding bitcoind.
+///
+/// Test Execution:
+/// The stacks node is advanced to epoch 3.0 and a Nakamoto block is mined.  The miner is then
+/// stalled, and the burn block which started the current tenure is forked away.  A block proposal
+/// which builds on the orphaned tenure's last block, in that tenure, is forcibly written to the
+/// miner's slot.
+///
+/// Test Assertion:
+/// - Each signer rejects the block proposal with a NonCanonicalTenure reason.
+/// - The stacks-node never reports a validation response for the block proposal.
+fn block_proposal_non_canonical_tenure_rejections() {
+    if env::var("BITCOIND_TEST") != Ok("1".into()) {
+        return;
+    }
+
+    tracing_subscriber::registry()
+        .with(fmt::layer())
+        .with(EnvFilter::from_default_env())
+        .init();
+
+    info!("------------------------- Test Setup -------------------------");
+    let num_signers = 5;
+    let signer_test: SignerTest<SpawnedSigner> = SignerTest::new(num_signers, vec![]);
+    let all_signers = signer_test.signer_test_pks();
+    let conf = &signer_test.running_nodes.conf;
+    let btc_controller = &signer_test.running_nodes.btc_regtest_controller;
+    signer_test.boot_to_epoch_3();
+    let short_timeout = Duration::from_secs(30);
+
+    signer_test.mine_nakamoto_block(short_timeout, true);
+    signer_test.check_signer_states_normal();
+
+    let pre_fork_info = get_chain_info(conf);
+    let orphaned_tenure = pre_fork_info.stacks_tip_consensus_hash.clone();
+    let orphaned_tip = StacksBlockId::new(&orphaned_tenure, &pre_fork_info.stacks_tip);
+
+    info!("------------------------- Triggering Bitcoin Fork -------------------------");
+    fault_injection_stall_miner();
+    let burn_header_hash_to_fork = btc_controller.get_block_hash(pre_fork_info.burn_block_height);
+    btc_controller.invalidate_block(&burn_header_hash_to_fork);
+    btc_controller.build_next_block(3);
+    wait_for(30, || {
+        let info = get_chain_info(conf);
+        Ok(info.burn_block_height > pre_fork_info.burn_block_height
+            && info.pox_consensus != pre_fork_info.pox_consensus)
+    })
+    .expect("Timed out waiting for the node to process the Bitcoin fork");
+
+    info!("------------------------- Send Block Proposal To Signers -------------------------");
+    let mut block = NakamotoBlock {
+        header: NakamotoBlockHeader::empty(),
+        txs: vec![],
+    };
+    block.header.consensus_hash = orphaned_tenure;
+    block.header.parent_block_id = orphaned_tip;
+    block.header.chain_length = pre_fork_info.stacks_tip_height + 1;
+    block.header.timestamp = get_epoch_time_secs();
+    block
+        .header
+        .sign_miner(signer_test.get_miner_key())
+        .unwrap();
+    let block_signer_signature_hash = block.header.signer_signature_hash();
+    signer_test.propose_block(block, short_timeout);
+
+    info!("------------------------- Test Block Proposal Rejected -------------------------");
+    let rejections =
+        wait_for_block_rejections_from_signers(30, &block_signer_signature_hash, &all_signers)
+            .expect("Timed out waiting for block rejections");
+    for rejection in rejections {
+        assert_eq!(
+            rejection.response_data.reject_reason,
+            RejectReason::NonCanonicalTenure
+        );
+    }
+
+    // The signers never asked the node to validate the block
+    assert!(!test_observer::get_proposal_responses()
+        .iter()
+        .any(|response| response.signer_signature_hash() == &block_signer_signature_hash));
+
+    fault_injection_unstall_miner();
+
+    info!("------------------------- Test Shutdown-------------------------");
+    signer_test.shutdown();
+}
+
 #[test]
 #[ignore]
 /// Test that signers do not mark a block as globally accepted if it was not announced by the node.
@@ -417,6 +417,59 @@ impl SortitionData {
         )
     }
 
+    /// Check that `block`'s tenure, and the tenure it builds on, are on the canonical sortition
+    /// fork according to the Stacks node.  The tenure it builds on is the one named by its
+    /// tenure-change (or tenure-extend) transaction, if it has one, and its own tenure otherwise.
+    /// Returns `Ok(true)` if both tenures are ancestors of the node's latest winning sortition.
+    /// Returns `Ok(false)` if either tenure is on an orphaned fork.
+    ///
+    /// If the node can't be queried, this returns an error, and it's up to the caller to decide
+    /// what to do.  The node's proposal endpoint will still validate the block's chainstate.
+    pub fn check_tenure_is_canonical(
+        block: &NakamotoBlock,
+        client: &StacksClient,
+    ) -> Result<bool, ClientError> {
+        let parent_tenure_id = block
+            .get_tenure_tx_payload()
+            .map(|payload| &payload.prev_tenure_consensus_hash)
+            .unwrap_or(&block.header.consensus_hash);
+        let canonical_tip = client
+            .get_current_and_last_sortition()?
+            .current_sortition
+            .consensus_hash;
+
+        // the tenures between the parent tenure and the canonical tip, if the parent tenure is an
+        // ancestor of the tip.
+        let tenures = match client.get_tenure_forking_info(parent_tenure_id, &canonical_tip) {
+            Ok(tenures) => tenures,
+            Err(ClientError::RequestFailure(reqwest::StatusCode::BAD_REQUEST)) => {
+                warn!(
+                    "Miner block proposal builds on a tenure which is not on the canonical sortition fork";
+                    "proposed_block_consensus_hash" => %block.header.consensus_hash,
+                    "signer_signature_hash" => %block.header.signer_signature_hash(),
+                    "parent_tenure" => %parent_tenure_id,
+                    "canonical_tip" => %canonical_tip,
+                );
+                return Ok(false);
+            }
+            Err(e) => return Err(e),
+        };
+        if !tenures
+            .iter()
+            .any(|tenure| tenure.consensus_hash == block.header.consensus_hash)
+        {
+            warn!(
+                "Miner block proposal's tenure is not on the canonical sortition fork";
+                "proposed_block_consensus_hash" => %block.header.consensus_hash,
+                "signer_signature_hash" => %block.header.signer_signature_hash(),
+                "parent_tenure" => %parent_tenure_id,
+                "canonical_tip" => %canonical_tip,
+            );
+            return Ok(false);
+        }
+        Ok(true)
+    }
+
     fn confirms_latest_block_in_same_tenure(
         block: &NakamotoBlock,
         signer_db: &mut SignerDb,
@@ -27,6 +27,7 @@ use blockstack_lib::chainstate::stacks::{
 };
 use blockstack_lib::core::test_util::make_stacks_transfer_tx;
 use blockstack_lib::net::api::get_tenures_fork_info::TenureForkingInfo;
+use blockstack_lib::net::api::getsortition::SortitionInfo;
 use clarity::types::chainstate::{BurnchainHeaderHash, SortitionId, StacksAddress};
 use clarity::types::PrivateKey;
 use clarity::util::secp256k1::Secp256k1PublicKey;
@@ -432,6 +433,82 @@ fn check_proposal_with_extend_during_replay() {
         .expect("Proposal should validate");
 }
 
+/// Run `SortitionData::check_tenure_is_canonical()` on `block` against a mock node whose latest
+/// winning sortition is `canonical_tip`, and which answers the tenure forking info query with
+/// `fork_info_response`.
+fn tenure_is_canonical_testing(
+    block: NakamotoBlock,
+    canonical_tip: &SortitionState,
+    fork_info_response: String,
+) -> bool {
+    let MockServerClient {
+        mut server,
+        client,
+        config,
+    } = MockServerClient::new();
+    let h = std::thread::spawn(move || SortitionData::check_tenure_is_canonical(&block, &client));
+
+    let latest_sortition = vec![SortitionInfo {
+        burn_block_hash: canonical_tip.data.burn_block_hash.clone(),
+        burn_block_height: 2,
+        burn_header_timestamp: canonical_tip.data.burn_header_timestamp,
+        sortition_id: SortitionId([2; 32]),
+        parent_sortition_id: SortitionId([1; 32]),
+        consensus_hash: canonical_tip.data.consensus_hash.clone(),
+        was_sortition: true,
+        miner_pk_hash160: Some(canonical_tip.data.miner_pkh.clone()),
+        stacks_parent_ch: Some(canonical_tip.data.parent_tenure_id.clone()),
+        last_sortition_ch: None,
+        committed_block_hash: None,
+        vrf_seed: None,
+    }];
+    crate::client::tests::write_response(
+        server,
+        format!("HTTP/1.1 200 Ok\n\n{}", serde_json::json!(latest_sortition)).as_bytes(),
+    );
+    server = crate::client::tests::mock_server_from_config(&config);
+    crate::client::tests::write_response(server, fork_info_response.as_bytes());
+
+    h.join().unwrap().unwrap()
+}
+
+#[test]
+fn check_tenure_is_canonical() {
+    let (_, _, _, mut block, cur_sortition, _, _) = setup_test_environment(function_name!());
+
+    // the block is in the tenure at the canonical tip
+    block.header.consensus_hash = cur_sortition.data.consensus_hash.clone();
+    let fork_info = vec![TenureForkingInfo {
+        burn_block_hash: cur_sortition.data.burn_block_hash.clone(),
+        burn_block_height: 2,
+        sortition_id: SortitionId([2; 32]),
+        parent_sortition_id: SortitionId([1; 32]),
+        consensus_hash: cur_sortition.data.consensus_hash.clone(),
+        was_sortition: true,
+        first_block_mined: Some(StacksBlockId([1; 32])),
+        nakamoto_blocks: None,
+    }];
+    assert!(tenure_is_canonical_testing(
+        block.clone(),
+        &cur_sortition,
+        format!("HTTP/1.1 200 Ok\n\n{}", serde_json::json!(fork_info)),
+    ));
+
+    // the block is in a tenure which was orphaned, so the node reports that it isn't an ancestor
+    // of the canonical tip
+    block.header.consensus_hash = ConsensusHash([0x42; 20]);
+    assert!(!tenure_is_canonical_testing(
+        block,
+        &cur_sortition,
+        format!(
+            "HTTP/1.1 400 Bad Request\n\n{}",
+            serde_json::json!(
+                "Supplied start and end sortitions are not in the same sortition fork"
+            )
+        ),
+    ));
+}
+
 #[test]
 fn check_sortition_timeout() {
     let signer_db_path = tmp_db_path();
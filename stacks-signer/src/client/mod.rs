@@ -23,6 +23,8 @@ use std::time::Duration;
 
 use clarity::vm::errors::Error as ClarityError;
 use clarity::vm::types::serialization::SerializationError;
+use clarity::vm::types::{BuffData, PrincipalData, QualifiedContractIdentifier, SequenceData};
+use clarity::vm::Value as ClarityValue;
 use libsigner::RPCError;
 use libstackerdb::Error as StackerDBError;
 pub use stackerdb::*;
@@ -58,6 +60,9 @@ pub enum ClientError {
     /// Failed to call a read only function
     #[error("Failed to call read only function. {0}")]
     ReadOnlyFailure(String),
+    /// The contract of a read only function call is not deployed (yet)
+    #[error("No such contract: {0}")]
+    ContractNotFound(QualifiedContractIdentifier),
     /// Reqwest specific error occurred
     #[error("{0}")]
     ReqwestError(#[from] reqwest::Error),
@@ -99,12 +104,57 @@ pub enum ClientError {
     RPCError(#[from] RPCError),
 }
 
-/// Retry a function F with an exponential backoff and notification on transient failure
-pub fn retry_with_exponential_backoff<F, E, T>(request_fn: F) -> Result<T, ClientError>
+impl ClientError {
+    /// Could retrying the request that produced this error succeed?  Errors that arise from
+    /// malformed data will recur on every attempt, so there is no point in retrying them.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            ClientError::ClaritySerializationError(_)
+            | ClientError::MalformedClarityValue(_)
+            | ClientError::ClarityError(_)
+            | ClientError::MalformedContractData(_)
+            | ClientError::TransactionGenerationFailure(_)
+            | ClientError::FailToSign(_) => false,
+            ClientError::UnexpectedResponseFormat(_)
+            | ClientError::StackerDBSerializationError(_)
+            | ClientError::SignerDBError(_)
+            | ClientError::PutChunkRejected(_)
+            | ClientError::ReadOnlyFailure(_)
+            | ClientError::ContractNotFound(_)
+            | ClientError::ReqwestError(_)
+            | ClientError::RequestFailure(_)
+            | ClientError::RetryTimeout
+            | ClientError::NotConnected
+            | ClientError::UnsupportedStacksFeature(_)
+            | ClientError::InvalidResponse(_)
+            | ClientError::UnexpectedSortitionInfo
+            | ClientError::RPCError(_) => true,
+        }
+    }
+}
+
+/// Retry a function F with an exponential backoff and notification on transient failure.
+/// Transient failures whose error is not retryable (see `ClientError::is_retryable()`) are
+/// treated as permanent, and are returned immediately.
+pub fn retry_with_exponential_backoff<F, E, T>(mut request_fn: F) -> Result<T, ClientError>
 where
     F: FnMut() -> Result<T, backoff::Error<E>>,
     E: std::fmt::Debug + Into<ClientError>,
 {
+    let classified_request_fn = || {
+        request_fn().map_err(|e| match e {
+            backoff::Error::Permanent(err) => backoff::Error::Permanent(err.into()),
+            backoff::Error::Transient { err, retry_after } => {
+                let err: ClientError = err.into();
+                if err.is_retryable() {
+                    backoff::Error::Transient { err, retry_after }
+                } else {
+                    backoff::Error::Permanent(err)
+                }
+            }
+        })
+    };
+
     let notify = |err, dur| {
         debug!(
             "Failed to connect to stacks node and/or deserialize its response: {err:?}. Next attempt in {dur:?}"
@@ -117,18 +167,89 @@ where
         .with_max_elapsed_time(Some(Duration::from_secs(BACKOFF_MAX_ELAPSED)))
         .build();
 
-    backoff::retry_notify(backoff_timer, request_fn, notify).map_err(|e| match e {
+    backoff::retry_notify(backoff_timer, classified_request_fn, notify).map_err(|e| match e {
         backoff::Error::Permanent(err) => {
             warn!("Non-retry error during request: {err:?}");
-            err.into()
+            err
         }
         backoff::Error::Transient { err, .. } => {
             warn!("Exceeded max retries during request: {err:?}");
-            err.into()
+            err
         }
     })
 }
 
+/// A type which can be read out of a field of a Clarity tuple returned by a read-only call
+pub trait FromClarityTupleField: Sized {
+    /// The name of the Clarity type, used in error messages
+    const CLARITY_TYPE: &'static str;
+    /// Convert the field's value, or return `None` if it is not of the expected type
+    fn from_clarity_value(value: &ClarityValue) -> Option<Self>;
+}
+
+impl FromClarityTupleField for u128 {
+    const CLARITY_TYPE: &'static str = "uint";
+    fn from_clarity_value(value: &ClarityValue) -> Option<Self> {
+        match value {
+            ClarityValue::UInt(x) => Some(*x),
+            _ => None,
+        }
+    }
+}
+
+impl FromClarityTupleField for Vec<u8> {
+    const CLARITY_TYPE: &'static str = "buff";
+    fn from_clarity_value(value: &ClarityValue) -> Option<Self> {
+        match value {
+            ClarityValue::Sequence(SequenceData::Buffer(BuffData { data })) => Some(data.clone()),
+            _ => None,
+        }
+    }
+}
+
+impl FromClarityTupleField for PrincipalData {
+    const CLARITY_TYPE: &'static str = "principal";
+    fn from_clarity_value(value: &ClarityValue) -> Option<Self> {
+        match value {
+            ClarityValue::Principal(principal) => Some(principal.clone()),
+            _ => None,
+        }
+    }
+}
+
+impl FromClarityTupleField for bool {
+    const CLARITY_TYPE: &'static str = "bool";
+    fn from_clarity_value(value: &ClarityValue) -> Option<Self> {
+        match value {
+            ClarityValue::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+}
+
+/// Read the field `field` out of the Clarity tuple `value`.
+/// Returns `ClientError::MalformedClarityValue` if `value` is not a tuple, if the field is
+/// missing, or if the field is not of the expected type.
+pub fn parse_clarity_tuple_field<T: FromClarityTupleField>(
+    value: &ClarityValue,
+    field: &str,
+) -> Result<T, ClientError> {
+    let ClarityValue::Tuple(tuple_data) = value else {
+        return Err(ClientError::MalformedClarityValue(format!(
+            "Expected a tuple with field '{field}', got {value:?}"
+        )));
+    };
+    let field_value = tuple_data.data_map.get(field).ok_or_else(|| {
+        ClientError::MalformedClarityValue(format!("Tuple is missing field '{field}'"))
+    })?;
+    T::from_clarity_value(field_value).ok_or_else(|| {
+        ClientError::MalformedClarityValue(format!(
+            "Expected tuple field '{field}' to be a {}, got {field_value:?}",
+            T::CLARITY_TYPE
+        ))
+    })
+}
+
 #[cfg(test)]
 pub(crate) mod tests {
     use std::collections::{BTreeMap, HashMap};
@@ -143,7 +264,7 @@ pub(crate) mod tests {
     };
     use blockstack_lib::util_lib::boot::boot_code_id;
     use clarity::vm::costs::ExecutionCost;
-    use clarity::vm::Value as ClarityValue;
+    use clarity::vm::types::TupleData;
     use libsigner::SignerEntries;
     use rand::distributions::Standard;
     use rand::{thread_rng, Rng};
@@ -440,6 +561,9 @@ pub(crate) mod tests {
             #[cfg(any(test, feature = "testing"))]
             supported_signer_protocol_version: SUPPORTED_SIGNER_PROTOCOL_VERSION,
             stackerdb_timeout: Duration::from_secs(DEFAULT_STACKERDB_TIMEOUT_SECS),
+            confirm_writes: config.confirm_writes,
+            max_block_txs: config.max_block_txs,
+            max_block_size: config.max_block_size,
         }
     }
 
@@ -453,4 +577,113 @@ pub(crate) mod tests {
         let clarity_value = ClarityValue::okay(ClarityValue::UInt(cycle as u128)).unwrap();
         build_read_only_response(&clarity_value)
     }
+
+    fn build_test_tuple() -> ClarityValue {
+        ClarityValue::Tuple(
+            TupleData::from_data(vec![
+                ("num-slots".into(), ClarityValue::UInt(13)),
+                (
+                    "signing-key".into(),
+                    ClarityValue::buff_from(vec![0x02; 33]).unwrap(),
+                ),
+                (
+                    "signer".into(),
+                    ClarityValue::Principal(StacksAddress::burn_address(false).into()),
+                ),
+                ("active".into(), ClarityValue::Bool(true)),
+            ])
+            .unwrap(),
+        )
+    }
+
+    #[test]
+    fn parse_clarity_tuple_field_uint() {
+        let value = build_test_tuple();
+        let num_slots: u128 = parse_clarity_tuple_field(&value, "num-slots").unwrap();
+        assert_eq!(num_slots, 13);
+    }
+
+    #[test]
+    fn parse_clarity_tuple_field_buff() {
+        let value = build_test_tuple();
+        let signing_key: Vec<u8> = parse_clarity_tuple_field(&value, "signing-key").unwrap();
+        assert_eq!(signing_key, vec![0x02; 33]);
+    }
+
+    #[test]
+    fn parse_clarity_tuple_field_principal() {
+        let value = build_test_tuple();
+        let signer: PrincipalData = parse_clarity_tuple_field(&value, "signer").unwrap();
+        assert_eq!(
+            signer,
+            PrincipalData::from(StacksAddress::burn_address(false))
+        );
+    }
+
+    #[test]
+    fn parse_clarity_tuple_field_bool() {
+        let value = build_test_tuple();
+        let active: bool = parse_clarity_tuple_field(&value, "active").unwrap();
+        assert!(active);
+    }
+
+    #[test]
+    fn parse_clarity_tuple_field_malformed() {
+        let value = build_test_tuple();
+        // Not a tuple
+        assert!(matches!(
+            parse_clarity_tuple_field::<u128>(&ClarityValue::UInt(1), "num-slots"),
+            Err(ClientError::MalformedClarityValue(_))
+        ));
+        // Missing field
+        assert!(matches!(
+            parse_clarity_tuple_field::<u128>(&value, "no-such-field"),
+            Err(ClientError::MalformedClarityValue(_))
+        ));
+        // Wrong types
+        assert!(matches!(
+            parse_clarity_tuple_field::<u128>(&value, "active"),
+            Err(ClientError::MalformedClarityValue(_))
+        ));
+        assert!(matches!(
+            parse_clarity_tuple_field::<Vec<u8>>(&value, "num-slots"),
+            Err(ClientError::MalformedClarityValue(_))
+        ));
+        assert!(matches!(
+            parse_clarity_tuple_field::<PrincipalData>(&value, "signing-key"),
+            Err(ClientError::MalformedClarityValue(_))
+        ));
+        assert!(matches!(
+            parse_clarity_tuple_field::<bool>(&value, "signer"),
+            Err(ClientError::MalformedClarityValue(_))
+        ));
+    }
+
+    #[test]
+    fn retry_non_retryable_error_returns_immediately() {
+        let mut attempts = 0;
+        let start = std::time::Instant::now();
+        let result: Result<(), ClientError> = retry_with_exponential_backoff(|| {
+            attempts += 1;
+            Err(backoff::Error::transient(
+                ClientError::MalformedClarityValue("bad value".into()),
+            ))
+        });
+        assert!(matches!(result, Err(ClientError::MalformedClarityValue(_))));
+        assert_eq!(attempts, 1);
+        assert!(start.elapsed() < Duration::from_millis(BACKOFF_INITIAL_INTERVAL));
+    }
+
+    #[test]
+    fn retry_retryable_error_until_success() {
+        let mut attempts = 0;
+        let result = retry_with_exponential_backoff(|| {
+            attempts += 1;
+            if attempts < 3 {
+                return Err(backoff::Error::transient(ClientError::NotConnected));
+            }
+            Ok(attempts)
+        });
+        assert_eq!(result.unwrap(), 3);
+    }
 }
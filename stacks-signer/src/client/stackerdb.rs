@@ -61,6 +61,8 @@ pub struct StackerDB<M: MessageSlotID + std::cmp::Eq> {
     reward_cycle: u64,
     /// signerdb connection
     signer_db: SignerDb,
+    /// Whether or not to read back each written chunk and confirm that the node stored it
+    confirm_writes: bool,
 }
 
 impl<M: MessageSlotID + 'static> From<&SignerConfig> for StackerDB<M> {
@@ -83,6 +85,7 @@ impl<M: MessageSlotID + 'static> From<&SignerConfig> for StackerDB<M> {
             signer_db,
             mode,
             config.stackerdb_timeout,
+            config.confirm_writes,
         )
     }
 }
@@ -107,10 +110,12 @@ impl<M: MessageSlotID + 'static> StackerDB<M> {
             signer_db,
             StackerDBMode::Normal { signer_slot_id },
             socket_timeout,
+            false,
         )
     }
 
     /// Create a new StackerDB client
+    #[allow(clippy::too_many_arguments)]
     fn new(
         host: &str,
         stacks_private_key: StacksPrivateKey,
@@ -119,6 +124,7 @@ impl<M: MessageSlotID + 'static> StackerDB<M> {
         signer_db: SignerDb,
         signer_mode: StackerDBMode,
         socket_timeout: Duration,
+        confirm_writes: bool,
     ) -> Self {
         let mut signers_message_stackerdb_sessions = HashMap::new();
         for msg_id in M::all() {
@@ -136,6 +142,7 @@ impl<M: MessageSlotID + 'static> StackerDB<M> {
             mode: signer_mode,
             reward_cycle,
             signer_db,
+            confirm_writes,
         }
     }
 
@@ -203,6 +210,9 @@ impl<M: MessageSlotID + 'static> StackerDB<M> {
                 debug!("Chunk accepted by stackerdb: {chunk_ack:?}");
                 self.signer_db
                     .set_latest_chunk_version(&signer_pk, slot_id.0, slot_version)?;
+                if self.confirm_writes {
+                    Self::confirm_chunk_write(session, slot_id, &message_bytes)?;
+                }
                 return Ok(chunk_ack);
             } else {
                 warn!("Chunk rejected by stackerdb: {chunk_ack:?}");
@@ -239,6 +249,33 @@ impl<M: MessageSlotID + 'static> StackerDB<M> {
         }
     }
 
+    /// Read back the latest chunk in the given slot and confirm that it matches the bytes
+    /// that were just written. Returns `ClientError::PutChunkRejected` if the node does not
+    /// have the chunk or if it was clobbered (e.g. by a chunk with a higher version).
+    fn confirm_chunk_write(
+        session: &mut StackerDBSession,
+        slot_id: &SignerSlotID,
+        message_bytes: &[u8],
+    ) -> Result<(), ClientError> {
+        let get_request = || {
+            session
+                .get_latest_chunk(slot_id.0)
+                .map_err(backoff::Error::transient)
+        };
+        let stored_bytes = retry_with_exponential_backoff(get_request)?;
+        if stored_bytes.as_deref() == Some(message_bytes) {
+            debug!("Confirmed chunk write to stackerdb slot ID {slot_id}");
+            return Ok(());
+        }
+        warn!(
+            "Chunk written to stackerdb slot ID {slot_id} does not match the stored chunk";
+            "stored_bytes" => stored_bytes.as_deref().map(to_hex),
+        );
+        Err(ClientError::PutChunkRejected(format!(
+            "Stored chunk in slot {slot_id} does not match the written chunk"
+        )))
+    }
+
     /// Get all signer messages from stackerdb for the given slot IDs
     pub fn get_messages<T: SignerMessage<M>>(
         session: &mut StackerDBSession,
@@ -287,9 +324,10 @@ mod tests {
     use clarity::util::hash::{MerkleTree, Sha512Trunc256Sum};
     use clarity::util::secp256k1::MessageSignature;
     use libsigner::v0::messages::{
-        BlockRejection, BlockResponse, BlockResponseData, RejectCode, RejectReason, SignerMessage,
-        SignerMessageMetadata,
+        BlockRejection, BlockResponse, BlockResponseData, MessageSlotID, RejectCode, RejectReason,
+        SignerMessage, SignerMessageMetadata,
     };
+    use libstackerdb::SlotMetadata;
     use rand::{thread_rng, RngCore};
 
     use super::*;
@@ -361,4 +399,131 @@ mod tests {
         write_response(mock_server, response_bytes.as_slice());
         assert_eq!(ack, sender_thread.join().unwrap());
     }
+
+    #[test]
+    fn send_signer_message_with_confirm_writes_should_detect_mismatch() {
+        let signer_config = build_signer_config_tomls(
+            &[StacksPrivateKey::random()],
+            "localhost:20443",
+            Some(Duration::from_millis(128)), // Timeout defaults to 5 seconds. Let's override it to 128 milliseconds.
+            &Network::Testnet,
+            "1234",
+            16,
+            3000,
+            Some(100_000),
+            None,
+            Some(9000),
+            None,
+        );
+        let config = GlobalConfig::load_from_str(&signer_config[0]).unwrap();
+        let mut signer_config = generate_signer_config(&config, 5);
+        signer_config.confirm_writes = true;
+        let mut stackerdb = StackerDB::<MessageSlotID>::from(&signer_config);
+
+        let message_bytes = vec![1, 2, 3, 4];
+        let ack = StackerDBChunkAckData {
+            accepted: true,
+            reason: None,
+            metadata: None,
+            code: None,
+        };
+        let mock_server = mock_server_from_config(&config);
+        let sender_thread = spawn(move || {
+            stackerdb.send_message_bytes_with_retry(&MessageSlotID::BlockResponse, message_bytes)
+        });
+
+        // The node accepts the write...
+        let mut response_bytes = b"HTTP/1.1 200 OK\n\n".to_vec();
+        let payload = serde_json::to_string(&ack).expect("Failed to serialize ack");
+        response_bytes.extend(payload.as_bytes());
+        write_response(mock_server, response_bytes.as_slice());
+
+        // ...but hands back different bytes when the slot is read back
+        let mock_server = mock_server_from_config(&config);
+        let mut response_bytes = b"HTTP/1.1 200 OK\n\n".to_vec();
+        response_bytes.extend([5, 6, 7, 8]);
+        write_response(mock_server, response_bytes.as_slice());
+
+        let result = sender_thread.join().unwrap();
+        assert!(
+            matches!(result, Err(ClientError::PutChunkRejected(_))),
+            "Expected PutChunkRejected, got {result:?}"
+        );
+    }
+
+    #[test]
+    fn send_signer_message_should_retry_at_reported_slot_version() {
+        let signer_config = build_signer_config_tomls(
+            &[StacksPrivateKey::random()],
+            "localhost:20443",
+            Some(Duration::from_millis(128)), // Timeout defaults to 5 seconds. Let's override it to 128 milliseconds.
+            &Network::Testnet,
+            "1234",
+            16,
+            3000,
+            Some(100_000),
+            None,
+            Some(9000),
+            None,
+        );
+        let config = GlobalConfig::load_from_str(&signer_config[0]).unwrap();
+        let signer_config = generate_signer_config(&config, 5);
+        let mut stackerdb = StackerDB::<MessageSlotID>::from(&signer_config);
+
+        let message_bytes = vec![1, 2, 3, 4];
+        let mock_server = mock_server_from_config(&config);
+        let sender_thread = spawn(move || {
+            let result = stackerdb
+                .send_message_bytes_with_retry(&MessageSlotID::BlockResponse, message_bytes);
+            (stackerdb, result)
+        });
+
+        // The node already has version 3 in our slot, so it rejects version 0...
+        let stale_ack = StackerDBChunkAckData {
+            accepted: false,
+            reason: Some("Data for this slot and version already exist".into()),
+            metadata: Some(SlotMetadata {
+                slot_id: 0,
+                slot_version: 3,
+                data_hash: Sha512Trunc256Sum([0u8; 32]),
+                signature: MessageSignature::empty(),
+            }),
+            code: Some(StackerDBErrorCodes::DataAlreadyExists.code()),
+        };
+        let mut response_bytes = b"HTTP/1.1 200 OK\n\n".to_vec();
+        let payload = serde_json::to_string(&stale_ack).expect("Failed to serialize ack");
+        response_bytes.extend(payload.as_bytes());
+        let request_bytes = write_response(mock_server, response_bytes.as_slice());
+        let request = String::from_utf8_lossy(&request_bytes);
+        assert!(request.contains("\"slot_version\":0"), "{request}");
+
+        // ...and the signer re-signs the chunk at the next version after the reported one
+        let ack = StackerDBChunkAckData {
+            accepted: true,
+            reason: None,
+            metadata: None,
+            code: None,
+        };
+        let mock_server = mock_server_from_config(&config);
+        let mut response_bytes = b"HTTP/1.1 200 OK\n\n".to_vec();
+        let payload = serde_json::to_string(&ack).expect("Failed to serialize ack");
+        response_bytes.extend(payload.as_bytes());
+        let request_bytes = write_response(mock_server, response_bytes.as_slice());
+        let request = String::from_utf8_lossy(&request_bytes);
+        assert!(request.contains("\"slot_version\":4"), "{request}");
+
+        let (stackerdb, result) = sender_thread.join().unwrap();
+        assert_eq!(result.unwrap(), ack);
+        let StackerDBMode::Normal { signer_slot_id } = &stackerdb.mode else {
+            panic!("Expected a normal-mode stackerdb");
+        };
+        let signer_pk = StacksPublicKey::from_private(&stackerdb.stacks_private_key);
+        assert_eq!(
+            stackerdb
+                .signer_db
+                .get_latest_chunk_version(&signer_pk, signer_slot_id.0)
+                .unwrap(),
+            Some(4)
+        );
+    }
 }
@@ -16,7 +16,9 @@
 use std::collections::{HashMap, VecDeque};
 
 use blockstack_lib::chainstate::nakamoto::NakamotoBlock;
-use blockstack_lib::chainstate::stacks::boot::{NakamotoSignerEntry, SIGNERS_NAME};
+use blockstack_lib::chainstate::stacks::boot::{
+    NakamotoSignerEntry, SIGNERS_NAME, SIGNERS_VOTING_NAME,
+};
 use blockstack_lib::chainstate::stacks::db::StacksBlockHeaderTypes;
 use blockstack_lib::chainstate::stacks::{StacksTransaction, TransactionVersion};
 use blockstack_lib::net::api::callreadonly::CallReadOnlyResponse;
@@ -46,7 +48,7 @@ use stacks_common::types::StacksEpochId;
 use stacks_common::{debug, warn};
 
 use super::SignerSlotID;
-use crate::client::{retry_with_exponential_backoff, ClientError};
+use crate::client::{parse_clarity_tuple_field, retry_with_exponential_backoff, ClientError};
 use crate::config::GlobalConfig;
 use crate::runloop::RewardCycleInfo;
 
@@ -197,6 +199,35 @@ impl StacksClient {
         Ok(value.expect_result_ok()?.expect_u128()?)
     }
 
+    /// Get the aggregate public key approved for the given reward cycle from the signers-voting
+    /// contract. The key is parsed as a compressed secp256k1 point, so a malformed key is an error.
+    /// Returns Ok(None) if no key has been approved for that reward cycle.
+    pub fn get_aggregate_public_key(
+        &self,
+        reward_cycle: u64,
+    ) -> Result<Option<StacksPublicKey>, ClientError> {
+        debug!("StacksClient: Getting aggregate public key"; "reward_cycle" => reward_cycle);
+        let signers_voting_contract_id = boot_code_id(SIGNERS_VOTING_NAME, self.mainnet);
+        let function_name = ClarityName::from("get-approved-aggregate-key");
+        let value = self.read_only_contract_call(
+            &signers_voting_contract_id.issuer.clone().into(),
+            &signers_voting_contract_id.name,
+            &function_name,
+            &[ClarityValue::UInt(reward_cycle.into())],
+        )?;
+        let Some(key_value) = value.expect_optional()? else {
+            return Ok(None);
+        };
+        // A point should have 33 bytes exactly.
+        let key_bytes = key_value.expect_buff(33)?;
+        let aggregate_public_key = StacksPublicKey::from_slice(&key_bytes).map_err(|e| {
+            ClientError::MalformedClarityValue(format!(
+                "Invalid aggregate public key for reward cycle {reward_cycle}: {e}"
+            ))
+        })?;
+        Ok(Some(aggregate_public_key))
+    }
+
     /// Retrieve the signer slots stored within the stackerdb contract
     pub fn get_stackerdb_signer_slots(
         &self,
@@ -219,6 +250,44 @@ impl StacksClient {
         self.parse_signer_slots(value)
     }
 
+    /// Get the IDs of the stackerdb slots owned by `signer_address` in the given page of the
+    /// stackerdb contract. Returns an empty list if the address owns no slots.
+    pub fn get_stackerdb_slots_for_signer(
+        &self,
+        stackerdb_contract: &QualifiedContractIdentifier,
+        page: u32,
+        signer_address: &StacksAddress,
+    ) -> Result<Vec<u32>, ClientError> {
+        let signer_slots = self.get_stackerdb_signer_slots(stackerdb_contract, page)?;
+        Self::find_signer_slot_ids(stackerdb_contract, &signer_slots, signer_address)
+    }
+
+    /// Slots are assigned contiguously to each signer, in the order in which the stackerdb
+    /// contract lists them (this mirrors how the node builds its stackerdb signer list).
+    /// Returns an error if the contract stipulates more than u32::MAX slots.
+    fn find_signer_slot_ids(
+        stackerdb_contract: &QualifiedContractIdentifier,
+        signer_slots: &[(StacksAddress, u128)],
+        signer_address: &StacksAddress,
+    ) -> Result<Vec<u32>, ClientError> {
+        let mut slot_id: u32 = 0;
+        for (address, num_slots) in signer_slots {
+            let next_slot_id = u32::try_from(*num_slots)
+                .ok()
+                .and_then(|num_slots| slot_id.checked_add(num_slots))
+                .ok_or_else(|| {
+                    ClientError::MalformedContractData(format!(
+                        "Contract {stackerdb_contract} stipulates more than u32::MAX slots"
+                    ))
+                })?;
+            if address == signer_address {
+                return Ok((slot_id..next_slot_id).collect());
+            }
+            slot_id = next_slot_id;
+        }
+        Ok(vec![])
+    }
+
     /// Helper function  that attempts to deserialize a clarity hext string as a list of signer slots and their associated number of signer slots
     fn parse_signer_slots(
         &self,
@@ -229,14 +298,13 @@ impl StacksClient {
         let values = value.expect_list()?;
         let mut signer_slots = Vec::with_capacity(values.len());
         for value in values {
-            let tuple_data = value.expect_tuple()?;
-            let principal_data = tuple_data.get("signer")?.clone().expect_principal()?;
+            let principal_data: PrincipalData = parse_clarity_tuple_field(&value, "signer")?;
             let signer = if let PrincipalData::Standard(signer) = principal_data {
                 signer.into()
             } else {
                 panic!("BUG: Signers stackerdb contract is corrupted");
             };
-            let num_slots = tuple_data.get("num-slots")?.clone().expect_u128()?;
+            let num_slots: u128 = parse_clarity_tuple_field(&value, "num-slots")?;
             signer_slots.push((signer, num_slots));
         }
         Ok(signer_slots)
@@ -685,11 +753,14 @@ impl StacksClient {
         }
         let call_read_only_response = response.json::<CallReadOnlyResponse>()?;
         if !call_read_only_response.okay {
+            let cause = call_read_only_response
+                .cause
+                .unwrap_or_else(|| "unknown".to_string());
+            if let Some(contract_id) = Self::parse_no_such_contract(&cause) {
+                return Err(ClientError::ContractNotFound(contract_id));
+            }
             return Err(ClientError::ReadOnlyFailure(format!(
-                "{function_name}: {}",
-                call_read_only_response
-                    .cause
-                    .unwrap_or_else(|| "unknown".to_string())
+                "{function_name}: {cause}"
             )));
         }
         let hex = call_read_only_response.result.unwrap_or_default();
@@ -697,6 +768,15 @@ impl StacksClient {
         Ok(value)
     }
 
+    /// If the node's reason for failing a read-only call is that the contract does not exist
+    /// (i.e. `Unchecked(NoSuchContract("..."))`), then get the missing contract's ID.
+    fn parse_no_such_contract(cause: &str) -> Option<QualifiedContractIdentifier> {
+        let contract_id = cause
+            .strip_prefix("Unchecked(NoSuchContract(\"")?
+            .strip_suffix("\"))")?;
+        QualifiedContractIdentifier::parse(contract_id).ok()
+    }
+
     fn pox_path(&self) -> String {
         format!("{}/v2/pox", self.http_origin)
     }
@@ -775,8 +855,10 @@ mod tests {
     };
     use rand::thread_rng;
     use rand_core::RngCore;
+    use stacks_common::address::C32_ADDRESS_VERSION_TESTNET_SINGLESIG;
     use stacks_common::bitvec::BitVec;
     use stacks_common::consts::SIGNER_SLOTS_PER_USER;
+    use stacks_common::util::hash::Hash160;
 
     use super::*;
     use crate::client::tests::{
@@ -803,6 +885,93 @@ mod tests {
         assert_eq!(result, value);
     }
 
+    #[test]
+    fn get_stackerdb_slots_for_signer_should_succeed() {
+        let signers: Vec<(StacksAddress, u128)> = (0..3u8)
+            .map(|i| {
+                let address =
+                    StacksAddress::new(C32_ADDRESS_VERSION_TESTNET_SINGLESIG, Hash160([i; 20]))
+                        .unwrap();
+                (address, u128::from(i) + 1)
+            })
+            .collect();
+        let slots_list = ClarityValue::cons_list_unsanitized(
+            signers
+                .iter()
+                .map(|(address, num_slots)| {
+                    ClarityValue::Tuple(
+                        TupleData::from_data(vec![
+                            (
+                                "signer".into(),
+                                ClarityValue::Principal(address.clone().into()),
+                            ),
+                            ("num-slots".into(), ClarityValue::UInt(*num_slots)),
+                        ])
+                        .unwrap(),
+                    )
+                })
+                .collect(),
+        )
+        .unwrap();
+        let response = build_read_only_response(&ClarityValue::okay(slots_list).unwrap());
+        let contract_id = boot_code_id(SIGNERS_NAME, false);
+        let unknown_address =
+            StacksAddress::new(C32_ADDRESS_VERSION_TESTNET_SINGLESIG, Hash160([0xff; 20])).unwrap();
+
+        // Slot 0 belongs to the first signer, so the second signer owns slots 1 and 2,
+        // and the third signer owns slots 3, 4 and 5
+        let expected_slots = [vec![0], vec![1, 2], vec![3, 4, 5]];
+        let mut config = MockServerClient::new().config;
+        for ((address, _), expected) in signers.iter().zip(expected_slots) {
+            let mock = MockServerClient::from_config(config);
+            let address = address.clone();
+            let contract_id = contract_id.clone();
+            let h = spawn(move || {
+                mock.client
+                    .get_stackerdb_slots_for_signer(&contract_id, 0, &address)
+            });
+            write_response(mock.server, response.as_bytes());
+            assert_eq!(h.join().unwrap().unwrap(), expected);
+            config = mock.config;
+        }
+
+        let mock = MockServerClient::from_config(config);
+        let h = spawn(move || {
+            mock.client
+                .get_stackerdb_slots_for_signer(&contract_id, 0, &unknown_address)
+        });
+        write_response(mock.server, response.as_bytes());
+        assert!(h.join().unwrap().unwrap().is_empty());
+    }
+
+    #[test]
+    fn get_stackerdb_slots_for_signer_too_many_slots() {
+        let make_address = |i| {
+            StacksAddress::new(C32_ADDRESS_VERSION_TESTNET_SINGLESIG, Hash160([i; 20])).unwrap()
+        };
+        let contract_id = boot_code_id(SIGNERS_NAME, false);
+        let find_slot_ids = |signer_slots: &[(StacksAddress, u128)]| {
+            StacksClient::find_signer_slot_ids(&contract_id, signer_slots, &make_address(1))
+        };
+
+        // one signer has more than u32::MAX slots
+        let result = find_slot_ids(&[(make_address(0), u128::from(u32::MAX) + 1)]);
+        assert!(
+            matches!(result, Err(ClientError::MalformedContractData(_))),
+            "{result:?}"
+        );
+
+        // the signers have more than u32::MAX slots between them
+        let result = find_slot_ids(&[
+            (make_address(0), u128::from(u32::MAX)),
+            (make_address(1), 1),
+        ]);
+        assert!(
+            matches!(result, Err(ClientError::MalformedContractData(_))),
+            "{result:?}"
+        );
+    }
+
     #[test]
     fn read_only_contract_call_with_function_args_200_success() {
         let mock = MockServerClient::new();
@@ -840,6 +1009,32 @@ mod tests {
         assert!(matches!(result, Err(ClientError::ReadOnlyFailure(_))));
     }
 
+    #[test]
+    fn read_only_contract_call_200_no_such_contract() {
+        let mock = MockServerClient::new();
+        let contract_id = boot_code_id(SIGNERS_NAME, false);
+        let contract_addr: StacksAddress = contract_id.issuer.clone().into();
+        let contract_name = contract_id.name.clone();
+        let h = spawn(move || {
+            mock.client.read_only_contract_call(
+                &contract_addr,
+                &contract_name,
+                &ClarityName::from("function-name"),
+                &[],
+            )
+        });
+        let body = json!({
+            "okay": false,
+            "cause": format!("Unchecked(NoSuchContract(\"{contract_id}\"))"),
+        });
+        write_response(mock.server, format!("HTTP/1.1 200 OK\n\n{body}").as_bytes());
+        let result = h.join().unwrap();
+        assert!(
+            matches!(&result, Err(ClientError::ContractNotFound(id)) if id == &contract_id),
+            "{result:?}"
+        );
+    }
+
     #[test]
     fn read_only_contract_call_400_failure() {
         let mock = MockServerClient::new();
@@ -1200,6 +1395,40 @@ mod tests {
         assert_eq!(h.join().unwrap().unwrap(), reward_cycle as u128);
     }
 
+    #[test]
+    fn get_aggregate_public_key_should_succeed() {
+        let reward_cycle = thread_rng().next_u64();
+        let aggregate_public_key = StacksPublicKey::from_private(&StacksPrivateKey::random());
+        let key_value =
+            ClarityValue::buff_from(aggregate_public_key.to_bytes_compressed()).unwrap();
+
+        let mock = MockServerClient::new();
+        let response = build_read_only_response(&ClarityValue::some(key_value).unwrap());
+        let h = spawn(move || mock.client.get_aggregate_public_key(reward_cycle));
+        let request_bytes = write_response(mock.server, response.as_bytes());
+        let request = String::from_utf8_lossy(&request_bytes);
+        assert!(request.contains("/signers-voting/get-approved-aggregate-key"));
+        assert_eq!(h.join().unwrap().unwrap(), Some(aggregate_public_key));
+
+        // no key approved for this cycle
+        let mock = MockServerClient::new();
+        let response = build_read_only_response(&ClarityValue::none());
+        let h = spawn(move || mock.client.get_aggregate_public_key(reward_cycle));
+        write_response(mock.server, response.as_bytes());
+        assert_eq!(h.join().unwrap().unwrap(), None);
+
+        // 33 bytes, but not a point on the curve
+        let mock = MockServerClient::new();
+        let bad_key_value = ClarityValue::buff_from(vec![0x05; 33]).unwrap();
+        let response = build_read_only_response(&ClarityValue::some(bad_key_value).unwrap());
+        let h = spawn(move || mock.client.get_aggregate_public_key(reward_cycle));
+        write_response(mock.server, response.as_bytes());
+        assert!(matches!(
+            h.join().unwrap(),
+            Err(ClientError::MalformedClarityValue(_))
+        ));
+    }
+
     #[test]
     fn get_chain_id_from_config() {
         let mock = MockServerClient::from_config(
@@ -87,6 +87,8 @@ pub enum Network {
     Testnet,
     /// The mocknet network
     Mocknet,
+    /// The regtest network
+    Regtest,
 }
 
 impl std::fmt::Display for Network {
@@ -95,6 +97,7 @@ impl std::fmt::Display for Network {
             Self::Mainnet => write!(f, "mainnet"),
             Self::Testnet => write!(f, "testnet"),
             Self::Mocknet => write!(f, "mocknet"),
+            Self::Regtest => write!(f, "regtest"),
         }
     }
 }
@@ -104,7 +107,7 @@ impl Network {
     pub const fn to_address_version(&self) -> u8 {
         match self {
             Self::Mainnet => C32_ADDRESS_VERSION_MAINNET_SINGLESIG,
-            Self::Testnet | Self::Mocknet => C32_ADDRESS_VERSION_TESTNET_SINGLESIG,
+            Self::Testnet | Self::Mocknet | Self::Regtest => C32_ADDRESS_VERSION_TESTNET_SINGLESIG,
         }
     }
 
@@ -112,7 +115,7 @@ impl Network {
     pub const fn to_transaction_version(&self) -> TransactionVersion {
         match self {
             Self::Mainnet => TransactionVersion::Mainnet,
-            Self::Testnet | Self::Mocknet => TransactionVersion::Testnet,
+            Self::Testnet | Self::Mocknet | Self::Regtest => TransactionVersion::Testnet,
         }
     }
 
@@ -120,7 +123,7 @@ impl Network {
     pub const fn is_mainnet(&self) -> bool {
         match self {
             Self::Mainnet => true,
-            Self::Testnet | Self::Mocknet => false,
+            Self::Testnet | Self::Mocknet | Self::Regtest => false,
         }
     }
 }
@@ -201,6 +204,15 @@ pub struct SignerConfig {
     pub capitulate_miner_view_timeout: Duration,
     /// The HTTP timeout for read/write operations with StackerDB.
     pub stackerdb_timeout: Duration,
+    /// Whether or not to read back each StackerDB chunk after writing it and
+    /// confirm that the node stored the bytes that were sent
+    pub confirm_writes: bool,
+    /// The maximum number of transactions a proposed block may contain before it is
+    /// rejected without being submitted to the node for validation
+    pub max_block_txs: Option<u64>,
+    /// The maximum serialized size (in bytes) a proposed block may have before it is
+    /// rejected without being submitted to the node for validation
+    pub max_block_size: Option<u64>,
     #[cfg(any(test, feature = "testing"))]
     /// Only used for testing purposes to enable overriding the signer version
     pub supported_signer_protocol_version: u64,
@@ -217,7 +229,7 @@ pub struct GlobalConfig {
     pub stacks_private_key: StacksPrivateKey,
     /// The signer's Stacks address
     pub stacks_address: StacksAddress,
-    /// The network to use. One of "mainnet" or "testnet".
+    /// The network to use. One of "mainnet", "testnet", "mocknet" or "regtest".
     pub network: Network,
     /// The time to wait for a response from the stacker-db instance
     pub event_timeout: Duration,
@@ -264,6 +276,15 @@ pub struct GlobalConfig {
     pub capitulate_miner_view_timeout: Duration,
     /// The HTTP timeout for read/write operations with StackerDB.
     pub stackerdb_timeout: Duration,
+    /// Whether or not to read back each StackerDB chunk after writing it and
+    /// confirm that the node stored the bytes that were sent
+    pub confirm_writes: bool,
+    /// The maximum number of transactions a proposed block may contain before it is
+    /// rejected without being submitted to the node for validation
+    pub max_block_txs: Option<u64>,
+    /// The maximum serialized size (in bytes) a proposed block may have before it is
+    /// rejected without being submitted to the node for validation
+    pub max_block_size: Option<u64>,
     #[cfg(any(test, feature = "testing"))]
     /// Only used for testing to enable specific signer protocol versions
     pub supported_signer_protocol_version: u64,
@@ -279,7 +300,7 @@ struct RawConfigFile {
     /// The hex representation of the signer's Stacks private key used for communicating
     /// with the Stacks Node, including writing to the Stacker DB instance.
     pub stacks_private_key: String,
-    /// The network to use. One of "mainnet" or "testnet".
+    /// The network to use. One of "mainnet", "testnet", "mocknet" or "regtest".
     pub network: Network,
     /// The time to wait (in millisecs) for a response from the stacker-db instance
     pub event_timeout_ms: Option<u64>,
@@ -325,6 +346,15 @@ struct RawConfigFile {
     pub capitulate_miner_view_timeout_secs: Option<u64>,
     /// Time to wait (in secs) before timing out an HTTP request with StackerDB.
     pub stackerdb_timeout_secs: Option<u64>,
+    /// Whether or not to read back each StackerDB chunk after writing it and
+    /// confirm that the node stored the bytes that were sent
+    pub confirm_writes: Option<bool>,
+    /// The maximum number of transactions a proposed block may contain before it is
+    /// rejected without being submitted to the node for validation
+    pub max_block_txs: Option<u64>,
+    /// The maximum serialized size (in bytes) a proposed block may have before it is
+    /// rejected without being submitted to the node for validation
+    pub max_block_size: Option<u64>,
     #[cfg(any(test, feature = "testing"))]
     /// Only used for testing to enable specific signer protocol versions
     pub supported_signer_protocol_version: Option<u64>,
@@ -468,6 +498,9 @@ impl TryFrom<RawConfigFile> for GlobalConfig {
                 .stackerdb_timeout_secs
                 .unwrap_or(DEFAULT_STACKERDB_TIMEOUT_SECS),
         );
+
+        let confirm_writes = raw_data.confirm_writes.unwrap_or(false);
+
         #[cfg(any(test, feature = "testing"))]
         let supported_signer_protocol_version = raw_data
             .supported_signer_protocol_version
@@ -498,6 +531,9 @@ impl TryFrom<RawConfigFile> for GlobalConfig {
             reset_replay_set_after_fork_blocks,
             capitulate_miner_view_timeout,
             stackerdb_timeout,
+            confirm_writes,
+            max_block_txs: raw_data.max_block_txs,
+            max_block_size: raw_data.max_block_size,
             #[cfg(any(test, feature = "testing"))]
             supported_signer_protocol_version,
         })
@@ -560,7 +596,7 @@ Dry run: {dry_run}
     pub fn to_chain_id(&self) -> u32 {
         self.chain_id.unwrap_or(match self.network {
             Network::Mainnet => CHAIN_ID_MAINNET,
-            Network::Testnet | Network::Mocknet => CHAIN_ID_TESTNET,
+            Network::Testnet | Network::Mocknet | Network::Regtest => CHAIN_ID_TESTNET,
         })
     }
 }
@@ -673,6 +709,9 @@ chain_id = {chain_id}
 
 #[cfg(test)]
 mod tests {
+    use blockstack_lib::chainstate::stacks::boot::MINERS_NAME;
+    use blockstack_lib::util_lib::boot::boot_code_id;
+
     use super::*;
 
     #[test]
@@ -827,4 +866,33 @@ capitulate_miner_view_timeout_secs = 1000
         let global_config = GlobalConfig::try_from(config).unwrap();
         assert_eq!(global_config.to_chain_id(), 0x80000100);
     }
+
+    #[test]
+    fn test_regtest_network() {
+        let config_toml = r#"
+stacks_private_key = "2de4e77aab89c0c2570bb8bb90824f5cf2a5204a975905fee450ff9dad0fcf28"
+node_host = "localhost"
+endpoint = "localhost:30000"
+network = "regtest"
+auth_password = "abcd"
+db_path = ":memory:"
+            "#;
+        let config = GlobalConfig::load_from_str(config_toml).unwrap();
+        assert_eq!(config.network, Network::Regtest);
+        assert!(!config.network.is_mainnet());
+        assert_eq!(config.network.to_string(), "regtest");
+        assert_eq!(config.to_chain_id(), CHAIN_ID_TESTNET);
+        assert_eq!(
+            config.stacks_address.version(),
+            C32_ADDRESS_VERSION_TESTNET_SINGLESIG
+        );
+        assert_eq!(
+            config.network.to_transaction_version(),
+            TransactionVersion::Testnet
+        );
+        assert_eq!(
+            boot_code_id(MINERS_NAME, config.network.is_mainnet()).to_string(),
+            "ST000000000000000000002AMW42H.miners"
+        );
+    }
 }
@@ -250,11 +250,20 @@ impl<Signer: SignerTrait<T>, T: StacksMessageCodec + Clone + Send + Debug> RunLo
         // Ensure that the stackerdb has been updated for the reward cycle before proceeding
         let last_calculated_reward_cycle =
             self.stacks_client.get_last_set_cycle().map_err(|e| {
-                warn!(
-                    "Failed to fetch last calculated stackerdb cycle from stacks-node";
-                    "reward_cycle" => reward_cycle,
-                    "err" => ?e
-                );
+                if let ClientError::ContractNotFound(contract_id) = &e {
+                    // Expected until the signers contract is deployed
+                    debug!(
+                        "Signers contract is not deployed yet";
+                        "reward_cycle" => reward_cycle,
+                        "contract_id" => %contract_id
+                    );
+                } else {
+                    warn!(
+                        "Failed to fetch last calculated stackerdb cycle from stacks-node";
+                        "reward_cycle" => reward_cycle,
+                        "err" => ?e
+                    );
+                }
                 ConfigurationError::StackerDBNotUpdated
             })?;
         if last_calculated_reward_cycle < reward_cycle as u128 {
@@ -332,6 +341,9 @@ impl<Signer: SignerTrait<T>, T: StacksMessageCodec + Clone + Send + Debug> RunLo
             reset_replay_set_after_fork_blocks: self.config.reset_replay_set_after_fork_blocks,
             capitulate_miner_view_timeout: self.config.capitulate_miner_view_timeout,
             stackerdb_timeout: self.config.stackerdb_timeout,
+            confirm_writes: self.config.confirm_writes,
+            max_block_txs: self.config.max_block_txs,
+            max_block_size: self.config.max_block_size,
             #[cfg(any(test, feature = "testing"))]
             supported_signer_protocol_version: self.config.supported_signer_protocol_version,
         }))
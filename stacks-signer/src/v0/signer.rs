@@ -25,7 +25,7 @@ use blockstack_lib::net::api::postblock_proposal::{
     TOO_MANY_REQUESTS_STATUS,
 };
 use blockstack_lib::util_lib::db::Error as DBError;
-use clarity::codec::read_next;
+use clarity::codec::{read_next, StacksMessageCodec};
 use clarity::types::chainstate::{StacksBlockId, StacksPrivateKey};
 use clarity::types::{PrivateKey, StacksEpochId};
 use clarity::util::hash::{MerkleHashFunc, Sha512Trunc256Sum};
@@ -120,6 +120,10 @@ pub struct Signer {
     pub submitted_block_proposal: Option<(Sha512Trunc256Sum, Instant)>,
     /// Maximum age of a block proposal in seconds before it is dropped without processing
     pub block_proposal_max_age_secs: u64,
+    /// Maximum number of transactions in a block proposal before it is rejected without validation
+    pub max_block_txs: Option<u64>,
+    /// Maximum serialized size of a block proposal before it is rejected without validation
+    pub max_block_size: Option<u64>,
     /// The signer's local state machine used in signer set agreement
     pub local_state_machine: LocalStateMachine,
     /// Cache of stacks block IDs for blocks recently processed by our stacks-node
@@ -307,6 +311,8 @@ impl SignerTrait<SignerMessage> for Signer {
             submitted_block_proposal: None,
             block_proposal_validation_timeout: signer_config.block_proposal_validation_timeout,
             block_proposal_max_age_secs: signer_config.block_proposal_max_age_secs,
+            max_block_txs: signer_config.max_block_txs,
+            max_block_size: signer_config.max_block_size,
             local_state_machine: signer_state,
             recently_processed: RecentlyProcessedBlocks::new(),
             global_state_evaluator,
@@ -733,7 +739,22 @@ impl Signer {
         sortition_state: &mut Option<SortitionsView>,
         block: &NakamotoBlock,
     ) -> Option<BlockRejection> {
-        // First update our global state evaluator with our local state if we have one
+        // Regardless of which state we check against, the block must not build on an orphaned fork
+        match SortitionData::check_tenure_is_canonical(block, stacks_client) {
+            Ok(true) => {}
+            Ok(false) => {
+                return Some(self.create_block_rejection(RejectReason::NonCanonicalTenure, block));
+            }
+            Err(e) => {
+                warn!(
+                    "{self}: Failed to check if the block proposal's tenure is canonical: {e:?}. Assuming it is for now.";
+                    "signer_signature_hash" => %block.header.signer_signature_hash(),
+                    "block_id" => %block.block_id(),
+                );
+            }
+        }
+
+        // Update our global state evaluator with our local state if we have one
         let local_version = self.get_signer_protocol_version();
         if let Ok(update) = self
             .local_state_machine
@@ -1137,6 +1158,25 @@ impl Signer {
                 "reject_reason" => ?block_info.reject_reason);
         }
 
+        // Reject implausibly large blocks outright, without asking the node to validate them.
+        if let Err(reason) = check_block_size_limits(
+            &block_proposal.block,
+            self.max_block_txs,
+            self.max_block_size,
+        ) {
+            warn!("{self}: Received a block proposal that exceeds our block limits. Rejecting.";
+                "signer_signature_hash" => %signer_signature_hash,
+                "block_id" => %block_proposal.block.block_id(),
+                "block_height" => block_proposal.block.header.chain_length,
+                "burn_height" => block_proposal.burn_height,
+                "reason" => reason,
+            );
+            let block_rejection =
+                self.create_block_rejection(RejectReason::OversizedBlock, &block_proposal.block);
+            self.send_block_response(&block_proposal.block, block_rejection.into());
+            return;
+        }
+
         info!(
             "{self}: received a block proposal for a new block.";
             "signer_signature_hash" => %signer_signature_hash,
@@ -2057,6 +2097,33 @@ impl Signer {
     }
 }
 
+/// Check a proposed block against the signer's transaction count and serialized size limits.
+/// Returns Ok(()) if the block is within both limits (or no limits are set)
+/// Returns Err(reason) if not
+fn check_block_size_limits(
+    block: &NakamotoBlock,
+    max_block_txs: Option<u64>,
+    max_block_size: Option<u64>,
+) -> Result<(), String> {
+    if let Some(max_block_txs) = max_block_txs {
+        let num_txs = u64::try_from(block.txs.len()).unwrap_or(u64::MAX);
+        if num_txs > max_block_txs {
+            return Err(format!(
+                "Block has {num_txs} transactions, more than the limit of {max_block_txs}"
+            ));
+        }
+    }
+    if let Some(max_block_size) = max_block_size {
+        let block_size = u64::try_from(block.serialize_to_vec().len()).unwrap_or(u64::MAX);
+        if block_size > max_block_size {
+            return Err(format!(
+                "Block is {block_size} bytes, more than the limit of {max_block_size}"
+            ));
+        }
+    }
+    Ok(())
+}
+
 /// Determine if a block should be re-evaluated based on its rejection reason˝
 fn should_reevaluate_block(block_info: &BlockInfo) -> bool {
     if let Some(reject_reason) = &block_info.reject_reason {
@@ -2067,6 +2134,7 @@ fn should_reevaluate_block(block_info: &BlockInfo) -> bool {
             | RejectReason::TestingDirective
             | RejectReason::InvalidTenureExtend
             | RejectReason::ConsensusHashMismatch { .. }
+            | RejectReason::NonCanonicalTenure
             | RejectReason::NoSignerConsensus
             | RejectReason::NotRejected
             | RejectReason::Unknown(_) => true,
@@ -2080,7 +2148,8 @@ fn should_reevaluate_block(block_info: &BlockInfo) -> bool {
             | RejectReason::NotLatestSortitionWinner
             | RejectReason::InvalidParentBlock
             | RejectReason::DuplicateBlockFound
-            | RejectReason::IrrecoverablePubkeyHash => {
+            | RejectReason::IrrecoverablePubkeyHash
+            | RejectReason::OversizedBlock => {
                 // No need to re-validate these types of rejections.
                 false
             }
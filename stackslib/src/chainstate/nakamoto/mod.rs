@@ -1412,6 +1412,17 @@ impl NakamotoBlock {
         self.header.block_id()
     }
 
+    /// Compute the Merkle root of this block's transactions, for comparison with the header's
+    /// `tx_merkle_root`
+    pub fn compute_tx_merkle_root(&self) -> Sha512Trunc256Sum {
+        let txid_vecs: Vec<_> = self
+            .txs
+            .iter()
+            .map(|tx| tx.txid().as_bytes().to_vec())
+            .collect();
+        MerkleTree::<Sha512Trunc256Sum>::new(&txid_vecs).root()
+    }
+
     /// Get the miner's public key hash160 from this signature
     pub(crate) fn recover_miner_pubkh(&self) -> Result<Hash160, ChainstateError> {
         let recovered_miner_pubk = self.header.recover_miner_pk().ok_or_else(|| {
@@ -2882,6 +2893,60 @@ impl NakamotoChainState {
         Self::get_block_header_nakamoto(chainstate_conn.sqlite(), &block_id)
     }
 
+    /// DO NOT USE IN CONSENSUS CODE.
+    ///
+    /// Find the highest block at or below `tip_block_id` such that all of the blocks in its
+    /// tenure up to and including it are present in the staging blocks DB.  The canonical tip can
+    /// be ahead of this block if some of the ongoing tenure's blocks are missing, in which case
+    /// the block downloader must not treat the blocks in the gap as already obtained.
+    ///
+    /// Only the tip's tenure is checked.  If its tenure-start block is missing, then the
+    /// tenure-start block's parent is returned.
+    ///
+    /// Returns Ok(Some((block ID, chain length))) on success
+    /// Returns Ok(None) if `tip_block_id` is not a processed Nakamoto block
+    /// Returns Err(..) on DB error
+    pub fn highest_contiguous_processed_height(
+        chainstate: &StacksChainState,
+        tip_block_id: &StacksBlockId,
+    ) -> Result<Option<(StacksBlockId, u64)>, ChainstateError> {
+        let Some(tip_header_info) = Self::get_block_header_nakamoto(chainstate.db(), tip_block_id)?
+        else {
+            return Ok(None);
+        };
+        let Some(tip_header) = tip_header_info.anchored_header.as_stacks_nakamoto() else {
+            return Ok(None);
+        };
+
+        let staging_blocks = chainstate.nakamoto_blocks_db();
+        let mut highest = (tip_block_id.clone(), tip_header.chain_length);
+        let mut cursor = tip_header.clone();
+        loop {
+            if !staging_blocks.has_nakamoto_block_with_index_hash(&cursor.block_id())? {
+                // gap -- the contiguous run of blocks ends at this block's parent at the latest
+                highest = (
+                    cursor.parent_block_id.clone(),
+                    cursor.chain_length.saturating_sub(1),
+                );
+            }
+            let Some(parent_header_info) =
+                Self::get_block_header_nakamoto(chainstate.db(), &cursor.parent_block_id)?
+            else {
+                break;
+            };
+            let Some(parent_header) = parent_header_info.anchored_header.as_stacks_nakamoto()
+            else {
+                break;
+            };
+            if parent_header.consensus_hash != tip_header.consensus_hash {
+                // cursor is the tenure-start block
+                break;
+            }
+            cursor = parent_header.clone();
+        }
+        Ok(Some(highest))
+    }
+
     /// DO NOT USE IN CONSENSUS CODE.  Different nodes can have different blocks for the same
     /// tenure.
     ///
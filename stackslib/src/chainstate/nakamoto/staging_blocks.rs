@@ -30,7 +30,7 @@ use crate::chainstate::stacks::index::marf::MarfConnection;
 use crate::chainstate::stacks::Error as ChainstateError;
 use crate::stacks_common::codec::StacksMessageCodec;
 use crate::util_lib::db::{
-    query_row, query_rows, sqlite_open, table_exists, tx_begin_immediate, u64_to_sql,
+    query_count, query_row, query_rows, sqlite_open, table_exists, tx_begin_immediate, u64_to_sql,
     Error as DBError,
 };
 
@@ -166,7 +166,67 @@ pub const NAKAMOTO_STAGING_DB_SCHEMA_5: &[&str] = &[
     r#"UPDATE db_version SET version = 5"#,
 ];
 
-pub const NAKAMOTO_STAGING_DB_SCHEMA_LATEST: u32 = 5;
+pub const NAKAMOTO_STAGING_DB_SCHEMA_6: &[&str] = &[
+    r#"
+  -- Table for the block downloader's partial progress through tenures, so that it can resume
+  -- fetching a tenure's blocks from where it left off (e.g. after a restart)
+  CREATE TABLE nakamoto_tenure_download_checkpoints (
+                 -- The consensus hash of the burnchain block that selected the tenure's miner's block-commit.
+                 tenure_id_consensus_hash TEXT NOT NULL,
+                 -- the tenure-start block
+                 tenure_start_block BLOB NOT NULL,
+                 -- the tenure-end block (i.e. the next tenure's tenure-start block)
+                 tenure_end_block BLOB NOT NULL,
+                 -- the tenure-end block's index block hash.  Once it is processed, so is the tenure.
+                 tenure_end_block_id TEXT NOT NULL,
+
+                 PRIMARY KEY(tenure_id_consensus_hash)
+    );"#,
+    r#"
+  -- Table for the tenure blocks downloaded so far for each checkpoint, so that a checkpoint can be
+  -- extended without rewriting the blocks it already has
+  CREATE TABLE nakamoto_tenure_download_checkpoint_blocks (
+                 -- The consensus hash of the checkpoint's tenure
+                 tenure_id_consensus_hash TEXT NOT NULL,
+                 -- the block's position in the tenure blocks downloaded so far, which are in
+                 -- descending order by height (so the tenure-end block is first)
+                 block_index INTEGER NOT NULL,
+                 -- the block
+                 block BLOB NOT NULL,
+
+                 PRIMARY KEY(tenure_id_consensus_hash, block_index)
+    );"#,
+    r#"UPDATE db_version SET version = 6"#,
+];
+
+pub const NAKAMOTO_STAGING_DB_SCHEMA_LATEST: u32 = 6;
+
+/// The block downloader's partial progress through a tenure.  A tenure's blocks are fetched from
+/// highest to lowest, so the next block to fetch is the parent of the lowest block obtained.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NakamotoTenureDownloadCheckpoint {
+    /// Consensus hash that identifies the tenure
+    pub tenure_id_consensus_hash: ConsensusHash,
+    /// The tenure-start block
+    pub tenure_start_block: NakamotoBlock,
+    /// The tenure-end block (i.e. the next tenure's tenure-start block)
+    pub tenure_end_block: NakamotoBlock,
+    /// The tenure blocks downloaded so far, in descending order by height.  If not empty, then
+    /// the first block is the tenure-end block.
+    pub tenure_blocks: Vec<NakamotoBlock>,
+}
+
+impl NakamotoTenureDownloadCheckpoint {
+    /// The ID of the next block to download
+    pub fn block_cursor(&self) -> &StacksBlockId {
+        &self
+            .tenure_blocks
+            .last()
+            .unwrap_or(&self.tenure_end_block)
+            .header
+            .parent_block_id
+    }
+}
 
 pub struct NakamotoStagingBlocksConn(rusqlite::Connection);
 
@@ -458,6 +518,15 @@ impl<'a> NakamotoStagingBlocksConnRef<'a> {
             .collect())
     }
 
+    /// How many stored, non-orphaned Nakamoto blocks have yet to be processed?
+    /// Used by the block downloader to avoid outrunning block processing.
+    pub fn count_unprocessed_nakamoto_blocks(&self) -> Result<u64, ChainstateError> {
+        let qry =
+            "SELECT COUNT(*) FROM nakamoto_staging_blocks WHERE processed = 0 AND orphaned = 0";
+        let cnt = query_count(self, qry, NO_PARAMS)?;
+        Ok(u64::try_from(cnt).expect("more than i64::MAX rows"))
+    }
+
     /// Find the next ready-to-process Nakamoto block, given a connection to the staging blocks DB.
     /// NOTE: the relevant field queried from `nakamoto_staging_blocks` are updated by a separate
     /// tx from block-processing, so it's imperative that the thread that calls this function is
@@ -539,6 +608,41 @@ impl<'a> NakamotoStagingBlocksConnRef<'a> {
         let res: Option<u64> = query_row(self, sql, args)?;
         Ok(res.is_some())
     }
+
+    /// Get the block downloader's checkpoint for a tenure.
+    /// Returns Ok(Some(checkpoint)) if there is one
+    /// Returns Ok(None) if not
+    /// Returns Err(..) on DB error, including corruption
+    pub fn get_tenure_download_checkpoint(
+        &self,
+        tenure_id_consensus_hash: &ConsensusHash,
+    ) -> Result<Option<NakamotoTenureDownloadCheckpoint>, ChainstateError> {
+        let qry = "SELECT tenure_start_block, tenure_end_block FROM nakamoto_tenure_download_checkpoints WHERE tenure_id_consensus_hash = ?1";
+        let args = params![tenure_id_consensus_hash];
+        let res: Option<(Vec<u8>, Vec<u8>)> = self
+            .query_row(qry, args, |row| Ok((row.get(0)?, row.get(1)?)))
+            .optional()?;
+        let Some((start_block_bytes, end_block_bytes)) = res else {
+            return Ok(None);
+        };
+        let qry = "SELECT block FROM nakamoto_tenure_download_checkpoint_blocks WHERE tenure_id_consensus_hash = ?1 ORDER BY block_index ASC";
+        let args = params![tenure_id_consensus_hash];
+        let tenure_blocks_bytes: Vec<Vec<u8>> = query_rows(self, qry, args)?;
+        let tenure_blocks = tenure_blocks_bytes
+            .into_iter()
+            .map(|block_bytes| NakamotoBlock::consensus_deserialize(&mut block_bytes.as_slice()))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Some(NakamotoTenureDownloadCheckpoint {
+            tenure_id_consensus_hash: tenure_id_consensus_hash.clone(),
+            tenure_start_block: NakamotoBlock::consensus_deserialize(
+                &mut start_block_bytes.as_slice(),
+            )?,
+            tenure_end_block: NakamotoBlock::consensus_deserialize(
+                &mut end_block_bytes.as_slice(),
+            )?,
+            tenure_blocks,
+        }))
+    }
 }
 
 impl NakamotoStagingBlocksTx<'_> {
@@ -714,6 +818,94 @@ impl NakamotoStagingBlocksTx<'_> {
                     ])?;
         Ok(())
     }
+
+    /// Store the block downloader's checkpoint for a tenure, replacing any prior one.
+    pub fn store_tenure_download_checkpoint(
+        &self,
+        checkpoint: &NakamotoTenureDownloadCheckpoint,
+    ) -> Result<(), ChainstateError> {
+        self.delete_tenure_download_checkpoint(&checkpoint.tenure_id_consensus_hash)?;
+        self.execute(
+            "INSERT INTO nakamoto_tenure_download_checkpoints (
+                     tenure_id_consensus_hash,
+                     tenure_start_block,
+                     tenure_end_block,
+                     tenure_end_block_id
+            ) VALUES (?1, ?2, ?3, ?4)",
+            params![
+                &checkpoint.tenure_id_consensus_hash,
+                checkpoint.tenure_start_block.serialize_to_vec(),
+                checkpoint.tenure_end_block.serialize_to_vec(),
+                &checkpoint.tenure_end_block.block_id(),
+            ],
+        )?;
+        self.append_tenure_download_checkpoint_blocks(
+            &checkpoint.tenure_id_consensus_hash,
+            0,
+            &checkpoint.tenure_blocks,
+        )
+    }
+
+    /// Add newly-downloaded tenure blocks to the block downloader's checkpoint for a tenure.
+    /// `first_block_index` is the number of tenure blocks the checkpoint already has.
+    pub fn append_tenure_download_checkpoint_blocks(
+        &self,
+        tenure_id_consensus_hash: &ConsensusHash,
+        first_block_index: usize,
+        tenure_blocks: &[NakamotoBlock],
+    ) -> Result<(), ChainstateError> {
+        for (i, block) in tenure_blocks.iter().enumerate() {
+            let block_index =
+                u64::try_from(first_block_index.saturating_add(i)).unwrap_or(u64::MAX);
+            self.execute(
+                "INSERT OR REPLACE INTO nakamoto_tenure_download_checkpoint_blocks (
+                         tenure_id_consensus_hash,
+                         block_index,
+                         block
+                ) VALUES (?1, ?2, ?3)",
+                params![
+                    tenure_id_consensus_hash,
+                    u64_to_sql(block_index)?,
+                    block.serialize_to_vec(),
+                ],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Delete the block downloader's checkpoint for a tenure, if there is one.
+    pub fn delete_tenure_download_checkpoint(
+        &self,
+        tenure_id_consensus_hash: &ConsensusHash,
+    ) -> Result<(), ChainstateError> {
+        self.execute(
+            "DELETE FROM nakamoto_tenure_download_checkpoint_blocks WHERE tenure_id_consensus_hash = ?1",
+            params![tenure_id_consensus_hash],
+        )?;
+        self.execute(
+            "DELETE FROM nakamoto_tenure_download_checkpoints WHERE tenure_id_consensus_hash = ?1",
+            params![tenure_id_consensus_hash],
+        )?;
+        Ok(())
+    }
+
+    /// Delete the block downloader's checkpoints for tenures which have since been processed
+    /// (i.e. whose tenure-end blocks have been processed).
+    /// Returns the number of checkpoints deleted.
+    pub fn prune_tenure_download_checkpoints(&self) -> Result<usize, ChainstateError> {
+        let processed_tenures = "SELECT tenure_id_consensus_hash FROM nakamoto_tenure_download_checkpoints
+            JOIN nakamoto_staging_blocks ON nakamoto_staging_blocks.index_block_hash = nakamoto_tenure_download_checkpoints.tenure_end_block_id
+            WHERE nakamoto_staging_blocks.processed = 1";
+        self.execute(
+            &format!("DELETE FROM nakamoto_tenure_download_checkpoint_blocks WHERE tenure_id_consensus_hash IN ({processed_tenures})"),
+            NO_PARAMS,
+        )?;
+        let num_pruned = self.execute(
+            &format!("DELETE FROM nakamoto_tenure_download_checkpoints WHERE tenure_id_consensus_hash IN ({processed_tenures})"),
+            NO_PARAMS,
+        )?;
+        Ok(num_pruned)
+    }
 }
 
 impl StacksChainState {
@@ -844,6 +1036,15 @@ impl StacksChainState {
                     assert_eq!(version, 5, "Nakamoto staging DB migration failure");
                     debug!("Migrated Nakamoto staging blocks DB to schema 5");
                 }
+                5 => {
+                    debug!("Migrate Nakamoto staging blocks DB to schema 6");
+                    for cmd in NAKAMOTO_STAGING_DB_SCHEMA_6.iter() {
+                        conn.execute(cmd, NO_PARAMS)?;
+                    }
+                    let version = Self::get_nakamoto_staging_blocks_db_version(conn)?;
+                    assert_eq!(version, 6, "Nakamoto staging DB migration failure");
+                    debug!("Migrated Nakamoto staging blocks DB to schema 6");
+                }
                 NAKAMOTO_STAGING_DB_SCHEMA_LATEST => {
                     break;
                 }
@@ -882,7 +1083,9 @@ impl StacksChainState {
             for cmd in NAKAMOTO_STAGING_DB_SCHEMA_2.iter() {
                 conn.execute(cmd, NO_PARAMS)?;
             }
-        } else if readwrite {
+        }
+        if readwrite {
+            // a new DB is only instantiated readwrite, so this brings it up to date too
             Self::migrate_nakamoto_staging_blocks(&conn)?;
         }
 
@@ -33,7 +33,7 @@ use stacks_common::types::chainstate::StacksAddress;
 use stacks_common::types::net::PeerAddress;
 use stacks_common::types::Address;
 use stacks_common::util::get_epoch_time_ms;
-use stacks_common::util::hash::hex_bytes;
+use stacks_common::util::hash::{hex_bytes, Hash160};
 use stacks_common::util::secp256k1::{Secp256k1PrivateKey, Secp256k1PublicKey};
 
 use crate::burnchains::bitcoin::BitcoinNetworkType;
@@ -3347,6 +3347,118 @@ pub struct ConnectionOptionsFile {
     /// ---
     /// @default: `6`
     pub max_inflight_attachments: Option<u64>,
+    /// Number of unconfirmed tenure downloaders that may run while the highest complete
+    /// tenure is still being downloaded.
+    ///
+    /// This lets the node keep learning about the chain tip while it finishes fetching
+    /// the last confirmed tenure. If set to 0, unconfirmed tenure downloads wait until
+    /// the highest complete tenure has been downloaded.
+    /// ---
+    /// @default: `0`
+    pub nakamoto_unconfirmed_downloader_reserved_inflight: Option<u64>,
+    /// Maximum number of blocks by which the sortition tip and the burnchain height may
+    /// differ for the block downloader to start fetching unconfirmed tenures.
+    /// ---
+    /// @default: `0`
+    /// @notes:
+    ///   - 0 means that the sortition tip must be at the burnchain tip.
+    /// @units: burnchain blocks
+    pub nakamoto_unconfirmed_downloader_max_burnchain_divergence: Option<u64>,
+    /// Maximum number of unconfirmed tenure downloaders to run at once.
+    ///
+    /// Every neighbor serves roughly the same unconfirmed blocks, so there is little
+    /// point in asking many of them.
+    /// ---
+    /// @default: `3`
+    pub nakamoto_max_unconfirmed_downloaders: Option<u64>,
+    /// Minimum number of confirmed tenure downloaders to reserve for tenures in the
+    /// previous reward cycle while any of them remain to be downloaded, regardless of
+    /// how they are ordered in the download schedule.
+    /// ---
+    /// @default: `0` (no downloaders are reserved)
+    pub nakamoto_prev_cycle_download_slots: Option<u64>,
+    /// Number of unprocessed blocks in the Nakamoto staging DB at which the block
+    /// downloader stops scheduling new confirmed tenure downloads, so that it does not
+    /// outrun block processing. In-flight downloads are not cancelled.
+    /// ---
+    /// @default: `0` (check disabled)
+    /// @units: blocks
+    pub nakamoto_download_staging_high_water_mark: Option<u64>,
+    /// Number of unprocessed blocks in the Nakamoto staging DB at or below which the
+    /// block downloader resumes scheduling confirmed tenure downloads, once it has
+    /// stopped due to [`ConnectionOptionsFile::nakamoto_download_staging_high_water_mark`].
+    /// ---
+    /// @default: `0`
+    /// @units: blocks
+    pub nakamoto_download_staging_low_water_mark: Option<u64>,
+    /// Peers which the block downloader asks for each tenure before any other peer that
+    /// can serve it. This is an operator allowlist for private or federated deployments.
+    ///
+    /// Peers are specified as comma-separated strings in the format `"PUBKEY@IP:PORT"`.
+    /// Unlike [`NodeConfig::bootstrap_node`], hostnames are not resolved.
+    /// ---
+    /// @default: `None` (no trusted download peers)
+    /// @toml_example: |
+    ///   trusted_download_peers = "pubkey1@192.168.1.100:20444,pubkey2@192.168.1.101:20444"
+    pub trusted_download_peers: Option<String>,
+    /// If true, the block downloader only fetches confirmed tenures, and never the
+    /// ongoing tenure's blocks.
+    ///
+    /// Archival and indexer nodes which only care about finalized tenures can set this
+    /// to avoid running unconfirmed tenure downloaders altogether.
+    /// ---
+    /// @default: `false`
+    pub nakamoto_download_confirmed_only: Option<bool>,
+    /// Minimum number of peers which must have provided their tenure inventories for
+    /// the block downloader's target reward cycle before it schedules confirmed tenure
+    /// downloads during Initial Block Download (IBD).
+    ///
+    /// This keeps a booting node from committing to the inventories of its first one
+    /// or two peers, which may be partial or biased.
+    /// ---
+    /// @default: `1`
+    /// @notes:
+    ///   - 0 and 1 are equivalent.
+    pub min_peers_for_ibd: Option<u64>,
+    /// If true, the block downloader holds back downloaded tenures whose first block's
+    /// parent is neither stored nor downloaded, instead of returning them for processing.
+    /// ---
+    /// @default: `false`
+    pub nakamoto_download_quarantine_disconnected_tenures: Option<bool>,
+    /// If true, the block downloader recomputes the transaction Merkle root of each
+    /// downloaded tenure block and rejects blocks whose transactions do not match
+    /// their header.
+    /// ---
+    /// @default: `false`
+    /// @notes:
+    ///   - Blocks are checked again when they are processed, so this is only
+    ///     defense-in-depth.
+    pub nakamoto_download_verify_tx_merkle_roots: Option<bool>,
+    /// Minimum download backlog at which the block downloader considers the node to be
+    /// in Initial Block Download (IBD), and fetches tenures in sortition order instead
+    /// of rarest-first.
+    ///
+    /// The backlog counts unprocessed wanted tenures plus the burnchain blocks the
+    /// sortition DB has yet to process.
+    /// ---
+    /// @default: `144`
+    /// @units: tenures
+    pub nakamoto_download_ibd_backlog_threshold: Option<u64>,
+    /// If true, the block downloader checkpoints its progress through each confirmed
+    /// tenure's blocks to the staging DB, so that a long tenure's download can resume
+    /// from where it left off after a restart instead of starting over.
+    /// ---
+    /// @default: `false`
+    pub nakamoto_download_checkpoint_tenures: Option<bool>,
+    /// Maximum number of blocks the block downloader returns for processing from a
+    /// single pass over the unconfirmed tenures. Blocks in excess of this are held
+    /// until the next pass.
+    /// ---
+    /// @default: `4096`
+    /// @notes:
+    ///   - 0 means no limit.
+    /// @units: blocks
+    pub nakamoto_unconfirmed_download_max_coalesced_blocks: Option<u64>,
     /// Maximum total size (in bytes) of data allowed to be written during a read-only call.
     /// ---
     /// @default: `0`
@@ -3616,6 +3728,16 @@ impl ConnectionOptionsFile {
                     .map_err(|e| format!("Invalid connection_option.public_ip_address: {e}"))
             })
             .transpose()?;
+        let trusted_download_peers = self
+            .trusted_download_peers
+            .map(|trusted_download_peers| {
+                trusted_download_peers
+                    .split(',')
+                    .filter(|part| !part.is_empty())
+                    .map(ConnectionOptionsFile::parse_trusted_download_peer)
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .transpose()?;
         let mut read_only_call_limit = HELIUM_DEFAULT_CONNECTION_OPTIONS
             .read_only_call_limit
             .clone();
@@ -3701,6 +3823,76 @@ impl ConnectionOptionsFile {
             max_inflight_attachments: self
                 .max_inflight_attachments
                 .unwrap_or_else(|| HELIUM_DEFAULT_CONNECTION_OPTIONS.max_inflight_attachments),
+            nakamoto_unconfirmed_downloader_reserved_inflight: self
+                .nakamoto_unconfirmed_downloader_reserved_inflight
+                .unwrap_or_else(|| {
+                    HELIUM_DEFAULT_CONNECTION_OPTIONS
+                        .nakamoto_unconfirmed_downloader_reserved_inflight
+                }),
+            nakamoto_unconfirmed_downloader_max_burnchain_divergence: self
+                .nakamoto_unconfirmed_downloader_max_burnchain_divergence
+                .unwrap_or_else(|| {
+                    HELIUM_DEFAULT_CONNECTION_OPTIONS
+                        .nakamoto_unconfirmed_downloader_max_burnchain_divergence
+                }),
+            nakamoto_max_unconfirmed_downloaders: self
+                .nakamoto_max_unconfirmed_downloaders
+                .unwrap_or_else(|| {
+                    HELIUM_DEFAULT_CONNECTION_OPTIONS.nakamoto_max_unconfirmed_downloaders
+                }),
+            nakamoto_prev_cycle_download_slots: self
+                .nakamoto_prev_cycle_download_slots
+                .unwrap_or_else(|| {
+                    HELIUM_DEFAULT_CONNECTION_OPTIONS.nakamoto_prev_cycle_download_slots
+                }),
+            nakamoto_download_staging_high_water_mark: self
+                .nakamoto_download_staging_high_water_mark
+                .unwrap_or_else(|| {
+                    HELIUM_DEFAULT_CONNECTION_OPTIONS.nakamoto_download_staging_high_water_mark
+                }),
+            nakamoto_download_staging_low_water_mark: self
+                .nakamoto_download_staging_low_water_mark
+                .unwrap_or_else(|| {
+                    HELIUM_DEFAULT_CONNECTION_OPTIONS.nakamoto_download_staging_low_water_mark
+                }),
+            trusted_download_peers: trusted_download_peers.unwrap_or_else(|| {
+                HELIUM_DEFAULT_CONNECTION_OPTIONS
+                    .trusted_download_peers
+                    .clone()
+            }),
+            nakamoto_download_confirmed_only: self.nakamoto_download_confirmed_only.unwrap_or_else(
+                || HELIUM_DEFAULT_CONNECTION_OPTIONS.nakamoto_download_confirmed_only,
+            ),
+            min_peers_for_ibd: self
+                .min_peers_for_ibd
+                .unwrap_or_else(|| HELIUM_DEFAULT_CONNECTION_OPTIONS.min_peers_for_ibd),
+            nakamoto_download_quarantine_disconnected_tenures: self
+                .nakamoto_download_quarantine_disconnected_tenures
+                .unwrap_or_else(|| {
+                    HELIUM_DEFAULT_CONNECTION_OPTIONS
+                        .nakamoto_download_quarantine_disconnected_tenures
+                }),
+            nakamoto_download_verify_tx_merkle_roots: self
+                .nakamoto_download_verify_tx_merkle_roots
+                .unwrap_or_else(|| {
+                    HELIUM_DEFAULT_CONNECTION_OPTIONS.nakamoto_download_verify_tx_merkle_roots
+                }),
+            nakamoto_download_ibd_backlog_threshold: self
+                .nakamoto_download_ibd_backlog_threshold
+                .unwrap_or_else(|| {
+                    HELIUM_DEFAULT_CONNECTION_OPTIONS.nakamoto_download_ibd_backlog_threshold
+                }),
+            nakamoto_download_checkpoint_tenures: self
+                .nakamoto_download_checkpoint_tenures
+                .unwrap_or_else(|| {
+                    HELIUM_DEFAULT_CONNECTION_OPTIONS.nakamoto_download_checkpoint_tenures
+                }),
+            nakamoto_unconfirmed_download_max_coalesced_blocks: self
+                .nakamoto_unconfirmed_download_max_coalesced_blocks
+                .unwrap_or_else(|| {
+                    HELIUM_DEFAULT_CONNECTION_OPTIONS
+                        .nakamoto_unconfirmed_download_max_coalesced_blocks
+                }),
             maximum_call_argument_size: self
                 .maximum_call_argument_size
                 .unwrap_or_else(|| HELIUM_DEFAULT_CONNECTION_OPTIONS.maximum_call_argument_size),
@@ -3760,6 +3952,28 @@ impl ConnectionOptionsFile {
             ..default
         })
     }
+
+    /// Parse a trusted download peer in the format `PUBKEY@IP:PORT`
+    fn parse_trusted_download_peer(peer: &str) -> Result<NeighborAddress, String> {
+        let Some((pubkey_str, addr_str)) = peer.split_once('@') else {
+            return Err(format!(
+                "Invalid connection_options.trusted_download_peers entry '{peer}': expected PUBKEY@IP:PORT"
+            ));
+        };
+        let pubkey = Secp256k1PublicKey::from_hex(pubkey_str).map_err(|e| {
+            format!(
+                "Invalid connection_options.trusted_download_peers public key '{pubkey_str}': {e}"
+            )
+        })?;
+        let addr = addr_str.parse::<SocketAddr>().map_err(|e| {
+            format!("Invalid connection_options.trusted_download_peers address '{addr_str}': {e}")
+        })?;
+        Ok(NeighborAddress {
+            addrbytes: PeerAddress::from_socketaddr(&addr),
+            port: addr.port(),
+            public_key_hash: Hash160::from_node_public_key(&pubkey),
+        })
+    }
 }
 
 #[derive(Clone, Deserialize, Default, Debug)]
@@ -4782,6 +4996,112 @@ mod tests {
         );
     }
 
+    #[test]
+    fn should_load_download_options() {
+        let config = Config::from_config_file(
+            ConfigFile::from_str(
+                r#"
+                [connection_options]
+                nakamoto_unconfirmed_downloader_reserved_inflight = 1
+                nakamoto_unconfirmed_downloader_max_burnchain_divergence = 2
+                nakamoto_max_unconfirmed_downloaders = 4
+                nakamoto_prev_cycle_download_slots = 5
+                nakamoto_download_staging_high_water_mark = 600
+                nakamoto_download_staging_low_water_mark = 300
+                trusted_download_peers = "029266faff4c8e0ca4f934f34996a96af481df94a89b0c9bd515f3536a95682ddc@192.168.1.100:20444,02196f005965cebe6ddc3901b7b1cc1aa7a88f305bb8c5893456b8f9a605923893@10.0.0.1:30444"
+                nakamoto_download_confirmed_only = true
+                min_peers_for_ibd = 3
+                nakamoto_download_quarantine_disconnected_tenures = true
+                nakamoto_download_verify_tx_merkle_roots = true
+                nakamoto_download_ibd_backlog_threshold = 12
+                nakamoto_download_checkpoint_tenures = true
+                nakamoto_unconfirmed_download_max_coalesced_blocks = 7
+                "#,
+            )
+            .unwrap(),
+            false,
+        )
+        .expect("Expected to be able to parse download options from file");
+
+        let opts = &config.connection_options;
+        assert_eq!(opts.nakamoto_unconfirmed_downloader_reserved_inflight, 1);
+        assert_eq!(
+            opts.nakamoto_unconfirmed_downloader_max_burnchain_divergence,
+            2
+        );
+        assert_eq!(opts.nakamoto_max_unconfirmed_downloaders, 4);
+        assert_eq!(opts.nakamoto_prev_cycle_download_slots, 5);
+        assert_eq!(opts.nakamoto_download_staging_high_water_mark, 600);
+        assert_eq!(opts.nakamoto_download_staging_low_water_mark, 300);
+        assert!(opts.nakamoto_download_confirmed_only);
+        assert_eq!(opts.min_peers_for_ibd, 3);
+        assert!(opts.nakamoto_download_quarantine_disconnected_tenures);
+        assert!(opts.nakamoto_download_verify_tx_merkle_roots);
+        assert_eq!(opts.nakamoto_download_ibd_backlog_threshold, 12);
+        assert!(opts.nakamoto_download_checkpoint_tenures);
+        assert_eq!(opts.nakamoto_unconfirmed_download_max_coalesced_blocks, 7);
+
+        let pubkey = Secp256k1PublicKey::from_hex(
+            "029266faff4c8e0ca4f934f34996a96af481df94a89b0c9bd515f3536a95682ddc",
+        )
+        .unwrap();
+        assert_eq!(opts.trusted_download_peers.len(), 2);
+        assert_eq!(
+            opts.trusted_download_peers[0],
+            NeighborAddress {
+                addrbytes: PeerAddress::from_ipv4(192, 168, 1, 100),
+                port: 20444,
+                public_key_hash: Hash160::from_node_public_key(&pubkey),
+            }
+        );
+        assert_eq!(
+            opts.trusted_download_peers[1].addrbytes,
+            PeerAddress::from_ipv4(10, 0, 0, 1)
+        );
+        assert_eq!(opts.trusted_download_peers[1].port, 30444);
+
+        // unset options keep their defaults
+        let config = Config::from_config_file(ConfigFile::from_str("").unwrap(), false).unwrap();
+        let default = ConnectionOptions::default();
+        let opts = &config.connection_options;
+        assert!(opts.trusted_download_peers.is_empty());
+        assert_eq!(
+            opts.nakamoto_max_unconfirmed_downloaders,
+            default.nakamoto_max_unconfirmed_downloaders
+        );
+        assert_eq!(
+            opts.nakamoto_download_ibd_backlog_threshold,
+            default.nakamoto_download_ibd_backlog_threshold
+        );
+        assert_eq!(
+            opts.nakamoto_unconfirmed_download_max_coalesced_blocks,
+            default.nakamoto_unconfirmed_download_max_coalesced_blocks
+        );
+
+        // malformed trusted peers are rejected
+        for bad_peer in [
+            "192.168.1.100:20444",
+            "not-a-pubkey@192.168.1.100:20444",
+            "029266faff4c8e0ca4f934f34996a96af481df94a89b0c9bd515f3536a95682ddc@example.com:20444",
+        ] {
+            let err = Config::from_config_file(
+                ConfigFile::from_str(&format!(
+                    r#"
+                    [connection_options]
+                    trusted_download_peers = "{bad_peer}"
+                    "#
+                ))
+                .unwrap(),
+                false,
+            )
+            .unwrap_err();
+            assert!(
+                err.starts_with("Invalid connection_options.trusted_download_peers"),
+                "{err}"
+            );
+        }
+    }
+
     #[test]
     fn test_into_config_default_chain_id() {
         // Helper function to create BurnchainConfigFile with mode and optional chain_id
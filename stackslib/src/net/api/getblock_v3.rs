@@ -25,9 +25,9 @@ use crate::chainstate::nakamoto::{NakamotoBlock, NakamotoStagingBlocksConn};
 use crate::chainstate::stacks::db::StacksChainState;
 use crate::chainstate::stacks::Error as ChainError;
 use crate::net::http::{
-    parse_bytes, Error, HttpChunkGenerator, HttpContentType, HttpNotFound, HttpRequest,
-    HttpRequestContents, HttpRequestPreamble, HttpResponse, HttpResponseContents,
-    HttpResponsePayload, HttpResponsePreamble, HttpServerError,
+    accepts_gzip, parse_bytes, Error, GzipChunkGenerator, HttpChunkGenerator, HttpContentType,
+    HttpNotFound, HttpRequest, HttpRequestContents, HttpRequestPreamble, HttpResponse,
+    HttpResponseContents, HttpResponsePayload, HttpResponsePreamble, HttpServerError,
 };
 use crate::net::httpcore::{RPCRequestHandler, StacksHttpRequest, StacksHttpResponse};
 use crate::net::{Error as NetError, StacksNodeState};
@@ -202,17 +202,20 @@ impl RPCRequestHandler for RPCNakamotoBlockRequestHandler {
             }
         };
 
-        let resp_preamble = HttpResponsePreamble::from_http_request_preamble(
+        let mut resp_preamble = HttpResponsePreamble::from_http_request_preamble(
             &preamble,
             200,
             "OK",
             None,
             HttpContentType::Bytes,
         );
-        Ok((
-            resp_preamble,
-            HttpResponseContents::from_stream(Box::new(stream)),
-        ))
+        let stream: Box<dyn HttpChunkGenerator> = if accepts_gzip(&preamble) {
+            resp_preamble.add_header("Content-Encoding".into(), "gzip".into());
+            Box::new(GzipChunkGenerator::new(Box::new(stream)))
+        } else {
+            Box::new(stream)
+        };
+        Ok((resp_preamble, HttpResponseContents::from_stream(stream)))
     }
 }
 
@@ -285,6 +288,7 @@ impl HttpChunkGenerator for NakamotoBlockStream {
 }
 
 impl StacksHttpRequest {
+    /// The response body may be gzip-encoded; `decode_nakamoto_block()` handles this.
     pub fn new_get_nakamoto_block(host: PeerHost, block_id: StacksBlockId) -> StacksHttpRequest {
         StacksHttpRequest::new_for_peer(
             host,
@@ -293,6 +297,7 @@ impl StacksHttpRequest {
             HttpRequestContents::new(),
         )
         .expect("FATAL: failed to construct request from infallible data")
+        .with_header("Accept-Encoding".into(), "gzip".into())
     }
 }
 
@@ -24,9 +24,9 @@ use crate::chainstate::stacks::db::StacksChainState;
 use crate::chainstate::stacks::Error as ChainError;
 use crate::net::api::getblock_v3::NakamotoBlockStream;
 use crate::net::http::{
-    parse_bytes, Error, HttpChunkGenerator, HttpContentType, HttpNotFound, HttpRequest,
-    HttpRequestContents, HttpRequestPreamble, HttpResponse, HttpResponseContents,
-    HttpResponsePayload, HttpResponsePreamble, HttpServerError,
+    accepts_gzip, parse_bytes, Error, GzipChunkGenerator, HttpChunkGenerator, HttpContentType,
+    HttpNotFound, HttpRequest, HttpRequestContents, HttpRequestPreamble, HttpResponse,
+    HttpResponseContents, HttpResponsePayload, HttpResponsePreamble, HttpServerError,
 };
 use crate::net::httpcore::{RPCRequestHandler, StacksHttpRequest, StacksHttpResponse};
 use crate::net::{Error as NetError, StacksNodeState};
@@ -251,17 +251,20 @@ impl RPCRequestHandler for RPCNakamotoTenureRequestHandler {
             }
         };
 
-        let resp_preamble = HttpResponsePreamble::from_http_request_preamble(
+        let mut resp_preamble = HttpResponsePreamble::from_http_request_preamble(
             &preamble,
             200,
             "OK",
             None,
             HttpContentType::Bytes,
         );
-        Ok((
-            resp_preamble,
-            HttpResponseContents::from_stream(Box::new(stream)),
-        ))
+        let stream: Box<dyn HttpChunkGenerator> = if accepts_gzip(&preamble) {
+            resp_preamble.add_header("Content-Encoding".into(), "gzip".into());
+            Box::new(GzipChunkGenerator::new(Box::new(stream)))
+        } else {
+            Box::new(stream)
+        };
+        Ok((resp_preamble, HttpResponseContents::from_stream(stream)))
     }
 }
 
@@ -315,6 +318,7 @@ impl HttpChunkGenerator for NakamotoTenureStream {
 }
 
 impl StacksHttpRequest {
+    /// The response body may be gzip-encoded; `decode_nakamoto_tenure()` handles this.
     pub fn new_get_nakamoto_tenure(
         host: PeerHost,
         block_id: StacksBlockId,
@@ -333,6 +337,38 @@ impl StacksHttpRequest {
             HttpRequestContents::new(),
         )
         .expect("FATAL: failed to construct request from infallible data")
+        .with_header("Accept-Encoding".into(), "gzip".into())
+    }
+}
+
+/// Decodes the blocks of a tenure response body one at a time, in the order in which they were
+/// sent.  Once a block fails to decode, no more blocks are yielded.
+pub struct NakamotoTenureBlockDecoder {
+    /// The response body
+    tenure_bytes: Vec<u8>,
+    /// Offset into `tenure_bytes` of the next block
+    offset: usize,
+}
+
+impl Iterator for NakamotoTenureBlockDecoder {
+    type Item = Result<NakamotoBlock, NetError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let remaining = self.tenure_bytes.get(self.offset..).unwrap_or(&[]);
+        if remaining.is_empty() {
+            return None;
+        }
+        let ptr = &mut &remaining[..];
+        match NakamotoBlock::consensus_deserialize(ptr) {
+            Ok(block) => {
+                self.offset += remaining.len() - ptr.len();
+                Some(Ok(block))
+            }
+            Err(e) => {
+                self.offset = self.tenure_bytes.len();
+                Some(Err(e.into()))
+            }
+        }
     }
 }
 
@@ -341,18 +377,20 @@ impl StacksHttpResponse {
     /// The bytes are a concatenation of Nakamoto blocks, with no length prefix.
     /// If it fails, return Self::Error(..)
     pub fn decode_nakamoto_tenure(self) -> Result<Vec<NakamotoBlock>, NetError> {
+        self.decode_nakamoto_tenure_stream()?.collect()
+    }
+
+    /// Decode an HTTP response into a tenure, one block at a time, so the caller can deal with
+    /// each block before the next one is decoded.  See `decode_nakamoto_tenure()`.
+    /// If the response is not a tenure, return Self::Error(..)
+    pub fn decode_nakamoto_tenure_stream(self) -> Result<NakamotoTenureBlockDecoder, NetError> {
         let contents = self.get_http_payload_ok()?;
 
         // contents will be raw bytes
         let tenure_bytes: Vec<u8> = contents.try_into()?;
-        let ptr = &mut tenure_bytes.as_slice();
-
-        let mut blocks = vec![];
-        while !ptr.is_empty() {
-            let block = NakamotoBlock::consensus_deserialize(ptr)?;
-            blocks.push(block);
-        }
-
-        Ok(blocks)
+        Ok(NakamotoTenureBlockDecoder {
+            tenure_bytes,
+            offset: 0,
+        })
     }
 }
@@ -66,6 +66,7 @@ pub mod postfeerate;
 pub mod postmempoolquery;
 pub mod postmicroblock;
 pub mod poststackerdbchunk;
+pub mod posttenure;
 pub mod posttransaction;
 
 #[cfg(test)]
@@ -154,6 +155,7 @@ impl StacksHttp {
         self.register_rpc_endpoint(postmempoolquery::RPCMempoolQueryRequestHandler::new());
         self.register_rpc_endpoint(postmicroblock::RPCPostMicroblockRequestHandler::new());
         self.register_rpc_endpoint(poststackerdbchunk::RPCPostStackerDBChunkRequestHandler::new());
+        self.register_rpc_endpoint(posttenure::RPCPostTenureRequestHandler::new());
         self.register_rpc_endpoint(posttransaction::RPCPostTransactionRequestHandler::new());
     }
 }
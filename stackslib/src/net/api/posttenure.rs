@@ -0,0 +1,324 @@
+// Copyright (C) 2025 Stacks Open Internet Foundation
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use regex::{Captures, Regex};
+use stacks_common::codec::{Error as CodecError, StacksMessageCodec, MAX_PAYLOAD_LEN};
+use stacks_common::types::chainstate::StacksBlockId;
+use stacks_common::types::net::PeerHost;
+
+use super::postblock::StacksBlockAcceptedData;
+use crate::burnchains::Burnchain;
+use crate::chainstate::burn::db::sortdb::SortitionDB;
+use crate::chainstate::nakamoto::staging_blocks::NakamotoBlockObtainMethod;
+use crate::chainstate::nakamoto::{NakamotoBlock, NakamotoChainState};
+use crate::chainstate::stacks::db::StacksChainState;
+use crate::chainstate::stacks::Error as ChainstateError;
+use crate::net::http::{
+    parse_json, Error, HttpContentType, HttpError, HttpRequest, HttpRequestContents,
+    HttpRequestPreamble, HttpResponse, HttpResponseContents, HttpResponsePayload,
+    HttpResponsePreamble,
+};
+use crate::net::httpcore::{RPCRequestHandler, StacksHttpRequest, StacksHttpResponse};
+use crate::net::relay::{BlockAcceptResponse, Relayer};
+use crate::net::{Error as NetError, NakamotoBlocksData, StacksNodeState};
+
+pub static PATH: &str = "/v3/tenures";
+
+#[derive(Clone, Default)]
+pub struct RPCPostTenureRequestHandler {
+    pub blocks: Option<Vec<NakamotoBlock>>,
+}
+
+impl RPCPostTenureRequestHandler {
+    pub fn new() -> Self {
+        Self { blocks: None }
+    }
+
+    /// Decode the tenure's blocks from the body
+    fn parse_posttenure_octets(mut body: &[u8]) -> Result<Vec<NakamotoBlock>, Error> {
+        let blocks = Vec::<NakamotoBlock>::consensus_deserialize(&mut body).map_err(|e| {
+            if let CodecError::DeserializeError(msg) = e {
+                Error::DecodeError(format!("Failed to deserialize posted tenure: {}", msg))
+            } else {
+                e.into()
+            }
+        })?;
+        Ok(blocks)
+    }
+
+    /// Check that the given blocks form a contiguous run of blocks from a single tenure, in
+    /// ascending order by height.
+    /// Returns Ok(()) if so
+    /// Returns Err(reason) if not
+    pub fn check_tenure_contiguity(blocks: &[NakamotoBlock]) -> Result<(), String> {
+        let Some(first_block) = blocks.first() else {
+            return Err("No blocks given".into());
+        };
+        for (parent, block) in blocks.iter().zip(blocks.iter().skip(1)) {
+            if block.header.consensus_hash != first_block.header.consensus_hash {
+                return Err(format!(
+                    "Block {} is in tenure {}, not {}",
+                    &block.block_id(),
+                    &block.header.consensus_hash,
+                    &first_block.header.consensus_hash
+                ));
+            }
+            if block.header.parent_block_id != parent.block_id()
+                || block.header.chain_length != parent.header.chain_length.saturating_add(1)
+            {
+                return Err(format!(
+                    "Block {} does not build on block {}",
+                    &block.block_id(),
+                    &parent.block_id()
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Is the block with the given ID stored in either the staging blocks DB or the headers DB?
+    fn is_block_stored(
+        chainstate: &StacksChainState,
+        block_id: &StacksBlockId,
+    ) -> Result<bool, ChainstateError> {
+        if chainstate
+            .nakamoto_blocks_db()
+            .has_nakamoto_block_with_index_hash(block_id)?
+        {
+            return Ok(true);
+        }
+        Ok(NakamotoChainState::get_block_header(chainstate.db(), block_id)?.is_some())
+    }
+
+    /// Check that every block in the tenure can be stored, before any of them are.  Each
+    /// block's sortition must be known and valid, its parent must be either stored or the block
+    /// before it in the tenure, and its signer signatures must match its reward set.
+    /// Returns Ok(()) if so
+    /// Returns Err(reason) if not
+    fn validate_tenure(
+        burnchain: &Burnchain,
+        sortdb: &SortitionDB,
+        chainstate: &mut StacksChainState,
+        stacks_tip: &StacksBlockId,
+        tenure: &NakamotoBlocksData,
+    ) -> Result<(), String> {
+        let mut prev_block_id = None;
+        for block in tenure.blocks.iter() {
+            let block_id = block.block_id();
+            let sn_opt = SortitionDB::get_block_snapshot_consensus(
+                sortdb.conn(),
+                &block.header.consensus_hash,
+            )
+            .map_err(|e| format!("Failed to load sortition of block {}: {}", &block_id, e))?;
+            if !sn_opt.is_some_and(|sn| sn.pox_valid) {
+                return Err(format!(
+                    "Block {} is in unknown or invalid sortition {}",
+                    &block_id, &block.header.consensus_hash
+                ));
+            }
+
+            let parent_block_id = &block.header.parent_block_id;
+            if prev_block_id.as_ref() != Some(parent_block_id) {
+                let parent_stored =
+                    Self::is_block_stored(chainstate, parent_block_id).map_err(|e| {
+                        format!("Failed to load parent of block {}: {:?}", &block_id, &e)
+                    })?;
+                if !parent_stored {
+                    return Err(format!(
+                        "Parent {} of block {} is not stored",
+                        parent_block_id, &block_id
+                    ));
+                }
+            }
+            prev_block_id = Some(block_id);
+        }
+
+        Relayer::validate_nakamoto_blocks_push(burnchain, sortdb, chainstate, stacks_tip, tenure)
+            .map_err(|e| format!("Invalid tenure: {}", e))
+    }
+}
+
+/// Decode the HTTP request
+impl HttpRequest for RPCPostTenureRequestHandler {
+    fn verb(&self) -> &'static str {
+        "POST"
+    }
+
+    fn path_regex(&self) -> Regex {
+        Regex::new(&format!("^{}(/)?$", PATH)).unwrap()
+    }
+
+    fn metrics_identifier(&self) -> &str {
+        PATH
+    }
+
+    /// Try to decode this request.
+    /// There's nothing to load here, so just make sure the request is well-formed and that the
+    /// blocks are contiguous.
+    fn try_parse_request(
+        &mut self,
+        preamble: &HttpRequestPreamble,
+        _captures: &Captures,
+        query: Option<&str>,
+        body: &[u8],
+    ) -> Result<HttpRequestContents, Error> {
+        if preamble.get_content_length() == 0 {
+            return Err(Error::DecodeError(
+                "Invalid Http request: expected non-zero-length body for PostTenure".to_string(),
+            ));
+        }
+
+        if preamble.get_content_length() > MAX_PAYLOAD_LEN {
+            return Err(Error::DecodeError(
+                "Invalid Http request: PostTenure body is too big".to_string(),
+            ));
+        }
+
+        if Some(HttpContentType::Bytes) != preamble.content_type || preamble.content_type.is_none()
+        {
+            return Err(Error::DecodeError(
+                "Invalid Http request: PostTenure takes application/octet-stream".to_string(),
+            ));
+        }
+
+        let blocks = Self::parse_posttenure_octets(body)?;
+        Self::check_tenure_contiguity(&blocks).map_err(|msg| {
+            Error::DecodeError(format!("Invalid Http request: invalid tenure: {}", msg))
+        })?;
+
+        self.blocks = Some(blocks);
+        Ok(HttpRequestContents::new().query_string(query))
+    }
+}
+
+impl RPCRequestHandler for RPCPostTenureRequestHandler {
+    /// Reset internal state
+    fn restart(&mut self) {
+        self.blocks = None;
+    }
+
+    /// Make the response.
+    /// Every block is validated (see `validate_tenure()`) before any of them are stored, so a
+    /// tenure with a bad block is rejected as a whole.  If a block is nevertheless rejected when
+    /// it is stored, then the request fails, and none of the blocks after it are stored.
+    fn try_handle_request(
+        &mut self,
+        preamble: HttpRequestPreamble,
+        _contents: HttpRequestContents,
+        node: &mut StacksNodeState,
+    ) -> Result<(HttpResponsePreamble, HttpResponseContents), NetError> {
+        // get out the request body
+        let blocks = self
+            .blocks
+            .take()
+            .ok_or(NetError::SendError("`blocks` not set".into()))?;
+        let tenure = NakamotoBlocksData { blocks };
+
+        let response = node
+            .with_node_state(|network, sortdb, chainstate, _mempool, rpc_args| {
+                let stacks_tip = network.stacks_tip.block_id();
+                Self::validate_tenure(
+                    &network.burnchain,
+                    sortdb,
+                    chainstate,
+                    &stacks_tip,
+                    &tenure,
+                )?;
+
+                let mut handle_conn = sortdb.index_handle_at_tip();
+                let mut accepted_blocks = Vec::with_capacity(tenure.blocks.len());
+                for block in tenure.blocks.iter() {
+                    let accepted = Relayer::process_new_nakamoto_block_ext(
+                        &network.burnchain,
+                        sortdb,
+                        &mut handle_conn,
+                        chainstate,
+                        &stacks_tip,
+                        block,
+                        rpc_args.coord_comms,
+                        NakamotoBlockObtainMethod::Uploaded,
+                        false,
+                    )
+                    .map_err(|e| format!("Failed to store block {}: {}", &block.block_id(), e))?;
+                    if let BlockAcceptResponse::Rejected(reason) = &accepted {
+                        return Err(format!(
+                            "Block {} was rejected: {}",
+                            &block.block_id(),
+                            reason
+                        ));
+                    }
+                    accepted_blocks.push(StacksBlockAcceptedData {
+                        accepted: accepted.is_accepted(),
+                        stacks_block_id: block.block_id(),
+                    });
+                }
+                Ok(accepted_blocks)
+            })
+            .map_err(|e: String| StacksHttpResponse::new_error(&preamble, &HttpError::new(400, e)));
+
+        let data_resp = match response {
+            Ok(accepted_blocks) => {
+                debug!(
+                    "Received POSTed Nakamoto tenure with {} blocks",
+                    accepted_blocks.len();
+                    "accepted" => accepted_blocks.iter().filter(|data| data.accepted).count()
+                );
+                accepted_blocks
+            }
+            Err(e) => {
+                return e.try_into_contents().map_err(NetError::from);
+            }
+        };
+
+        let preamble = HttpResponsePreamble::ok_json(&preamble);
+        let body = HttpResponseContents::try_from_json(&data_resp)?;
+        Ok((preamble, body))
+    }
+}
+
+/// Decode the HTTP response
+impl HttpResponse for RPCPostTenureRequestHandler {
+    fn try_parse_response(
+        &self,
+        preamble: &HttpResponsePreamble,
+        body: &[u8],
+    ) -> Result<HttpResponsePayload, Error> {
+        let accepted: Vec<StacksBlockAcceptedData> = parse_json(preamble, body)?;
+        HttpResponsePayload::try_from_json(accepted)
+    }
+}
+
+impl StacksHttpRequest {
+    /// Make a new post-tenure request.  The blocks must be in ascending order by height.
+    pub fn new_post_nakamoto_tenure(host: PeerHost, blocks: &[NakamotoBlock]) -> StacksHttpRequest {
+        StacksHttpRequest::new_for_peer(
+            host,
+            "POST".into(),
+            PATH.into(),
+            HttpRequestContents::new().payload_stacks(&blocks.to_vec()),
+        )
+        .expect("FATAL: failed to construct request from infallible data")
+    }
+}
+
+impl StacksHttpResponse {
+    pub fn decode_stacks_tenure_accepted(self) -> Result<Vec<StacksBlockAcceptedData>, NetError> {
+        let contents = self.get_http_payload_ok()?;
+        let response_json: serde_json::Value = contents.try_into()?;
+        let result: Vec<StacksBlockAcceptedData> = serde_json::from_value(response_json)
+            .map_err(|_e| NetError::DeserializeError("Failed to load from JSON".to_string()))?;
+        Ok(result)
+    }
+}
@@ -54,6 +54,7 @@ fn test_try_parse_request() {
 
     // parsed request consumes headers that would not be in a constructed reqeuest
     parsed_request.clear_headers();
+    parsed_request.add_header("accept-encoding".into(), "gzip".into());
     let (preamble, contents) = parsed_request.destruct();
 
     // consumed path args
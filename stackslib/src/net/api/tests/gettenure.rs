@@ -25,8 +25,8 @@ use crate::chainstate::nakamoto::{NakamotoBlock, NakamotoChainState};
 use crate::net::api::gettenure::NakamotoTenureStream;
 use crate::net::api::*;
 use crate::net::connection::ConnectionOptions;
-use crate::net::http::HttpChunkGenerator;
-use crate::net::httpcore::{RPCRequestHandler, StacksHttp, StacksHttpRequest};
+use crate::net::http::{HttpChunkGenerator, HttpResponsePayload};
+use crate::net::httpcore::{RPCRequestHandler, StacksHttp, StacksHttpRequest, StacksHttpResponse};
 use crate::net::test::TestEventObserver;
 use crate::net::tests::inv::nakamoto::make_nakamoto_peer_from_invs;
 use crate::net::ProtocolFamily;
@@ -54,6 +54,7 @@ fn test_try_parse_request() {
 
     // parsed request consumes headers that would not be in a constructed reqeuest
     parsed_request.clear_headers();
+    parsed_request.add_header("accept-encoding".into(), "gzip".into());
     let (preamble, contents) = parsed_request.destruct();
 
     // consumed path args
@@ -107,6 +108,102 @@ fn test_try_make_response() {
     assert_eq!(preamble.status_code, 404);
 }
 
+/// A gzip-encoded tenure response decodes to the same blocks as an uncompressed one
+#[test]
+fn test_try_make_gzip_response() {
+    let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 33333);
+
+    let test_observer = TestEventObserver::new();
+    let rpc_test = TestRPC::setup_nakamoto(function_name!(), &test_observer);
+
+    let nakamoto_chain_tip = rpc_test.canonical_tip.clone();
+
+    // downloader requests ask for gzip
+    let gzip_request =
+        StacksHttpRequest::new_get_nakamoto_tenure(addr.into(), nakamoto_chain_tip.clone(), None);
+    assert_eq!(
+        gzip_request.preamble().get_header("accept-encoding".into()),
+        Some("gzip".to_string())
+    );
+
+    let mut plain_request = gzip_request.clone();
+    assert!(plain_request
+        .preamble_mut()
+        .remove_header("accept-encoding".into()));
+
+    let mut responses = rpc_test.run(vec![gzip_request, plain_request]);
+
+    let gzip_response = responses.remove(0);
+    assert_eq!(
+        gzip_response
+            .preamble()
+            .get_header("content-encoding".into()),
+        Some("gzip".to_string())
+    );
+
+    let plain_response = responses.remove(0);
+    assert!(plain_response
+        .preamble()
+        .get_header("content-encoding".into())
+        .is_none());
+
+    let gzip_blocks = gzip_response.decode_nakamoto_tenure().unwrap();
+    let plain_blocks = plain_response.decode_nakamoto_tenure().unwrap();
+
+    assert_eq!(gzip_blocks.len(), 10);
+    assert_eq!(
+        gzip_blocks.first().unwrap().header.block_id(),
+        nakamoto_chain_tip
+    );
+    assert_eq!(gzip_blocks, plain_blocks);
+}
+
+/// Decoding a tenure one block at a time yields the same blocks as decoding it all at once, and
+/// stops at the first block which can't be decoded
+#[test]
+fn test_decode_nakamoto_tenure_stream() {
+    let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 33333);
+
+    let test_observer = TestEventObserver::new();
+    let rpc_test = TestRPC::setup_nakamoto(function_name!(), &test_observer);
+
+    let nakamoto_chain_tip = rpc_test.canonical_tip.clone();
+    let request =
+        StacksHttpRequest::new_get_nakamoto_tenure(addr.into(), nakamoto_chain_tip.clone(), None);
+
+    let mut responses = rpc_test.run(vec![request]);
+    let response = responses.remove(0);
+
+    let blocks = response.clone().decode_nakamoto_tenure().unwrap();
+    assert_eq!(blocks.len(), 10);
+    assert_eq!(
+        blocks.first().unwrap().header.block_id(),
+        nakamoto_chain_tip
+    );
+
+    let streamed_blocks: Vec<_> = response
+        .clone()
+        .decode_nakamoto_tenure_stream()
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(streamed_blocks, blocks);
+
+    // cut off the last block
+    let (preamble, body) = response.destruct();
+    let mut tenure_bytes: Vec<u8> = body.try_into().unwrap();
+    tenure_bytes.pop();
+    let truncated_response =
+        StacksHttpResponse::new(preamble, HttpResponsePayload::Bytes(tenure_bytes));
+
+    let mut stream = truncated_response.decode_nakamoto_tenure_stream().unwrap();
+    for block in blocks.iter().take(9) {
+        assert_eq!(&stream.next().unwrap().unwrap(), block);
+    }
+    assert!(stream.next().unwrap().is_err());
+    assert!(stream.next().is_none());
+}
+
 #[test]
 fn test_stream_nakamoto_tenure() {
     let test_observer = TestEventObserver::new();
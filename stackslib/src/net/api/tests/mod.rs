@@ -106,6 +106,7 @@ mod postfeerate;
 mod postmempoolquery;
 mod postmicroblock;
 mod poststackerdbchunk;
+mod posttenure;
 mod posttransaction;
 
 const TEST_CONTRACT: &str = "
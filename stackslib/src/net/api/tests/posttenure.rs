@@ -0,0 +1,224 @@
+// Copyright (C) 2025 Stacks Open Internet Foundation
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+
+use stacks_common::types::chainstate::{ConsensusHash, StacksBlockId, StacksPrivateKey};
+use stacks_common::types::StacksEpochId;
+
+use super::TestRPC;
+use crate::chainstate::nakamoto::NakamotoBlock;
+use crate::chainstate::stacks::test::make_codec_test_nakamoto_block;
+use crate::net::api::*;
+use crate::net::connection::ConnectionOptions;
+use crate::net::httpcore::{RPCRequestHandler, StacksHttp, StacksHttpRequest};
+use crate::net::test::TestEventObserver;
+
+/// Make a contiguous run of `num_blocks` codec-test blocks
+fn make_codec_test_tenure(miner_sk: &StacksPrivateKey, num_blocks: usize) -> Vec<NakamotoBlock> {
+    let mut blocks: Vec<NakamotoBlock> = vec![];
+    for _ in 0..num_blocks {
+        let mut block = make_codec_test_nakamoto_block(StacksEpochId::Epoch30, miner_sk);
+        if let Some(parent) = blocks.last() {
+            block.header.parent_block_id = parent.block_id();
+            block.header.chain_length = parent.header.chain_length + 1;
+        }
+        blocks.push(block);
+    }
+    blocks
+}
+
+#[test]
+fn parse_request() {
+    let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 33333);
+    let mut http = StacksHttp::new(addr, &ConnectionOptions::default());
+
+    let miner_sk = StacksPrivateKey::from_seed(&[0, 1, 2, 3, 4, 5, 6, 7, 8]);
+    let blocks = make_codec_test_tenure(&miner_sk, 3);
+    let request = StacksHttpRequest::new_post_nakamoto_tenure(addr.into(), &blocks);
+    let bytes = request.try_serialize().unwrap();
+
+    let (parsed_preamble, offset) = http.read_preamble(&bytes).unwrap();
+    let mut handler = posttenure::RPCPostTenureRequestHandler::new();
+    let mut parsed_request = http
+        .handle_try_parse_request(
+            &mut handler,
+            &parsed_preamble.expect_request(),
+            &bytes[offset..],
+        )
+        .unwrap();
+
+    assert_eq!(handler.blocks, Some(blocks.clone()));
+
+    // parsed request consumes headers that would not be in a constructed reqeuest
+    parsed_request.clear_headers();
+    let (preamble, _contents) = parsed_request.destruct();
+
+    assert_eq!(&preamble, request.preamble());
+
+    handler.restart();
+    assert!(handler.blocks.is_none());
+
+    // blocks must be contiguous, in ascending order, and from the same tenure
+    let mut out_of_order = blocks.clone();
+    out_of_order.swap(1, 2);
+
+    let mut wrong_tenure = blocks.clone();
+    wrong_tenure[2].header.consensus_hash = ConsensusHash([0x22; 20]);
+
+    let mut gap = blocks.clone();
+    gap.remove(1);
+
+    for bad_blocks in [vec![], out_of_order, wrong_tenure, gap] {
+        let mut http = StacksHttp::new(addr, &ConnectionOptions::default());
+        let request = StacksHttpRequest::new_post_nakamoto_tenure(addr.into(), &bad_blocks);
+        let bytes = request.try_serialize().unwrap();
+        let (parsed_preamble, offset) = http.read_preamble(&bytes).unwrap();
+        match http.handle_try_parse_request(
+            &mut handler,
+            &parsed_preamble.expect_request(),
+            &bytes[offset..],
+        ) {
+            Err(NetError::Http(Error::DecodeError(..))) => {}
+            _ => {
+                panic!("worked with bad tenure");
+            }
+        }
+
+        handler.restart();
+        assert!(handler.blocks.is_none());
+    }
+}
+
+#[test]
+fn handle_req_accepted() {
+    let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 33333);
+
+    let observer = TestEventObserver::new();
+    let mut rpc_test = TestRPC::setup_nakamoto(function_name!(), &observer);
+    let (next_block, ..) = rpc_test.peer_1.single_block_tenure(
+        &rpc_test.privk1,
+        |_| {},
+        |burn_ops| {
+            rpc_test.peer_2.next_burnchain_block(burn_ops.clone());
+        },
+        |_| true,
+    );
+    let next_block_id = next_block.block_id();
+
+    // same block, but without signer signatures (the block ID doesn't change)
+    let mut unsigned_block = next_block.clone();
+    unsigned_block.header.signer_signature.clear();
+    assert_eq!(unsigned_block.block_id(), next_block_id);
+
+    let requests = vec![
+        // rejected as a whole, so nothing gets stored
+        StacksHttpRequest::new_post_nakamoto_tenure(addr.into(), &[unsigned_block]),
+        // post the tenure
+        StacksHttpRequest::new_post_nakamoto_tenure(addr.into(), &[next_block.clone()]),
+        // idempotent
+        StacksHttpRequest::new_post_nakamoto_tenure(addr.into(), &[next_block]),
+    ];
+
+    let mut responses = rpc_test.run(requests);
+
+    let response = responses.remove(0);
+    info!(
+        "Response: {}",
+        std::str::from_utf8(&response.try_serialize().unwrap()).unwrap()
+    );
+    let (preamble, _body) = response.destruct();
+    assert_eq!(preamble.status_code, 400);
+
+    let response = responses.remove(0);
+    info!(
+        "Response: {}",
+        std::str::from_utf8(&response.try_serialize().unwrap()).unwrap()
+    );
+    let resp = response.decode_stacks_tenure_accepted().unwrap();
+    assert_eq!(resp.len(), 1);
+    assert!(resp[0].accepted);
+    assert_eq!(resp[0].stacks_block_id, next_block_id);
+
+    // the block was staged by the previous request
+    let response = responses.remove(0);
+    info!(
+        "Response: {}",
+        std::str::from_utf8(&response.try_serialize().unwrap()).unwrap()
+    );
+    let resp = response.decode_stacks_tenure_accepted().unwrap();
+    assert_eq!(resp.len(), 1);
+    assert!(!resp[0].accepted);
+    assert_eq!(resp[0].stacks_block_id, next_block_id);
+}
+
+#[test]
+fn handle_req_rejects_whole_tenure() {
+    let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 33333);
+
+    let observer = TestEventObserver::new();
+    let mut rpc_test = TestRPC::setup_nakamoto(function_name!(), &observer);
+    let (next_block, ..) = rpc_test.peer_1.single_block_tenure(
+        &rpc_test.privk1,
+        |_| {},
+        |burn_ops| {
+            rpc_test.peer_2.next_burnchain_block(burn_ops.clone());
+        },
+        |_| true,
+    );
+    let next_block_id = next_block.block_id();
+
+    // a child of the block in the same tenure, whose signer signatures are for its parent
+    let mut badly_signed_child = next_block.clone();
+    badly_signed_child.header.parent_block_id = next_block_id.clone();
+    badly_signed_child.header.chain_length += 1;
+
+    // a block whose parent is neither stored nor posted
+    let mut orphan = next_block.clone();
+    orphan.header.parent_block_id = StacksBlockId([0x11; 32]);
+
+    let requests = vec![
+        // the first block is fine, but the second is not, so neither gets stored
+        StacksHttpRequest::new_post_nakamoto_tenure(
+            addr.into(),
+            &[next_block.clone(), badly_signed_child],
+        ),
+        StacksHttpRequest::new_post_nakamoto_tenure(addr.into(), &[orphan]),
+        // the first block was not staged by the failed requests
+        StacksHttpRequest::new_post_nakamoto_tenure(addr.into(), &[next_block]),
+    ];
+
+    let mut responses = rpc_test.run(requests);
+
+    for _ in 0..2 {
+        let response = responses.remove(0);
+        info!(
+            "Response: {}",
+            std::str::from_utf8(&response.try_serialize().unwrap()).unwrap()
+        );
+        let (preamble, _body) = response.destruct();
+        assert_eq!(preamble.status_code, 400);
+    }
+
+    let response = responses.remove(0);
+    info!(
+        "Response: {}",
+        std::str::from_utf8(&response.try_serialize().unwrap()).unwrap()
+    );
+    let resp = response.decode_stacks_tenure_accepted().unwrap();
+    assert_eq!(resp.len(), 1);
+    assert!(resp[0].accepted);
+    assert_eq!(resp[0].stacks_block_id, next_block_id);
+}
@@ -428,6 +428,61 @@ pub struct ConnectionOptions {
     pub nakamoto_inv_sync_burst_interval_ms: u128,
     /// time between unconfirmed downloader runs
     pub nakamoto_unconfirmed_downloader_interval_ms: u128,
+    /// number of unconfirmed tenure downloaders that may run while the highest complete tenure is
+    /// still being downloaded, so that the node keeps learning about the chain tip.  0 means that
+    /// unconfirmed tenure downloads wait until the highest complete tenure is downloaded.
+    pub nakamoto_unconfirmed_downloader_reserved_inflight: u64,
+    /// maximum number of blocks by which the sortition tip and the burnchain height may differ
+    /// for the block downloader to start fetching unconfirmed tenures.  0 means that the
+    /// sortition tip must be at the burnchain tip.
+    pub nakamoto_unconfirmed_downloader_max_burnchain_divergence: u64,
+    /// maximum number of unconfirmed tenure downloaders to run at once.  Every neighbor serves
+    /// roughly the same unconfirmed blocks, so there's little point in asking many of them.
+    pub nakamoto_max_unconfirmed_downloaders: u64,
+    /// minimum number of confirmed tenure downloaders to reserve for tenures in the previous
+    /// reward cycle while any of them remain to be downloaded, regardless of how they're ordered
+    /// in the download schedule.  0 means no downloaders are reserved.
+    pub nakamoto_prev_cycle_download_slots: u64,
+    /// number of unprocessed blocks in the Nakamoto staging DB at which the block downloader stops
+    /// scheduling new confirmed tenure downloads, so it doesn't outrun block processing.
+    /// In-flight downloads are not cancelled.  0 disables this check.
+    pub nakamoto_download_staging_high_water_mark: u64,
+    /// number of unprocessed blocks in the Nakamoto staging DB at or below which the block
+    /// downloader resumes scheduling confirmed tenure downloads, once it has stopped due to
+    /// `nakamoto_download_staging_high_water_mark`.
+    pub nakamoto_download_staging_low_water_mark: u64,
+    /// neighbors which the block downloader asks for each tenure before any other neighbor that
+    /// can serve it.  This is an operator allowlist for private or federated deployments.
+    pub trusted_download_peers: Vec<NeighborAddress>,
+    /// whether or not the block downloader only fetches confirmed tenures, and never the ongoing
+    /// tenure's blocks.  Archival and indexer nodes which only care about finalized tenures can
+    /// set this to avoid running unconfirmed tenure downloaders altogether.
+    pub nakamoto_download_confirmed_only: bool,
+    /// minimum number of peers which must have provided their tenure inventories for the block
+    /// downloader's target reward cycle before it schedules confirmed tenure downloads during IBD.
+    /// This keeps a booting node from committing to the inventories of its first one or two
+    /// peers, which may be partial or biased.  0 and 1 are equivalent.
+    pub min_peers_for_ibd: u64,
+    /// whether or not the block downloader holds back downloaded tenures whose first block's
+    /// parent is neither stored nor downloaded, instead of returning them for processing
+    pub nakamoto_download_quarantine_disconnected_tenures: bool,
+    /// whether or not the block downloader recomputes the transaction Merkle root of each
+    /// downloaded tenure block and rejects blocks whose transactions don't match their header.
+    /// Blocks are checked again when they are processed, so this is only defense-in-depth.
+    pub nakamoto_download_verify_tx_merkle_roots: bool,
+    /// minimum download backlog, in tenures, at which the block downloader considers the node to
+    /// be in IBD (and fetches tenures in sortition order instead of rarest-first), for when its
+    /// caller doesn't say.  The backlog counts unprocessed wanted tenures plus the burnchain
+    /// blocks the sortition DB has yet to process.
+    pub nakamoto_download_ibd_backlog_threshold: u64,
+    /// whether or not the block downloader checkpoints its progress through each confirmed
+    /// tenure's blocks to the staging DB, so that a long tenure's download can resume from where
+    /// it left off after a restart instead of starting over
+    pub nakamoto_download_checkpoint_tenures: bool,
+    /// maximum number of blocks the block downloader returns for processing from a single pass
+    /// over the unconfirmed tenures.  Blocks in excess of this are held until the next pass.
+    /// 0 means no limit.
+    pub nakamoto_unconfirmed_download_max_coalesced_blocks: u64,
     /// The authorization token to enable privileged RPC endpoints
     pub auth_token: Option<String>,
     /// The maximum age in seconds of a block that can be validated by the block proposal endpoint
@@ -568,6 +623,20 @@ impl std::default::Default for ConnectionOptions {
             nakamoto_push_interval_ms: 30_000, // re-send a block no more than once every 30 seconds
             nakamoto_inv_sync_burst_interval_ms: 1_000, // wait 1 second after a sortition before running inventory sync
             nakamoto_unconfirmed_downloader_interval_ms: 5_000, // run unconfirmed downloader once every 5 seconds
+            nakamoto_unconfirmed_downloader_reserved_inflight: 0,
+            nakamoto_unconfirmed_downloader_max_burnchain_divergence: 0,
+            nakamoto_max_unconfirmed_downloaders: 3,
+            nakamoto_prev_cycle_download_slots: 0,
+            nakamoto_download_staging_high_water_mark: 0,
+            nakamoto_download_staging_low_water_mark: 0,
+            trusted_download_peers: vec![],
+            nakamoto_download_confirmed_only: false,
+            min_peers_for_ibd: 1,
+            nakamoto_download_quarantine_disconnected_tenures: false,
+            nakamoto_download_verify_tx_merkle_roots: false,
+            nakamoto_download_ibd_backlog_threshold: 144,
+            nakamoto_download_checkpoint_tenures: false,
+            nakamoto_unconfirmed_download_max_coalesced_blocks: 4096,
             auth_token: None,
             block_proposal_max_age_secs: DEFAULT_BLOCK_PROPOSAL_MAX_AGE_SECS,
             stackerdb_hint_replicas: HashMap::new(),
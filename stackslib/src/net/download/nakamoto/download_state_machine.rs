@@ -18,7 +18,7 @@ use std::convert::TryFrom;
 use std::fmt;
 
 use stacks_common::types::chainstate::{ConsensusHash, StacksBlockId};
-use stacks_common::util::get_epoch_time_ms;
+use stacks_common::util::{get_epoch_time_ms, get_epoch_time_secs};
 
 use crate::burnchains::{BurnchainView, PoxConstants};
 use crate::chainstate::burn::db::sortdb::{SortitionDB, SortitionHandleConn};
@@ -28,8 +28,9 @@ use crate::chainstate::stacks::db::StacksChainState;
 use crate::net::chat::ConversationP2P;
 use crate::net::connection::ConnectionOptions;
 use crate::net::download::nakamoto::{
-    AvailableTenures, NakamotoTenureDownloader, NakamotoTenureDownloaderSet,
-    NakamotoUnconfirmedTenureDownloader, TenureStartEnd, WantedTenure,
+    AbandonedTenureReason, AvailableTenures, BlockSink, DownloadPeerSelector,
+    NakamotoTenureDownloader, NakamotoTenureDownloaderSet, NakamotoUnconfirmedTenureDownloader,
+    SignerSignatureCache, TenureStartEnd, WantedTenure,
 };
 use crate::net::inv::nakamoto::NakamotoTenureInv;
 use crate::net::neighbors::rpc::NeighborRPC;
@@ -40,12 +41,49 @@ use crate::util_lib::db::Error as DBError;
 /// How often to check for unconfirmed tenures
 const CHECK_UNCONFIRMED_TENURES_MS: u128 = 1_000;
 
+/// How many seconds of history to average over when computing the download rate
+const DOWNLOAD_RATE_WINDOW_SECS: u64 = 60;
+
+/// How long a neighbor which we marked dead or broken is treated as unable to serve any tenure
+pub const PEER_BLOCKLIST_TIME_SECS: u64 = 60;
+
+/// How long a downloaded tenure which does not connect to the chainstate is held back, waiting
+/// for its parent to arrive, before it is discarded
+pub const TENURE_QUARANTINE_TIME_SECS: u64 = 300;
+
+/// How many download passes in a row may find no tenures to download before the downloader stops
+/// recomputing the available tenures on every pass
+const EMPTY_PASS_BACKOFF_THRESHOLD: u64 = 3;
+
+/// How long to wait before recomputing the available tenures, once the downloader backs off.  This
+/// doubles with each further empty pass, up to `EMPTY_PASS_BACKOFF_MAX_MS`.
+const EMPTY_PASS_BACKOFF_BASE_MS: u128 = 1_000;
+
+/// The longest the downloader will wait before recomputing the available tenures, absent any
+/// change in its peers' inventories or its wanted tenures
+const EMPTY_PASS_BACKOFF_MAX_MS: u128 = 60_000;
+
+/// A consumer of reward cycle download progress, for nodes which act once they have every tenure
+/// in a reward cycle (e.g. to compute a reward set).
+pub trait RewardCycleCompleteHandler {
+    /// Called once the downloader has processed every tenure in `reward_cycle`
+    fn on_reward_cycle_complete(&mut self, reward_cycle: u64);
+}
+
+impl<F: FnMut(u64)> RewardCycleCompleteHandler for F {
+    fn on_reward_cycle_complete(&mut self, reward_cycle: u64) {
+        self(reward_cycle)
+    }
+}
+
 /// The overall downloader can operate in one of two states:
 /// * it's doing IBD, in which case it's downloading tenures using neighbor inventories and
 /// the start/end block ID hashes obtained from block-commits.  This works up until the last two
 /// tenures.
 /// * it's in steady-state, in which case it's downloading the last two tenures from its neighbors.
-#[derive(Debug, Clone, PartialEq)]
+///
+/// Serializes to the same string as its `Display` form (e.g. `"Confirmed"`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum NakamotoDownloadState {
     /// confirmed tenure download (IBD)
     Confirmed,
@@ -59,6 +97,126 @@ impl fmt::Display for NakamotoDownloadState {
     }
 }
 
+/// Why the block downloader is not (yet) fetching unconfirmed tenures
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UnconfirmedTenuresBlocker {
+    /// The sortition DB lags the burnchain tip by more than the allowed divergence
+    SortitionBehindBurnchain {
+        sort_tip_height: u64,
+        burnchain_height: u64,
+    },
+    /// The sortition tip is too far ahead of our view of the burnchain tip
+    BurnchainDivergence {
+        sort_tip_height: u64,
+        burnchain_height: u64,
+    },
+    /// There are no wanted tenures in the current reward cycle
+    NoWantedTenures,
+    /// There are no wanted tenures in the previous reward cycle
+    NoPrevWantedTenures,
+    /// No neighbor has told us which tenures it can serve
+    NoTenureAvailability,
+    /// This tenure can be fetched by the confirmed tenure downloader, but has not been processed
+    UnprocessedTenure(ConsensusHash),
+}
+
+impl UnconfirmedTenuresBlocker {
+    /// A stable, machine-readable name for this reason, for logging
+    pub fn reason(&self) -> &'static str {
+        match self {
+            Self::SortitionBehindBurnchain { .. } => "sortition_behind_burnchain",
+            Self::BurnchainDivergence { .. } => "burnchain_divergence",
+            Self::NoWantedTenures => "no_wanted_tenures",
+            Self::NoPrevWantedTenures => "no_prev_wanted_tenures",
+            Self::NoTenureAvailability => "no_tenure_availability",
+            Self::UnprocessedTenure(..) => "unprocessed_tenure",
+        }
+    }
+}
+
+/// Summary of the block downloader's progress, for reporting to operators.  This is also attached
+/// to the log lines at the start and end of each download pass.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NakamotoDownloadMetrics {
+    /// Number of download passes (calls to `run()`) so far
+    pub download_pass: u64,
+    /// Download behavior we're in
+    pub state: NakamotoDownloadState,
+    /// Reward cycle we're tracking
+    pub reward_cycle: u64,
+    /// Number of confirmed tenure downloaders
+    pub num_downloaders: usize,
+    /// Number of unconfirmed tenure downloaders
+    pub num_unconfirmed_downloaders: usize,
+    /// Number of confirmed tenure downloaders with a request in flight
+    pub inflight: usize,
+    /// Number of confirmed tenures downloaded so far
+    pub completed_tenures: usize,
+    /// Number of confirmed tenures waiting to be downloaded
+    pub scheduled_tenures: usize,
+    /// Number of neighbors waiting to be asked for unconfirmed tenures
+    pub scheduled_unconfirmed_tenures: usize,
+    /// Number of neighbors disconnected because they stopped responding
+    pub dead_peers: u64,
+    /// Number of neighbors banned because they misbehaved
+    pub broken_peers: u64,
+}
+
+impl slog::KV for NakamotoDownloadMetrics {
+    fn serialize(
+        &self,
+        _record: &slog::Record,
+        serializer: &mut dyn slog::Serializer,
+    ) -> slog::Result {
+        serializer.emit_u64("download_pass", self.download_pass)?;
+        serializer.emit_arguments("state", &format_args!("{}", &self.state))?;
+        serializer.emit_u64("reward_cycle", self.reward_cycle)?;
+        serializer.emit_usize("num_downloaders", self.num_downloaders)?;
+        serializer.emit_usize(
+            "num_unconfirmed_downloaders",
+            self.num_unconfirmed_downloaders,
+        )?;
+        serializer.emit_usize("inflight", self.inflight)?;
+        serializer.emit_usize("completed_tenures", self.completed_tenures)?;
+        serializer.emit_usize("scheduled_tenures", self.scheduled_tenures)?;
+        serializer.emit_usize(
+            "scheduled_unconfirmed_tenures",
+            self.scheduled_unconfirmed_tenures,
+        )?;
+        serializer.emit_u64("dead_peers", self.dead_peers)?;
+        serializer.emit_u64("broken_peers", self.broken_peers)
+    }
+}
+
+/// What the block downloader knows about a wanted tenure, for reporting to operators
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TenureStatus {
+    /// Consensus hash of the tenure's sortition
+    pub tenure_id: ConsensusHash,
+    /// Burnchain height of the tenure's sortition
+    pub burn_height: u64,
+    /// Whether or not the tenure has been processed
+    pub processed: bool,
+    /// Number of neighbors which can serve the tenure
+    pub num_available_peers: usize,
+    /// Tenure-start block ID, if any neighbor's inventory has told us what it is
+    pub start_block_id: Option<StacksBlockId>,
+    /// Tenure-end block ID, if any neighbor's inventory has told us what it is
+    pub end_block_id: Option<StacksBlockId>,
+    /// Whether or not a confirmed tenure downloader is fetching the tenure
+    pub downloading: bool,
+}
+
+/// The tenures a neighbor can serve, as learned from its inventory.  This is the form in which
+/// `NakamotoDownloadStateMachine::export_tenure_block_ids()` writes out each neighbor's entry.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NeighborAvailableTenures {
+    /// The neighbor
+    pub neighbor: NeighborAddress,
+    /// The tenures it can serve, keyed by tenure ID
+    pub tenures: BTreeMap<ConsensusHash, TenureStartEnd>,
+}
+
 /// The top-level block download state machine
 pub struct NakamotoDownloadStateMachine {
     /// What's the start burn block height for Nakamoto?
@@ -71,11 +229,11 @@ pub struct NakamotoDownloadStateMachine {
     /// cycle of Nakamoto
     pub(crate) prev_wanted_tenures: Option<Vec<WantedTenure>>,
     /// Last burnchain tip we've seen
-    last_sort_tip: Option<BlockSnapshot>,
+    pub(crate) last_sort_tip: Option<BlockSnapshot>,
     /// Download behavior we're in
     state: NakamotoDownloadState,
     /// Map a tenure ID to its tenure start-block and end-block for each of our neighbors' invs
-    tenure_block_ids: HashMap<NeighborAddress, AvailableTenures>,
+    pub(crate) tenure_block_ids: HashMap<NeighborAddress, AvailableTenures>,
     /// Who can serve a given tenure
     pub(crate) available_tenures: HashMap<ConsensusHash, Vec<NeighborAddress>>,
     /// Confirmed tenure download schedule
@@ -85,21 +243,80 @@ pub struct NakamotoDownloadStateMachine {
     /// Ongoing unconfirmed tenure downloads, prioritized in who announces the latest block
     unconfirmed_tenure_downloads: HashMap<NeighborAddress, NakamotoUnconfirmedTenureDownloader>,
     /// Ongoing confirmed tenure downloads for when we know the start and end block hashes.
-    tenure_downloads: NakamotoTenureDownloaderSet,
+    pub(crate) tenure_downloads: NakamotoTenureDownloaderSet,
     /// comms to remote neighbors
     pub(super) neighbor_rpc: NeighborRPC,
     /// Nakamoto chain tip
     nakamoto_tip: StacksBlockId,
     /// do we need to fetch unconfirmed tenures?
-    fetch_unconfirmed_tenures: bool,
+    pub(crate) fetch_unconfirmed_tenures: bool,
     /// last time an unconfirmed tenures was checked
     last_unconfirmed_download_check_ms: u128,
+    /// why we did not start fetching unconfirmed tenures the last time we checked, if we didn't
+    last_unconfirmed_tenures_blocker: Option<UnconfirmedTenuresBlocker>,
     /// last time an unconfirmed downloader was run
     last_unconfirmed_download_run_ms: u128,
+    /// Number of download passes (calls to `run()`) so far.  Logged with each pass so that log
+    /// lines from the same pass can be correlated.
+    download_pass: u64,
+    /// Where to send the tenures obtained in each download pass.  If not set, they are returned
+    /// from `run()`.
+    block_sink: Option<Box<dyn BlockSink + Send>>,
+    /// Number of blocks downloaded in each of the last `DOWNLOAD_RATE_WINDOW_SECS` seconds, as
+    /// (epoch second, number of blocks).  Seconds without any blocks are omitted.
+    pub(crate) downloaded_blocks_per_sec: VecDeque<(u64, u64)>,
+    /// Tenure-start blocks supplied by `seed_tenure_start_blocks()`, which downloaders use instead
+    /// of fetching them
+    known_tenure_start_blocks: HashMap<StacksBlockId, NakamotoBlock>,
+    /// Number of neighbors we've disconnected from for not responding
+    pub(super) num_dead_peers: u64,
+    /// Number of neighbors we've banned for misbehaving
+    pub(super) num_broken_peers: u64,
+    /// Neighbors we recently marked dead or broken, mapped to the epoch second at which they can
+    /// be considered for tenure availability again.  This outlives the neighbor RPC's dead and
+    /// broken sets, which are cleared after every pass.
+    pub(crate) blocklisted_peers: HashMap<NeighborAddress, u64>,
+    /// Downloaded tenures whose first block's parent we do not have, mapped to the epoch second
+    /// at which they were first quarantined and their blocks.
+    pub(crate) quarantined_tenures: HashMap<ConsensusHash, (u64, Vec<NakamotoBlock>)>,
+    /// Blocks obtained while fetching unconfirmed tenures which did not fit in the pass's block
+    /// limit, and which will be returned in a subsequent pass.  Each tenure's blocks are sorted
+    /// by height.
+    pub(crate) deferred_unconfirmed_blocks: HashMap<ConsensusHash, Vec<NakamotoBlock>>,
+    /// Whether or not we've stopped scheduling confirmed tenure downloads because too many
+    /// downloaded blocks are waiting to be processed
+    pub(crate) staging_backpressure: bool,
+    /// Neighbors to ask for each tenure before any others, from the connection options
+    pub(crate) trusted_download_peers: HashSet<NeighborAddress>,
+    /// Number of times in a row that recomputing the available tenures yielded nothing to download
+    pub(crate) num_empty_passes: u64,
+    /// Epoch millisecond before which the available tenures will not be recomputed, unless our
+    /// peers' inventories (or our wanted tenures) change, or a blocklisted peer is let back in
+    pub(crate) empty_pass_backoff_deadline_ms: u128,
+    /// Each peer's tenure inventory generation as of the last time the available tenures were
+    /// considered
+    last_inventory_generations: HashMap<NeighborAddress, u64>,
+    /// The reward cycle, the number of wanted tenures, and how many of them were processed, as of
+    /// the last time the available tenures were considered
+    last_wanted_tenures_progress: Option<(u64, usize, usize)>,
+    /// Who to tell when every tenure in a reward cycle has been processed
+    reward_cycle_complete_handler: Option<Box<dyn RewardCycleCompleteHandler + Send>>,
+    /// Highest reward cycle whose tenures have all been processed, as reported to
+    /// `reward_cycle_complete_handler`
+    pub(crate) last_complete_reward_cycle: Option<u64>,
 }
 
 impl NakamotoDownloadStateMachine {
     pub fn new(nakamoto_start_height: u64, nakamoto_tip: StacksBlockId) -> Self {
+        Self::with_rpc(nakamoto_start_height, nakamoto_tip, NeighborRPC::new())
+    }
+
+    /// Instantiate the state machine with the given neighbor RPC state, instead of a fresh one.
+    pub fn with_rpc(
+        nakamoto_start_height: u64,
+        nakamoto_tip: StacksBlockId,
+        neighbor_rpc: NeighborRPC,
+    ) -> Self {
         Self {
             nakamoto_start_height,
             reward_cycle: 0, // will be calculated at runtime
@@ -113,14 +330,436 @@ impl NakamotoDownloadStateMachine {
             unconfirmed_tenure_download_schedule: VecDeque::new(),
             tenure_downloads: NakamotoTenureDownloaderSet::new(),
             unconfirmed_tenure_downloads: HashMap::new(),
-            neighbor_rpc: NeighborRPC::new(),
+            neighbor_rpc,
             nakamoto_tip,
             fetch_unconfirmed_tenures: false,
             last_unconfirmed_download_check_ms: 0,
+            last_unconfirmed_tenures_blocker: None,
             last_unconfirmed_download_run_ms: 0,
+            download_pass: 0,
+            block_sink: None,
+            downloaded_blocks_per_sec: VecDeque::new(),
+            known_tenure_start_blocks: HashMap::new(),
+            num_dead_peers: 0,
+            num_broken_peers: 0,
+            blocklisted_peers: HashMap::new(),
+            quarantined_tenures: HashMap::new(),
+            deferred_unconfirmed_blocks: HashMap::new(),
+            staging_backpressure: false,
+            trusted_download_peers: HashSet::new(),
+            num_empty_passes: 0,
+            empty_pass_backoff_deadline_ms: 0,
+            last_inventory_generations: HashMap::new(),
+            last_wanted_tenures_progress: None,
+            reward_cycle_complete_handler: None,
+            last_complete_reward_cycle: None,
+        }
+    }
+
+    /// Supply tenure-start blocks that this node already has (e.g. from a chainstate snapshot), so
+    /// that tenure downloaders can use them instead of fetching them.  Each block must be keyed by
+    /// its block ID and must be a well-formed tenure-start block; others are ignored.  Signer
+    /// signatures are checked later, by the downloader which uses the block.
+    ///
+    /// Returns the number of blocks accepted.
+    pub fn seed_tenure_start_blocks(
+        &mut self,
+        blocks: HashMap<StacksBlockId, NakamotoBlock>,
+    ) -> usize {
+        let mut accepted = 0;
+        for (block_id, block) in blocks.into_iter() {
+            if block_id != block.block_id() {
+                warn!(
+                    "Will not seed tenure-start block: block ID mismatch";
+                    "block_id" => %block_id,
+                    "block.block_id" => %block.block_id(),
+                );
+                continue;
+            }
+            if !matches!(block.is_wellformed_tenure_start_block(), Ok(true)) {
+                warn!(
+                    "Will not seed tenure-start block: not a well-formed tenure-start block";
+                    "block_id" => %block_id,
+                );
+                continue;
+            }
+            self.known_tenure_start_blocks.insert(block_id, block);
+            accepted += 1;
+        }
+        accepted
+    }
+
+    /// Record that `num_blocks` blocks were downloaded at epoch second `now_secs`.  Forgets
+    /// anything older than `DOWNLOAD_RATE_WINDOW_SECS`.
+    pub(crate) fn record_downloaded_blocks(&mut self, now_secs: u64, num_blocks: u64) {
+        if num_blocks > 0 {
+            if let Some((_, count)) = self
+                .downloaded_blocks_per_sec
+                .back_mut()
+                .filter(|(secs, _)| *secs == now_secs)
+            {
+                *count = count.saturating_add(num_blocks);
+            } else {
+                self.downloaded_blocks_per_sec
+                    .push_back((now_secs, num_blocks));
+            }
+        }
+        while let Some((secs, _)) = self.downloaded_blocks_per_sec.front() {
+            if secs.saturating_add(DOWNLOAD_RATE_WINDOW_SECS) > now_secs {
+                break;
+            }
+            self.downloaded_blocks_per_sec.pop_front();
+        }
+    }
+
+    /// Exclude a neighbor from tenure availability until `PEER_BLOCKLIST_TIME_SECS` seconds after
+    /// epoch second `now_secs`.
+    pub(crate) fn blocklist_peer(&mut self, naddr: NeighborAddress, now_secs: u64) {
+        self.blocklisted_peers
+            .insert(naddr, now_secs.saturating_add(PEER_BLOCKLIST_TIME_SECS));
+    }
+
+    /// Forget neighbors whose blocklisting has expired as of epoch second `now_secs`, and return
+    /// the ones which are still blocklisted.
+    pub(crate) fn expire_blocklisted_peers(&mut self, now_secs: u64) -> HashSet<NeighborAddress> {
+        self.blocklisted_peers
+            .retain(|_, expires_at| *expires_at > now_secs);
+        self.blocklisted_peers.keys().cloned().collect()
+    }
+
+    /// Blocklist each neighbor we know of which the neighbor RPC marked dead or broken on this
+    /// pass, so it won't be selected to serve tenures again once the RPC forgets about it.
+    fn blocklist_dead_peers(&mut self, network: &PeerNetwork) {
+        let now_secs = get_epoch_time_secs();
+        let dead_naddrs: HashSet<NeighborAddress> = self
+            .tenure_block_ids
+            .keys()
+            .chain(self.available_tenures.values().flatten())
+            .filter(|naddr| self.neighbor_rpc.is_dead_or_broken(network, naddr))
+            .cloned()
+            .collect();
+        for naddr in dead_naddrs.into_iter() {
+            debug!(
+                "Blocklist {naddr} for {PEER_BLOCKLIST_TIME_SECS}s";
+                "download_pass" => self.download_pass,
+            );
+            self.blocklist_peer(naddr, now_secs);
+        }
+    }
+
+    /// Is the block with the given ID stored in either the staging blocks DB or the headers DB?
+    fn is_block_stored(
+        chainstate: &StacksChainState,
+        block_id: &StacksBlockId,
+    ) -> Result<bool, NetError> {
+        if chainstate
+            .nakamoto_blocks_db()
+            .has_nakamoto_block_with_index_hash(block_id)?
+        {
+            return Ok(true);
+        }
+        Ok(NakamotoChainState::get_block_header(chainstate.db(), block_id)?.is_some())
+    }
+
+    /// Hold back downloaded tenures which do not connect to blocks we already have.  A tenure
+    /// connects if its earliest block's parent is stored (per `is_block_stored`) or is in another
+    /// connected tenure.  Tenures quarantined on earlier passes are re-checked along with
+    /// `new_blocks`, and discarded once they have been quarantined for
+    /// `TENURE_QUARANTINE_TIME_SECS` seconds as of epoch second `now_secs`.
+    ///
+    /// Returns the connected tenures.
+    pub(crate) fn quarantine_disconnected_tenures<F>(
+        &mut self,
+        new_blocks: HashMap<ConsensusHash, Vec<NakamotoBlock>>,
+        now_secs: u64,
+        mut is_block_stored: F,
+    ) -> Result<HashMap<ConsensusHash, Vec<NakamotoBlock>>, NetError>
+    where
+        F: FnMut(&StacksBlockId) -> Result<bool, NetError>,
+    {
+        // merge in previously-quarantined tenures which have not yet expired
+        let mut quarantined_at = HashMap::new();
+        let mut candidates: HashMap<ConsensusHash, BTreeMap<StacksBlockId, NakamotoBlock>> =
+            HashMap::new();
+        for (ch, (since_secs, blocks)) in self.quarantined_tenures.drain() {
+            if since_secs.saturating_add(TENURE_QUARANTINE_TIME_SECS) <= now_secs {
+                info!(
+                    "Discard quarantined tenure {ch}: its parent never arrived";
+                    "num_blocks" => blocks.len(),
+                );
+                continue;
+            }
+            quarantined_at.insert(ch.clone(), since_secs);
+            candidates
+                .entry(ch)
+                .or_default()
+                .extend(blocks.into_iter().map(|blk| (blk.block_id(), blk)));
+        }
+        for (ch, blocks) in new_blocks.into_iter() {
+            candidates
+                .entry(ch)
+                .or_default()
+                .extend(blocks.into_iter().map(|blk| (blk.block_id(), blk)));
+        }
+
+        // find each tenure's earliest block's parent
+        let mut parents = HashMap::new();
+        for (ch, blocks) in candidates.iter() {
+            let Some(first_block) = blocks.values().min_by_key(|blk| blk.header.chain_length)
+            else {
+                continue;
+            };
+            parents.insert(ch.clone(), first_block.header.parent_block_id.clone());
+        }
+
+        // tenures whose parent is stored connect, and so do tenures whose parent is in a
+        // connected tenure.  Grow the connected set until it stops changing.
+        let mut connected = HashSet::new();
+        for (ch, parent_block_id) in parents.iter() {
+            if is_block_stored(parent_block_id)? {
+                connected.insert(ch.clone());
+            }
+        }
+        loop {
+            let connected_block_ids: HashSet<_> = connected
+                .iter()
+                .filter_map(|ch| candidates.get(ch))
+                .flat_map(|blocks| blocks.keys())
+                .collect();
+            let newly_connected: Vec<_> = parents
+                .iter()
+                .filter(|(ch, parent_block_id)| {
+                    !connected.contains(*ch) && connected_block_ids.contains(parent_block_id)
+                })
+                .map(|(ch, _)| ch.clone())
+                .collect();
+            if newly_connected.is_empty() {
+                break;
+            }
+            connected.extend(newly_connected);
+        }
+
+        let mut connected_tenures = HashMap::new();
+        for (ch, block_map) in candidates.into_iter() {
+            let mut blocks: Vec<_> = block_map.into_values().collect();
+            blocks.sort_unstable_by_key(|blk| blk.header.chain_length);
+            if connected.contains(&ch) {
+                connected_tenures.insert(ch, blocks);
+                continue;
+            }
+            let since_secs = if let Some(since_secs) = quarantined_at.get(&ch) {
+                *since_secs
+            } else {
+                debug!(
+                    "Quarantine tenure {ch}: parent of its first block is unknown";
+                    "parent_block_id" => ?parents.get(&ch),
+                    "num_blocks" => blocks.len(),
+                    "download_pass" => self.download_pass,
+                );
+                now_secs
+            };
+            self.quarantined_tenures.insert(ch, (since_secs, blocks));
+        }
+        Ok(connected_tenures)
+    }
+
+    /// Take every block which has been downloaded but not yet returned from `run()` -- those held
+    /// by in-progress downloaders and by quarantined tenures -- and reset the per-reward-cycle
+    /// scheduling state, so the next pass reloads its wanted tenures from scratch.  The set of
+    /// completed tenures is kept, so they won't be downloaded again.
+    ///
+    /// Returns the blocks grouped by tenure and sorted by height.  They may not be contiguous.
+    pub fn flush(&mut self) -> HashMap<ConsensusHash, Vec<NakamotoBlock>> {
+        let mut buffered_blocks = vec![];
+        for downloader in self.tenure_downloads.downloaders.drain(..).flatten() {
+            buffered_blocks.extend(downloader.tenure_start_block);
+            buffered_blocks.extend(downloader.tenure_blocks.into_iter().flatten());
+        }
+        self.tenure_downloads.peers.clear();
+        for (_, downloader) in self.unconfirmed_tenure_downloads.drain() {
+            buffered_blocks.extend(downloader.unconfirmed_tenure_start_block);
+            buffered_blocks.extend(downloader.unconfirmed_tenure_blocks.into_iter().flatten());
+        }
+        for (_, (_, blocks)) in self.quarantined_tenures.drain() {
+            buffered_blocks.extend(blocks);
+        }
+        for (_, blocks) in self.deferred_unconfirmed_blocks.drain() {
+            buffered_blocks.extend(blocks);
+        }
+
+        self.wanted_tenures.clear();
+        self.prev_wanted_tenures = None;
+        self.last_sort_tip = None;
+        self.tenure_block_ids.clear();
+        self.available_tenures.clear();
+        self.tenure_download_schedule.clear();
+        self.unconfirmed_tenure_download_schedule.clear();
+        self.state = NakamotoDownloadState::Confirmed;
+
+        let mut coalesced_blocks: HashMap<ConsensusHash, BTreeMap<StacksBlockId, NakamotoBlock>> =
+            HashMap::new();
+        for block in buffered_blocks.into_iter() {
+            coalesced_blocks
+                .entry(block.header.consensus_hash.clone())
+                .or_default()
+                .insert(block.block_id(), block);
+        }
+        let flushed: HashMap<_, Vec<_>> = coalesced_blocks
+            .into_iter()
+            .map(|(consensus_hash, block_map)| {
+                let mut block_list: Vec<_> = block_map.into_values().collect();
+                block_list.sort_unstable_by_key(|blk| blk.header.chain_length);
+                (consensus_hash, block_list)
+            })
+            .collect();
+
+        debug!("Flushed downloader";
+            "download_pass" => self.download_pass,
+            "num_tenures" => flushed.len(),
+            "num_completed_tenures" => self.tenure_downloads.completed_tenures.len(),
+        );
+        flushed
+    }
+
+    /// Blocks downloaded per second, averaged over the `DOWNLOAD_RATE_WINDOW_SECS` seconds before
+    /// epoch second `now_secs`.
+    pub(crate) fn download_rate_at(&self, now_secs: u64) -> f64 {
+        let num_blocks: u64 = self
+            .downloaded_blocks_per_sec
+            .iter()
+            .filter(|(secs, _)| secs.saturating_add(DOWNLOAD_RATE_WINDOW_SECS) > now_secs)
+            .map(|(_, count)| *count)
+            .sum();
+        num_blocks as f64 / DOWNLOAD_RATE_WINDOW_SECS as f64
+    }
+
+    /// Blocks downloaded per second, averaged over the last minute.  Together with the number of
+    /// tenures left to download, this can be used to estimate when IBD will finish.
+    pub fn download_rate(&self) -> f64 {
+        self.download_rate_at(get_epoch_time_secs())
+    }
+
+    /// The reward cycle whose tenures the downloader is currently tracking
+    pub fn current_reward_cycle(&self) -> u64 {
+        self.reward_cycle
+    }
+
+    /// The sortition tip as of the end of the last download pass, if there has been one
+    pub fn last_processed_sort_tip(&self) -> Option<&BlockSnapshot> {
+        self.last_sort_tip.as_ref()
+    }
+
+    /// Summarize the downloader's progress
+    pub fn metrics(&self) -> NakamotoDownloadMetrics {
+        NakamotoDownloadMetrics {
+            download_pass: self.download_pass,
+            state: self.state.clone(),
+            reward_cycle: self.reward_cycle,
+            num_downloaders: self.tenure_downloads.num_downloaders(),
+            num_unconfirmed_downloaders: self.unconfirmed_tenure_downloads.len(),
+            inflight: self.tenure_downloads.inflight(),
+            completed_tenures: self.tenure_downloads.completed_tenures.len(),
+            scheduled_tenures: self.tenure_download_schedule.len(),
+            scheduled_unconfirmed_tenures: self.unconfirmed_tenure_download_schedule.len(),
+            dead_peers: self.num_dead_peers,
+            broken_peers: self.num_broken_peers,
         }
     }
 
+    /// Tenures which the confirmed tenure downloaders dropped from the schedule without
+    /// downloading them, and why.  A tenure stays here until a downloader is made for it.
+    pub fn abandoned_tenures(&self) -> &HashMap<ConsensusHash, AbandonedTenureReason> {
+        self.tenure_downloads.abandoned_tenures()
+    }
+
+    /// Why the downloader did not start fetching unconfirmed tenures the last time it checked.
+    /// None if it did, or if it has not checked yet.
+    pub fn unconfirmed_tenures_blocker(&self) -> Option<&UnconfirmedTenuresBlocker> {
+        self.last_unconfirmed_tenures_blocker.as_ref()
+    }
+
+    /// Summarize what we know about each wanted tenure in the previous and current reward cycles,
+    /// in order of burnchain height.
+    pub fn tenure_status(&self) -> Vec<TenureStatus> {
+        self.prev_wanted_tenures
+            .iter()
+            .flatten()
+            .chain(self.wanted_tenures.iter())
+            .map(|wt| {
+                let ch = &wt.tenure_id_consensus_hash;
+                let tenure_start_end = self
+                    .tenure_block_ids
+                    .values()
+                    .find_map(|available| available.get(ch));
+                TenureStatus {
+                    tenure_id: ch.clone(),
+                    burn_height: wt.burn_height,
+                    processed: wt.processed,
+                    num_available_peers: self
+                        .available_tenures
+                        .get(ch)
+                        .map(|naddrs| naddrs.len())
+                        .unwrap_or(0),
+                    start_block_id: tenure_start_end.map(|tse| tse.start_block_id.clone()),
+                    end_block_id: tenure_start_end.map(|tse| tse.end_block_id.clone()),
+                    downloading: self.tenure_downloads.has_downloader_for_tenure(ch),
+                }
+            })
+            .collect()
+    }
+
+    /// Export the tenure start and end blocks which each neighbor can serve, as learned from their
+    /// inventories, as JSON.  Neighbors and tenures are sorted, so that snapshots taken on
+    /// different nodes or at different times can be diffed to diagnose inventory disagreements.
+    pub fn export_tenure_block_ids(&self) -> Result<String, NetError> {
+        let mut entries: Vec<_> = self
+            .tenure_block_ids
+            .iter()
+            .map(|(naddr, available)| NeighborAvailableTenures {
+                neighbor: naddr.clone(),
+                tenures: available
+                    .iter()
+                    .map(|(ch, tse)| (ch.clone(), tse.clone()))
+                    .collect(),
+            })
+            .collect();
+        entries.sort_by(|a, b| a.neighbor.cmp(&b.neighbor));
+        serde_json::to_string_pretty(&entries).map_err(|e| NetError::SerializeError(e.to_string()))
+    }
+
+    /// Replace the tenure start and end blocks which each neighbor can serve with those in the
+    /// given JSON, as written by `export_tenure_block_ids()`.
+    pub fn import_tenure_block_ids(&mut self, json: &str) -> Result<(), NetError> {
+        let entries: Vec<NeighborAvailableTenures> =
+            serde_json::from_str(json).map_err(|e| NetError::DeserializeError(e.to_string()))?;
+        self.tenure_block_ids = entries
+            .into_iter()
+            .map(|entry| (entry.neighbor, entry.tenures.into_iter().collect()))
+            .collect();
+        Ok(())
+    }
+
+    /// Send the tenures obtained in each download pass to `sink`, instead of returning them from
+    /// `run()`.  They are sent once the pass is over, so they have been quarantined, coalesced,
+    /// and capped just as `run()`'s return value would be.
+    pub fn set_block_sink(&mut self, sink: Box<dyn BlockSink + Send>) {
+        self.block_sink = Some(sink);
+    }
+
+    /// Use `selector` to choose which neighbor to ask for each confirmed tenure, instead of the
+    /// default policy.
+    pub fn set_peer_selector(&mut self, selector: Box<dyn DownloadPeerSelector + Send>) {
+        self.tenure_downloads.peer_selector = selector;
+    }
+
+    /// Tell `handler` whenever every tenure in a reward cycle has been processed.
+    pub fn set_reward_cycle_complete_handler(
+        &mut self,
+        handler: Box<dyn RewardCycleCompleteHandler + Send>,
+    ) {
+        self.reward_cycle_complete_handler = Some(handler);
+    }
+
     /// Return the reward cycle which could be confirmed by a nakamoto block commit
     ///  in burn block height `burn_height`.
     ///
@@ -355,17 +994,51 @@ impl NakamotoDownloadStateMachine {
             &mut self.wanted_tenures,
             chainstate,
             &self.nakamoto_tip,
-        )
+        )?;
+        self.check_reward_cycle_complete();
+        Ok(())
+    }
+
+    /// If every tenure in the previous reward cycle has now been processed, and we haven't said
+    /// so yet, then tell the reward cycle completion handler (if there is one).  Sortitions
+    /// without a winner have no tenure to process, so they don't count.
+    ///
+    /// Only `self.prev_wanted_tenures` is considered, since it's the only list of wanted tenures
+    /// guaranteed to cover a whole reward cycle.  `self.wanted_tenures` keeps growing until the
+    /// sortition DB crosses into the next reward cycle, at which point it becomes
+    /// `self.prev_wanted_tenures`.
+    pub(crate) fn check_reward_cycle_complete(&mut self) {
+        let Some(prev_wanted_tenures) = self.prev_wanted_tenures.as_ref() else {
+            return;
+        };
+        if prev_wanted_tenures.is_empty()
+            || prev_wanted_tenures
+                .iter()
+                .any(|wt| wt.has_winner() && !wt.processed)
+        {
+            return;
+        }
+        let prev_reward_cycle = self.reward_cycle.saturating_sub(1);
+        if self
+            .last_complete_reward_cycle
+            .is_some_and(|rc| rc >= prev_reward_cycle)
+        {
+            return;
+        }
+
+        info!("All tenures in reward cycle {prev_reward_cycle} have been processed");
+        self.last_complete_reward_cycle = Some(prev_reward_cycle);
+        if let Some(handler) = self.reward_cycle_complete_handler.as_mut() {
+            handler.on_reward_cycle_complete(prev_reward_cycle);
+        }
     }
 
     /// Update `self.wanted_tenures` with newly-discovered sortition data.
     fn extend_wanted_tenures(
         &mut self,
-        network: &PeerNetwork,
+        sort_tip: &BlockSnapshot,
         sortdb: &SortitionDB,
     ) -> Result<(), NetError> {
-        let sort_tip = &network.burnchain_tip;
-
         let mut new_wanted_tenures = Self::load_wanted_tenures_at_tip(
             self.last_sort_tip.as_ref(),
             sort_tip,
@@ -395,12 +1068,13 @@ impl NakamotoDownloadStateMachine {
     ) -> Result<(), NetError> {
         // check for reorgs
         let reorg = PeerNetwork::is_reorg(self.last_sort_tip.as_ref(), sort_tip, sortdb);
-        if reorg {
+        let reorged_wanted_tenures = if reorg {
             // force a reload
             debug!("Detected reorg! Refreshing wanted tenures");
-            self.prev_wanted_tenures = None;
-            self.wanted_tenures.clear();
-        }
+            Some(self.take_wanted_tenures())
+        } else {
+            None
+        };
 
         if self
             .prev_wanted_tenures
@@ -457,9 +1131,61 @@ impl NakamotoDownloadStateMachine {
             self.wanted_tenures = wanted_tenures;
             self.reward_cycle = sort_rc;
         }
+        if let Some(old_wanted_tenures) = reorged_wanted_tenures {
+            self.cancel_dropped_tenures(old_wanted_tenures);
+        }
         Ok(())
     }
 
+    /// Remove and return all of `self.prev_wanted_tenures` and `self.wanted_tenures`.
+    fn take_wanted_tenures(&mut self) -> Vec<WantedTenure> {
+        let mut old_wanted_tenures = self.prev_wanted_tenures.take().unwrap_or_default();
+        old_wanted_tenures.append(&mut self.wanted_tenures);
+        old_wanted_tenures
+    }
+
+    /// Cancel downloads for tenures in `old_wanted_tenures` which are no longer wanted, because
+    /// they are no longer on the canonical sortition history.  Tenures which are older than the
+    /// lowest tenure in `self.prev_wanted_tenures` and `self.wanted_tenures` have simply aged out
+    /// of the download window, so downloads for them (if any) are left to finish.
+    fn cancel_dropped_tenures(&mut self, old_wanted_tenures: Vec<WantedTenure>) {
+        let cur_wanted_tenures: HashSet<_> = self
+            .prev_wanted_tenures
+            .iter()
+            .flatten()
+            .chain(self.wanted_tenures.iter())
+            .map(|wt| &wt.tenure_id_consensus_hash)
+            .collect();
+        let Some(lowest_burn_height) = self
+            .prev_wanted_tenures
+            .iter()
+            .flatten()
+            .chain(self.wanted_tenures.iter())
+            .map(|wt| wt.burn_height)
+            .min()
+        else {
+            return;
+        };
+
+        let dropped: HashSet<_> = old_wanted_tenures
+            .into_iter()
+            .filter(|wt| {
+                wt.burn_height >= lowest_burn_height
+                    && !cur_wanted_tenures.contains(&wt.tenure_id_consensus_hash)
+            })
+            .map(|wt| wt.tenure_id_consensus_hash)
+            .collect();
+
+        for ch in dropped.iter() {
+            if self.tenure_downloads.cancel_tenure(ch) {
+                info!("Cancelled download of tenure {ch}, which is no longer canonical");
+            }
+            self.available_tenures.remove(ch);
+        }
+        self.tenure_download_schedule
+            .retain(|ch| !dropped.contains(ch));
+    }
+
     /// Update the state machine's wanted tenures and processed tenures, if it's time to do so.
     /// This will only happen when the sortition DB has finished processing a reward cycle of
     /// tenures when in IBD mode, _OR_ when the sortition tip advances when in steady-state mode.
@@ -483,8 +1209,16 @@ impl NakamotoDownloadStateMachine {
         network: &PeerNetwork,
         sortdb: &SortitionDB,
     ) -> Result<(), NetError> {
-        let sort_tip = &network.burnchain_tip;
+        self.update_wanted_tenures_at(&network.burnchain_tip, sortdb)
+    }
 
+    /// Update the state machine's wanted tenures given the sortition tip `sort_tip`.
+    /// See `update_wanted_tenures()`.
+    pub(crate) fn update_wanted_tenures_at(
+        &mut self,
+        sort_tip: &BlockSnapshot,
+        sortdb: &SortitionDB,
+    ) -> Result<(), NetError> {
         self.initialize_wanted_tenures(sort_tip, sortdb)?;
         let last_sort_height_opt = self.last_sort_tip.as_ref().map(|sn| sn.block_height);
         let last_sort_height = last_sort_height_opt.unwrap_or(sort_tip.block_height);
@@ -498,7 +1232,7 @@ impl NakamotoDownloadStateMachine {
         if self.reward_cycle == sort_rc {
             // not at a reward cycle boundary, so just extend self.wanted_tenures
             debug!("Extend wanted tenures since no sort_rc change and we have tenure data");
-            self.extend_wanted_tenures(network, sortdb)?;
+            self.extend_wanted_tenures(sort_tip, sortdb)?;
             return Ok(());
         }
 
@@ -530,6 +1264,7 @@ impl NakamotoDownloadStateMachine {
             &new_prev_wanted_tenures
         );
 
+        let old_wanted_tenures = self.take_wanted_tenures();
         self.prev_wanted_tenures = if new_prev_wanted_tenures.is_empty() {
             None
         } else {
@@ -537,25 +1272,29 @@ impl NakamotoDownloadStateMachine {
         };
         self.wanted_tenures = new_wanted_tenures;
         self.reward_cycle = sort_rc;
+        self.cancel_dropped_tenures(old_wanted_tenures);
 
         Ok(())
     }
 
     /// Given a set of inventory bit vectors for the current reward cycle, find out which neighbors
     /// can serve each tenure (identified by the tenure ID consensus hash).
-    /// Every tenure ID consensus hash in `wanted_tenures` will be mapped to the returned hash
-    /// table, but the list of addresses may be empty if no neighbor reports having that tenure.
+    /// Only tenures which at least one neighbor reports having will be mapped in the returned hash
+    /// table; each mapped list of addresses is non-empty.  Wanted tenures which no neighbor can
+    /// serve are omitted, so they will not be scheduled for download.
+    /// Neighbors in `blocklisted_peers` are treated as though they have no tenures.
     pub(crate) fn find_available_tenures<'a>(
         reward_cycle: u64,
         wanted_tenures: &[WantedTenure],
+        blocklisted_peers: &HashSet<NeighborAddress>,
         mut inventory_iter: impl Iterator<Item = (&'a NeighborAddress, &'a NakamotoTenureInv)>,
     ) -> HashMap<ConsensusHash, Vec<NeighborAddress>> {
         let mut available: HashMap<ConsensusHash, Vec<NeighborAddress>> = HashMap::new();
-        for wt in wanted_tenures.iter() {
-            available.insert(wt.tenure_id_consensus_hash.clone(), vec![]);
-        }
-
         while let Some((naddr, inv)) = inventory_iter.next() {
+            if blocklisted_peers.contains(naddr) {
+                debug!("Peer {naddr} is blocklisted; ignoring its inventory");
+                continue;
+            }
             let Some(rc_inv) = inv.tenures_inv.get(&reward_cycle) else {
                 // this peer has no inventory data for this reward cycle
                 debug!(
@@ -570,10 +1309,10 @@ impl NakamotoDownloadStateMachine {
                     continue;
                 }
 
-                let (ch, ibh) = (&wt.tenure_id_consensus_hash, &wt.winning_block_id);
-                if ibh == &StacksBlockId([0x00; 32]) {
+                if !wt.has_winner() {
                     continue;
                 }
+                let ch = &wt.tenure_id_consensus_hash;
 
                 let bit = u16::try_from(i).expect("FATAL: more sortitions than u16::MAX");
                 if !rc_inv.get(bit).unwrap_or(false) {
@@ -592,9 +1331,40 @@ impl NakamotoDownloadStateMachine {
                 }
             }
         }
+
+        let num_unservable = wanted_tenures
+            .iter()
+            .filter(|wt| {
+                !wt.processed
+                    && wt.has_winner()
+                    && !available.contains_key(&wt.tenure_id_consensus_hash)
+            })
+            .count();
+        if num_unservable > 0 {
+            info!(
+                "No neighbor can serve {num_unservable} wanted tenure(s) in reward cycle {reward_cycle}; need more peers";
+                "num_wanted_tenures" => wanted_tenures.len(),
+                "num_available_tenures" => available.len(),
+            );
+        }
         available
     }
 
+    /// Reorder each tenure's neighbors so that trusted peers are asked for it before any other
+    /// neighbor.  `make_tenure_downloaders()` takes neighbors from the back of each list, so this
+    /// moves trusted peers to the back.  Otherwise, the neighbors' order is preserved.
+    pub(crate) fn prioritize_trusted_peers(
+        available: &mut HashMap<ConsensusHash, Vec<NeighborAddress>>,
+        trusted_peers: &HashSet<NeighborAddress>,
+    ) {
+        if trusted_peers.is_empty() {
+            return;
+        }
+        for neighbors in available.values_mut() {
+            neighbors.sort_by_key(|naddr| trusted_peers.contains(naddr));
+        }
+    }
+
     /// Find each peer's mapping between tenure ID consensus hashes for the tenures it claims to
     /// have in its inventory vector, and its tenure start block ID.
     ///
@@ -640,6 +1410,9 @@ impl NakamotoDownloadStateMachine {
             if wt.burn_height < nakamoto_start {
                 continue;
             }
+            if !wt.has_winner() {
+                continue;
+            }
             if !available.contains_key(&wt.tenure_id_consensus_hash) {
                 continue;
             }
@@ -664,6 +1437,9 @@ impl NakamotoDownloadStateMachine {
             if wt.burn_height < nakamoto_start {
                 continue;
             }
+            if !wt.has_winner() {
+                continue;
+            }
             let Some(neighbors) = available.get(&wt.tenure_id_consensus_hash) else {
                 continue;
             };
@@ -675,6 +1451,57 @@ impl NakamotoDownloadStateMachine {
         schedule.into_iter().map(|(_count, ch)| ch).collect()
     }
 
+    /// Produce a download schedule for either IBD mode (if `ibd` is true) or steady-state mode.
+    /// The first item will be fetched first.
+    pub(crate) fn make_download_schedule(
+        nakamoto_start: u64,
+        wanted_tenures: &[WantedTenure],
+        available: &HashMap<ConsensusHash, Vec<NeighborAddress>>,
+        ibd: bool,
+    ) -> VecDeque<ConsensusHash> {
+        if ibd {
+            Self::make_ibd_download_schedule(nakamoto_start, wanted_tenures, available)
+        } else {
+            Self::make_rarest_first_download_schedule(nakamoto_start, wanted_tenures, available)
+        }
+    }
+
+    /// Infer whether or not the node is in IBD, for when the caller doesn't know.  The node is
+    /// considered to be in IBD if its download backlog is at least `ibd_backlog_threshold`
+    /// tenures long.  The backlog is the number of unprocessed wanted tenures with a sortition
+    /// winner, plus the number of burnchain blocks the sortition DB has yet to process (each of
+    /// which can produce a tenure we'll need).
+    ///
+    /// This method is static to facilitate testing.
+    pub(crate) fn infer_ibd(
+        burnchain_height: u64,
+        sort_tip: &BlockSnapshot,
+        wanted_tenures: &[WantedTenure],
+        prev_wanted_tenures: &[WantedTenure],
+        ibd_backlog_threshold: u64,
+    ) -> bool {
+        let num_unprocessed = prev_wanted_tenures
+            .iter()
+            .chain(wanted_tenures.iter())
+            .filter(|wt| !wt.processed && wt.has_winner())
+            .count();
+        let burnchain_lag = burnchain_height.saturating_sub(sort_tip.block_height);
+        let backlog = u64::try_from(num_unprocessed)
+            .unwrap_or(u64::MAX)
+            .saturating_add(burnchain_lag);
+
+        let ibd = backlog >= ibd_backlog_threshold;
+        debug!(
+            "Inferred download phase: {}",
+            if ibd { "IBD" } else { "steady-state" };
+            "backlog" => backlog,
+            "num_unprocessed" => num_unprocessed,
+            "burnchain_lag" => burnchain_lag,
+            "ibd_backlog_threshold" => ibd_backlog_threshold,
+        );
+        ibd
+    }
+
     /// How many neighbors can we contact still, given the map of tenures to neighbors which can
     /// serve it?
     fn count_available_tenure_neighbors(
@@ -708,8 +1535,36 @@ impl NakamotoDownloadStateMachine {
         first_burn_height: u64,
         ibd: bool,
     ) {
-        if self.tenure_download_schedule.is_empty() {
-            // try again
+        let wanted_tenures_progress = Self::wanted_tenures_progress(
+            self.reward_cycle,
+            &self.wanted_tenures,
+            self.prev_wanted_tenures.as_deref().unwrap_or(&[]),
+        );
+        if Self::inventories_changed(&self.last_inventory_generations, inventories)
+            || self.last_wanted_tenures_progress != Some(wanted_tenures_progress)
+        {
+            // new inventory data (or new wanted tenures), so there may be new tenures available
+            self.reset_empty_pass_backoff();
+            self.last_inventory_generations = inventories
+                .iter()
+                .map(|(naddr, inv)| (naddr.clone(), inv.generation))
+                .collect();
+            self.last_wanted_tenures_progress = Some(wanted_tenures_progress);
+        }
+
+        let num_blocklisted_peers = self.blocklisted_peers.len();
+        let blocklisted_peers = self.expire_blocklisted_peers(get_epoch_time_secs());
+        if blocklisted_peers.len() < num_blocklisted_peers {
+            // some neighbors can serve tenures again
+            self.reset_empty_pass_backoff();
+        }
+
+        let now_ms = get_epoch_time_ms();
+        let backing_off = now_ms < self.empty_pass_backoff_deadline_ms;
+        if self.tenure_download_schedule.is_empty() && !backing_off {
+            // try again.  While backing off, the last-computed tenure block IDs and availability
+            // are kept, since they're still needed to decide whether or not to fetch unconfirmed
+            // tenures.
             self.available_tenures.clear();
             self.tenure_block_ids.clear();
         }
@@ -727,6 +1582,15 @@ impl NakamotoDownloadStateMachine {
             debug!("No inventories available");
             return;
         }
+        if backing_off {
+            debug!(
+                "Not recomputing available tenures: {} passes found nothing to download, and neither the inventories nor the wanted tenures have changed since",
+                self.num_empty_passes;
+                "download_pass" => self.download_pass,
+                "backoff_deadline_ms" => self.empty_pass_backoff_deadline_ms,
+            );
+            return;
+        }
 
         // calculate self.available
         // get available tenures for both the current and previous reward cycles
@@ -742,6 +1606,7 @@ impl NakamotoDownloadStateMachine {
                 Self::find_available_tenures(
                     self.reward_cycle.saturating_sub(1),
                     prev_wanted_tenures,
+                    &blocklisted_peers,
                     inventories.iter(),
                 )
             })
@@ -750,9 +1615,11 @@ impl NakamotoDownloadStateMachine {
         let mut available = Self::find_available_tenures(
             self.reward_cycle,
             &self.wanted_tenures,
+            &blocklisted_peers,
             inventories.iter(),
         );
         available.extend(prev_available);
+        Self::prioritize_trusted_peers(&mut available, &self.trusted_download_peers);
 
         // calculate self.tenure_block_ids
         let prev_tenure_block_ids = self.prev_wanted_tenures
@@ -797,61 +1664,103 @@ impl NakamotoDownloadStateMachine {
         }
 
         // create download schedules for unprocessed blocks
-        let schedule = if ibd {
-            let mut prev_schedule = self
-                .prev_wanted_tenures
-                .as_ref()
-                .map(|prev_wanted_tenures| {
-                    Self::make_ibd_download_schedule(
-                        self.nakamoto_start_height,
-                        prev_wanted_tenures,
-                        &available,
-                    )
-                })
-                .unwrap_or_default();
-
-            let schedule = Self::make_ibd_download_schedule(
-                self.nakamoto_start_height,
-                &self.wanted_tenures,
-                &available,
-            );
-
-            prev_schedule.extend(schedule);
-            prev_schedule
-        } else {
-            let mut prev_schedule = self
-                .prev_wanted_tenures
-                .as_ref()
-                .map(|prev_wanted_tenures| {
-                    Self::make_rarest_first_download_schedule(
-                        self.nakamoto_start_height,
-                        prev_wanted_tenures,
-                        &available,
-                    )
-                })
-                .unwrap_or_default();
-
-            let schedule = Self::make_rarest_first_download_schedule(
-                self.nakamoto_start_height,
-                &self.wanted_tenures,
-                &available,
-            );
+        let mut schedule = self
+            .prev_wanted_tenures
+            .as_ref()
+            .map(|prev_wanted_tenures| {
+                Self::make_download_schedule(
+                    self.nakamoto_start_height,
+                    prev_wanted_tenures,
+                    &available,
+                    ibd,
+                )
+            })
+            .unwrap_or_default();
 
-            prev_schedule.extend(schedule);
-            prev_schedule
-        };
+        schedule.extend(Self::make_download_schedule(
+            self.nakamoto_start_height,
+            &self.wanted_tenures,
+            &available,
+            ibd,
+        ));
 
         test_debug!("new schedule: {schedule:?}");
         test_debug!("new available: {available:?}");
         test_debug!("new tenure_block_ids: {tenure_block_ids:?}");
 
+        if schedule.is_empty() {
+            self.num_empty_passes = self.num_empty_passes.saturating_add(1);
+            if self.num_empty_passes >= EMPTY_PASS_BACKOFF_THRESHOLD {
+                let delay_ms = Self::empty_pass_backoff_ms(self.num_empty_passes);
+                debug!(
+                    "No tenures to download after {} passes; backing off for {delay_ms}ms",
+                    self.num_empty_passes;
+                    "download_pass" => self.download_pass,
+                );
+                self.empty_pass_backoff_deadline_ms = now_ms.saturating_add(delay_ms);
+            }
+        } else {
+            self.reset_empty_pass_backoff();
+        }
+
         self.tenure_download_schedule = schedule;
         self.tenure_block_ids = tenure_block_ids;
         self.available_tenures = available;
     }
 
+    /// How long to wait before recomputing the available tenures, given how many passes in a row
+    /// have found nothing to download.
+    fn empty_pass_backoff_ms(num_empty_passes: u64) -> u128 {
+        let exponent = num_empty_passes
+            .saturating_sub(EMPTY_PASS_BACKOFF_THRESHOLD)
+            .min(16);
+        EMPTY_PASS_BACKOFF_BASE_MS
+            .saturating_mul(1u128 << exponent)
+            .min(EMPTY_PASS_BACKOFF_MAX_MS)
+    }
+
+    /// Recompute the available tenures on the next pass, and start counting empty passes afresh.
+    fn reset_empty_pass_backoff(&mut self) {
+        self.num_empty_passes = 0;
+        self.empty_pass_backoff_deadline_ms = 0;
+    }
+
+    /// Have any of our peers' tenure inventories changed (or have peers come or gone) since their
+    /// generations were recorded in `last_generations`?  Only the generations are compared, so
+    /// this is cheap enough to check on every pass.
+    fn inventories_changed(
+        last_generations: &HashMap<NeighborAddress, u64>,
+        inventories: &HashMap<NeighborAddress, NakamotoTenureInv>,
+    ) -> bool {
+        last_generations.len() != inventories.len()
+            || inventories
+                .iter()
+                .any(|(naddr, inv)| last_generations.get(naddr) != Some(&inv.generation))
+    }
+
+    /// Summarize how far along we are in processing our wanted tenures: the reward cycle, the
+    /// number of wanted tenures, and how many of them have been processed.  If this changes, then
+    /// recomputing the available tenures could yield something new.
+    fn wanted_tenures_progress(
+        reward_cycle: u64,
+        wanted_tenures: &[WantedTenure],
+        prev_wanted_tenures: &[WantedTenure],
+    ) -> (u64, usize, usize) {
+        let num_processed = prev_wanted_tenures
+            .iter()
+            .chain(wanted_tenures.iter())
+            .filter(|wt| wt.processed)
+            .count();
+        (
+            reward_cycle,
+            prev_wanted_tenures.len() + wanted_tenures.len(),
+            num_processed,
+        )
+    }
+
     /// Update our tenure download state machines, given our download schedule, our peers' tenure
-    /// availabilities, and our computed `TenureStartEnd`s
+    /// availabilities, and our computed `TenureStartEnd`s.  Any seeded tenure-start blocks are
+    /// handed to the downloaders that need them.
     fn update_tenure_downloaders(
         &mut self,
         count: usize,
@@ -863,7 +1772,90 @@ impl NakamotoDownloadStateMachine {
             &self.tenure_block_ids,
             count,
             current_reward_sets,
-        )
+        );
+        self.tenure_downloads
+            .use_known_tenure_start_blocks(&self.neighbor_rpc, &self.known_tenure_start_blocks);
+    }
+
+    /// Count the peers which have provided a tenure inventory for the given reward cycle.
+    pub(crate) fn count_inventory_peers(
+        inventories: &HashMap<NeighborAddress, NakamotoTenureInv>,
+        reward_cycle: u64,
+    ) -> usize {
+        inventories
+            .values()
+            .filter(|inv| inv.tenures_inv.contains_key(&reward_cycle))
+            .count()
+    }
+
+    /// Update whether or not the staging DB is backpressured, given how many blocks in it have
+    /// yet to be processed.  Backpressure begins once there are at least `high_water_mark`
+    /// unprocessed blocks, and ends once there are no more than `low_water_mark` of them.  A
+    /// `high_water_mark` of 0 disables backpressure.
+    ///
+    /// Returns true if the staging DB is backpressured.
+    pub(crate) fn update_staging_backpressure(
+        &mut self,
+        num_unprocessed: u64,
+        high_water_mark: u64,
+        low_water_mark: u64,
+    ) -> bool {
+        let backpressure = if high_water_mark == 0 {
+            false
+        } else if self.staging_backpressure {
+            num_unprocessed > low_water_mark
+        } else {
+            num_unprocessed >= high_water_mark
+        };
+        if backpressure != self.staging_backpressure {
+            info!(
+                "{} confirmed tenure downloads: {num_unprocessed} unprocessed staging block(s)",
+                if backpressure { "Pause" } else { "Resume" };
+                "high_water_mark" => high_water_mark,
+                "low_water_mark" => low_water_mark,
+                "download_pass" => self.download_pass,
+            );
+        }
+        self.staging_backpressure = backpressure;
+        backpressure
+    }
+
+    /// Update our tenure download state machines as `update_tenure_downloaders()` does.  During
+    /// IBD, this is only done once at least `min_peers_for_ibd` peers have provided their
+    /// inventories for the target reward cycle, so that we don't fetch tenures from what may be a
+    /// sybil set of peers.  It is never done while the staging DB is backpressured (see
+    /// `update_staging_backpressure()`), but in-flight downloads carry on.
+    ///
+    /// Returns true if the downloaders were updated.
+    /// Returns false if there are not yet enough peers, or if the staging DB is backpressured.
+    pub(crate) fn try_update_tenure_downloaders(
+        &mut self,
+        count: usize,
+        current_reward_sets: &BTreeMap<u64, CurrentRewardSet>,
+        inventories: &HashMap<NeighborAddress, NakamotoTenureInv>,
+        min_peers_for_ibd: u64,
+        ibd: bool,
+    ) -> bool {
+        if self.staging_backpressure {
+            debug!(
+                "Not scheduling confirmed tenure downloads: too many unprocessed staging blocks";
+                "download_pass" => self.download_pass,
+            );
+            return false;
+        }
+        if ibd {
+            let num_peers = Self::count_inventory_peers(inventories, self.reward_cycle);
+            if u64::try_from(num_peers).unwrap_or(u64::MAX) < min_peers_for_ibd {
+                debug!(
+                    "Not scheduling confirmed tenure downloads: {num_peers} peer(s) have inventories for reward cycle {}, but {min_peers_for_ibd} are required",
+                    self.reward_cycle;
+                    "download_pass" => self.download_pass,
+                );
+                return false;
+            }
+        }
+        self.update_tenure_downloaders(count, current_reward_sets);
+        true
     }
 
     /// Find the two highest tenure IDs that are available for download.
@@ -904,44 +1896,57 @@ impl NakamotoDownloadStateMachine {
     }
 
     /// Determine whether or not we can start downloading the highest complete tenure and the
-    /// unconfirmed tenure.  Only do this if (1) the sortition DB is at the burnchain tip and (2)
-    /// all of our wanted tenures are marked as either downloaded or complete.
+    /// unconfirmed tenure, and if not, why not.  Only do this if (1) the sortition DB is at (or
+    /// near) the burnchain tip and (2) all of our wanted tenures are marked as either downloaded or
+    /// complete.
+    ///
+    /// The sortition tip must be within `max_burnchain_divergence` blocks of `burnchain_height`.
+    /// If it isn't, then either the sortition DB is still catching up, or the sortition DB and our
+    /// burnchain view disagree (e.g. during a burnchain reorg), and the chain tip we'd chase is
+    /// likely about to change.
     ///
     /// To fully determine if it's appropriate to download unconfirmed tenures, the caller should
     /// additionally ensure that there are no in-flight confirmed tenure downloads.
     ///
+    /// Returns None if we can start downloading unconfirmed tenures
+    /// Returns Some(blocker) with the first reason found why we can't, if we can't
+    ///
     /// This method is static to facilitate testing.
-    pub(crate) fn need_unconfirmed_tenures<'a>(
+    pub(crate) fn find_unconfirmed_tenures_blocker(
         burnchain_height: u64,
         sort_tip: &BlockSnapshot,
+        max_burnchain_divergence: u64,
         wanted_tenures: &[WantedTenure],
         prev_wanted_tenures: &[WantedTenure],
         tenure_block_ids: &HashMap<NeighborAddress, AvailableTenures>,
         available_tenures: &HashMap<ConsensusHash, Vec<NeighborAddress>>,
-    ) -> bool {
+    ) -> Option<UnconfirmedTenuresBlocker> {
         debug!("Check if we need unconfirmed tenures");
 
-        if sort_tip.block_height < burnchain_height {
-            debug!(
-                "sort_tip {} < burn tip {}",
-                sort_tip.block_height, burnchain_height
-            );
-            return false;
+        if burnchain_height.saturating_sub(sort_tip.block_height) > max_burnchain_divergence {
+            return Some(UnconfirmedTenuresBlocker::SortitionBehindBurnchain {
+                sort_tip_height: sort_tip.block_height,
+                burnchain_height,
+            });
+        }
+
+        if sort_tip.block_height.abs_diff(burnchain_height) > max_burnchain_divergence {
+            return Some(UnconfirmedTenuresBlocker::BurnchainDivergence {
+                sort_tip_height: sort_tip.block_height,
+                burnchain_height,
+            });
         }
 
         if wanted_tenures.is_empty() {
-            debug!("No wanted tenures");
-            return false;
+            return Some(UnconfirmedTenuresBlocker::NoWantedTenures);
         }
 
         if prev_wanted_tenures.is_empty() {
-            debug!("No prev wanted tenures");
-            return false;
+            return Some(UnconfirmedTenuresBlocker::NoPrevWantedTenures);
         }
 
         if tenure_block_ids.is_empty() {
-            debug!("No tenure availability known");
-            return false;
+            return Some(UnconfirmedTenuresBlocker::NoTenureAvailability);
         }
 
         let (unconfirmed_tenure_opt, confirmed_tenure_opt) = Self::find_unconfirmed_tenure_ids(
@@ -986,15 +1991,56 @@ impl NakamotoDownloadStateMachine {
                 // a tenure is available but not yet processed, so we can't yet transition to
                 // fetching unconfirmed tenures (we'd have no way to validate them).
                 // TODO: also check that this cannot be fetched by confirmed downloader
-                debug!(
-                    "Tenure {} is available but not yet processed",
-                    &wt.tenure_id_consensus_hash
-                );
-                return false;
+                return Some(UnconfirmedTenuresBlocker::UnprocessedTenure(
+                    wt.tenure_id_consensus_hash.clone(),
+                ));
             }
         }
 
-        true
+        None
+    }
+
+    /// Decide whether or not this pass should fetch unconfirmed tenures, and set
+    /// `self.fetch_unconfirmed_tenures` accordingly.  The decision is only revisited once every
+    /// `CHECK_UNCONFIRMED_TENURES_MS`; in between, unconfirmed tenures are not fetched.  The
+    /// reason why they can't be fetched, if any, is recorded in
+    /// `self.last_unconfirmed_tenures_blocker`.
+    pub(crate) fn update_fetch_unconfirmed_tenures(
+        &mut self,
+        burnchain_height: u64,
+        sort_tip: &BlockSnapshot,
+        connection_opts: &ConnectionOptions,
+        now_ms: u128,
+    ) {
+        self.fetch_unconfirmed_tenures = if connection_opts.nakamoto_download_confirmed_only {
+            // never leave the Confirmed state
+            false
+        } else if self
+            .last_unconfirmed_download_check_ms
+            .saturating_add(CHECK_UNCONFIRMED_TENURES_MS)
+            > now_ms
+        {
+            false
+        } else {
+            let blocker = Self::find_unconfirmed_tenures_blocker(
+                burnchain_height,
+                sort_tip,
+                connection_opts.nakamoto_unconfirmed_downloader_max_burnchain_divergence,
+                &self.wanted_tenures,
+                self.prev_wanted_tenures.as_ref().unwrap_or(&vec![]),
+                &self.tenure_block_ids,
+                &self.available_tenures,
+            );
+            if let Some(blocker) = blocker.as_ref() {
+                debug!("Not fetching unconfirmed tenures";
+                       "reason" => blocker.reason(),
+                       "details" => ?blocker);
+            }
+            self.last_unconfirmed_download_check_ms = now_ms;
+            let do_fetch = blocker.is_none();
+            self.last_unconfirmed_tenures_blocker = blocker;
+            do_fetch
+        };
     }
 
     /// Select neighbors to query for unconfirmed tenures, given this node's view of the burnchain
@@ -1031,7 +2077,7 @@ impl NakamotoDownloadStateMachine {
     /// downloaders gets created, lest it replace the unconfirmed tenure request.
     ///
     /// This method removes items from `schedule` and adds unconfirmed downloaders to
-    /// `downloaders`.
+    /// `downloaders`.  Each new downloader shares `signature_cache`.
     ///
     /// This method is static to facilitate testing.
     pub(crate) fn make_unconfirmed_tenure_downloaders(
@@ -1039,6 +2085,7 @@ impl NakamotoDownloadStateMachine {
         count: usize,
         downloaders: &mut HashMap<NeighborAddress, NakamotoUnconfirmedTenureDownloader>,
         highest_processed_block_id: Option<StacksBlockId>,
+        signature_cache: &SignerSignatureCache,
     ) -> usize {
         let mut added = 0;
         schedule.retain(|naddr| {
@@ -1052,7 +2099,8 @@ impl NakamotoDownloadStateMachine {
             let unconfirmed_tenure_download = NakamotoUnconfirmedTenureDownloader::new(
                 naddr.clone(),
                 highest_processed_block_id.clone(),
-            );
+            )
+            .with_signature_cache(signature_cache.clone());
 
             debug!("Request unconfirmed tenure state from neighbor {}", &naddr);
             downloaders.insert(naddr.clone(), unconfirmed_tenure_download);
@@ -1062,6 +2110,22 @@ impl NakamotoDownloadStateMachine {
         added
     }
 
+    /// How many more unconfirmed tenure downloaders can be created, given that `num_downloaders`
+    /// of them already exist.  This is bounded by both `max_inflight_blocks` and
+    /// `nakamoto_max_unconfirmed_downloaders`.
+    pub(crate) fn unconfirmed_downloader_budget(
+        connection_opts: &ConnectionOptions,
+        num_downloaders: usize,
+    ) -> usize {
+        let max_inflight = usize::try_from(connection_opts.max_inflight_blocks)
+            .expect("FATAL: max_inflight_blocks exceeds usize::MAX");
+        let max_downloaders = usize::try_from(connection_opts.nakamoto_max_unconfirmed_downloaders)
+            .expect("FATAL: nakamoto_max_unconfirmed_downloaders exceeds usize::MAX");
+        max_inflight
+            .min(max_downloaders)
+            .saturating_sub(num_downloaders)
+    }
+
     /// Update our unconfirmed tenure download state machines
     fn update_unconfirmed_tenure_downloaders(
         &mut self,
@@ -1087,10 +2151,37 @@ impl NakamotoDownloadStateMachine {
             count,
             &mut self.unconfirmed_tenure_downloads,
             highest_processed_block_id,
+            &self.tenure_downloads.signature_cache,
         );
         self.last_unconfirmed_download_run_ms = get_epoch_time_ms();
     }
 
+    /// Find the highest processed block which the unconfirmed tenure downloaders can treat as
+    /// already obtained, as well as its height.  This is the canonical Stacks tip, unless some of
+    /// the ongoing tenure's blocks below it are missing, in which case it's the block just below
+    /// the lowest such gap (so the downloaders will go and fetch the missing blocks).
+    ///
+    /// Returns None if the canonical Stacks tip is not a Nakamoto block.
+    fn highest_processed_block(
+        network: &PeerNetwork,
+        chainstate: &StacksChainState,
+    ) -> Option<(StacksBlockId, u64)> {
+        if !network.stacks_tip.is_nakamoto {
+            return None;
+        }
+        let tip_block_id = network.stacks_tip.block_id();
+        match NakamotoChainState::highest_contiguous_processed_height(chainstate, &tip_block_id) {
+            Ok(Some(highest_processed)) => Some(highest_processed),
+            Ok(None) => Some((tip_block_id, network.stacks_tip.height)),
+            Err(e) => {
+                warn!(
+                    "Failed to find highest contiguous processed block below {tip_block_id}: {e:?}"
+                );
+                Some((tip_block_id, network.stacks_tip.height))
+            }
+        }
+    }
+
     /// Run unconfirmed tenure download state machines.
     /// * Update the highest-processed block in each downloader to our highest-processed block
     /// * Send any HTTP requests that the downloaders indicate are needed (if they are not blocked
@@ -1100,7 +2191,7 @@ impl NakamotoDownloadStateMachine {
     /// * Obtain downloaded blocks, and create new confirmed tenure downloaders for the
     /// highest-complete tenure downloader.
     /// * Clear out downloader state for peers who have disconnected or have finished processing
-    /// their machines.
+    /// their machines, or whose ongoing tenure is no longer on the canonical sortition fork.
     ///
     /// As the local node processes blocks, update each downloader's view of the highest-processed
     /// block so it can cancel itself early if it finds that we've already got the blocks, or if
@@ -1111,6 +2202,12 @@ impl NakamotoDownloadStateMachine {
     /// that_ the download state machine is currently concerned with running unconfirmed tenure
     /// downloaders (i.e. it's not in IBD).
     ///
+    /// If `own_replies_only` is true, then only replies from the neighbors in `downloaders` are
+    /// collected from `neighbor_rpc`; the rest are left for the confirmed tenure downloaders.
+    ///
+    /// Each downloader is tagged with `download_pass` so that its logs can be matched up with the
+    /// pass that ran it.
+    ///
     /// This method is static to facilitate testing.
     ///
     /// Returns the map from neighbors to the unconfirmed blocks they serve, as well as a map from
@@ -1124,28 +2221,31 @@ impl NakamotoDownloadStateMachine {
         sortdb: &SortitionDB,
         sort_tip: &BlockSnapshot,
         chainstate: &StacksChainState,
+        own_replies_only: bool,
+        download_pass: u64,
     ) -> (
         HashMap<NeighborAddress, Vec<NakamotoBlock>>,
         HashMap<NeighborAddress, NakamotoTenureDownloader>,
     ) {
-        debug!("Run unconfirmed tenure downloaders");
+        debug!("Run unconfirmed tenure downloaders";
+            "download_pass" => download_pass,
+        );
+        for downloader in downloaders.values_mut() {
+            downloader.download_pass = download_pass;
+        }
 
         let addrs: Vec<_> = downloaders.keys().cloned().collect();
         let mut finished = vec![];
         let mut unconfirmed_blocks = HashMap::new();
         let mut highest_completed_tenure_downloaders = HashMap::new();
 
-        if network.stacks_tip.is_nakamoto {
+        if let Some((highest_processed_block_id, highest_processed_block_height)) =
+            Self::highest_processed_block(network, chainstate)
+        {
             // find the highest-processed block, and update all ongoing state-machines.
             // Then, as faster state-machines linked to more up-to-date peers download newer blocks,
             // other state-machines will automatically terminate once they reach the highest block this
             // peer has now processed.
-            let highest_processed_block_id = StacksBlockId::new(
-                &network.stacks_tip.consensus_hash,
-                &network.stacks_tip.block_hash,
-            );
-            let highest_processed_block_height = network.stacks_tip.height;
-
             for (_, downloader) in downloaders.iter_mut() {
                 downloader.set_highest_processed_block(
                     highest_processed_block_id.clone(),
@@ -1156,6 +2256,24 @@ impl NakamotoDownloadStateMachine {
 
         // send requests
         for (naddr, downloader) in downloaders.iter_mut() {
+            match downloader.check_reorg(sortdb, sort_tip) {
+                Ok(true) => {
+                    debug!(
+                        "Downloader for {:?} is aborted (tenure reorged out; {})",
+                        &downloader.unconfirmed_tenure_id(),
+                        naddr
+                    );
+                    finished.push(naddr.clone());
+                    continue;
+                }
+                Ok(false) => {}
+                Err(e) => {
+                    warn!(
+                        "Failed to check downloader for {:?} against the sortition tip: {e:?}",
+                        &downloader.unconfirmed_tenure_id()
+                    );
+                }
+            }
             if downloader.is_done() {
                 debug!(
                     "Downloader for {:?} is done (finished {})",
@@ -1211,7 +2329,12 @@ impl NakamotoDownloadStateMachine {
         }
 
         // handle responses
-        for (naddr, response) in neighbor_rpc.collect_replies(network) {
+        let replies = if own_replies_only {
+            neighbor_rpc.collect_replies_from(network, |naddr| downloaders.contains_key(naddr))
+        } else {
+            neighbor_rpc.collect_replies(network)
+        };
+        for (naddr, response) in replies {
             let Some(downloader) = downloaders.get_mut(&naddr) else {
                 debug!("Got rogue response from {}", &naddr);
                 continue;
@@ -1235,6 +2358,15 @@ impl NakamotoDownloadStateMachine {
                     );
                     continue;
                 }
+                Err(NetError::Transient(msg)) => {
+                    // we're not ready for this peer's data yet (e.g. we don't know a reward set
+                    // yet), but the peer did nothing wrong.  Keep the downloader and try again.
+                    debug!(
+                        "Unconfirmed downloader for {naddr} is not ready in state {}: {msg}",
+                        &downloader.state
+                    );
+                    continue;
+                }
                 Err(e) => {
                     debug!("Failed to handle next download response from unconfirmed downloader for {:?} in state {:?}: {:?}", &naddr, &downloader.state, &e);
                     neighbor_rpc.add_dead(
@@ -1305,7 +2437,11 @@ impl NakamotoDownloadStateMachine {
     /// Run and process all confirmed tenure downloaders, and do the necessary bookkeeping to deal
     /// with failed peer connections.
     ///
-    /// At most `max_count` downloaders will be instantiated at once.
+    /// At most `max_count` downloaders will be instantiated at once.  If `ibd` is true, then new
+    /// downloaders are only instantiated once enough peers have provided inventories (see
+    /// `try_update_tenure_downloaders()`).  If `own_replies_only` is true, then the downloaders only
+    /// collect the replies from their own neighbors, since unconfirmed tenure downloaders are
+    /// running alongside them.
     ///
     /// Returns the set of downloaded confirmed tenures obtained.
     fn download_confirmed_tenures(
@@ -1313,9 +2449,34 @@ impl NakamotoDownloadStateMachine {
         network: &mut PeerNetwork,
         chainstate: &mut StacksChainState,
         max_count: usize,
+        ibd: bool,
+        own_replies_only: bool,
     ) -> HashMap<ConsensusHash, Vec<NakamotoBlock>> {
         // queue up more downloaders
-        self.update_tenure_downloaders(max_count, &network.current_reward_sets);
+        let min_peers_for_ibd = network.get_connection_opts().min_peers_for_ibd;
+        self.tenure_downloads.prev_cycle_download_slots = usize::try_from(
+            network
+                .get_connection_opts()
+                .nakamoto_prev_cycle_download_slots,
+        )
+        .unwrap_or(usize::MAX);
+        self.tenure_downloads.verify_tx_merkle_roots = network
+            .get_connection_opts()
+            .nakamoto_download_verify_tx_merkle_roots;
+        self.tenure_downloads.checkpoint_tenure_downloads = network
+            .get_connection_opts()
+            .nakamoto_download_checkpoint_tenures;
+        self.tenure_downloads.own_replies_only = own_replies_only;
+        self.tenure_downloads.download_pass = self.download_pass;
+        if let Some(invs) = network.inv_state_nakamoto.as_ref() {
+            self.try_update_tenure_downloaders(
+                max_count,
+                &network.current_reward_sets,
+                &invs.inventories,
+                min_peers_for_ibd,
+                ibd,
+            );
+        }
 
         // run all downloaders
         let new_blocks = self
@@ -1325,6 +2486,74 @@ impl NakamotoDownloadStateMachine {
         new_blocks
     }
 
+    /// Choose up to `count` unconfirmed tenure downloaders which can run alongside the confirmed
+    /// tenure downloaders.  These are the ones whose neighbors are not serving a confirmed tenure,
+    /// so their requests and replies can't be confused with those of a confirmed tenure
+    /// downloader.
+    pub(crate) fn find_reserved_unconfirmed_downloaders(
+        unconfirmed_tenure_downloads: &HashMap<
+            NeighborAddress,
+            NakamotoUnconfirmedTenureDownloader,
+        >,
+        tenure_downloads: &NakamotoTenureDownloaderSet,
+        count: usize,
+    ) -> Vec<NeighborAddress> {
+        unconfirmed_tenure_downloads
+            .keys()
+            .filter(|naddr| !tenure_downloads.has_downloader(naddr))
+            .take(count)
+            .cloned()
+            .collect()
+    }
+
+    /// Run up to `count` unconfirmed tenure downloaders while the highest complete tenure is still
+    /// being downloaded.  Only the replies to these downloaders are collected.
+    ///
+    /// Returns the same values as `run_unconfirmed_downloaders()`.
+    fn run_reserved_unconfirmed_downloaders(
+        &mut self,
+        network: &mut PeerNetwork,
+        sortdb: &SortitionDB,
+        sort_tip: &BlockSnapshot,
+        chainstate: &StacksChainState,
+        count: usize,
+    ) -> (
+        HashMap<NeighborAddress, Vec<NakamotoBlock>>,
+        HashMap<NeighborAddress, NakamotoTenureDownloader>,
+    ) {
+        let naddrs = Self::find_reserved_unconfirmed_downloaders(
+            &self.unconfirmed_tenure_downloads,
+            &self.tenure_downloads,
+            count,
+        );
+        let mut reserved_downloaders: HashMap<_, _> = naddrs
+            .into_iter()
+            .filter_map(|naddr| self.unconfirmed_tenure_downloads.remove_entry(&naddr))
+            .collect();
+
+        debug!(
+            "Run {} reserved unconfirmed tenure downloaders",
+            reserved_downloaders.len();
+            "download_pass" => self.download_pass,
+            "inflight" => self.tenure_downloads.inflight(),
+        );
+        let res = Self::run_unconfirmed_downloaders(
+            &mut reserved_downloaders,
+            network,
+            &mut self.neighbor_rpc,
+            sortdb,
+            sort_tip,
+            chainstate,
+            true,
+            self.download_pass,
+        );
+
+        // put back the ones still running
+        self.unconfirmed_tenure_downloads
+            .extend(reserved_downloaders);
+        res
+    }
+
     /// Run and process all unconfirmed tenure downloads, and highest complete tenure downloads.
     /// Do the needful bookkeeping to remove dead peers.
     fn download_unconfirmed_tenures(
@@ -1337,8 +2566,10 @@ impl NakamotoDownloadStateMachine {
         // queue up more downloaders
         self.update_unconfirmed_tenure_downloaders(
             network.get_connection_opts(),
-            usize::try_from(network.get_connection_opts().max_inflight_blocks)
-                .expect("FATAL: max_inflight_blocks exceeds usize::MAX"),
+            Self::unconfirmed_downloader_budget(
+                network.get_connection_opts(),
+                self.unconfirmed_tenure_downloads.len(),
+            ),
             highest_processed_block_id,
         );
 
@@ -1346,69 +2577,199 @@ impl NakamotoDownloadStateMachine {
         // highest complete tenure
         let burnchain_tip = network.burnchain_tip.clone();
 
-        // Run the confirmed downloader state machine set, since we could already be processing the
-        // highest complete tenure download.  NOTE: due to the way that we call this method, we're
-        // guaranteed that if the `tenure_downloads` downloader set has any downloads at all, they
-        // will only be for the highest complete tenure (i.e. we only call this method if we've
-        // already downloaded all confirmed tenures), so there's no risk of clobberring any other
-        // in-flight requests.
-        let new_confirmed_blocks = if self.tenure_downloads.inflight() > 0 {
-            self.download_confirmed_tenures(network, chainstate, 0)
-        } else {
-            HashMap::new()
-        };
-
-        // Only run unconfirmed downloaders if we're _not_ busy obtaining the highest confirmed
-        // tenure.  The behavior here ensures that we first obtain the highest complete tenure, and
-        // then poll for new unconfirmed tenure blocks.
-        let (new_unconfirmed_blocks, new_highest_confirmed_downloaders) =
-            if self.tenure_downloads.inflight() > 0 {
-                (HashMap::new(), HashMap::new())
-            } else {
-                Self::run_unconfirmed_downloaders(
-                    &mut self.unconfirmed_tenure_downloads,
+        let reserved_inflight = usize::try_from(
+            network
+                .get_connection_opts()
+                .nakamoto_unconfirmed_downloader_reserved_inflight,
+        )
+        .unwrap_or(usize::MAX);
+
+        let (new_confirmed_blocks, (new_unconfirmed_blocks, new_highest_confirmed_downloaders)) =
+            if reserved_inflight > 0 && self.tenure_downloads.inflight() > 0 {
+                // We're busy obtaining the highest complete tenure, but some unconfirmed
+                // downloaders are allowed to run anyway.  Both sides share the `NeighborRPC`, so
+                // each of them only collects the replies from its own neighbors.
+                let new_unconfirmed = self.run_reserved_unconfirmed_downloaders(
                     network,
-                    &mut self.neighbor_rpc,
                     sortdb,
                     &burnchain_tip,
                     chainstate,
-                )
+                    reserved_inflight,
+                );
+                let new_confirmed =
+                    self.download_confirmed_tenures(network, chainstate, 0, false, true);
+                (new_confirmed, new_unconfirmed)
+            } else {
+                // Run the confirmed downloader state machine set, since we could already be
+                // processing the highest complete tenure download.  NOTE: due to the way that we
+                // call this method, we're guaranteed that if the `tenure_downloads` downloader
+                // set has any downloads at all, they will only be for the highest complete tenure
+                // (i.e. we only call this method if we've already downloaded all confirmed
+                // tenures), so there's no risk of clobberring any other in-flight requests.
+                let new_confirmed = if self.tenure_downloads.inflight() > 0 {
+                    self.download_confirmed_tenures(network, chainstate, 0, false, false)
+                } else {
+                    HashMap::new()
+                };
+
+                // Only run unconfirmed downloaders if we're _not_ busy obtaining the highest
+                // confirmed tenure.  The behavior here ensures that we first obtain the highest
+                // complete tenure, and then poll for new unconfirmed tenure blocks.
+                let new_unconfirmed = if self.tenure_downloads.inflight() > 0 {
+                    (HashMap::new(), HashMap::new())
+                } else {
+                    Self::run_unconfirmed_downloaders(
+                        &mut self.unconfirmed_tenure_downloads,
+                        network,
+                        &mut self.neighbor_rpc,
+                        sortdb,
+                        &burnchain_tip,
+                        chainstate,
+                        false,
+                        self.download_pass,
+                    )
+                };
+                (new_confirmed, new_unconfirmed)
             };
 
         // schedule downloaders for the highest-confirmed tenure, if we generated any
         self.tenure_downloads
             .add_downloaders(new_highest_confirmed_downloaders);
 
-        // coalesce blocks -- maps consensus hash to map of block id to block
+        let max_blocks = usize::try_from(
+            network
+                .get_connection_opts()
+                .nakamoto_unconfirmed_download_max_coalesced_blocks,
+        )
+        .unwrap_or(usize::MAX);
+        self.coalesce_unconfirmed_pass_blocks(
+            new_confirmed_blocks,
+            new_unconfirmed_blocks,
+            max_blocks,
+        )
+    }
+
+    /// Coalesce the blocks obtained while fetching unconfirmed tenures in this pass, together with
+    /// any blocks deferred from prior passes, and return no more than `max_blocks` of them.  The
+    /// rest are deferred to the next pass.  0 means no limit.
+    pub(crate) fn coalesce_unconfirmed_pass_blocks(
+        &mut self,
+        mut confirmed_blocks: HashMap<ConsensusHash, Vec<NakamotoBlock>>,
+        unconfirmed_blocks: HashMap<NeighborAddress, Vec<NakamotoBlock>>,
+        max_blocks: usize,
+    ) -> HashMap<ConsensusHash, Vec<NakamotoBlock>> {
+        for (consensus_hash, deferred_blocks) in self.deferred_unconfirmed_blocks.drain() {
+            // put the deferred blocks first, so a freshly-obtained confirmed copy of a block
+            // replaces its deferred copy
+            let blocks = confirmed_blocks.entry(consensus_hash).or_default();
+            let new_blocks = std::mem::replace(blocks, deferred_blocks);
+            blocks.extend(new_blocks);
+        }
+        let coalesced = Self::coalesce_tenure_blocks(confirmed_blocks, unconfirmed_blocks);
+        let (blocks, deferred) = Self::defer_excess_blocks(coalesced, max_blocks);
+        if !deferred.is_empty() {
+            debug!("Deferring unconfirmed pass blocks to the next pass";
+                "download_pass" => self.download_pass,
+                "max_blocks" => max_blocks,
+                "num_deferred_blocks" => deferred.values().map(|blocks| blocks.len()).sum::<usize>(),
+            );
+        }
+        self.deferred_unconfirmed_blocks = deferred;
+        blocks
+    }
+
+    /// Split coalesced tenure blocks into those to return now and those to defer, such that no
+    /// more than `max_blocks` are returned.  Tenures are visited in order by the height of their
+    /// lowest block, so lower tenures are returned first, and within a tenure only a prefix of
+    /// its blocks is returned.  0 means no limit.
+    ///
+    /// Returns (blocks to return, blocks to defer)
+    ///
+    /// This method is static to facilitate testing.
+    pub(crate) fn defer_excess_blocks(
+        coalesced: HashMap<ConsensusHash, Vec<NakamotoBlock>>,
+        max_blocks: usize,
+    ) -> (
+        HashMap<ConsensusHash, Vec<NakamotoBlock>>,
+        HashMap<ConsensusHash, Vec<NakamotoBlock>>,
+    ) {
+        if max_blocks == 0 {
+            return (coalesced, HashMap::new());
+        }
+        let mut tenures: Vec<_> = coalesced.into_iter().collect();
+        tenures.sort_by_key(|(consensus_hash, blocks)| {
+            (
+                blocks.first().map(|blk| blk.header.chain_length),
+                consensus_hash.clone(),
+            )
+        });
+
+        let mut budget = max_blocks;
+        let mut returned = HashMap::new();
+        let mut deferred = HashMap::new();
+        for (consensus_hash, mut blocks) in tenures.into_iter() {
+            let num_returned = budget.min(blocks.len());
+            let rest = blocks.split_off(num_returned);
+            budget -= num_returned;
+            if !blocks.is_empty() {
+                returned.insert(consensus_hash.clone(), blocks);
+            }
+            if !rest.is_empty() {
+                deferred.insert(consensus_hash, rest);
+            }
+        }
+        (returned, deferred)
+    }
+
+    /// Merge the blocks obtained by the confirmed and unconfirmed tenure downloaders, grouping
+    /// them by tenure and sorting them by height.  If both obtained a block with the same ID, then
+    /// the confirmed downloader's copy is kept, since it was checked against the confirmed
+    /// tenure's signer keys.
+    ///
+    /// This method is static to facilitate testing.
+    pub(crate) fn coalesce_tenure_blocks(
+        confirmed_blocks: HashMap<ConsensusHash, Vec<NakamotoBlock>>,
+        unconfirmed_blocks: HashMap<NeighborAddress, Vec<NakamotoBlock>>,
+    ) -> HashMap<ConsensusHash, Vec<NakamotoBlock>> {
+        // maps consensus hash to map of block id to block
         let mut coalesced_blocks: HashMap<ConsensusHash, HashMap<StacksBlockId, NakamotoBlock>> =
             HashMap::new();
-        for blocks in new_unconfirmed_blocks
-            .into_values()
-            .chain(new_confirmed_blocks.into_values())
-        {
+        for block in confirmed_blocks.into_values().flatten() {
+            coalesced_blocks
+                .entry(block.header.consensus_hash.clone())
+                .or_default()
+                .insert(block.header.block_id(), block);
+        }
+        for (naddr, blocks) in unconfirmed_blocks.into_iter() {
             for block in blocks.into_iter() {
+                let block_map = coalesced_blocks
+                    .entry(block.header.consensus_hash.clone())
+                    .or_default();
                 let block_id = block.header.block_id();
-                if let Some(block_map) = coalesced_blocks.get_mut(&block.header.consensus_hash) {
+                let Some(confirmed_block) = block_map.get(&block_id) else {
                     block_map.insert(block_id, block);
+                    continue;
+                };
+                if confirmed_block == &block {
+                    debug!(
+                        "Unconfirmed tenure block {block_id} from {naddr} was also obtained as a confirmed tenure block"
+                    );
                 } else {
-                    let mut block_map = HashMap::new();
-                    let ch = block.header.consensus_hash.clone();
-                    block_map.insert(block_id, block);
-                    coalesced_blocks.insert(ch, block_map);
+                    info!(
+                        "Unconfirmed tenure block {block_id} from {naddr} differs from the confirmed tenure block with the same ID; keeping the confirmed one"
+                    );
                 }
             }
         }
 
-        let tenure_blocks = coalesced_blocks
+        coalesced_blocks
             .into_iter()
             .map(|(consensus_hash, block_map)| {
                 let mut block_list: Vec<_> = block_map.into_values().collect();
                 block_list.sort_unstable_by_key(|blk| blk.header.chain_length);
                 (consensus_hash, block_list)
             })
-            .collect();
-
-        tenure_blocks
+            .collect()
     }
 
     /// Top-level download state machine execution.
@@ -1433,6 +2794,7 @@ impl NakamotoDownloadStateMachine {
         debug!(
             "run_downloads: burnchain_height={}, network.burnchain_tip.block_height={}, state={}",
             burnchain_height, network.burnchain_tip.block_height, &self.state;
+            "download_pass" => self.download_pass,
             "has_network_inventories" => network.inv_state_nakamoto.is_some(),
             "next_unconfirmed_check" => self.last_unconfirmed_download_check_ms.saturating_add(CHECK_UNCONFIRMED_TENURES_MS) / 1000,
             "timestamp_ms" => get_epoch_time_ms(),
@@ -1443,6 +2805,12 @@ impl NakamotoDownloadStateMachine {
             return HashMap::new();
         };
 
+        self.trusted_download_peers = network
+            .get_connection_opts()
+            .trusted_download_peers
+            .iter()
+            .cloned()
+            .collect();
         self.update_available_tenures(
             &invs.inventories,
             &sortdb.pox_constants,
@@ -1451,24 +2819,38 @@ impl NakamotoDownloadStateMachine {
         );
 
         // check this now, since we mutate self.available
-        self.fetch_unconfirmed_tenures = if self
-            .last_unconfirmed_download_check_ms
-            .saturating_add(CHECK_UNCONFIRMED_TENURES_MS)
-            > get_epoch_time_ms()
-        {
-            false
+        self.update_fetch_unconfirmed_tenures(
+            burnchain_height,
+            &network.burnchain_tip,
+            network.get_connection_opts(),
+            get_epoch_time_ms(),
+        );
+
+        let high_water_mark = network
+            .get_connection_opts()
+            .nakamoto_download_staging_high_water_mark;
+        let low_water_mark = network
+            .get_connection_opts()
+            .nakamoto_download_staging_low_water_mark;
+        if high_water_mark > 0 {
+            match chainstate
+                .nakamoto_blocks_db()
+                .count_unprocessed_nakamoto_blocks()
+            {
+                Ok(num_unprocessed) => {
+                    self.update_staging_backpressure(
+                        num_unprocessed,
+                        high_water_mark,
+                        low_water_mark,
+                    );
+                }
+                Err(e) => {
+                    warn!("Failed to count unprocessed staging blocks: {e:?}");
+                }
+            }
         } else {
-            let do_fetch = Self::need_unconfirmed_tenures(
-                burnchain_height,
-                &network.burnchain_tip,
-                &self.wanted_tenures,
-                self.prev_wanted_tenures.as_ref().unwrap_or(&vec![]),
-                &self.tenure_block_ids,
-                &self.available_tenures,
-            );
-            self.last_unconfirmed_download_check_ms = get_epoch_time_ms();
-            do_fetch
-        };
+            self.update_staging_backpressure(0, 0, low_water_mark);
+        }
 
         match self.state {
             NakamotoDownloadState::Confirmed => {
@@ -1477,13 +2859,16 @@ impl NakamotoDownloadStateMachine {
                     chainstate,
                     usize::try_from(network.get_connection_opts().max_inflight_blocks)
                         .expect("FATAL: max_inflight_blocks exceeds usize::MAX"),
+                    ibd,
+                    false,
                 );
 
                 if self.tenure_downloads.is_empty() && self.fetch_unconfirmed_tenures {
                     debug!(
                         "Transition from {} to {}",
                         &self.state,
-                        NakamotoDownloadState::Unconfirmed
+                        NakamotoDownloadState::Unconfirmed;
+                        "download_pass" => self.download_pass,
                     );
 
                     self.unconfirmed_tenure_download_schedule =
@@ -1497,20 +2882,14 @@ impl NakamotoDownloadStateMachine {
                 return new_blocks;
             }
             NakamotoDownloadState::Unconfirmed => {
-                let highest_processed_block_id = StacksBlockId::new(
-                    &network.stacks_tip.consensus_hash,
-                    &network.stacks_tip.block_hash,
-                );
+                let highest_processed_block_id = Self::highest_processed_block(network, chainstate)
+                    .map(|(block_id, _)| block_id);
 
                 let new_blocks = self.download_unconfirmed_tenures(
                     network,
                     sortdb,
                     chainstate,
-                    if network.stacks_tip.is_nakamoto {
-                        Some(highest_processed_block_id)
-                    } else {
-                        None
-                    },
+                    highest_processed_block_id,
                 );
 
                 if !self.tenure_downloads.is_empty() {
@@ -1518,7 +2897,8 @@ impl NakamotoDownloadStateMachine {
                     debug!(
                         "Transition from {} to {} -- need confirmed tenure still",
                         &self.state,
-                        NakamotoDownloadState::Confirmed
+                        NakamotoDownloadState::Confirmed;
+                        "download_pass" => self.download_pass,
                     );
                     self.state = NakamotoDownloadState::Confirmed;
                 } else if self.unconfirmed_tenure_downloads.is_empty()
@@ -1534,14 +2914,16 @@ impl NakamotoDownloadStateMachine {
                         debug!(
                             "Transition from {} to {}",
                             &self.state,
-                            NakamotoDownloadState::Unconfirmed
+                            NakamotoDownloadState::Unconfirmed;
+                            "download_pass" => self.download_pass,
                         );
                         self.state = NakamotoDownloadState::Unconfirmed;
                     } else {
                         debug!(
                             "Transition from {} to {}",
                             &self.state,
-                            NakamotoDownloadState::Confirmed
+                            NakamotoDownloadState::Confirmed;
+                            "download_pass" => self.download_pass,
                         );
                         self.state = NakamotoDownloadState::Confirmed;
                     }
@@ -1554,20 +2936,96 @@ impl NakamotoDownloadStateMachine {
 
     /// Go and get tenures. Returns list of blocks per tenure, identified by consensus hash.
     /// The blocks will be sorted by height, but may not be contiguous.
+    ///
+    /// If `ibd` is `None`, then whether or not the node is in IBD is inferred from the size of
+    /// the download backlog (see `infer_ibd()`).
     pub fn run(
         &mut self,
         burnchain_height: u64,
         network: &mut PeerNetwork,
         sortdb: &SortitionDB,
         chainstate: &mut StacksChainState,
-        ibd: bool,
+        ibd: Option<bool>,
     ) -> Result<HashMap<ConsensusHash, Vec<NakamotoBlock>>, NetError> {
+        self.download_pass = self.download_pass.wrapping_add(1);
         self.nakamoto_tip = network.stacks_tip.block_id();
-        debug!("Downloader: Nakamoto tip is {:?}", &self.nakamoto_tip);
+        debug!("Downloader: begin pass";
+            "nakamoto_tip" => %self.nakamoto_tip,
+            self.metrics(),
+        );
         self.update_wanted_tenures(network, sortdb)?;
         self.update_processed_tenures(chainstate)?;
+        let ibd = ibd.unwrap_or_else(|| {
+            Self::infer_ibd(
+                burnchain_height,
+                &network.burnchain_tip,
+                &self.wanted_tenures,
+                self.prev_wanted_tenures.as_deref().unwrap_or(&[]),
+                network
+                    .get_connection_opts()
+                    .nakamoto_download_ibd_backlog_threshold,
+            )
+        });
         let new_blocks = self.run_downloads(burnchain_height, network, sortdb, chainstate, ibd);
+        self.blocklist_dead_peers(network);
+        let new_blocks = self.finish_pass(
+            new_blocks,
+            network
+                .get_connection_opts()
+                .nakamoto_download_quarantine_disconnected_tenures,
+            get_epoch_time_secs(),
+            |id| Self::is_block_stored(chainstate, id),
+        )?;
         self.last_sort_tip = Some(network.burnchain_tip.clone());
+        debug!("Downloader: end pass";
+            self.metrics(),
+        );
         Ok(new_blocks)
     }
+
+    /// Finish off a download pass, given the tenures it obtained.  If `quarantine` is set, then
+    /// tenures which do not connect to stored blocks are held back (see
+    /// `quarantine_disconnected_tenures()`).  The remaining blocks are counted towards the download
+    /// rate, and then pushed into the block sink, if there is one, in ascending order by the
+    /// height of each tenure's lowest block.
+    ///
+    /// Returns the tenures to hand back from `run()`.  If a block sink is set, then these have
+    /// been pushed to it instead, and the returned set is empty.
+    pub(crate) fn finish_pass<F>(
+        &mut self,
+        new_blocks: HashMap<ConsensusHash, Vec<NakamotoBlock>>,
+        quarantine: bool,
+        now_secs: u64,
+        is_block_stored: F,
+    ) -> Result<HashMap<ConsensusHash, Vec<NakamotoBlock>>, NetError>
+    where
+        F: FnMut(&StacksBlockId) -> Result<bool, NetError>,
+    {
+        let new_blocks = if quarantine {
+            self.quarantine_disconnected_tenures(new_blocks, now_secs, is_block_stored)?
+        } else {
+            new_blocks
+        };
+        let num_new_blocks: usize = new_blocks.values().map(|blocks| blocks.len()).sum();
+        self.record_downloaded_blocks(now_secs, u64::try_from(num_new_blocks).unwrap_or(u64::MAX));
+        debug!("Downloader: obtained {} blocks in {} tenures", num_new_blocks, new_blocks.len();
+            "download_pass" => self.download_pass,
+            "block_sink" => self.block_sink.is_some(),
+        );
+
+        let Some(sink) = self.block_sink.as_mut() else {
+            return Ok(new_blocks);
+        };
+        let mut tenures: Vec<_> = new_blocks.into_iter().collect();
+        tenures.sort_by_key(|(consensus_hash, blocks)| {
+            (
+                blocks.first().map(|blk| blk.header.chain_length),
+                consensus_hash.clone(),
+            )
+        });
+        for (consensus_hash, blocks) in tenures.into_iter() {
+            sink.push_tenure_blocks(&consensus_hash, blocks);
+        }
+        Ok(HashMap::new())
+    }
 }
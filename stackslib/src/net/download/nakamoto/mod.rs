@@ -122,18 +122,29 @@ use crate::net::Error as NetError;
 
 mod download_state_machine;
 mod tenure;
+mod tenure_archive;
 mod tenure_downloader;
 mod tenure_downloader_set;
 mod tenure_downloader_unconfirmed;
 
 pub use crate::net::download::nakamoto::download_state_machine::{
-    NakamotoDownloadState, NakamotoDownloadStateMachine,
+    NakamotoDownloadMetrics, NakamotoDownloadState, NakamotoDownloadStateMachine,
+    NeighborAvailableTenures, RewardCycleCompleteHandler, TenureStatus, UnconfirmedTenuresBlocker,
+    PEER_BLOCKLIST_TIME_SECS, TENURE_QUARANTINE_TIME_SECS,
 };
 pub use crate::net::download::nakamoto::tenure::{AvailableTenures, TenureStartEnd, WantedTenure};
+pub use crate::net::download::nakamoto::tenure_archive::{
+    export_tenure_archive, write_tenure_archive, TenureArchiveReader, TENURE_ARCHIVE_MAGIC,
+    TENURE_ARCHIVE_VERSION,
+};
 pub use crate::net::download::nakamoto::tenure_downloader::{
-    NakamotoTenureDownloadState, NakamotoTenureDownloader,
+    NakamotoTenureDownloadState, NakamotoTenureDownloader, SignerSignatureCache,
+    TenureDownloaderSnapshot, SIGNER_SIGNATURE_CACHE_SIZE,
+};
+pub use crate::net::download::nakamoto::tenure_downloader_set::{
+    AbandonedTenureReason, BlockSink, DefaultDownloadPeerSelector, DownloadPeerSelector,
+    NakamotoTenureDownloaderSet, CHECKPOINT_PRUNE_INTERVAL_SECS,
 };
-pub use crate::net::download::nakamoto::tenure_downloader_set::NakamotoTenureDownloaderSet;
 pub use crate::net::download::nakamoto::tenure_downloader_unconfirmed::{
     NakamotoUnconfirmedDownloadState, NakamotoUnconfirmedTenureDownloader,
 };
@@ -150,6 +161,14 @@ impl PeerNetwork {
         self.block_downloader_nakamoto = Some(downloader);
     }
 
+    /// Summarize the Nakamoto block downloader's progress.
+    /// Returns None if the downloader has not been set up yet.
+    pub fn nakamoto_download_metrics(&self) -> Option<NakamotoDownloadMetrics> {
+        self.block_downloader_nakamoto
+            .as_ref()
+            .map(|downloader| downloader.metrics())
+    }
+
     /// Drive the block download state machine
     pub fn sync_blocks_nakamoto(
         &mut self,
@@ -165,7 +184,8 @@ impl PeerNetwork {
             return Ok(HashMap::new());
         };
 
-        let new_blocks_res = block_downloader.run(burnchain_height, self, sortdb, chainstate, ibd);
+        let new_blocks_res =
+            block_downloader.run(burnchain_height, self, sortdb, chainstate, Some(ibd));
         self.block_downloader_nakamoto = Some(block_downloader);
 
         new_blocks_res
@@ -191,10 +211,12 @@ impl PeerNetwork {
         };
 
         for broken in block_downloader.neighbor_rpc.take_broken() {
+            block_downloader.num_broken_peers += 1;
             self.deregister_and_ban_neighbor(&broken.key, broken.reason, broken.source);
         }
 
         for dead in block_downloader.neighbor_rpc.take_dead() {
+            block_downloader.num_dead_peers += 1;
             self.deregister_neighbor(&dead.key, dead.reason, dead.source);
         }
 
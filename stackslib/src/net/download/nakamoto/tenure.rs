@@ -16,13 +16,14 @@
 use std::collections::HashMap;
 use std::convert::TryFrom;
 
+use stacks_common::bitvec::BitVec;
 use stacks_common::types::chainstate::{ConsensusHash, StacksBlockId};
 
 use crate::burnchains::PoxConstants;
 use crate::net::inv::nakamoto::NakamotoTenureInv;
 
 /// A tenure that this node needs data for.
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub struct WantedTenure {
     /// Consensus hash that identifies the start of the tenure
     pub tenure_id_consensus_hash: ConsensusHash,
@@ -49,11 +50,17 @@ impl WantedTenure {
             processed: false,
         }
     }
+
+    /// Did this tenure's sortition have a winning block-commit?  If not, its `winning_block_id`
+    /// is the all-zero sentinel, and there is no tenure (nor tenure-start block) to download.
+    pub fn has_winner(&self) -> bool {
+        self.winning_block_id != StacksBlockId([0x00; 32])
+    }
 }
 
 /// A tenure's start and end blocks.  This is constructed from a sequence of `WantedTenure`s and a
 /// node's inventory vector over them.
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub struct TenureStartEnd {
     /// Consensus hash that identifies the start of the tenure
     pub tenure_id_consensus_hash: ConsensusHash,
@@ -107,6 +114,21 @@ impl TenureStartEnd {
         }
     }
 
+    /// Does `wanted_tenures` begin at the first burnchain block of reward cycle `rc` (i.e. the mod
+    /// 0 block)?  An empty list is trivially aligned.
+    fn is_reward_cycle_aligned(
+        rc: u64,
+        wanted_tenures: &[WantedTenure],
+        pox_constants: &PoxConstants,
+        first_burn_height: u64,
+    ) -> bool {
+        let Some(first_wanted_tenure) = wanted_tenures.first() else {
+            return true;
+        };
+        first_wanted_tenure.burn_height
+            == pox_constants.nakamoto_first_block_of_cycle(first_burn_height, rc)
+    }
+
     /// Given a list of wanted tenures and a peer's inventory bitvectors over the same range of
     /// tenures, calculate the list of start/end blocks for each wanted tenure.
     ///
@@ -125,7 +147,9 @@ impl TenureStartEnd {
     ///
     /// Returns the set of available tenures for all tenures in `wanted_tenures` that can be found
     /// with the available information.
-    /// Returns None if there is no inventory data for the given reward cycle.
+    /// Returns None if there is no inventory data for the given reward cycle, or if
+    /// `wanted_tenures` (or `next_wanted_tenures`) does not start at the first burnchain block of
+    /// reward cycle `rc` (or `rc + 1`).
     pub fn from_inventory(
         rc: u64,
         wanted_tenures: &[WantedTenure],
@@ -139,11 +163,232 @@ impl TenureStartEnd {
         // parent tenure, the start-block ID for tenure i would be the StacksBlockId for the
         // next-available tenure.  Its end-block ID would be the StacksBlockId for the
         // next-available tenure after that.
+        if !Self::is_reward_cycle_aligned(rc, wanted_tenures, pox_constants, first_burn_height) {
+            warn!(
+                "Wanted tenures are not aligned to the start of reward cycle {rc}";
+                "first_burn_height" => wanted_tenures.first().map(|wt| wt.burn_height),
+                "expected_first_burn_height" => pox_constants.nakamoto_first_block_of_cycle(first_burn_height, rc),
+            );
+            return None;
+        }
+        if let Some(next_wanted_tenures) = next_wanted_tenures {
+            let next_rc = rc.saturating_add(1);
+            if !Self::is_reward_cycle_aligned(
+                next_rc,
+                next_wanted_tenures,
+                pox_constants,
+                first_burn_height,
+            ) {
+                warn!(
+                    "Next wanted tenures are not aligned to the start of reward cycle {next_rc}";
+                    "first_burn_height" => next_wanted_tenures.first().map(|wt| wt.burn_height),
+                    "expected_first_burn_height" => pox_constants.nakamoto_first_block_of_cycle(first_burn_height, next_rc),
+                );
+                return None;
+            }
+        }
+
         let invbits = invs.tenures_inv.get(&rc)?;
+        let next_invbits = invs.tenures_inv.get(&rc.saturating_add(1));
+        debug!("Find available tenures in inventory {:?} rc {}", invs, rc);
+        Some(Self::from_inventory_bits(
+            rc,
+            wanted_tenures,
+            invbits,
+            next_wanted_tenures,
+            next_invbits,
+            pox_constants,
+            first_burn_height,
+        ))
+    }
+
+    /// The core of `from_inventory()`.  Given the wanted tenures for reward cycle `rc` and a peer's
+    /// inventory bits over them, as well as (optionally) the next reward cycle's wanted tenures and
+    /// the peer's inventory bits over those, calculate the list of start/end blocks for each
+    /// wanted tenure.
+    ///
+    /// The wanted tenures are assumed to be aligned to reward cycle boundaries, as described in
+    /// `from_inventory()`, but nothing else is assumed about the arguments.  In debug builds, the
+    /// result is checked with `check_available_tenures()`.
+    pub(crate) fn from_inventory_bits(
+        rc: u64,
+        wanted_tenures: &[WantedTenure],
+        invbits: &BitVec<2100>,
+        next_wanted_tenures: Option<&[WantedTenure]>,
+        next_invbits: Option<&BitVec<2100>>,
+        pox_constants: &PoxConstants,
+        first_burn_height: u64,
+    ) -> AvailableTenures {
+        let invbits = Self::clear_tenures_without_winner(wanted_tenures, invbits);
+        let next_invbits =
+            next_wanted_tenures
+                .zip(next_invbits)
+                .map(|(next_wanted_tenures, next_invbits)| {
+                    Self::clear_tenures_without_winner(next_wanted_tenures, next_invbits)
+                });
+        let tenure_block_ids = Self::find_available_tenures(
+            rc,
+            wanted_tenures,
+            &invbits,
+            next_wanted_tenures,
+            next_invbits.as_ref(),
+            pox_constants,
+            first_burn_height,
+        );
+        debug_assert_eq!(
+            Self::check_available_tenures(
+                rc,
+                wanted_tenures,
+                &invbits,
+                next_wanted_tenures,
+                next_invbits.as_ref(),
+                &tenure_block_ids,
+            ),
+            Ok(())
+        );
+        tenure_block_ids
+    }
+
+    /// Clear the inventory bits of the wanted tenures whose sortitions had no winner, so that
+    /// they are never treated as a tenure, nor used as another tenure's start or end block.
+    fn clear_tenures_without_winner(
+        wanted_tenures: &[WantedTenure],
+        invbits: &BitVec<2100>,
+    ) -> BitVec<2100> {
+        let mut invbits = invbits.clone();
+        for (i, wt) in wanted_tenures.iter().enumerate() {
+            if wt.has_winner() {
+                continue;
+            }
+            let bit = u16::try_from(i).expect("FATAL: more sortitions than u16::MAX");
+            if invbits.get(bit).unwrap_or(false) {
+                debug!("Ignoring inventory bit {bit} for tenure without a winner: {wt:?}");
+                invbits
+                    .set(bit, false)
+                    .expect("FATAL: failed to clear a set bit");
+            }
+        }
+        invbits
+    }
+
+    /// Check the invariants of the available tenures computed by `from_inventory_bits()` from the
+    /// given arguments.  Consider the sequence of wanted tenures whose inventory bits are set,
+    /// first in `wanted_tenures` and then (if both it and its bits are given) in
+    /// `next_wanted_tenures`.  Then:
+    ///
+    /// * each wanted tenure in `wanted_tenures` with at least two tenures after it in this
+    ///   sequence is available, and no other tenure is;
+    /// * its tenure-start block is the winning block of the first tenure after it in the
+    ///   sequence, and its tenure-end block is the winning block of the second;
+    /// * its start reward cycle is `rc`, and its end reward cycle is `rc` or `rc + 1`.
+    ///
+    /// Returns Ok(()) if the invariants hold
+    /// Returns Err(..) with a description of the first one which doesn't
+    pub(crate) fn check_available_tenures(
+        rc: u64,
+        wanted_tenures: &[WantedTenure],
+        invbits: &BitVec<2100>,
+        next_wanted_tenures: Option<&[WantedTenure]>,
+        next_invbits: Option<&BitVec<2100>>,
+        available: &AvailableTenures,
+    ) -> Result<(), String> {
+        let has_tenure = |bits: &BitVec<2100>, i: usize| {
+            u16::try_from(i)
+                .ok()
+                .and_then(|bit| bits.get(bit))
+                .unwrap_or(false)
+        };
+        let next = next_wanted_tenures.zip(next_invbits);
+        let present: Vec<(bool, &WantedTenure)> = wanted_tenures
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| has_tenure(invbits, *i))
+            .map(|(_, wt)| (false, wt))
+            .chain(
+                next.into_iter()
+                    .flat_map(|(next_wanted_tenures, next_invbits)| {
+                        next_wanted_tenures
+                            .iter()
+                            .enumerate()
+                            .filter(move |(i, _)| has_tenure(next_invbits, *i))
+                            .map(|(_, wt)| (true, wt))
+                    }),
+            )
+            .collect();
+
+        let mut num_expected = 0;
+        for (k, (in_next, wt)) in present.iter().enumerate() {
+            if *in_next {
+                break;
+            }
+            let (Some((_, wt_start)), Some((_, wt_end))) = (present.get(k + 1), present.get(k + 2))
+            else {
+                break;
+            };
+            num_expected += 1;
+
+            let ch = &wt.tenure_id_consensus_hash;
+            let Some(tse) = available.get(ch) else {
+                return Err(format!("Tenure {ch} is not available"));
+            };
+            if &tse.tenure_id_consensus_hash != ch
+                || tse.tenure_id_burn_block_height != wt.burn_height
+                || tse.processed != wt.processed
+            {
+                return Err(format!("Tenure {ch} does not match its wanted tenure"));
+            }
+            if tse.start_block_snapshot_consensus_hash != wt_start.tenure_id_consensus_hash
+                || tse.start_block_id != wt_start.winning_block_id
+            {
+                return Err(format!(
+                    "Tenure {ch} starts at {}, not {}",
+                    &tse.start_block_id, &wt_start.winning_block_id
+                ));
+            }
+            if tse.end_block_snapshot_consensus_hash != wt_end.tenure_id_consensus_hash
+                || tse.end_block_id != wt_end.winning_block_id
+            {
+                return Err(format!(
+                    "Tenure {ch} ends at {}, not {}",
+                    &tse.end_block_id, &wt_end.winning_block_id
+                ));
+            }
+            if tse.start_reward_cycle != rc {
+                return Err(format!(
+                    "Tenure {ch} start reward cycle is {}, not {rc}",
+                    tse.start_reward_cycle
+                ));
+            }
+            if tse.end_reward_cycle != rc && tse.end_reward_cycle != rc.saturating_add(1) {
+                return Err(format!(
+                    "Tenure {ch} end reward cycle {} is not in [{rc}, {}]",
+                    tse.end_reward_cycle,
+                    rc.saturating_add(1)
+                ));
+            }
+        }
+        if available.len() != num_expected {
+            return Err(format!(
+                "Expected {num_expected} available tenures, but got {}",
+                available.len()
+            ));
+        }
+        Ok(())
+    }
+
+    /// Walk the inventory bits for `from_inventory_bits()`.
+    fn find_available_tenures(
+        rc: u64,
+        wanted_tenures: &[WantedTenure],
+        invbits: &BitVec<2100>,
+        next_wanted_tenures: Option<&[WantedTenure]>,
+        next_invbits: Option<&BitVec<2100>>,
+        pox_constants: &PoxConstants,
+        first_burn_height: u64,
+    ) -> AvailableTenures {
         let mut tenure_block_ids = AvailableTenures::new();
         let mut last_tenure = 0;
         let mut last_tenure_ch = None;
-        debug!("Find available tenures in inventory {:?} rc {}", invs, rc);
         for (i, wt) in wanted_tenures.iter().enumerate() {
             debug!("consider wanted tenure which starts with i={} {:?}", i, &wt);
 
@@ -207,7 +452,7 @@ impl TenureStartEnd {
         let Some(next_wanted_tenures) = next_wanted_tenures else {
             // nothing more to do
             debug!("No next_wanted_tenures");
-            return Some(tenure_block_ids);
+            return tenure_block_ids;
         };
 
         // `wanted_tenures` was a full reward cycle, so be sure to fetch the tenure-end block of
@@ -222,10 +467,10 @@ impl TenureStartEnd {
             }
         }
 
-        let Some(next_invbits) = invs.tenures_inv.get(&rc.saturating_add(1)) else {
+        let Some(next_invbits) = next_invbits else {
             // nothing more to do
             debug!("no inventory for cycle {}", rc.saturating_add(1));
-            return Some(tenure_block_ids);
+            return tenure_block_ids;
         };
 
         // start iterating from `last_tenures`
@@ -324,6 +569,6 @@ impl TenureStartEnd {
             tenure_block_ids.insert(wt.tenure_id_consensus_hash.clone(), tenure_start_end);
         }
 
-        Some(tenure_block_ids)
+        tenure_block_ids
     }
 }
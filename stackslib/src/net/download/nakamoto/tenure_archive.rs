@@ -0,0 +1,290 @@
+// Copyright (C) 2025 Stacks Open Internet Foundation
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A portable archive of downloaded tenures, so an operator can seed other nodes with them.
+//!
+//! The archive is laid out as follows (all integers are big-endian):
+//!
+//! ```text
+//! magic (8 bytes) | version (u32) | number of tenures (u32) | tenure...
+//! ```
+//!
+//! where each tenure is
+//!
+//! ```text
+//! consensus hash (20 bytes) | number of blocks (u32) | (block length (u32) | block)...
+//! ```
+//!
+//! Each tenure's blocks are stored in ascending order by height, and each block is its
+//! consensus serialization.  A tenure's last block may be its tenure-end block (i.e. the next
+//! tenure's start block), as in the tenures produced by the downloader.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+use stacks_common::codec::{read_next, write_next, StacksMessageCodec, MAX_PAYLOAD_LEN};
+use stacks_common::types::chainstate::ConsensusHash;
+
+use crate::chainstate::nakamoto::NakamotoBlock;
+use crate::chainstate::stacks::boot::RewardSet;
+use crate::net::Error as NetError;
+
+/// Identifies a file as a tenure archive
+pub const TENURE_ARCHIVE_MAGIC: [u8; 8] = *b"STXTENUR";
+/// Version of the tenure archive format written by `export_tenure_archive()`
+pub const TENURE_ARCHIVE_VERSION: u32 = 1;
+
+/// Write `tenures` to a tenure archive at `path`, replacing any file already there.  Tenures are
+/// written in ascending order by the height of their lowest block, and each tenure's blocks are
+/// written in ascending order by height.
+pub fn export_tenure_archive<P: AsRef<Path>>(
+    path: P,
+    tenures: &HashMap<ConsensusHash, Vec<NakamotoBlock>>,
+) -> Result<(), NetError> {
+    let file = File::create(path).map_err(NetError::WriteError)?;
+    let mut fd = BufWriter::new(file);
+    write_tenure_archive(&mut fd, tenures)?;
+    fd.flush().map_err(NetError::WriteError)
+}
+
+/// Write `tenures` as a tenure archive to `fd`.  See `export_tenure_archive()`.
+pub fn write_tenure_archive<W: Write>(
+    fd: &mut W,
+    tenures: &HashMap<ConsensusHash, Vec<NakamotoBlock>>,
+) -> Result<(), NetError> {
+    let mut sorted_tenures: Vec<_> = tenures
+        .iter()
+        .map(|(consensus_hash, blocks)| {
+            let mut blocks: Vec<_> = blocks.iter().collect();
+            blocks.sort_by_key(|blk| blk.header.chain_length);
+            (consensus_hash, blocks)
+        })
+        .collect();
+    sorted_tenures.sort_by_key(|(consensus_hash, blocks)| {
+        (
+            blocks.first().map(|blk| blk.header.chain_length),
+            (*consensus_hash).clone(),
+        )
+    });
+
+    fd.write_all(&TENURE_ARCHIVE_MAGIC)
+        .map_err(NetError::WriteError)?;
+    write_next(fd, &TENURE_ARCHIVE_VERSION)?;
+    let num_tenures = u32::try_from(sorted_tenures.len())
+        .map_err(|_| NetError::SerializeError("Too many tenures to archive".into()))?;
+    write_next(fd, &num_tenures)?;
+    for (consensus_hash, blocks) in sorted_tenures.into_iter() {
+        write_next(fd, consensus_hash)?;
+        let num_blocks = u32::try_from(blocks.len())
+            .map_err(|_| NetError::SerializeError("Too many blocks to archive".into()))?;
+        write_next(fd, &num_blocks)?;
+        for block in blocks.into_iter() {
+            let bytes = block.serialize_to_vec();
+            let len = u32::try_from(bytes.len())
+                .map_err(|_| NetError::SerializeError("Block is too big to archive".into()))?;
+            write_next(fd, &len)?;
+            fd.write_all(&bytes).map_err(NetError::WriteError)?;
+        }
+    }
+    Ok(())
+}
+
+/// Reads tenures out of a tenure archive, one at a time.  Each tenure is checked before it is
+/// yielded: its blocks must all belong to it (save for a trailing tenure-end block), and must
+/// form a contiguous chain in ascending order by height.  If a reward set lookup is given, each
+/// block's signer signatures are checked too.
+pub struct TenureArchiveReader<R: Read> {
+    fd: R,
+    /// Number of tenures left to read
+    num_remaining: u32,
+    /// Finds the reward set whose signers must have signed a given tenure's blocks
+    reward_set_lookup: Option<Box<dyn FnMut(&ConsensusHash) -> Result<RewardSet, NetError>>>,
+}
+
+impl TenureArchiveReader<BufReader<File>> {
+    /// Open the tenure archive at `path`
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, NetError> {
+        let file = File::open(path).map_err(NetError::ReadError)?;
+        Self::new(BufReader::new(file))
+    }
+}
+
+impl<R: Read> TenureArchiveReader<R> {
+    /// Start reading a tenure archive from `fd`.  Fails if `fd` does not begin with a supported
+    /// tenure archive header.
+    pub fn new(mut fd: R) -> Result<Self, NetError> {
+        let mut magic = [0u8; 8];
+        fd.read_exact(&mut magic).map_err(NetError::ReadError)?;
+        if magic != TENURE_ARCHIVE_MAGIC {
+            return Err(NetError::DeserializeError(
+                "Not a tenure archive: bad magic bytes".into(),
+            ));
+        }
+        let version: u32 = read_next(&mut fd)?;
+        if version != TENURE_ARCHIVE_VERSION {
+            return Err(NetError::DeserializeError(format!(
+                "Unsupported tenure archive version {version}"
+            )));
+        }
+        let num_remaining: u32 = read_next(&mut fd)?;
+        Ok(Self {
+            fd,
+            num_remaining,
+            reward_set_lookup: None,
+        })
+    }
+
+    /// Check each tenure's blocks' signer signatures against the reward set that `lookup`
+    /// returns for the tenure's consensus hash.
+    pub fn with_reward_set_lookup<F>(mut self, lookup: F) -> Self
+    where
+        F: FnMut(&ConsensusHash) -> Result<RewardSet, NetError> + 'static,
+    {
+        self.reward_set_lookup = Some(Box::new(lookup));
+        self
+    }
+
+    /// Read and check the next tenure
+    fn read_tenure(&mut self) -> Result<(ConsensusHash, Vec<NakamotoBlock>), NetError> {
+        let consensus_hash: ConsensusHash = read_next(&mut self.fd)?;
+        let num_blocks: u32 = read_next(&mut self.fd)?;
+        let mut blocks = vec![];
+        for _ in 0..num_blocks {
+            let len: u32 = read_next(&mut self.fd)?;
+            if len > MAX_PAYLOAD_LEN {
+                return Err(NetError::DeserializeError(format!(
+                    "Archived block in tenure {consensus_hash} is too big ({len} bytes)"
+                )));
+            }
+            let mut bytes = vec![0u8; len as usize];
+            self.fd
+                .read_exact(&mut bytes)
+                .map_err(NetError::ReadError)?;
+            let block = NakamotoBlock::consensus_deserialize(&mut &bytes[..])?;
+            blocks.push(block);
+        }
+
+        let num_tenure_blocks = Self::check_tenure(&consensus_hash, &blocks)?;
+        if let Some(lookup) = self.reward_set_lookup.as_mut() {
+            let (tenure_blocks, tenure_end_block) = blocks.split_at(num_tenure_blocks);
+            let reward_set = lookup(&consensus_hash)?;
+            for block in tenure_blocks.iter() {
+                Self::check_signer_signatures(&consensus_hash, block, &reward_set)?;
+            }
+            // the tenure-end block belongs to the next tenure, which may be in the next reward
+            // cycle
+            for block in tenure_end_block.iter() {
+                let reward_set = lookup(&block.header.consensus_hash)?;
+                Self::check_signer_signatures(&consensus_hash, block, &reward_set)?;
+            }
+        }
+        Ok((consensus_hash, blocks))
+    }
+
+    /// Check that `block`, archived in the tenure identified by `consensus_hash`, was signed by
+    /// the signers in `reward_set`.
+    fn check_signer_signatures(
+        consensus_hash: &ConsensusHash,
+        block: &NakamotoBlock,
+        reward_set: &RewardSet,
+    ) -> Result<(), NetError> {
+        block
+            .header
+            .verify_signer_signatures(reward_set)
+            .map_err(|e| {
+                warn!("Archived block has bad signer signatures";
+                      "tenure_id" => %consensus_hash,
+                      "block_id" => %block.block_id(),
+                      "error" => %e);
+                NetError::InvalidMessage
+            })
+    }
+
+    /// Check that `blocks` all belong to the tenure identified by `consensus_hash`, and that they
+    /// form a contiguous chain in ascending order by height.  The last block may instead be the
+    /// tenure-end block, in which case it must be a well-formed tenure-start block whose
+    /// tenure-change points back to this tenure.
+    ///
+    /// Returns the number of blocks which belong to this tenure (i.e. all of them except the
+    /// tenure-end block, if present).
+    fn check_tenure(
+        consensus_hash: &ConsensusHash,
+        blocks: &[NakamotoBlock],
+    ) -> Result<usize, NetError> {
+        let num_tenure_blocks = blocks
+            .iter()
+            .take_while(|block| &block.header.consensus_hash == consensus_hash)
+            .count();
+        if let Some(block) = blocks.get(num_tenure_blocks) {
+            // only the last block can be from another tenure, and only if it ends this one
+            if num_tenure_blocks == 0
+                || num_tenure_blocks.saturating_add(1) != blocks.len()
+                || !Self::is_tenure_end_block(consensus_hash, block)
+            {
+                warn!("Archived block is not part of its tenure";
+                      "tenure_id" => %consensus_hash,
+                      "block_id" => %block.block_id(),
+                      "block.header.consensus_hash" => %block.header.consensus_hash);
+                return Err(NetError::InvalidMessage);
+            }
+        }
+        for (parent, block) in blocks.iter().zip(blocks.iter().skip(1)) {
+            if block.header.parent_block_id != parent.block_id()
+                || block.header.chain_length != parent.header.chain_length.saturating_add(1)
+            {
+                warn!("Archived tenure blocks are not contiguous";
+                      "tenure_id" => %consensus_hash,
+                      "block_id" => %block.block_id(),
+                      "parent_block_id" => %parent.block_id());
+                return Err(NetError::InvalidMessage);
+            }
+        }
+        Ok(num_tenure_blocks)
+    }
+
+    /// Is `block` the tenure-end block for the tenure identified by `consensus_hash`?  That is, is
+    /// it a well-formed tenure-start block whose tenure-change follows this tenure?
+    fn is_tenure_end_block(consensus_hash: &ConsensusHash, block: &NakamotoBlock) -> bool {
+        if !matches!(block.is_wellformed_tenure_start_block(), Ok(true)) {
+            return false;
+        }
+        block
+            .try_get_tenure_change_payload()
+            .is_some_and(|tc_payload| &tc_payload.prev_tenure_consensus_hash == consensus_hash)
+    }
+}
+
+impl<R: Read> Iterator for TenureArchiveReader<R> {
+    type Item = Result<(ConsensusHash, Vec<NakamotoBlock>), NetError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.num_remaining == 0 {
+            return None;
+        }
+        match self.read_tenure() {
+            Ok(tenure) => {
+                self.num_remaining -= 1;
+                Some(Ok(tenure))
+            }
+            Err(e) => {
+                // the rest of the archive can't be trusted
+                self.num_remaining = 0;
+                Some(Err(e))
+            }
+        }
+    }
+}
@@ -13,16 +13,21 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use std::fmt;
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use std::{fmt, io};
 
 use stacks_common::types::chainstate::{ConsensusHash, StacksBlockId};
 use stacks_common::types::net::PeerHost;
 use stacks_common::util::get_epoch_time_ms;
+use stacks_common::util::hash::Sha512Trunc256Sum;
+use stacks_common::util::lru_cache::LruCache;
 
-use crate::chainstate::nakamoto::NakamotoBlock;
+use crate::chainstate::nakamoto::staging_blocks::NakamotoTenureDownloadCheckpoint;
+use crate::chainstate::nakamoto::{NakamotoBlock, NakamotoBlockHeader};
 use crate::chainstate::stacks::boot::RewardSet;
 use crate::chainstate::stacks::db::StacksChainState;
-use crate::chainstate::stacks::TransactionPayload;
+use crate::chainstate::stacks::{Error as ChainstateError, TransactionPayload};
 use crate::net::httpcore::{StacksHttpRequest, StacksHttpResponse};
 use crate::net::neighbors::rpc::NeighborRPC;
 use crate::net::p2p::{DropReason, DropSource, PeerNetwork};
@@ -50,12 +55,178 @@ pub enum NakamotoTenureDownloadState {
 
 pub const WAIT_FOR_TENURE_END_BLOCK_TIMEOUT: u64 = 1;
 
+/// How many times in a row a request may be retried because its response was truncated
+pub const MAX_TRUNCATED_RESPONSE_RETRIES: u32 = 1;
+
 impl fmt::Display for NakamotoTenureDownloadState {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{:?}", self)
     }
 }
 
+/// A plain-data summary of a `NakamotoTenureDownloader`'s observable state.  The request
+/// timestamp in `state` is zeroed, so two snapshots of a downloader which has not made progress
+/// in between compare equal.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TenureDownloaderSnapshot {
+    /// What state the downloader is in, with a request timestamp of 0
+    pub state: NakamotoTenureDownloadState,
+    /// Whether or not the tenure-start block has been obtained
+    pub has_tenure_start_block: bool,
+    /// Whether or not the tenure-end block has been obtained
+    pub has_tenure_end_block: bool,
+    /// How many tenure blocks have been obtained so far, including the tenure-end block (this is 0
+    /// until the first batch of tenure blocks is accepted, and after the tenure is handed off)
+    pub num_tenure_blocks: usize,
+    /// The tenure length reported by the tenure-end block, if known
+    pub tenure_length: Option<u64>,
+    /// Whether or not there are no ongoing network requests
+    pub idle: bool,
+    /// Number of consecutive truncated responses
+    pub truncated_responses: u32,
+}
+
+/// How many verified blocks the signer signature cache remembers
+pub const SIGNER_SIGNATURE_CACHE_SIZE: usize = 1024;
+
+/// Cache of blocks whose signer signatures have already been verified this session.  It is
+/// shared by all confirmed and unconfirmed tenure downloaders, so that a block checked by one of
+/// them is not checked again by another.  The main beneficiary is the ongoing tenure's start
+/// block, which the unconfirmed downloader verifies and which is then handed to a confirmed
+/// downloader as the highest complete tenure's end block.
+///
+/// Entries are keyed by the block ID and a digest over the reward set's signers and the block's
+/// signer signatures.  The block ID alone does not commit to the signer signatures.
+#[derive(Clone)]
+pub struct SignerSignatureCache {
+    inner: Arc<Mutex<SignerSignatureCacheInner>>,
+}
+
+struct SignerSignatureCacheInner {
+    /// Verified (block ID, signer digest) pairs
+    verified: LruCache<(StacksBlockId, Sha512Trunc256Sum), bool>,
+    /// Capacity of `verified`, so it can be rebuilt if it gets corrupted
+    capacity: usize,
+    /// Number of times a signature check was skipped because of this cache
+    hits: u64,
+}
+
+impl fmt::Debug for SignerSignatureCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SignerSignatureCache")
+            .field("hits", &self.hits())
+            .finish_non_exhaustive()
+    }
+}
+
+/// The cache does not contribute to a downloader's state, so it is ignored when comparing
+/// downloaders.
+impl PartialEq for SignerSignatureCache {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Default for SignerSignatureCache {
+    fn default() -> Self {
+        Self::new(SIGNER_SIGNATURE_CACHE_SIZE)
+    }
+}
+
+impl SignerSignatureCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(SignerSignatureCacheInner {
+                verified: LruCache::new(capacity),
+                capacity,
+                hits: 0,
+            })),
+        }
+    }
+
+    /// Compute the cache key for a block header's signatures and the reward set they are
+    /// checked against.  `block_id` must be the header's block ID.
+    fn cache_key(
+        block_id: &StacksBlockId,
+        header: &NakamotoBlockHeader,
+        reward_set: &RewardSet,
+    ) -> (StacksBlockId, Sha512Trunc256Sum) {
+        let signers = reward_set.signers.as_deref().unwrap_or(&[]);
+        let mut bytes = vec![];
+        bytes.extend_from_slice(&(signers.len() as u64).to_be_bytes());
+        for signer in signers.iter() {
+            bytes.extend_from_slice(&signer.signing_key);
+            bytes.extend_from_slice(&signer.weight.to_be_bytes());
+        }
+        bytes.extend_from_slice(&(header.signer_signature.len() as u64).to_be_bytes());
+        for signature in header.signer_signature.iter() {
+            bytes.extend_from_slice(&signature.0);
+        }
+        (block_id.clone(), Sha512Trunc256Sum::from_data(&bytes))
+    }
+
+    /// Verify a block header's signer signatures against the given reward set, unless they have
+    /// already been verified.
+    /// Returns Ok(()) if the signatures are valid
+    /// Returns Err(..) if not
+    pub fn verify_signer_signatures(
+        &self,
+        header: &NakamotoBlockHeader,
+        reward_set: &RewardSet,
+    ) -> Result<(), ChainstateError> {
+        self.verify_signer_signatures_with_id(&header.block_id(), header, reward_set)
+    }
+
+    /// Same as `verify_signer_signatures()`, but for callers which have already computed the
+    /// header's block ID (such as while walking a tenure's blocks), so it isn't hashed again.
+    /// `block_id` must be the header's block ID.
+    pub fn verify_signer_signatures_with_id(
+        &self,
+        block_id: &StacksBlockId,
+        header: &NakamotoBlockHeader,
+        reward_set: &RewardSet,
+    ) -> Result<(), ChainstateError> {
+        let key = Self::cache_key(block_id, header, reward_set);
+        {
+            let mut inner = self
+                .inner
+                .lock()
+                .expect("FATAL: signer signature cache lock is poisoned");
+            match inner.verified.get(&key) {
+                Ok(Some(_)) => {
+                    inner.hits = inner.hits.saturating_add(1);
+                    return Ok(());
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    error!("Signer signature cache is corrupted. Clearing it."; "err" => %e);
+                    inner.verified = LruCache::new(inner.capacity);
+                }
+            }
+        }
+
+        header.verify_signer_signatures(reward_set)?;
+
+        let mut inner = self
+            .inner
+            .lock()
+            .expect("FATAL: signer signature cache lock is poisoned");
+        if let Err(e) = inner.verified.insert_clean(key, true) {
+            error!("Signer signature cache is corrupted. Clearing it."; "err" => %e);
+            inner.verified = LruCache::new(inner.capacity);
+        }
+        Ok(())
+    }
+
+    /// How many signature checks have been skipped because of this cache
+    pub fn hits(&self) -> u64 {
+        self.inner
+            .lock()
+            .expect("FATAL: signer signature cache lock is poisoned")
+            .hits
+    }
+}
+
 /// Download state machine for an historic tenure -- a tenure for which the start and end block IDs
 /// can be inferred from the chainstate and a peer's inventory (this excludes the two most recent
 /// tenures).
@@ -76,7 +247,8 @@ impl fmt::Display for NakamotoTenureDownloadState {
 ///    order.  As blocks are found, their signer signatures will be validated against the signer
 ///    public keys for this tenure; their hash-chain continuity will be validated against the start
 ///    and end block hashes; their quantity will be validated against the tenure-change transaction
-///    in the end-block.
+///    in the end-block.  If the tenure-start block is the tenure-end block's parent, then the
+///    tenure has only one block and this step is skipped.
 ///
 /// Once the machine has reached the `Done` state, it will have obtained the entire run of Nakamoto
 /// blocks for the given tenure (regardless of how many sortitions it straddles, and regardless of
@@ -117,6 +289,19 @@ pub struct NakamotoTenureDownloader {
     pub tenure_blocks: Option<Vec<NakamotoBlock>>,
     /// Whether this tenure is unconfirmed
     pub is_tenure_unconfirmed: bool,
+    /// Number of consecutive responses that could not be decoded because they were truncated
+    pub truncated_responses: u32,
+    /// Millisecond epoch timestamp at which this machine was created or last accepted a block
+    pub last_progress_ms: u128,
+    /// Cache of already-verified signer signatures, shared with other downloaders
+    pub signature_cache: SignerSignatureCache,
+    /// Whether or not to recompute each tenure block's transaction Merkle root and reject the
+    /// block if it does not match the header
+    pub verify_tx_merkle_roots: bool,
+    /// Download pass of the state machine that is driving this downloader, for logging
+    pub download_pass: u64,
+    /// Number of this machine's tenure blocks which are in its checkpoint in the staging DB
+    pub checkpoint_num_blocks: usize,
 }
 
 impl NakamotoTenureDownloader {
@@ -157,9 +342,28 @@ impl NakamotoTenureDownloader {
             tenure_end_block: None,
             tenure_blocks: None,
             is_tenure_unconfirmed,
+            truncated_responses: 0,
+            last_progress_ms: get_epoch_time_ms(),
+            signature_cache: SignerSignatureCache::default(),
+            verify_tx_merkle_roots: false,
+            download_pass: 0,
+            checkpoint_num_blocks: 0,
         }
     }
 
+    /// Follow-on constructor used to share a signer signature cache with other downloaders.
+    pub fn with_signature_cache(mut self, signature_cache: SignerSignatureCache) -> Self {
+        self.signature_cache = signature_cache;
+        self
+    }
+
+    /// Follow-on constructor used to have this machine check the transaction Merkle roots of the
+    /// tenure blocks it downloads.
+    pub fn with_tx_merkle_root_check(mut self, verify_tx_merkle_roots: bool) -> Self {
+        self.verify_tx_merkle_roots = verify_tx_merkle_roots;
+        self
+    }
+
     // /// Follow-on constructor used to instantiate a machine for downloading the highest-confirmed
     // /// tenure.  This supplies the tenure end-block if known in advance.
     // pub fn with_tenure_end_block(mut self, tenure_end_block: NakamotoBlock) -> Self {
@@ -177,6 +381,7 @@ impl NakamotoTenureDownloader {
         let NakamotoTenureDownloadState::GetTenureStartBlock(..) = &self.state else {
             // not the right state for this
             warn!("Invalid state for this method";
+                  "download_pass" => self.download_pass,
                   "state" => %self.state);
             return Err(NetError::InvalidState);
         };
@@ -184,6 +389,7 @@ impl NakamotoTenureDownloader {
         if self.tenure_start_block_id != tenure_start_block.header.block_id() {
             // not the block we were expecting
             warn!("Invalid tenure-start block: unexpected";
+                  "download_pass" => self.download_pass,
                   "tenure_id" => %self.tenure_id_consensus_hash,
                   "tenure_id_start_block" => %self.tenure_start_block_id,
                   "tenure_start_block ID" => %tenure_start_block.header.block_id(),
@@ -191,12 +397,13 @@ impl NakamotoTenureDownloader {
             return Err(NetError::InvalidMessage);
         }
 
-        if let Err(e) = tenure_start_block
-            .header
-            .verify_signer_signatures(&self.start_signer_keys)
+        if let Err(e) = self
+            .signature_cache
+            .verify_signer_signatures(&tenure_start_block.header, &self.start_signer_keys)
         {
             // signature verification failed
             warn!("Invalid tenure-start block: bad signer signature";
+                   "download_pass" => self.download_pass,
                    "tenure_id" => %self.tenure_id_consensus_hash,
                    "block.header.block_id" => %tenure_start_block.header.block_id(),
                    "state" => %self.state,
@@ -207,16 +414,19 @@ impl NakamotoTenureDownloader {
         debug!(
             "Accepted tenure-start block for tenure {} block={}",
             &self.tenure_id_consensus_hash,
-            &tenure_start_block.block_id()
+            &tenure_start_block.block_id();
+            "download_pass" => self.download_pass,
         );
         self.tenure_start_block = Some(tenure_start_block);
+        self.last_progress_ms = get_epoch_time_ms();
 
         if let Some(tenure_end_block) = self.tenure_end_block.take() {
             // we already have the tenure-end block, so immediately proceed to accept it.
             debug!(
                 "Preemptively process tenure-end block {} for tenure {}",
                 tenure_end_block.block_id(),
-                &self.tenure_id_consensus_hash
+                &self.tenure_id_consensus_hash;
+                "download_pass" => self.download_pass,
             );
             self.try_accept_tenure_end_block(&tenure_end_block)?;
         } else {
@@ -244,11 +454,14 @@ impl NakamotoTenureDownloader {
             NakamotoTenureDownloadState::GetTenureEndBlock(..)
         ) {
             warn!("Invalid state for this method";
+                  "download_pass" => self.download_pass,
                   "state" => %self.state);
             return Err(NetError::InvalidState);
         };
         let Some(tenure_start_block) = self.tenure_start_block.as_ref() else {
-            warn!("Invalid state -- tenure_start_block is not set");
+            warn!("Invalid state -- tenure_start_block is not set";
+                "download_pass" => self.download_pass,
+            );
             return Err(NetError::InvalidState);
         };
 
@@ -257,6 +470,7 @@ impl NakamotoTenureDownloader {
         {
             // not the block we asked for
             warn!("Invalid tenure-end block: unexpected";
+                  "download_pass" => self.download_pass,
                   "tenure_id" => %self.tenure_id_consensus_hash,
                   "tenure_id_end_block" => %self.tenure_end_block_id,
                   "block.header.block_id" => %tenure_end_block.header.block_id(),
@@ -264,12 +478,13 @@ impl NakamotoTenureDownloader {
             return Err(NetError::InvalidMessage);
         }
 
-        if let Err(e) = tenure_end_block
-            .header
-            .verify_signer_signatures(&self.end_signer_keys)
+        if let Err(e) = self
+            .signature_cache
+            .verify_signer_signatures(&tenure_end_block.header, &self.end_signer_keys)
         {
             // bad signature
             warn!("Invalid tenure-end block: bad signer signature";
+                  "download_pass" => self.download_pass,
                   "tenure_id" => %self.tenure_id_consensus_hash,
                   "block.header.block_id" => %tenure_end_block.header.block_id(),
                   "state" => %self.state,
@@ -282,25 +497,47 @@ impl NakamotoTenureDownloader {
         // the next block to download).
         let Ok(valid) = tenure_end_block.is_wellformed_tenure_start_block() else {
             warn!("Invalid tenure-end block: failed to validate tenure-start";
+                  "download_pass" => self.download_pass,
                   "block_id" => %tenure_end_block.block_id());
             return Err(NetError::InvalidMessage);
         };
 
         if !valid {
             warn!("Invalid tenure-end block: not a well-formed tenure-start block";
+                  "download_pass" => self.download_pass,
                   "block_id" => %tenure_end_block.block_id());
             return Err(NetError::InvalidMessage);
         }
 
         let Some(tc_payload) = tenure_end_block.try_get_tenure_change_payload() else {
             warn!("Invalid tenure-end block: no tenure-change transaction";
+                  "download_pass" => self.download_pass,
                   "block_id" => %tenure_end_block.block_id());
             return Err(NetError::InvalidMessage);
         };
 
+        // A tenure always contains at least its tenure-start block, and the tenure-end block is
+        // the tenure-start block of the _next_ tenure.  So, the tenure-end block cannot be the
+        // tenure-start block, and the tenure-change must report at least one block.
+        if tenure_end_block.block_id() == tenure_start_block.block_id() {
+            warn!("Invalid tenure-end block: same as tenure-start block";
+                  "download_pass" => self.download_pass,
+                  "tenure_id" => %self.tenure_id_consensus_hash,
+                  "block_id" => %tenure_end_block.block_id());
+            return Err(NetError::InvalidMessage);
+        }
+        if tc_payload.previous_tenure_blocks == 0 {
+            warn!("Invalid tenure-end block: tenure-change reports an empty tenure";
+                  "download_pass" => self.download_pass,
+                  "tenure_id" => %self.tenure_id_consensus_hash,
+                  "block_id" => %tenure_end_block.block_id());
+            return Err(NetError::InvalidMessage);
+        }
+
         // tc_payload must point to the tenure-start block's header
         if tc_payload.prev_tenure_consensus_hash != tenure_start_block.header.consensus_hash {
             warn!("Invalid tenure-end block: tenure-change does not point to tenure-start block";
+                  "download_pass" => self.download_pass,
                   "start_block_id" => %tenure_start_block.block_id(),
                   "end_block_id" => %tenure_end_block.block_id(),
                   "tc_payload.prev_tenure_consensus_hash" => %tc_payload.prev_tenure_consensus_hash,
@@ -308,13 +545,34 @@ impl NakamotoTenureDownloader {
             return Err(NetError::InvalidMessage);
         }
 
+        // The tenure-start and tenure-end block IDs were learned from the sortition DB, and a
+        // block ID commits to the block's chain length.  So, the number of blocks in this tenure
+        // is pinned by the difference in their chain lengths, and the tenure-change must agree.
+        // Otherwise, a peer could get us to accept a truncated tenure.
+        let expected_tenure_length = tenure_end_block
+            .header
+            .chain_length
+            .saturating_sub(tenure_start_block.header.chain_length);
+        if u64::from(tc_payload.previous_tenure_blocks) != expected_tenure_length {
+            warn!("Invalid tenure-end block: tenure-change reports the wrong number of blocks";
+                  "download_pass" => self.download_pass,
+                  "tenure_id" => %self.tenure_id_consensus_hash,
+                  "start_block_id" => %tenure_start_block.block_id(),
+                  "end_block_id" => %tenure_end_block.block_id(),
+                  "tc_payload.previous_tenure_blocks" => tc_payload.previous_tenure_blocks,
+                  "expected_tenure_length" => expected_tenure_length);
+            return Err(NetError::InvalidMessage);
+        }
+
         debug!(
             "Accepted tenure-end block for tenure {} block={}; expect {} blocks",
             &self.tenure_id_consensus_hash,
             &tenure_end_block.block_id(),
-            tc_payload.previous_tenure_blocks
+            tc_payload.previous_tenure_blocks;
+            "download_pass" => self.download_pass,
         );
         self.tenure_end_block = Some(tenure_end_block.clone());
+        self.last_progress_ms = get_epoch_time_ms();
         self.state = NakamotoTenureDownloadState::GetTenureBlocks(
             tenure_end_block.header.parent_block_id.clone(),
             get_epoch_time_ms(),
@@ -322,7 +580,35 @@ impl NakamotoTenureDownloader {
         Ok(())
     }
 
-    /// Determine how many blocks must be in this tenure.
+    /// If the tenure-end block's parent is the tenure-start block, then this tenure has exactly one
+    /// block, which we already have.  Finish the tenure without requesting it again.
+    ///
+    /// Returns Ok(Some([blocks])) if the tenure was completed this way.
+    /// Returns Ok(None) if there are more blocks to download (or we're not at that step yet).
+    /// Returns Err(..) if the blocks we have are invalid.
+    fn try_finish_single_block_tenure(&mut self) -> Result<Option<Vec<NakamotoBlock>>, NetError> {
+        let NakamotoTenureDownloadState::GetTenureBlocks(block_cursor, _) = &self.state else {
+            return Ok(None);
+        };
+        let Some(tenure_start_block) = self.tenure_start_block.as_ref() else {
+            return Ok(None);
+        };
+        if block_cursor != &tenure_start_block.block_id() {
+            return Ok(None);
+        }
+
+        debug!(
+            "Tenure {} has only its tenure-start block {}",
+            &self.tenure_id_consensus_hash, &block_cursor;
+            "download_pass" => self.download_pass,
+        );
+        let tenure_start_block = tenure_start_block.clone();
+        self.try_accept_tenure_blocks(vec![tenure_start_block])
+    }
+
+    /// Determine how many blocks must be in this tenure.  This is the length reported by the
+    /// tenure-end block's tenure-change, which has been checked against the chain lengths of the
+    /// tenure-start and tenure-end blocks.
     /// Returns None if we don't have the start and end blocks yet.
     pub fn tenure_length(&self) -> Option<u64> {
         self.tenure_end_block.as_ref().and_then(|tenure_end_block| {
@@ -345,61 +631,118 @@ impl NakamotoTenureDownloader {
     ///
     /// Returns Ok(None) if the given blocks were valid, but we still need more.  The pointer to
     /// the next block to fetch (stored in self.state) will be updated.
-    /// Returns Err(..) if the blocks were invalid.
+    /// Returns Err(NetError::DuplicateBlock(..)) if the same block appears more than once.
+    /// Returns Err(..) if the blocks were otherwise invalid.
     pub fn try_accept_tenure_blocks(
         &mut self,
-        mut tenure_blocks: Vec<NakamotoBlock>,
+        tenure_blocks: Vec<NakamotoBlock>,
     ) -> Result<Option<Vec<NakamotoBlock>>, NetError> {
+        self.try_accept_tenure_block_stream(tenure_blocks.into_iter().map(Ok))
+    }
+
+    /// Add tenure blocks to this machine as `tenure_blocks` decodes them, as in
+    /// `try_accept_tenure_blocks()`.  Each block is checked as soon as it is decoded, so decoding
+    /// stops at the first invalid block, or at the first block beyond the tenure's length.
+    ///
+    /// None of the blocks are added unless all of them decode and are valid.  If a block fails to
+    /// decode because the response was truncated, then Ok(None) is returned without a
+    /// state-transition, so the request can be retried (see `check_decoded_response()`).
+    pub fn try_accept_tenure_block_stream<I>(
+        &mut self,
+        tenure_blocks: I,
+    ) -> Result<Option<Vec<NakamotoBlock>>, NetError>
+    where
+        I: IntoIterator<Item = Result<NakamotoBlock, NetError>>,
+    {
         let NakamotoTenureDownloadState::GetTenureBlocks(block_cursor, start_request_time) =
             &self.state
         else {
             warn!("Invalid state for this method";
+                  "download_pass" => self.download_pass,
                   "state" => %self.state);
             return Err(NetError::InvalidState);
         };
-
-        if tenure_blocks.is_empty() {
-            // nothing to do
-            return Ok(None);
-        }
+        let (block_cursor, start_request_time) = (block_cursor.clone(), *start_request_time);
 
         // blocks must be contiguous and in order from highest to lowest
-        let mut expected_block_id = block_cursor;
+        let mut expected_block_id = block_cursor.clone();
         let mut count = 0;
-        for block in tenure_blocks.iter() {
+        let mut seen_block_ids = HashSet::new();
+        let mut tenure_blocks_accepted = vec![];
+        for decoded in tenure_blocks {
+            let block = match decoded {
+                Ok(block) => block,
+                Err(e) => {
+                    warn!("Failed to decode response for a Nakamoto tenure: {e:?}";
+                        "download_pass" => self.download_pass,
+                    );
+                    // truncated, so retry
+                    self.check_decoded_response::<()>(Err(e))?;
+                    self.idle = true;
+                    return Ok(None);
+                }
+            };
+
             // must be from this tenure
             // This may not always be the case, since a remote peer could have processed a
             // different Stacks micro-fork.  The consequence of erroring here (or below) is that we
             // disconnect from the peer that served this to us.
             if block.header.consensus_hash != self.tenure_id_consensus_hash {
                 warn!("Unexpected Nakamoto block -- not part of tenure";
+                      "download_pass" => self.download_pass,
                       "block.header.consensus_hash" => %block.header.consensus_hash,
                       "self.tenure_id_consensus_hash" => %self.tenure_id_consensus_hash,
                       "state" => %self.state);
                 return Err(NetError::InvalidMessage);
             }
 
-            if &block.header.block_id() != expected_block_id {
+            // a repeated block would fail the cursor check below, but report it as what it is
+            let block_id = block.header.block_id();
+            if !seen_block_ids.insert(block_id.clone()) {
+                warn!("Invalid blocks: duplicate Nakamoto block in response";
+                      "download_pass" => self.download_pass,
+                      "tenure_id" => %self.tenure_id_consensus_hash,
+                      "block_id" => %block_id,
+                      "state" => %self.state);
+                return Err(NetError::DuplicateBlock(block_id));
+            }
+
+            if block_id != expected_block_id {
                 warn!("Unexpected Nakamoto block -- does not match cursor";
+                      "download_pass" => self.download_pass,
                       "expected_block_id" => %expected_block_id,
-                      "block_id" => %block.header.block_id(),
+                      "block_id" => %block_id,
                       "state" => %self.state);
                 return Err(NetError::InvalidMessage);
             }
 
-            if let Err(e) = block
-                .header
-                .verify_signer_signatures(&self.start_signer_keys)
+            // the block ID only covers the header, so a peer could pair it with the wrong txs
+            if self.verify_tx_merkle_roots
+                && block.compute_tx_merkle_root() != block.header.tx_merkle_root
             {
+                warn!("Invalid block: tx Merkle root mismatch";
+                      "download_pass" => self.download_pass,
+                      "tenure_id" => %self.tenure_id_consensus_hash,
+                      "block_id" => %block_id,
+                      "state" => %self.state);
+                return Err(NetError::InvalidMessage);
+            }
+
+            if let Err(e) = self.signature_cache.verify_signer_signatures_with_id(
+                &block_id,
+                &block.header,
+                &self.start_signer_keys,
+            ) {
                 warn!("Invalid block: bad signer signature";
+                      "download_pass" => self.download_pass,
                       "tenure_id" => %self.tenure_id_consensus_hash,
-                      "block.header.block_id" => %block.header.block_id(),
+                      "block.header.block_id" => %block_id,
                       "state" => %self.state,
                       "error" => %e);
                 return Err(NetError::InvalidMessage);
             }
 
-            expected_block_id = &block.header.parent_block_id;
+            expected_block_id = block.header.parent_block_id.clone();
             count += 1;
             if self
                 .tenure_blocks
@@ -413,14 +756,24 @@ impl NakamotoTenureDownloader {
                 // there are more blocks downloaded than indicated by the end-blocks tenure-change
                 // transaction.
                 warn!("Invalid blocks: exceeded {} tenure blocks", self.tenure_length().unwrap_or(0);
+                      "download_pass" => self.download_pass,
                       "tenure_id" => %self.tenure_id_consensus_hash,
                       "count" => %count,
-                      "tenure_length" => self.tenure_length().unwrap_or(0),
-                      "num_blocks" => tenure_blocks.len());
+                      "tenure_length" => self.tenure_length().unwrap_or(0));
                 return Err(NetError::InvalidMessage);
             }
+            tenure_blocks_accepted.push(block);
         }
 
+        // the whole response decoded
+        self.truncated_responses = 0;
+        let mut tenure_blocks = tenure_blocks_accepted;
+        if tenure_blocks.is_empty() {
+            // nothing to do
+            return Ok(None);
+        }
+
+        self.last_progress_ms = get_epoch_time_ms();
         if let Some(blocks) = self.tenure_blocks.as_mut() {
             blocks.append(&mut tenure_blocks);
         } else {
@@ -434,25 +787,32 @@ impl NakamotoTenureDownloader {
         // did we reach the tenure start block?
         let Some(blocks) = self.tenure_blocks.as_ref() else {
             // unreachable but be defensive
-            warn!("Invalid state: no blocks (infallible -- got None)");
+            warn!("Invalid state: no blocks (infallible -- got None)";
+                "download_pass" => self.download_pass,
+            );
             return Err(NetError::InvalidState);
         };
 
         let Some(earliest_block) = blocks.last() else {
             // unreachable but be defensive
-            warn!("Invalid state: no blocks (infallible -- got empty vec)");
+            warn!("Invalid state: no blocks (infallible -- got empty vec)";
+                "download_pass" => self.download_pass,
+            );
             return Err(NetError::InvalidState);
         };
 
         let Some(tenure_start_block) = self.tenure_start_block.as_ref() else {
             // unreachable but be defensive
-            warn!("Invalid state: no tenure-start block (infallible)");
+            warn!("Invalid state: no tenure-start block (infallible)";
+                "download_pass" => self.download_pass,
+            );
             return Err(NetError::InvalidState);
         };
 
         debug!(
             "Accepted tenure blocks for tenure {} cursor={} ({})",
-            &self.tenure_id_consensus_hash, &block_cursor, count
+            &self.tenure_id_consensus_hash, &block_cursor, count;
+            "download_pass" => self.download_pass,
         );
         if earliest_block.block_id() != tenure_start_block.block_id() {
             // still have more blocks to download
@@ -462,10 +822,11 @@ impl NakamotoTenureDownloader {
                 &self.tenure_id_consensus_hash,
                 &block_cursor,
                 &earliest_block.block_id(),
-                &next_block_id
+                &next_block_id;
+                "download_pass" => self.download_pass,
             );
             self.state =
-                NakamotoTenureDownloadState::GetTenureBlocks(next_block_id, *start_request_time);
+                NakamotoTenureDownloadState::GetTenureBlocks(next_block_id, start_request_time);
             return Ok(None);
         }
 
@@ -496,21 +857,24 @@ impl NakamotoTenureDownloader {
             ) => {
                 debug!(
                     "Request tenure-start block {} at {}",
-                    &start_block_id, start_request_time
+                    &start_block_id, start_request_time;
+                    "download_pass" => self.download_pass,
                 );
                 StacksHttpRequest::new_get_nakamoto_block(peerhost, start_block_id.clone())
             }
             NakamotoTenureDownloadState::GetTenureEndBlock(end_block_id, start_request_time) => {
                 debug!(
                     "Request tenure-end block {} at {}",
-                    &end_block_id, start_request_time
+                    &end_block_id, start_request_time;
+                    "download_pass" => self.download_pass,
                 );
                 StacksHttpRequest::new_get_nakamoto_block(peerhost, end_block_id.clone())
             }
             NakamotoTenureDownloadState::GetTenureBlocks(end_block_id, start_request_time) => {
                 debug!(
                     "Downloading tenure ending at {} at {}",
-                    &end_block_id, start_request_time
+                    &end_block_id, start_request_time;
+                    "download_pass" => self.download_pass,
                 );
                 StacksHttpRequest::new_get_nakamoto_tenure(peerhost, end_block_id.clone(), None)
             }
@@ -542,7 +906,8 @@ impl NakamotoTenureDownloader {
                         debug!(
                             "Tenure {} start-block confirmed by shadow tenure {}",
                             &self.tenure_id_consensus_hash,
-                            &self.start_block_snapshot_consensus_hash
+                            &self.start_block_snapshot_consensus_hash;
+                            "download_pass" => self.download_pass,
                         );
                         let Some(shadow_block) = chainstate
                             .nakamoto_blocks_db()
@@ -552,7 +917,8 @@ impl NakamotoTenureDownloader {
                         else {
                             warn!(
                                 "No tenure-start block for shadow tenure {}",
-                                &self.start_block_snapshot_consensus_hash
+                                &self.start_block_snapshot_consensus_hash;
+                                "download_pass" => self.download_pass,
                             );
                             break;
                         };
@@ -561,7 +927,9 @@ impl NakamotoTenureDownloader {
                         // block-id of the parent tenure's start block (i.e. the information that
                         // would have been gleaned from a block-commit, if there was one).
                         let Some(shadow_coinbase) = shadow_block.get_coinbase_tx() else {
-                            warn!("Shadow block {} has no coinbase", &shadow_block.block_id());
+                            warn!("Shadow block {} has no coinbase", &shadow_block.block_id();
+                                "download_pass" => self.download_pass,
+                            );
                             break;
                         };
 
@@ -570,7 +938,8 @@ impl NakamotoTenureDownloader {
                         else {
                             warn!(
                                 "Shadow block {} coinbase tx is not a Coinbase",
-                                &shadow_block.block_id()
+                                &shadow_block.block_id();
+                                "download_pass" => self.download_pass,
                             );
                             break;
                         };
@@ -579,7 +948,8 @@ impl NakamotoTenureDownloader {
 
                         info!(
                             "Tenure {} starts at shadow tenure-start {}, not {}",
-                            &self.tenure_id_consensus_hash, &tenure_start_block_id, &start_block_id
+                            &self.tenure_id_consensus_hash, &tenure_start_block_id, &start_block_id;
+                            "download_pass" => self.download_pass,
                         );
                         self.tenure_start_block_id = tenure_start_block_id.clone();
                         self.state = NakamotoTenureDownloadState::GetTenureStartBlock(
@@ -616,7 +986,8 @@ impl NakamotoTenureDownloader {
                     {
                         debug!(
                             "Tenure {} end-block confirmed by shadow tenure {}",
-                            &self.tenure_id_consensus_hash, &self.end_block_snapshot_consensus_hash
+                            &self.tenure_id_consensus_hash, &self.end_block_snapshot_consensus_hash;
+                            "download_pass" => self.download_pass,
                         );
                         let Some(shadow_block) = chainstate
                             .nakamoto_blocks_db()
@@ -626,7 +997,8 @@ impl NakamotoTenureDownloader {
                         else {
                             warn!(
                                 "No tenure-start block for shadow tenure {}",
-                                &self.end_block_snapshot_consensus_hash
+                                &self.end_block_snapshot_consensus_hash;
+                                "download_pass" => self.download_pass,
                             );
                             break;
                         };
@@ -635,7 +1007,9 @@ impl NakamotoTenureDownloader {
                         // block-id of the parent tenure's start block (i.e. the information that
                         // would have been gleaned from a block-commit, if there was one).
                         let Some(shadow_coinbase) = shadow_block.get_coinbase_tx() else {
-                            warn!("Shadow block {} has no coinbase", &shadow_block.block_id());
+                            warn!("Shadow block {} has no coinbase", &shadow_block.block_id();
+                                "download_pass" => self.download_pass,
+                            );
                             break;
                         };
 
@@ -644,7 +1018,8 @@ impl NakamotoTenureDownloader {
                         else {
                             warn!(
                                 "Shadow block {} coinbase tx is not a Coinbase",
-                                &shadow_block.block_id()
+                                &shadow_block.block_id();
+                                "download_pass" => self.download_pass,
                             );
                             break;
                         };
@@ -653,7 +1028,8 @@ impl NakamotoTenureDownloader {
 
                         info!(
                             "Tenure {} ends at shadow tenure-start {}, not {}",
-                            &self.tenure_id_consensus_hash, &tenure_end_block_id, &end_block_id
+                            &self.tenure_id_consensus_hash, &tenure_end_block_id, &end_block_id;
+                            "download_pass" => self.download_pass,
                         );
                         self.tenure_end_block_id = tenure_end_block_id.clone();
                         self.state = NakamotoTenureDownloadState::GetTenureEndBlock(
@@ -706,7 +1082,9 @@ impl NakamotoTenureDownloader {
         neighbor_rpc: &mut NeighborRPC,
     ) -> Result<bool, NetError> {
         if neighbor_rpc.has_inflight(&self.naddr) {
-            debug!("Peer {} has an inflight request", &self.naddr);
+            debug!("Peer {} has an inflight request", &self.naddr;
+                "download_pass" => self.download_pass,
+            );
             return Ok(true);
         }
         if neighbor_rpc.is_dead_or_broken(network, &self.naddr) {
@@ -745,11 +1123,46 @@ impl NakamotoTenureDownloader {
         Ok(true)
     }
 
+    /// Check the result of decoding a response.  A response whose body ended early (e.g. because
+    /// the connection was reset mid-transfer) may be retried up to
+    /// `MAX_TRUNCATED_RESPONSE_RETRIES` times in a row; any other decode failure is returned.
+    /// Returns Ok(Some(..)) if the response decoded
+    /// Returns Ok(None) if the same request should be sent again
+    /// Returns Err(..) if the response is unusable
+    fn check_decoded_response<T>(
+        &mut self,
+        decoded: Result<T, NetError>,
+    ) -> Result<Option<T>, NetError> {
+        match decoded {
+            Ok(data) => {
+                self.truncated_responses = 0;
+                Ok(Some(data))
+            }
+            Err(NetError::ReadError(e))
+                if e.kind() == io::ErrorKind::UnexpectedEof
+                    && self.truncated_responses < MAX_TRUNCATED_RESPONSE_RETRIES =>
+            {
+                self.truncated_responses += 1;
+                info!(
+                    "Truncated download response from {}; will retry", &self.naddr;
+                    "download_pass" => self.download_pass,
+                    "tenure_id" => %self.tenure_id_consensus_hash,
+                    "state" => %self.state,
+                    "truncated_responses" => self.truncated_responses,
+                );
+                Ok(None)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
     /// Handle a received StacksHttpResponse and advance the state machine.
     /// If we get the full tenure's blocks, then return them.
     /// Returns Ok(Some([blocks])) if we successfully complete the state machine.
     /// Returns Ok(None) if we accepted the response and did a state-transition, but we're not done
     /// yet.  The caller should now call `send_next_download_request()`
+    /// Returns Ok(None) without a state-transition if the response was truncated and the request
+    /// can be retried.
     /// Returns Err(..) on failure to process the response.
     pub fn handle_next_download_response(
         &mut self,
@@ -760,36 +1173,54 @@ impl NakamotoTenureDownloader {
                 debug!(
                     "Got download response for tenure-start block {} in {}ms",
                     &block_id,
-                    get_epoch_time_ms().saturating_sub(*start_request_time)
+                    get_epoch_time_ms().saturating_sub(*start_request_time);
+                    "download_pass" => self.download_pass,
                 );
-                let block = response.decode_nakamoto_block().inspect_err(|e| {
-                    warn!("Failed to decode response for a Nakamoto block: {e:?}")
-                })?;
+                let decoded = response.decode_nakamoto_block().inspect_err(|e| {
+                    warn!("Failed to decode response for a Nakamoto block: {e:?}";
+                        "download_pass" => self.download_pass,
+                    )
+                });
+                let Some(block) = self.check_decoded_response(decoded)? else {
+                    self.idle = true;
+                    return Ok(None);
+                };
                 self.try_accept_tenure_start_block(block)?;
-                Ok(None)
+                self.try_finish_single_block_tenure()
             }
             NakamotoTenureDownloadState::GetTenureEndBlock(block_id, start_request_time) => {
                 debug!(
                     "Got download response to tenure-end block {} in {}ms",
                     &block_id,
-                    get_epoch_time_ms().saturating_sub(*start_request_time)
+                    get_epoch_time_ms().saturating_sub(*start_request_time);
+                    "download_pass" => self.download_pass,
                 );
-                let block = response.decode_nakamoto_block().inspect_err(|e| {
-                    warn!("Failed to decode response for a Nakamoto block: {e:?}")
-                })?;
+                let decoded = response.decode_nakamoto_block().inspect_err(|e| {
+                    warn!("Failed to decode response for a Nakamoto block: {e:?}";
+                        "download_pass" => self.download_pass,
+                    )
+                });
+                let Some(block) = self.check_decoded_response(decoded)? else {
+                    self.idle = true;
+                    return Ok(None);
+                };
                 self.try_accept_tenure_end_block(&block)?;
-                Ok(None)
+                self.try_finish_single_block_tenure()
             }
             NakamotoTenureDownloadState::GetTenureBlocks(end_block_id, start_request_time) => {
                 debug!(
                     "Got download response for tenure blocks ending at {} in {}ms",
                     &end_block_id,
-                    get_epoch_time_ms().saturating_sub(*start_request_time)
+                    get_epoch_time_ms().saturating_sub(*start_request_time);
+                    "download_pass" => self.download_pass,
                 );
-                let blocks = response.decode_nakamoto_tenure().inspect_err(|e| {
-                    warn!("Failed to decode response for a Nakamoto tenure: {e:?}")
+                // validate each block as it is decoded, so a bad response isn't decoded in full
+                let blocks = response.decode_nakamoto_tenure_stream().inspect_err(|e| {
+                    warn!("Failed to decode response for a Nakamoto tenure: {e:?}";
+                        "download_pass" => self.download_pass,
+                    )
                 })?;
-                let blocks_opt = self.try_accept_tenure_blocks(blocks)?;
+                let blocks_opt = self.try_accept_tenure_block_stream(blocks)?;
                 Ok(blocks_opt)
             }
             NakamotoTenureDownloadState::Done => Err(NetError::InvalidState),
@@ -798,7 +1229,208 @@ impl NakamotoTenureDownloader {
         handle_result
     }
 
+    /// The block ID of the tenure-start or tenure-end block that this machine will request next,
+    /// if it is fetching one of them.  Since a tenure's end block is the next tenure's start
+    /// block, another downloader may be fetching the same block.
+    pub fn requested_boundary_block_id(&self) -> Option<&StacksBlockId> {
+        match &self.state {
+            NakamotoTenureDownloadState::GetTenureStartBlock(block_id, _)
+            | NakamotoTenureDownloadState::GetTenureEndBlock(block_id, _) => Some(block_id),
+            _ => None,
+        }
+    }
+
     pub fn is_done(&self) -> bool {
         self.state == NakamotoTenureDownloadState::Done
     }
+
+    /// Take a snapshot of this downloader's observable state
+    pub fn snapshot(&self) -> TenureDownloaderSnapshot {
+        let state = match &self.state {
+            NakamotoTenureDownloadState::GetTenureStartBlock(block_id, _) => {
+                NakamotoTenureDownloadState::GetTenureStartBlock(block_id.clone(), 0)
+            }
+            NakamotoTenureDownloadState::GetTenureEndBlock(block_id, _) => {
+                NakamotoTenureDownloadState::GetTenureEndBlock(block_id.clone(), 0)
+            }
+            NakamotoTenureDownloadState::GetTenureBlocks(block_id, _) => {
+                NakamotoTenureDownloadState::GetTenureBlocks(block_id.clone(), 0)
+            }
+            NakamotoTenureDownloadState::Done => NakamotoTenureDownloadState::Done,
+        };
+        TenureDownloaderSnapshot {
+            state,
+            has_tenure_start_block: self.tenure_start_block.is_some(),
+            has_tenure_end_block: self.tenure_end_block.is_some(),
+            num_tenure_blocks: self.tenure_blocks.as_ref().map(Vec::len).unwrap_or(0),
+            tenure_length: self.tenure_length(),
+            idle: self.idle,
+            truncated_responses: self.truncated_responses,
+        }
+    }
+
+    /// Make a checkpoint of this machine's progress through its tenure's blocks, so that the
+    /// download can later be resumed with `try_resume_from_checkpoint()`.
+    /// Returns None if this machine is not fetching tenure blocks.
+    pub fn make_checkpoint(&self) -> Option<NakamotoTenureDownloadCheckpoint> {
+        let NakamotoTenureDownloadState::GetTenureBlocks(..) = &self.state else {
+            return None;
+        };
+        Some(NakamotoTenureDownloadCheckpoint {
+            tenure_id_consensus_hash: self.tenure_id_consensus_hash.clone(),
+            tenure_start_block: self.tenure_start_block.clone()?,
+            tenure_end_block: self.tenure_end_block.clone()?,
+            tenure_blocks: self.tenure_blocks.clone().unwrap_or_default(),
+        })
+    }
+
+    /// Resume this machine's download from a checkpoint.  The checkpoint's blocks are fed into a
+    /// copy of this machine, so they are validated exactly as if they had just been downloaded
+    /// (regardless of which peer they originally came from).  This machine is only updated if
+    /// they are all valid, in which case it will next fetch the checkpoint's block cursor.
+    ///
+    /// Returns Ok(()) if the download was resumed.
+    /// Returns Err(..) if this machine has already started, or if the checkpoint is not for this
+    /// machine's tenure or is otherwise invalid.
+    pub fn try_resume_from_checkpoint(
+        &mut self,
+        checkpoint: NakamotoTenureDownloadCheckpoint,
+    ) -> Result<(), NetError> {
+        let NakamotoTenureDownloadState::GetTenureStartBlock(..) = &self.state else {
+            warn!("Invalid state for this method";
+                  "download_pass" => self.download_pass,
+                  "state" => %self.state);
+            return Err(NetError::InvalidState);
+        };
+        if checkpoint.tenure_id_consensus_hash != self.tenure_id_consensus_hash {
+            warn!("Invalid checkpoint: not for this tenure";
+                  "download_pass" => self.download_pass,
+                  "tenure_id" => %self.tenure_id_consensus_hash,
+                  "checkpoint.tenure_id" => %checkpoint.tenure_id_consensus_hash);
+            return Err(NetError::InvalidMessage);
+        }
+
+        let mut resumed = self.clone();
+        resumed.try_accept_tenure_start_block(checkpoint.tenure_start_block)?;
+        if let NakamotoTenureDownloadState::GetTenureEndBlock(..) = &resumed.state {
+            resumed.try_accept_tenure_end_block(&checkpoint.tenure_end_block)?;
+        }
+
+        // the first checkpointed block is the tenure-end block, which was just accepted
+        let mut tenure_blocks = checkpoint.tenure_blocks.into_iter();
+        if let Some(first_block) = tenure_blocks.next() {
+            if first_block.block_id() != checkpoint.tenure_end_block.block_id() {
+                warn!("Invalid checkpoint: tenure blocks do not begin with the tenure-end block";
+                      "download_pass" => self.download_pass,
+                      "tenure_id" => %self.tenure_id_consensus_hash,
+                      "tenure_end_block" => %checkpoint.tenure_end_block.block_id(),
+                      "first_block" => %first_block.block_id());
+                return Err(NetError::InvalidMessage);
+            }
+        }
+        if resumed
+            .try_accept_tenure_blocks(tenure_blocks.collect())?
+            .is_some()
+        {
+            // checkpoints are only made while the tenure is incomplete
+            warn!("Invalid checkpoint: contains the whole tenure";
+                  "download_pass" => self.download_pass,
+                  "tenure_id" => %self.tenure_id_consensus_hash);
+            return Err(NetError::InvalidMessage);
+        }
+
+        debug!(
+            "Resumed download of tenure {} from checkpoint (state {})",
+            &self.tenure_id_consensus_hash, &resumed.state;
+            "download_pass" => self.download_pass,
+        );
+        *self = resumed;
+        Ok(())
+    }
+
+    /// Store a checkpoint of this machine's progress to the staging DB.  The first checkpoint
+    /// this machine stores replaces any prior checkpoint for this tenure; after that, only the
+    /// tenure blocks obtained since the last checkpoint are written.
+    /// Returns Ok(true) if stored
+    /// Returns Ok(false) if this machine is not fetching tenure blocks, or has not obtained any
+    /// new tenure blocks since its last checkpoint
+    /// Returns Err(..) on DB error
+    pub fn store_checkpoint(
+        &mut self,
+        chainstate: &mut StacksChainState,
+    ) -> Result<bool, NetError> {
+        let NakamotoTenureDownloadState::GetTenureBlocks(..) = &self.state else {
+            return Ok(false);
+        };
+        let num_blocks = self.tenure_blocks.as_ref().map(Vec::len).unwrap_or(0);
+        if num_blocks <= self.checkpoint_num_blocks {
+            return Ok(false);
+        }
+        let staging_tx = chainstate.staging_db_tx_begin()?;
+        if self.checkpoint_num_blocks == 0 {
+            let Some(checkpoint) = self.make_checkpoint() else {
+                return Ok(false);
+            };
+            staging_tx.store_tenure_download_checkpoint(&checkpoint)?;
+        } else {
+            let new_blocks = self
+                .tenure_blocks
+                .as_deref()
+                .unwrap_or(&[])
+                .get(self.checkpoint_num_blocks..)
+                .unwrap_or(&[]);
+            staging_tx.append_tenure_download_checkpoint_blocks(
+                &self.tenure_id_consensus_hash,
+                self.checkpoint_num_blocks,
+                new_blocks,
+            )?;
+        }
+        staging_tx.commit()?;
+        self.checkpoint_num_blocks = num_blocks;
+        Ok(true)
+    }
+
+    /// Resume this machine's download from the staging DB's checkpoint for its tenure, if there
+    /// is one and this machine has not yet started.  A checkpoint which cannot be resumed from is
+    /// deleted.
+    /// Returns Ok(true) if the download was resumed
+    /// Returns Ok(false) if not
+    /// Returns Err(..) on DB error
+    pub fn try_resume_from_stored_checkpoint(
+        &mut self,
+        chainstate: &mut StacksChainState,
+    ) -> Result<bool, NetError> {
+        let NakamotoTenureDownloadState::GetTenureStartBlock(..) = &self.state else {
+            // already started
+            return Ok(false);
+        };
+        let Some(checkpoint) = chainstate
+            .nakamoto_blocks_db()
+            .get_tenure_download_checkpoint(&self.tenure_id_consensus_hash)?
+        else {
+            return Ok(false);
+        };
+        if let Err(e) = self.try_resume_from_checkpoint(checkpoint) {
+            info!(
+                "Discarding unusable download checkpoint for tenure {}: {e:?}",
+                &self.tenure_id_consensus_hash;
+                "download_pass" => self.download_pass,
+            );
+            Self::delete_stored_checkpoint(chainstate, &self.tenure_id_consensus_hash)?;
+            return Ok(false);
+        }
+        self.checkpoint_num_blocks = self.tenure_blocks.as_ref().map(Vec::len).unwrap_or(0);
+        Ok(true)
+    }
+
+    /// Delete the staging DB's checkpoint for a tenure, if there is one.
+    pub fn delete_stored_checkpoint(
+        chainstate: &mut StacksChainState,
+        tenure_id_consensus_hash: &ConsensusHash,
+    ) -> Result<(), NetError> {
+        let staging_tx = chainstate.staging_db_tx_begin()?;
+        staging_tx.delete_tenure_download_checkpoint(tenure_id_consensus_hash)?;
+        staging_tx.commit()?;
+        Ok(())
+    }
 }
@@ -15,13 +15,17 @@
 
 use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use std::hash::Hash;
+use std::time::Duration;
 
 use stacks_common::types::chainstate::{ConsensusHash, StacksBlockId};
-use stacks_common::util::get_epoch_time_secs;
+use stacks_common::util::{get_epoch_time_ms, get_epoch_time_secs};
 
 use crate::chainstate::nakamoto::NakamotoBlock;
 use crate::chainstate::stacks::db::StacksChainState;
-use crate::net::download::nakamoto::{AvailableTenures, NakamotoTenureDownloader, TenureStartEnd};
+use crate::net::download::nakamoto::{
+    AvailableTenures, NakamotoTenureDownloadState, NakamotoTenureDownloader, SignerSignatureCache,
+    TenureStartEnd,
+};
 use crate::net::neighbors::rpc::NeighborRPC;
 use crate::net::p2p::{CurrentRewardSet, DropReason, DropSource, PeerNetwork};
 use crate::net::NeighborAddress;
@@ -55,6 +59,65 @@ impl From<&mut NakamotoTenureDownloader> for CompletedTenure {
 
 pub const PEER_DEPRIORITIZATION_TIME_SECS: u64 = 60;
 
+/// How often to delete the stored download checkpoints of tenures which have been processed
+pub const CHECKPOINT_PRUNE_INTERVAL_SECS: u64 = 600;
+
+/// Why a tenure was dropped from the download schedule without a downloader being made for it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AbandonedTenureReason {
+    /// No neighbor (or no neighbor left to try) can serve the tenure
+    NoNeighbors,
+    /// The reward set needed to validate the tenure's start or end block is not known
+    MissingRewardSet,
+}
+
+/// A consumer of downloaded tenure blocks.  The download state machine pushes the tenures obtained
+/// in each download pass into the sink, one tenure at a time, instead of returning them all in one
+/// map.  This lets a memory-constrained node hand blocks off (e.g. to storage) as each pass
+/// finishes during IBD.  `NakamotoTenureDownloaderSet::run_with_sink()` pushes each tenure into
+/// the sink as soon as its downloader yields it.
+pub trait BlockSink {
+    /// Consume the blocks obtained for the tenure identified by `tenure_id`.
+    fn push_tenure_blocks(&mut self, tenure_id: &ConsensusHash, blocks: Vec<NakamotoBlock>);
+}
+
+/// The default sink simply collects tenures' blocks, keyed by tenure ID.
+impl BlockSink for HashMap<ConsensusHash, Vec<NakamotoBlock>> {
+    fn push_tenure_blocks(&mut self, tenure_id: &ConsensusHash, blocks: Vec<NakamotoBlock>) {
+        self.insert(tenure_id.clone(), blocks);
+    }
+}
+
+/// A policy for choosing which neighbor to ask for a tenure.  The confirmed tenure downloaders
+/// consult it whenever a scheduled tenure needs a peer, so that alternative strategies (e.g.
+/// latency-aware or reputation-weighted ones) can be plugged in without changing the scheduler.
+pub trait DownloadPeerSelector {
+    /// Choose which of `neighbors` to ask for the tenure identified by `tenure_id`.  The chosen
+    /// neighbor is removed from the tenure's candidates, so it won't be chosen for it again.
+    /// Returns Some(index) of the chosen neighbor in `neighbors`
+    /// Returns None if none of them should be asked, in which case the tenure is not downloaded
+    fn select_peer(
+        &mut self,
+        tenure_id: &ConsensusHash,
+        neighbors: &[NeighborAddress],
+    ) -> Option<usize>;
+}
+
+/// The default selector chooses the last neighbor.  The block downloader orders each tenure's
+/// neighbors such that the preferred ones (e.g. trusted download peers) come last.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultDownloadPeerSelector;
+
+impl DownloadPeerSelector for DefaultDownloadPeerSelector {
+    fn select_peer(
+        &mut self,
+        _tenure_id: &ConsensusHash,
+        neighbors: &[NeighborAddress],
+    ) -> Option<usize> {
+        neighbors.len().checked_sub(1)
+    }
+}
+
 /// A set of confirmed downloader state machines assigned to one or more neighbors.  The block
 /// downloader runs tenure-downloaders in parallel, since the downloader for the N+1'st tenure
 /// needs to feed data into the Nth tenure.  This struct is responsible for scheduling peer
@@ -79,6 +142,39 @@ pub struct NakamotoTenureDownloaderSet {
     /// Peers that should be deprioritized because they're dead (maps to when they can be used
     /// again)
     pub(crate) deprioritized_peers: HashMap<NeighborAddress, u64>,
+    /// Cache of already-verified signer signatures, shared by all confirmed and unconfirmed
+    /// tenure downloaders
+    pub(crate) signature_cache: SignerSignatureCache,
+    /// Tenure-start and tenure-end blocks which a scheduled downloader has claimed the right to
+    /// fetch, mapped to the peer that will fetch it
+    pub(crate) block_requests: HashMap<StacksBlockId, NeighborAddress>,
+    /// Minimum number of scheduled downloaders which will be given to tenures in the oldest
+    /// reward cycle we know about, so long as any of them remain in the download schedule.  0
+    /// means that tenures are scheduled strictly in schedule order.
+    pub(crate) prev_cycle_download_slots: usize,
+    /// Tenures which were dropped from the download schedule without being downloaded, and why.
+    /// A tenure is removed from this map once a downloader is made for it.
+    pub(crate) abandoned_tenures: HashMap<ConsensusHash, AbandonedTenureReason>,
+    /// Whether or not new downloaders check the transaction Merkle roots of the tenure blocks
+    /// they download
+    pub(crate) verify_tx_merkle_roots: bool,
+    /// Whether or not downloaders checkpoint their progress through their tenures' blocks to the
+    /// staging DB, and resume from a stored checkpoint when they start
+    pub(crate) checkpoint_tenure_downloads: bool,
+    /// Tenures whose downloads were cancelled or abandoned, and whose stored checkpoints will be
+    /// deleted the next time this set runs
+    pub(crate) stale_checkpoints: HashSet<ConsensusHash>,
+    /// Epoch second at which the stored checkpoints of processed tenures were last deleted
+    pub(crate) last_checkpoint_prune_secs: u64,
+    /// Whether or not only the replies from this set's own peers are collected from the
+    /// `NeighborRPC`.  This is set when unconfirmed tenure downloaders share the `NeighborRPC`
+    /// with this set, so their replies are left for them to collect.
+    pub(crate) own_replies_only: bool,
+    /// How to choose which neighbor to ask for a scheduled tenure
+    pub(crate) peer_selector: Box<dyn DownloadPeerSelector + Send>,
+    /// Download pass of the state machine that is driving this set.  It is handed to each
+    /// downloader when the set runs, so their logs can be matched up with the pass.
+    pub(crate) download_pass: u64,
 }
 
 impl NakamotoTenureDownloaderSet {
@@ -90,6 +186,36 @@ impl NakamotoTenureDownloaderSet {
             attempted_tenures: HashMap::new(),
             attempt_failed_tenures: HashMap::new(),
             deprioritized_peers: HashMap::new(),
+            signature_cache: SignerSignatureCache::default(),
+            block_requests: HashMap::new(),
+            prev_cycle_download_slots: 0,
+            abandoned_tenures: HashMap::new(),
+            verify_tx_merkle_roots: false,
+            checkpoint_tenure_downloads: false,
+            stale_checkpoints: HashSet::new(),
+            last_checkpoint_prune_secs: 0,
+            own_replies_only: false,
+            peer_selector: Box::new(DefaultDownloadPeerSelector),
+            download_pass: 0,
+        }
+    }
+
+    /// Tenures which were dropped from the download schedule without being downloaded, and why
+    pub fn abandoned_tenures(&self) -> &HashMap<ConsensusHash, AbandonedTenureReason> {
+        &self.abandoned_tenures
+    }
+
+    /// Drop the tenure at the front of the download schedule, and remember why
+    fn abandon_scheduled_tenure(
+        &mut self,
+        schedule: &mut VecDeque<ConsensusHash>,
+        reason: AbandonedTenureReason,
+    ) {
+        if let Some(ch) = schedule.pop_front() {
+            if self.checkpoint_tenure_downloads {
+                self.stale_checkpoints.insert(ch.clone());
+            }
+            self.abandoned_tenures.insert(ch, reason);
         }
     }
 
@@ -131,7 +257,8 @@ impl NakamotoTenureDownloaderSet {
     fn add_downloader(&mut self, naddr: NeighborAddress, downloader: NakamotoTenureDownloader) {
         debug!(
             "Add downloader for tenure {} driven by {naddr}",
-            &downloader.tenure_id_consensus_hash
+            &downloader.tenure_id_consensus_hash;
+            "download_pass" => self.download_pass,
         );
         if let Some(idx) = self.peers.get(&naddr) {
             if let Some(downloader_slot) = self.downloaders.get_mut(*idx) {
@@ -194,13 +321,63 @@ impl NakamotoTenureDownloaderSet {
     ) {
         for (naddr, downloader) in iter {
             if self.has_downloader(&naddr) {
-                debug!("Already have downloader for {naddr}");
+                debug!("Already have downloader for {naddr}";
+                    "download_pass" => self.download_pass,
+                );
                 continue;
             }
             self.add_downloader(naddr, downloader);
         }
     }
 
+    /// Summarize which peers are working on which tenures, and what state each tenure's downloader
+    /// is in.  Every tenure with a downloader is mapped, even if no peer is currently bound to it
+    /// (in which case its list is empty).
+    pub fn assignments(
+        &self,
+    ) -> HashMap<ConsensusHash, Vec<(NeighborAddress, NakamotoTenureDownloadState)>> {
+        let mut assignments: HashMap<_, Vec<_>> = HashMap::new();
+        for downloader in self.downloaders.iter().flatten() {
+            assignments
+                .entry(downloader.tenure_id_consensus_hash.clone())
+                .or_default();
+        }
+        for (naddr, idx) in self.peers.iter() {
+            let Some(Some(downloader)) = self.downloaders.get(*idx) else {
+                continue;
+            };
+            assignments
+                .entry(downloader.tenure_id_consensus_hash.clone())
+                .or_default()
+                .push((naddr.clone(), downloader.state.clone()));
+        }
+        assignments
+    }
+
+    /// Find the downloaders which haven't accepted a block in the last `timeout`, so an operator
+    /// can see why a tenure isn't finishing.  This does not change any downloader's state.
+    pub fn stuck_downloaders(
+        &self,
+        timeout: Duration,
+    ) -> Vec<(ConsensusHash, NeighborAddress, NakamotoTenureDownloadState)> {
+        let now_ms = get_epoch_time_ms();
+        self.downloaders
+            .iter()
+            .flatten()
+            .filter(|downloader| {
+                !downloader.is_done()
+                    && now_ms.saturating_sub(downloader.last_progress_ms) > timeout.as_millis()
+            })
+            .map(|downloader| {
+                (
+                    downloader.tenure_id_consensus_hash.clone(),
+                    downloader.naddr.clone(),
+                    downloader.state.clone(),
+                )
+            })
+            .collect()
+    }
+
     /// Count up the number of in-flight messages, based on the states of each instantiated
     /// downloader.
     pub fn inflight(&self) -> usize {
@@ -229,7 +406,11 @@ impl NakamotoTenureDownloaderSet {
             if downloader.is_done() {
                 continue;
             }
-            debug!("TenureDownloadSet::is_empty(): have downloader for tenure {:?} assigned to {} in state {}", &downloader.tenure_id_consensus_hash, &downloader.naddr, &downloader.state);
+            debug!(
+                "TenureDownloadSet::is_empty(): have downloader for tenure {:?} assigned to {} in state {}",
+                &downloader.tenure_id_consensus_hash, &downloader.naddr, &downloader.state;
+                "download_pass" => self.download_pass,
+            );
             return false;
         }
         true
@@ -242,7 +423,9 @@ impl NakamotoTenureDownloaderSet {
     /// Returns true if the peer gets scheduled.
     /// Returns false if not.
     pub fn try_resume_peer(&mut self, naddr: NeighborAddress) -> bool {
-        debug!("Try resume {}", &naddr);
+        debug!("Try resume {}", &naddr;
+            "download_pass" => self.download_pass,
+        );
         if let Some(idx) = self.peers.get(&naddr) {
             let Some(Some(_downloader)) = self.downloaders.get(*idx) else {
                 return false;
@@ -250,7 +433,8 @@ impl NakamotoTenureDownloaderSet {
 
             debug!(
                 "Peer {naddr} already bound to downloader for {}",
-                &_downloader.tenure_id_consensus_hash
+                &_downloader.tenure_id_consensus_hash;
+                "download_pass" => self.download_pass,
             );
             return true;
         }
@@ -263,7 +447,8 @@ impl NakamotoTenureDownloaderSet {
             }
             debug!(
                 "Assign peer {naddr} to work on downloader for {} in state {}",
-                &downloader.tenure_id_consensus_hash, &downloader.state
+                &downloader.tenure_id_consensus_hash, &downloader.state;
+                "download_pass" => self.download_pass,
             );
             downloader.naddr = naddr.clone();
             self.peers.insert(naddr, i);
@@ -283,14 +468,17 @@ impl NakamotoTenureDownloaderSet {
                 continue;
             };
             let Some(downloader) = downloader_opt.as_ref() else {
-                debug!("Remove peer {naddr} for null download {i}");
+                debug!("Remove peer {naddr} for null download {i}";
+                    "download_pass" => self.download_pass,
+                );
                 idled.push(naddr.clone());
                 continue;
             };
             if downloader.idle {
                 debug!(
                     "Remove idled peer {naddr} for tenure download {}",
-                    &downloader.tenure_id_consensus_hash
+                    &downloader.tenure_id_consensus_hash;
+                    "download_pass" => self.download_pass,
                 );
                 idled.push(naddr.clone());
             }
@@ -315,22 +503,228 @@ impl NakamotoTenureDownloaderSet {
         }
     }
 
-    /// Find the downloaders that have obtained their tenure-start blocks, and extract them.  These
-    /// will be fed into other downloaders which are blocked on needing their tenure-end blocks.
+    /// Cancel any downloader (scheduled or not) for the given tenure, and free the peer slot
+    /// assigned to it.  Also forget that the tenure was downloaded, so that it can be scheduled
+    /// again, and discard its stored download checkpoint, if any.  Used when the tenure is no
+    /// longer on the canonical fork.
+    ///
+    /// Returns true if a downloader was cancelled.
+    pub fn cancel_tenure(&mut self, ch: &ConsensusHash) -> bool {
+        let mut cancelled = vec![];
+        for (i, downloader_opt) in self.downloaders.iter_mut().enumerate() {
+            let Some(downloader) = downloader_opt else {
+                continue;
+            };
+            if &downloader.tenure_id_consensus_hash != ch {
+                continue;
+            }
+            debug!(
+                "Cancel downloader for tenure {ch} (idle={}, state={}, naddr={})",
+                downloader.idle, &downloader.state, &downloader.naddr;
+                "download_pass" => self.download_pass,
+            );
+            *downloader_opt = None;
+            cancelled.push(i);
+        }
+        self.peers.retain(|_, idx| !cancelled.contains(idx));
+        self.completed_tenures
+            .retain(|completed| &completed.tenure_id != ch);
+        if self.checkpoint_tenure_downloads {
+            self.stale_checkpoints.insert(ch.clone());
+        }
+        !cancelled.is_empty()
+    }
+
+    /// Delete the stored download checkpoints of the tenures in `self.stale_checkpoints` which
+    /// have not since been given a new downloader.  Also, at most once every
+    /// `CHECKPOINT_PRUNE_INTERVAL_SECS`, delete the stored checkpoints of tenures which have been
+    /// processed.
+    pub(crate) fn delete_stale_checkpoints(
+        &mut self,
+        chainstate: &mut StacksChainState,
+        now_secs: u64,
+    ) {
+        let stale_checkpoints: Vec<_> = self.stale_checkpoints.drain().collect();
+        for ch in stale_checkpoints {
+            if self.has_downloader_for_tenure(&ch) {
+                continue;
+            }
+            debug!("Delete download checkpoint for tenure {ch}";
+                "download_pass" => self.download_pass,
+            );
+            let _ = NakamotoTenureDownloader::delete_stored_checkpoint(chainstate, &ch)
+                .inspect_err(|e| {
+                    warn!("Failed to delete download checkpoint for tenure {ch}: {e:?}";
+                        "download_pass" => self.download_pass,
+                    );
+                });
+        }
+
+        if self
+            .last_checkpoint_prune_secs
+            .saturating_add(CHECKPOINT_PRUNE_INTERVAL_SECS)
+            > now_secs
+        {
+            return;
+        }
+        self.last_checkpoint_prune_secs = now_secs;
+        let res = chainstate.staging_db_tx_begin().and_then(|staging_tx| {
+            let num_pruned = staging_tx.prune_tenure_download_checkpoints()?;
+            staging_tx.commit()?;
+            Ok(num_pruned)
+        });
+        match res {
+            Ok(num_pruned) => {
+                debug!("Deleted {num_pruned} download checkpoints for processed tenures";
+                    "download_pass" => self.download_pass,
+                );
+            }
+            Err(e) => {
+                warn!("Failed to delete download checkpoints for processed tenures: {e:?}";
+                    "download_pass" => self.download_pass,
+                );
+            }
+        }
+    }
+
+    /// Find the downloaders that have obtained their tenure-start or tenure-end blocks, and extract
+    /// them.  These will be fed into other downloaders which are blocked on needing them, since a
+    /// tenure-end block is the next tenure's tenure-start block.  A tenure-end block held by a
+    /// downloader that has not yet accepted its tenure-start block has not been validated, so it
+    /// is not included.
     pub(crate) fn find_new_tenure_start_blocks(&self) -> HashMap<StacksBlockId, NakamotoBlock> {
         let mut ret = HashMap::new();
         for downloader_opt in self.downloaders.iter() {
             let Some(downloader) = downloader_opt else {
                 continue;
             };
-            let Some(block) = downloader.tenure_start_block.as_ref() else {
+            if let Some(block) = downloader.tenure_start_block.as_ref() {
+                ret.insert(block.block_id(), block.clone());
+            }
+            if matches!(
+                downloader.state,
+                NakamotoTenureDownloadState::GetTenureStartBlock(..)
+            ) {
                 continue;
-            };
-            ret.insert(block.block_id(), block.clone());
+            }
+            if let Some(block) = downloader.tenure_end_block.as_ref() {
+                ret.insert(block.block_id(), block.clone());
+            }
         }
         ret
     }
 
+    /// Adjacent tenures share a block: a tenure's end block is the next tenure's start block.  If
+    /// two scheduled downloaders need to fetch the same such block, then only the one which
+    /// claimed it first may request it.  The others wait for it to be handed over by
+    /// `share_tenure_boundary_blocks()` once it arrives.  A claim lapses once its downloader no
+    /// longer needs the block or is no longer scheduled.
+    ///
+    /// Returns the peers whose downloaders must wait.
+    pub(crate) fn find_coalesced_requests(&mut self) -> HashSet<NeighborAddress> {
+        let mut wanted = HashMap::new();
+        for (naddr, index) in self.peers.iter() {
+            let Some(Some(downloader)) = self.downloaders.get(*index) else {
+                continue;
+            };
+            let Some(block_id) = downloader.requested_boundary_block_id() else {
+                continue;
+            };
+            wanted.insert(naddr.clone(), block_id.clone());
+        }
+
+        self.block_requests
+            .retain(|block_id, claimant| wanted.get(claimant) == Some(block_id));
+
+        let mut waiting = HashSet::new();
+        for (naddr, block_id) in wanted.into_iter() {
+            let claimant = self
+                .block_requests
+                .entry(block_id.clone())
+                .or_insert_with(|| naddr.clone());
+            if claimant != &naddr {
+                debug!("Peer {naddr} waits for {claimant} to fetch block {block_id}";
+                    "download_pass" => self.download_pass,
+                );
+                waiting.insert(naddr);
+            }
+        }
+        waiting
+    }
+
+    /// Hand the tenure-start and tenure-end blocks that downloaders have obtained to the other
+    /// downloaders which need them, so they don't fetch them again.
+    pub(crate) fn share_tenure_boundary_blocks(&mut self, neighbor_rpc: &NeighborRPC) {
+        let blocks = self.find_new_tenure_start_blocks();
+        self.use_known_tenure_start_blocks(neighbor_rpc, &blocks);
+    }
+
+    /// Feed already-known tenure-start blocks into downloaders which would otherwise fetch them,
+    /// either as their own tenure-start blocks or as their tenure-end blocks (which are the
+    /// tenure-start blocks of the next tenures).  Each block is validated by the downloader as if
+    /// it had been fetched.  Downloaders with a request in flight are skipped, since the reply
+    /// would no longer match their state.
+    pub(crate) fn use_known_tenure_start_blocks(
+        &mut self,
+        neighbor_rpc: &NeighborRPC,
+        blocks: &HashMap<StacksBlockId, NakamotoBlock>,
+    ) {
+        if blocks.is_empty() {
+            return;
+        }
+        for downloader in self.downloaders.iter_mut().flatten() {
+            if neighbor_rpc.has_inflight(&downloader.naddr) {
+                continue;
+            }
+            if matches!(
+                downloader.state,
+                NakamotoTenureDownloadState::GetTenureStartBlock(..)
+            ) {
+                if let Some(block) = blocks.get(&downloader.tenure_start_block_id) {
+                    debug!(
+                        "Use known tenure-start block {} for tenure {}",
+                        &downloader.tenure_start_block_id, &downloader.tenure_id_consensus_hash;
+                        "download_pass" => self.download_pass,
+                    );
+                    if let Err(e) = downloader.try_accept_tenure_start_block(block.clone()) {
+                        warn!(
+                            "Known tenure-start block {} is not valid for tenure {}: {e:?}",
+                            &downloader.tenure_start_block_id, &downloader.tenure_id_consensus_hash;
+                            "download_pass" => self.download_pass,
+                        );
+                        continue;
+                    }
+                } else if downloader.tenure_end_block.is_none() {
+                    // hold on to it until we get the tenure-start block
+                    if let Some(block) = blocks.get(&downloader.tenure_end_block_id) {
+                        downloader.tenure_end_block = Some(block.clone());
+                    }
+                    continue;
+                }
+            }
+            if matches!(
+                downloader.state,
+                NakamotoTenureDownloadState::GetTenureEndBlock(..)
+            ) {
+                let Some(block) = blocks.get(&downloader.tenure_end_block_id) else {
+                    continue;
+                };
+                debug!(
+                    "Use known tenure-end block {} for tenure {}",
+                    &downloader.tenure_end_block_id, &downloader.tenure_id_consensus_hash;
+                    "download_pass" => self.download_pass,
+                );
+                if let Err(e) = downloader.try_accept_tenure_end_block(block) {
+                    warn!(
+                        "Known tenure-end block {} is not valid for tenure {}: {e:?}",
+                        &downloader.tenure_end_block_id, &downloader.tenure_id_consensus_hash;
+                        "download_pass" => self.download_pass,
+                    );
+                }
+            }
+        }
+    }
+
     /// Does there exist a downloader (possibly unscheduled) for the given tenure?
     pub(crate) fn has_downloader_for_tenure(&self, tenure_id: &ConsensusHash) -> bool {
         for downloader_opt in self.downloaders.iter() {
@@ -340,7 +734,8 @@ impl NakamotoTenureDownloaderSet {
             if &downloader.tenure_id_consensus_hash == tenure_id {
                 debug!(
                     "Have downloader for tenure {tenure_id} already (idle={}, state={}, naddr={})",
-                    downloader.idle, &downloader.state, &downloader.naddr
+                    downloader.idle, &downloader.state, &downloader.naddr;
+                    "download_pass" => self.download_pass,
                 );
                 return true;
             }
@@ -348,6 +743,47 @@ impl NakamotoTenureDownloaderSet {
         false
     }
 
+    /// If fewer than `prev_cycle_download_slots` scheduled downloaders are fetching tenures from
+    /// the oldest reward cycle in `tenure_reward_cycles`, then move the first such tenure in
+    /// `schedule` to the front so it gets the next downloader slot.
+    fn prioritize_prev_cycle_tenure(
+        &self,
+        schedule: &mut VecDeque<ConsensusHash>,
+        tenure_reward_cycles: &HashMap<&ConsensusHash, u64>,
+    ) {
+        let Some(oldest_rc) = tenure_reward_cycles.values().min() else {
+            return;
+        };
+        let num_prev_cycle_downloaders = self
+            .peers
+            .values()
+            .filter_map(|idx| self.downloaders.get(*idx)?.as_ref())
+            .filter(|downloader| {
+                tenure_reward_cycles.get(&downloader.tenure_id_consensus_hash) == Some(oldest_rc)
+            })
+            .count();
+        if num_prev_cycle_downloaders >= self.prev_cycle_download_slots {
+            return;
+        }
+        let Some(pos) = schedule
+            .iter()
+            .position(|ch| tenure_reward_cycles.get(ch) == Some(oldest_rc))
+        else {
+            return;
+        };
+        if pos == 0 {
+            return;
+        }
+        if let Some(ch) = schedule.remove(pos) {
+            debug!(
+                "Prioritize tenure {ch} from reward cycle {oldest_rc} ({num_prev_cycle_downloaders} of {} reserved downloaders in use)",
+                self.prev_cycle_download_slots;
+                "download_pass" => self.download_pass,
+            );
+            schedule.push_front(ch);
+        }
+    }
+
     /// Create a given number of downloads from a schedule and availability set.
     /// Removes items from the schedule, and neighbors from the availability set.
     /// A neighbor will be issued at most one request.
@@ -360,6 +796,7 @@ impl NakamotoTenureDownloaderSet {
         current_reward_cycles: &BTreeMap<u64, CurrentRewardSet>,
     ) {
         test_debug!("make_tenure_downloaders";
+               "download_pass" => self.download_pass,
                "schedule" => ?schedule,
                "available" => ?available,
                "tenure_block_ids" => ?tenure_block_ids,
@@ -370,31 +807,59 @@ impl NakamotoTenureDownloaderSet {
 
         self.clear_finished_downloaders();
         self.clear_available_peers();
+
+        // reward cycle of each available tenure, for reserving downloaders for the oldest one
+        let tenure_reward_cycles: HashMap<_, _> = if self.prev_cycle_download_slots > 0 {
+            tenure_block_ids
+                .values()
+                .flat_map(|tenures| {
+                    tenures
+                        .iter()
+                        .map(|(ch, tenure_info)| (ch, tenure_info.start_reward_cycle))
+                })
+                .collect()
+        } else {
+            HashMap::new()
+        };
+
         while self.num_scheduled_downloaders() < count {
+            self.prioritize_prev_cycle_tenure(schedule, &tenure_reward_cycles);
             let Some(ch) = schedule.front() else {
                 break;
             };
             let Some(neighbors) = available.get_mut(ch) else {
                 // not found on any neighbors, so stop trying this tenure
-                debug!("No neighbors have tenure {ch}");
-                schedule.pop_front();
+                debug!("No neighbors have tenure {ch}";
+                    "download_pass" => self.download_pass,
+                );
+                self.abandon_scheduled_tenure(schedule, AbandonedTenureReason::NoNeighbors);
                 continue;
             };
             if neighbors.is_empty() {
                 // no more neighbors to try
-                debug!("No more neighbors can serve tenure {ch}");
-                schedule.pop_front();
+                debug!("No more neighbors can serve tenure {ch}";
+                    "download_pass" => self.download_pass,
+                );
+                self.abandon_scheduled_tenure(schedule, AbandonedTenureReason::NoNeighbors);
                 continue;
             }
-            let Some(naddr) = neighbors.pop() else {
-                debug!("No more neighbors can serve tenure {ch}");
-                schedule.pop_front();
+            let Some(naddr) = self
+                .peer_selector
+                .select_peer(ch, neighbors)
+                .filter(|index| *index < neighbors.len())
+                .map(|index| neighbors.remove(index))
+            else {
+                debug!("No more neighbors can serve tenure {ch}";
+                    "download_pass" => self.download_pass,
+                );
+                self.abandon_scheduled_tenure(schedule, AbandonedTenureReason::NoNeighbors);
                 continue;
             };
             if get_epoch_time_secs() < *self.deprioritized_peers.get(&naddr).unwrap_or(&0) {
                 debug!(
                     "Peer {} is deprioritized until {naddr}",
-                    self.deprioritized_peers.get(&naddr).unwrap_or(&0)
+                    self.deprioritized_peers.get(&naddr).unwrap_or(&0);
+                    "download_pass" => self.download_pass,
                 );
                 continue;
             }
@@ -409,18 +874,24 @@ impl NakamotoTenureDownloaderSet {
 
             let Some(available_tenures) = tenure_block_ids.get(&naddr) else {
                 // this peer doesn't have any known tenures, so try the others
-                debug!("No tenures available from {naddr}");
+                debug!("No tenures available from {naddr}";
+                    "download_pass" => self.download_pass,
+                );
                 continue;
             };
             let Some(tenure_info) = available_tenures.get(ch) else {
                 // this peer does not have a tenure start/end block for this tenure, so try the
                 // others.
-                debug!("Neighbor {naddr} does not serve tenure {ch}");
+                debug!("Neighbor {naddr} does not serve tenure {ch}";
+                    "download_pass" => self.download_pass,
+                );
                 continue;
             };
             if tenure_info.processed {
                 // we already have this tenure
-                debug!("Already have processed tenure {ch}");
+                debug!("Already have processed tenure {ch}";
+                    "download_pass" => self.download_pass,
+                );
                 self.completed_tenures
                     .remove(&CompletedTenure::from(tenure_info));
                 continue;
@@ -431,7 +902,8 @@ impl NakamotoTenureDownloaderSet {
             {
                 debug!(
                     "Already successfully downloaded tenure {ch} ({}-{})",
-                    &tenure_info.start_block_id, &tenure_info.end_block_id
+                    &tenure_info.start_block_id, &tenure_info.end_block_id;
+                    "download_pass" => self.download_pass,
                 );
                 schedule.pop_front();
                 continue;
@@ -442,9 +914,10 @@ impl NakamotoTenureDownloaderSet {
             else {
                 debug!(
                     "Cannot fetch tenure-start block due to no known start reward set for cycle {}: {tenure_info:?}",
-                    tenure_info.start_reward_cycle,
+                    tenure_info.start_reward_cycle;
+                    "download_pass" => self.download_pass,
                 );
-                schedule.pop_front();
+                self.abandon_scheduled_tenure(schedule, AbandonedTenureReason::MissingRewardSet);
                 continue;
             };
             let Some(Some(end_reward_set)) = current_reward_cycles
@@ -453,9 +926,10 @@ impl NakamotoTenureDownloaderSet {
             else {
                 debug!(
                     "Cannot fetch tenure-end block due to no known end reward set for cycle {}: {tenure_info:?}",
-                    tenure_info.end_reward_cycle,
+                    tenure_info.end_reward_cycle;
+                    "download_pass" => self.download_pass,
                 );
-                schedule.pop_front();
+                self.abandon_scheduled_tenure(schedule, AbandonedTenureReason::MissingRewardSet);
                 continue;
             };
 
@@ -466,6 +940,7 @@ impl NakamotoTenureDownloaderSet {
             let attempt_failed_count = *self.attempt_failed_tenures.get(ch).unwrap_or(&0);
 
             info!("Download tenure {ch}";
+                "download_pass" => self.download_pass,
                 "peer" => %naddr,
                 "attempt" => attempt_count.saturating_add(1),
                 "failed" => attempt_failed_count,
@@ -489,9 +964,14 @@ impl NakamotoTenureDownloaderSet {
                 start_reward_set.clone(),
                 end_reward_set.clone(),
                 false,
-            );
+            )
+            .with_signature_cache(self.signature_cache.clone())
+            .with_tx_merkle_root_check(self.verify_tx_merkle_roots);
 
-            debug!("Request tenure {ch} from neighbor {naddr}");
+            debug!("Request tenure {ch} from neighbor {naddr}";
+                "download_pass" => self.download_pass,
+            );
+            self.abandoned_tenures.remove(ch);
             self.add_downloader(naddr, tenure_download);
             schedule.pop_front();
         }
@@ -513,43 +993,88 @@ impl NakamotoTenureDownloaderSet {
         neighbor_rpc: &mut NeighborRPC,
         chainstate: &mut StacksChainState,
     ) -> HashMap<ConsensusHash, Vec<NakamotoBlock>> {
+        let mut new_blocks = HashMap::new();
+        self.run_with_sink(network, neighbor_rpc, chainstate, &mut new_blocks);
+        new_blocks
+    }
+
+    /// Run all confirmed downloaders, as in `run()`, but push each tenure's downloaded blocks into
+    /// `sink` as soon as its downloader yields them.
+    pub fn run_with_sink(
+        &mut self,
+        network: &mut PeerNetwork,
+        neighbor_rpc: &mut NeighborRPC,
+        chainstate: &mut StacksChainState,
+        sink: &mut dyn BlockSink,
+    ) {
         let addrs: Vec<_> = self.peers.keys().cloned().collect();
         let mut finished = vec![];
         let mut finished_tenures = vec![];
-        let mut new_blocks = HashMap::new();
+        let waiting = self.find_coalesced_requests();
+        for downloader in self.downloaders.iter_mut().flatten() {
+            downloader.download_pass = self.download_pass;
+        }
+        if self.checkpoint_tenure_downloads {
+            self.delete_stale_checkpoints(chainstate, get_epoch_time_secs());
+        }
 
         // send requests
         for (naddr, index) in self.peers.iter() {
             if neighbor_rpc.has_inflight(naddr) {
-                debug!("Peer {naddr} has an inflight request");
+                debug!("Peer {naddr} has an inflight request";
+                    "download_pass" => self.download_pass,
+                );
+                continue;
+            }
+            if waiting.contains(naddr) {
+                debug!("Peer {naddr} waits for another peer to fetch its next block";
+                    "download_pass" => self.download_pass,
+                );
                 continue;
             }
             let Some(Some(downloader)) = self.downloaders.get_mut(*index) else {
-                debug!("No downloader for {naddr}");
+                debug!("No downloader for {naddr}";
+                    "download_pass" => self.download_pass,
+                );
                 continue;
             };
             if downloader.is_done() {
                 debug!(
                     "Downloader for {naddr} on tenure {} is finished",
-                    &downloader.tenure_id_consensus_hash
+                    &downloader.tenure_id_consensus_hash;
+                    "download_pass" => self.download_pass,
                 );
                 finished.push(naddr.clone());
                 finished_tenures.push(CompletedTenure::from(downloader));
                 continue;
             }
 
+            if self.checkpoint_tenure_downloads {
+                let _ = downloader
+                    .try_resume_from_stored_checkpoint(chainstate)
+                    .inspect_err(|e| {
+                        warn!(
+                            "Failed to resume downloader for tenure {} from a checkpoint: {e:?}",
+                            &downloader.tenure_id_consensus_hash;
+                            "download_pass" => self.download_pass,
+                        );
+                    });
+            }
+
             let _ = downloader
                 .try_advance_from_chainstate(chainstate)
                 .inspect_err(|e| {
                     warn!(
                         "Failed to advance downloader in state {} for {}: {e:?}",
-                        &downloader.state, &downloader.naddr
+                        &downloader.state, &downloader.naddr;
+                        "download_pass" => self.download_pass,
                     );
                 });
 
             debug!(
                 "Send request to {naddr} for tenure {} (state {})",
-                &downloader.tenure_id_consensus_hash, &downloader.state
+                &downloader.tenure_id_consensus_hash, &downloader.state;
+                "download_pass" => self.download_pass,
             );
             match downloader.send_next_download_request(network, neighbor_rpc) {
                 Ok(true) => {}
@@ -561,7 +1086,8 @@ impl NakamotoTenureDownloaderSet {
                 Err(e) => {
                     info!(
                         "Downloader for tenure {} to {naddr} failed; this peer is dead",
-                        &downloader.tenure_id_consensus_hash,
+                        &downloader.tenure_id_consensus_hash;
+                        "download_pass" => self.download_pass,
                     );
                     Self::mark_failed_and_deprioritize_peer(
                         &mut self.attempt_failed_tenures,
@@ -583,12 +1109,16 @@ impl NakamotoTenureDownloaderSet {
         // clear dead, broken, and done
         for naddr in addrs.iter() {
             if neighbor_rpc.is_dead_or_broken(network, naddr) {
-                debug!("Remove dead/broken downloader for {naddr}");
+                debug!("Remove dead/broken downloader for {naddr}";
+                    "download_pass" => self.download_pass,
+                );
                 self.clear_downloader(naddr);
             }
         }
         for done_naddr in finished.drain(..) {
-            debug!("Remove finished downloader for {done_naddr}");
+            debug!("Remove finished downloader for {done_naddr}";
+                "download_pass" => self.download_pass,
+            );
             self.clear_downloader(&done_naddr);
         }
         for done_tenure in finished_tenures.drain(..) {
@@ -596,24 +1126,48 @@ impl NakamotoTenureDownloaderSet {
         }
 
         // handle responses
-        for (naddr, response) in neighbor_rpc.collect_replies(network) {
+        let replies = if self.own_replies_only {
+            let peers = &self.peers;
+            neighbor_rpc.collect_replies_from(network, |naddr| peers.contains_key(naddr))
+        } else {
+            neighbor_rpc.collect_replies(network)
+        };
+        for (naddr, response) in replies {
             let Some(index) = self.peers.get(&naddr) else {
-                debug!("No downloader for {naddr}");
+                debug!("No downloader for {naddr}";
+                    "download_pass" => self.download_pass,
+                );
                 continue;
             };
             let Some(Some(downloader)) = self.downloaders.get_mut(*index) else {
-                debug!("No downloader for {naddr}");
+                debug!("No downloader for {naddr}";
+                    "download_pass" => self.download_pass,
+                );
                 continue;
             };
-            debug!("Got response from {naddr}");
+            debug!("Got response from {naddr}";
+                "download_pass" => self.download_pass,
+            );
 
             let blocks = match downloader.handle_next_download_response(response) {
                 Ok(Some(blocks)) => blocks,
-                Ok(None) => continue,
+                Ok(None) => {
+                    if self.checkpoint_tenure_downloads {
+                        let _ = downloader.store_checkpoint(chainstate).inspect_err(|e| {
+                            warn!(
+                                "Failed to checkpoint downloader for tenure {}: {e:?}",
+                                &downloader.tenure_id_consensus_hash;
+                                "download_pass" => self.download_pass,
+                            );
+                        });
+                    }
+                    continue;
+                }
                 Err(e) => {
                     info!(
                         "Failed to handle response from {naddr} on tenure {}: {e}",
-                        &downloader.tenure_id_consensus_hash,
+                        &downloader.tenure_id_consensus_hash;
+                        "download_pass" => self.download_pass,
                     );
                     Self::mark_failed_and_deprioritize_peer(
                         &mut self.attempt_failed_tenures,
@@ -636,19 +1190,35 @@ impl NakamotoTenureDownloaderSet {
             debug!(
                 "Got {} blocks for tenure {}",
                 blocks.len(),
-                &downloader.tenure_id_consensus_hash
+                &downloader.tenure_id_consensus_hash;
+                "download_pass" => self.download_pass,
             );
-            new_blocks.insert(downloader.tenure_id_consensus_hash.clone(), blocks);
+            sink.push_tenure_blocks(&downloader.tenure_id_consensus_hash, blocks);
             if downloader.is_done() {
+                if self.checkpoint_tenure_downloads {
+                    let _ = NakamotoTenureDownloader::delete_stored_checkpoint(
+                        chainstate,
+                        &downloader.tenure_id_consensus_hash,
+                    )
+                    .inspect_err(|e| {
+                        warn!(
+                            "Failed to delete download checkpoint for tenure {}: {e:?}",
+                            &downloader.tenure_id_consensus_hash;
+                            "download_pass" => self.download_pass,
+                        );
+                    });
+                }
                 if downloader.is_tenure_unconfirmed {
                     debug!(
                         "Downloader for tenure {} finished on {naddr}",
-                        &downloader.tenure_id_consensus_hash,
+                        &downloader.tenure_id_consensus_hash;
+                        "download_pass" => self.download_pass,
                     );
                 } else {
                     info!(
                         "Downloader for tenure {} is finished",
-                        &downloader.tenure_id_consensus_hash
+                        &downloader.tenure_id_consensus_hash;
+                        "download_pass" => self.download_pass,
                     );
                 }
 
@@ -658,21 +1228,27 @@ impl NakamotoTenureDownloaderSet {
             }
         }
 
+        // feed any newly-obtained tenure-start and tenure-end blocks to the downloaders waiting on
+        // them
+        self.share_tenure_boundary_blocks(neighbor_rpc);
+
         // clear dead, broken, and done
         for naddr in addrs.iter() {
             if neighbor_rpc.is_dead_or_broken(network, naddr) {
-                debug!("Remove dead/broken downloader for {naddr}");
+                debug!("Remove dead/broken downloader for {naddr}";
+                    "download_pass" => self.download_pass,
+                );
                 self.clear_downloader(naddr);
             }
         }
         for done_naddr in finished.into_iter() {
-            debug!("Remove finished downloader for {done_naddr}");
+            debug!("Remove finished downloader for {done_naddr}";
+                "download_pass" => self.download_pass,
+            );
             self.clear_downloader(&done_naddr);
         }
         for done_tenure in finished_tenures.into_iter() {
             self.completed_tenures.insert(done_tenure);
         }
-
-        new_blocks
     }
 }
@@ -26,7 +26,7 @@ use crate::chainstate::nakamoto::{NakamotoBlock, NakamotoChainState};
 use crate::chainstate::stacks::boot::RewardSet;
 use crate::chainstate::stacks::db::StacksChainState;
 use crate::net::api::gettenureinfo::RPCGetTenureInfo;
-use crate::net::download::nakamoto::NakamotoTenureDownloader;
+use crate::net::download::nakamoto::{NakamotoTenureDownloader, SignerSignatureCache};
 use crate::net::httpcore::{StacksHttpRequest, StacksHttpResponse};
 use crate::net::neighbors::rpc::NeighborRPC;
 use crate::net::p2p::{CurrentRewardSet, DropReason, DropSource, PeerNetwork};
@@ -49,8 +49,14 @@ pub enum NakamotoUnconfirmedDownloadState {
     /// We have gotten all the unconfirmed blocks for this tenure, and we now have the end block
     /// for the highest complete tenure (which can now be obtained via `NakamotoTenureDownloadState`).
     Done,
+    /// The ongoing tenure we learned in `GetTenureInfo` is no longer on the canonical sortition
+    /// fork, so there is nothing more to fetch from this peer.
+    Aborted,
 }
 
+/// How many times a malformed tenure-info response may be retried before giving up on the peer
+pub const MAX_TENURE_INFO_RETRIES: u32 = 1;
+
 impl fmt::Display for NakamotoUnconfirmedDownloadState {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{:?}", self)
@@ -90,6 +96,12 @@ pub struct NakamotoUnconfirmedTenureDownloader {
     pub unconfirmed_tenure_start_block: Option<NakamotoBlock>,
     /// Unconfirmed tenure blocks obtained
     pub unconfirmed_tenure_blocks: Option<Vec<NakamotoBlock>>,
+    /// Cache of already-verified signer signatures, shared with other downloaders
+    pub signature_cache: SignerSignatureCache,
+    /// Number of malformed tenure-info responses we've retried
+    pub tenure_info_retries: u32,
+    /// Download pass of the state machine that is driving this downloader, for logging
+    pub download_pass: u64,
 }
 
 impl NakamotoUnconfirmedTenureDownloader {
@@ -106,9 +118,19 @@ impl NakamotoUnconfirmedTenureDownloader {
             tenure_tip: None,
             unconfirmed_tenure_start_block: None,
             unconfirmed_tenure_blocks: None,
+            signature_cache: SignerSignatureCache::default(),
+            tenure_info_retries: 0,
+            download_pass: 0,
         }
     }
 
+    /// Follow-on constructor used to share a signer signature cache with other downloaders.
+    /// The highest complete tenure downloader made by this machine will share it as well.
+    pub fn with_signature_cache(mut self, signature_cache: SignerSignatureCache) -> Self {
+        self.signature_cache = signature_cache;
+        self
+    }
+
     /// What's the tenure ID of the ongoing tenure?  This is learned from /v3/tenure/info, which is
     /// checked upon receipt against the burnchain state (so we're not blindly trusting the remote
     /// node).
@@ -129,6 +151,57 @@ impl NakamotoUnconfirmedTenureDownloader {
         self.highest_processed_block_height = Some(highest_processed_block_height);
     }
 
+    /// Check that the ongoing tenure (and its parent) which we learned from the remote peer are
+    /// still on the canonical sortition fork identified by `sort_tip`.  If not, then move this
+    /// machine to the `Aborted` state so it stops fetching blocks for the orphaned tenure.
+    ///
+    /// Returns Ok(true) if this machine is aborted.
+    /// Returns Ok(false) if it can keep going, including if it hasn't learned the tenure tip yet or
+    /// if it is already done.
+    /// Returns Err(..) on DB error.
+    pub fn check_reorg(
+        &mut self,
+        sortdb: &SortitionDB,
+        sort_tip: &BlockSnapshot,
+    ) -> Result<bool, NetError> {
+        match self.state {
+            NakamotoUnconfirmedDownloadState::Aborted => return Ok(true),
+            NakamotoUnconfirmedDownloadState::Done => return Ok(false),
+            _ => {}
+        }
+        let Some(tenure_tip) = self.tenure_tip.as_ref() else {
+            return Ok(false);
+        };
+
+        let ih = sortdb.index_handle(&sort_tip.sortition_id);
+        for consensus_hash in [
+            &tenure_tip.consensus_hash,
+            &tenure_tip.parent_consensus_hash,
+        ] {
+            let is_canonical =
+                match SortitionDB::get_block_snapshot_consensus(sortdb.conn(), consensus_hash)? {
+                    // N.B. the index does not map the tip's own height to the tip
+                    Some(sn) if sn.sortition_id == sort_tip.sortition_id => true,
+                    Some(sn) => ih
+                        .get_block_snapshot_by_height(sn.block_height)?
+                        .map(|ancestor_sn| ancestor_sn.sortition_id == sn.sortition_id)
+                        .unwrap_or(false),
+                    None => false,
+                };
+            if !is_canonical {
+                info!("Unconfirmed tenure is no longer canonical; aborting download";
+                      "download_pass" => self.download_pass,
+                      "peer" => %self.naddr,
+                      "consensus_hash" => %consensus_hash,
+                      "sort_tip" => %sort_tip.consensus_hash,
+                      "state" => %self.state);
+                self.state = NakamotoUnconfirmedDownloadState::Aborted;
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
     /// Try and accept the tenure info.  It will be validated against the sortition DB and its tip.
     ///
     /// * tenure_tip.consensus_hash
@@ -138,13 +211,18 @@ impl NakamotoUnconfirmedTenureDownloader {
     ///     This is the first block ID of the ongoing unconfirmed tenure.
     /// * tenure_tip.parent_consensus_hash
     ///     This is the consensus hash of the parent of the ongoing tenure. It's the node's highest
-    ///     complete tenure, for which we know the start and end block IDs.
+    ///     complete tenure, for which we know the start and end block IDs.  It must differ from
+    ///     tenure_tip.consensus_hash.
     /// * tenure_tip.parent_tenure_start_block_id
     ///     This is the tenure start block for the highest complete tenure.  It should be equal to
     ///     the winning Stacks block hash of the snapshot for the ongoing tenure.
     ///
     /// We may already have the tenure-start block for the unconfirmed tenure. If so, then don't go
     /// fetch it again; just get the new unconfirmed blocks.
+    ///
+    /// Returns Err(NetError::Transient(..)) if we don't yet know the reward set for either tenure
+    /// (which can happen early in epoch 3).  The state is left unchanged, so the caller can retry
+    /// later.
     pub fn try_accept_tenure_info(
         &mut self,
         sortdb: &SortitionDB,
@@ -162,10 +240,20 @@ impl NakamotoUnconfirmedTenureDownloader {
 
         debug!(
             "Got tenure info";
+            "download_pass" => self.download_pass,
             "remote_tenure_tip" => ?remote_tenure_tip,
             "local_sortition_tip" => %local_sort_tip.consensus_hash
         );
 
+        // a tenure cannot be its own parent
+        if remote_tenure_tip.consensus_hash == remote_tenure_tip.parent_consensus_hash {
+            warn!("Tenure info names the ongoing tenure as its own parent";
+                  "download_pass" => self.download_pass,
+                  "peer" => %self.naddr,
+                  "consensus_hash" => %remote_tenure_tip.consensus_hash);
+            return Err(NetError::InvalidMessage);
+        }
+
         // authenticate consensus hashes against canonical chain history
         let local_tenure_sn = SortitionDB::get_block_snapshot_consensus(
             sortdb.conn(),
@@ -174,7 +262,8 @@ impl NakamotoUnconfirmedTenureDownloader {
         .ok_or_else(|| {
             debug!(
                 "No snapshot for tenure {}",
-                &remote_tenure_tip.consensus_hash
+                &remote_tenure_tip.consensus_hash;
+                "download_pass" => self.download_pass,
             );
             NetError::DBError(DBError::NotFoundError)
         })?;
@@ -185,7 +274,8 @@ impl NakamotoUnconfirmedTenureDownloader {
         .ok_or_else(|| {
             debug!(
                 "No snapshot for parent tenure {}",
-                &remote_tenure_tip.parent_consensus_hash
+                &remote_tenure_tip.parent_consensus_hash;
+                "download_pass" => self.download_pass,
             );
             NetError::DBError(DBError::NotFoundError)
         })?;
@@ -198,7 +288,8 @@ impl NakamotoUnconfirmedTenureDownloader {
                     "No tenure snapshot at burn block height {} off of sortition {} ({})",
                     local_tenure_sn.block_height,
                     &local_tenure_sn.sortition_id,
-                    &local_tenure_sn.consensus_hash
+                    &local_tenure_sn.consensus_hash;
+                    "download_pass" => self.download_pass,
                 );
                 NetError::DBError(DBError::NotFoundError)
             })?;
@@ -206,6 +297,7 @@ impl NakamotoUnconfirmedTenureDownloader {
         if ancestor_local_tenure_sn.sortition_id != local_tenure_sn.sortition_id {
             // .consensus_hash is not on the canonical fork
             warn!("Unconfirmed tenure consensus hash is not canonical";
+                  "download_pass" => self.download_pass,
                   "peer" => %self.naddr,
                   "consensus_hash" => %remote_tenure_tip.consensus_hash);
             return Err(DBError::NotFoundError.into());
@@ -217,7 +309,8 @@ impl NakamotoUnconfirmedTenureDownloader {
                     "No parent tenure snapshot at burn block height {} off of sortition {} ({})",
                     local_tenure_sn.block_height,
                     &local_tenure_sn.sortition_id,
-                    &local_tenure_sn.consensus_hash
+                    &local_tenure_sn.consensus_hash;
+                    "download_pass" => self.download_pass,
                 );
                 NetError::DBError(DBError::NotFoundError)
             })?;
@@ -225,6 +318,7 @@ impl NakamotoUnconfirmedTenureDownloader {
         if ancestor_parent_local_tenure_sn.sortition_id != parent_local_tenure_sn.sortition_id {
             // .parent_consensus_hash is not on the canonical fork
             warn!("Parent unconfirmed tenure consensus hash is not canonical";
+                  "download_pass" => self.download_pass,
                   "peer" => %self.naddr,
                   "consensus_hash" => %remote_tenure_tip.parent_consensus_hash);
             return Err(DBError::NotFoundError.into());
@@ -233,6 +327,7 @@ impl NakamotoUnconfirmedTenureDownloader {
         // parent tenure sortition must precede the ongoing tenure sortition
         if local_tenure_sn.block_height <= parent_local_tenure_sn.block_height {
             warn!("Parent tenure snapshot is not an ancestor of the current tenure snapshot";
+                  "download_pass" => self.download_pass,
                   "peer" => %self.naddr,
                   "consensus_hash" => %remote_tenure_tip.consensus_hash,
                   "parent_consensus_hash" => %remote_tenure_tip.parent_consensus_hash);
@@ -245,6 +340,7 @@ impl NakamotoUnconfirmedTenureDownloader {
             != remote_tenure_tip.parent_tenure_start_block_id.0
         {
             debug!("Ongoing tenure does not commit to highest complete tenure's start block. Treating remote peer {} as stale.", &self.naddr;
+                  "download_pass" => self.download_pass,
                   "remote_tenure_tip.tenure_start_block_id" => %remote_tenure_tip.parent_tenure_start_block_id,
                   "local_tenure_sn.winning_stacks_block_hash" => %local_tenure_sn.winning_stacks_block_hash);
             return Err(NetError::StaleView);
@@ -256,7 +352,9 @@ impl NakamotoUnconfirmedTenureDownloader {
                 .nakamoto_blocks_db()
                 .get_nakamoto_block(highest_processed_block_id)?
                 .ok_or_else(|| {
-                    debug!("No such Nakamoto block {}", &highest_processed_block_id);
+                    debug!("No such Nakamoto block {}", &highest_processed_block_id;
+                        "download_pass" => self.download_pass,
+                    );
                     NetError::DBError(DBError::NotFoundError)
                 })?
                 .0;
@@ -289,6 +387,7 @@ impl NakamotoUnconfirmedTenureDownloader {
 
         debug!(
             "TenureDownloaderUnconfirmed not finished";
+            "download_pass" => self.download_pass,
             "tenure_burn_ht" => local_tenure_sn.block_height,
             "parent_tenure_burn_ht" => parent_local_tenure_sn.block_height
         );
@@ -311,22 +410,28 @@ impl NakamotoUnconfirmedTenureDownloader {
             .get(&parent_tenure_rc)
             .map(|cycle_info| cycle_info.reward_set())
         else {
-            warn!(
-                "No signer public keys for confirmed tenure {} (rc {})",
-                &parent_local_tenure_sn.consensus_hash, parent_tenure_rc
+            debug!(
+                "No signer public keys for confirmed tenure {} (rc {}) yet",
+                &parent_local_tenure_sn.consensus_hash, parent_tenure_rc;
+                "download_pass" => self.download_pass,
             );
-            return Err(NetError::InvalidState);
+            return Err(NetError::Transient(format!(
+                "No reward set for confirmed tenure reward cycle {parent_tenure_rc}"
+            )));
         };
 
         let Some(Some(unconfirmed_reward_set)) = current_reward_sets
             .get(&tenure_rc)
             .map(|cycle_info| cycle_info.reward_set())
         else {
-            warn!(
-                "No signer public keys for unconfirmed tenure {} (rc {})",
-                &local_tenure_sn.consensus_hash, tenure_rc
+            debug!(
+                "No signer public keys for unconfirmed tenure {} (rc {}) yet",
+                &local_tenure_sn.consensus_hash, tenure_rc;
+                "download_pass" => self.download_pass,
             );
-            return Err(NetError::InvalidState);
+            return Err(NetError::Transient(format!(
+                "No reward set for unconfirmed tenure reward cycle {tenure_rc}"
+            )));
         };
 
         if chainstate
@@ -340,7 +445,8 @@ impl NakamotoUnconfirmedTenureDownloader {
                 .ok_or_else(|| {
                     debug!(
                         "No such tenure-start Nakamoto block {}",
-                        &remote_tenure_tip.tenure_start_block_id
+                        &remote_tenure_tip.tenure_start_block_id;
+                        "download_pass" => self.download_pass,
                     );
                     NetError::DBError(DBError::NotFoundError)
                 })?
@@ -358,7 +464,8 @@ impl NakamotoUnconfirmedTenureDownloader {
 
         debug!(
             "Will validate unconfirmed blocks with reward sets in ({},{})",
-            parent_tenure_rc, tenure_rc
+            parent_tenure_rc, tenure_rc;
+            "download_pass" => self.download_pass,
         );
         self.confirmed_signer_keys = Some(confirmed_reward_set.clone());
         self.unconfirmed_signer_keys = Some(unconfirmed_reward_set.clone());
@@ -378,25 +485,31 @@ impl NakamotoUnconfirmedTenureDownloader {
             &self.state
         else {
             warn!("Invalid state for this method";
+                  "download_pass" => self.download_pass,
                   "state" => %self.state);
             return Err(NetError::InvalidState);
         };
         let Some(tenure_tip) = self.tenure_tip.as_ref() else {
-            warn!("tenure_tip is not set");
+            warn!("tenure_tip is not set";
+                "download_pass" => self.download_pass,
+            );
             return Err(NetError::InvalidState);
         };
 
         let Some(unconfirmed_signer_keys) = self.unconfirmed_signer_keys.as_ref() else {
-            warn!("unconfirmed_signer_keys is not set");
+            warn!("unconfirmed_signer_keys is not set";
+                "download_pass" => self.download_pass,
+            );
             return Err(NetError::InvalidState);
         };
 
         // stacker signature has to match the current reward set
-        if let Err(e) = unconfirmed_tenure_start_block
-            .header
-            .verify_signer_signatures(unconfirmed_signer_keys)
-        {
+        if let Err(e) = self.signature_cache.verify_signer_signatures(
+            &unconfirmed_tenure_start_block.header,
+            unconfirmed_signer_keys,
+        ) {
             warn!("Invalid tenure-start block: bad signer signature";
+                  "download_pass" => self.download_pass,
                   "tenure_start_block.header.consensus_hash" => %unconfirmed_tenure_start_block.header.consensus_hash,
                   "tenure_start_block.header.block_id" => %unconfirmed_tenure_start_block.header.block_id(),
                   "state" => %self.state,
@@ -407,6 +520,7 @@ impl NakamotoUnconfirmedTenureDownloader {
         // block has to match the expected hash
         if tenure_start_block_id != &unconfirmed_tenure_start_block.header.block_id() {
             warn!("Invalid tenure-start block";
+                  "download_pass" => self.download_pass,
                   "tenure_id_start_block" => %tenure_start_block_id,
                   "unconfirmed_tenure_start_block.header.consensus_hash" => %unconfirmed_tenure_start_block.header.consensus_hash,
                   "unconfirmed_tenure_start_block ID" => %unconfirmed_tenure_start_block.header.block_id(),
@@ -417,6 +531,7 @@ impl NakamotoUnconfirmedTenureDownloader {
         // furthermore, the block has to match the expected tenure ID
         if unconfirmed_tenure_start_block.header.consensus_hash != tenure_tip.consensus_hash {
             warn!("Invalid tenure-start block or tenure-tip: consensus hash mismatch";
+                  "download_pass" => self.download_pass,
                   "tenure_start_block.header.consensus_hash" => %unconfirmed_tenure_start_block.header.consensus_hash,
                   "tenure_tip.consensus_hash" => %tenure_tip.consensus_hash);
             return Err(NetError::InvalidMessage);
@@ -448,18 +563,24 @@ impl NakamotoUnconfirmedTenureDownloader {
         };
 
         let Some(tenure_tip) = self.tenure_tip.as_ref() else {
-            warn!("tenure_tip is not set");
+            warn!("tenure_tip is not set";
+                "download_pass" => self.download_pass,
+            );
             return Err(NetError::InvalidState);
         };
 
         let Some(unconfirmed_signer_keys) = self.unconfirmed_signer_keys.as_ref() else {
-            warn!("unconfirmed_signer_keys is not set");
+            warn!("unconfirmed_signer_keys is not set";
+                "download_pass" => self.download_pass,
+            );
             return Err(NetError::InvalidState);
         };
 
         if tenure_blocks.is_empty() {
             // nothing to do
-            debug!("No tenure blocks obtained");
+            debug!("No tenure blocks obtained";
+                "download_pass" => self.download_pass,
+            );
             return Ok(None);
         }
 
@@ -469,19 +590,23 @@ impl NakamotoUnconfirmedTenureDownloader {
         let mut finished_download = false;
         let mut last_block_index = None;
         for (cnt, block) in tenure_blocks.iter().enumerate() {
-            if &block.header.block_id() != expected_block_id {
+            let block_id = block.header.block_id();
+            if &block_id != expected_block_id {
                 warn!("Unexpected Nakamoto block -- not part of tenure";
+                      "download_pass" => self.download_pass,
                       "expected_block_id" => %expected_block_id,
-                      "block_id" => %block.header.block_id());
+                      "block_id" => %block_id);
                 return Err(NetError::InvalidMessage);
             }
-            if let Err(e) = block
-                .header
-                .verify_signer_signatures(unconfirmed_signer_keys)
-            {
+            if let Err(e) = self.signature_cache.verify_signer_signatures_with_id(
+                &block_id,
+                &block.header,
+                unconfirmed_signer_keys,
+            ) {
                 warn!("Invalid block: bad signer signature";
+                      "download_pass" => self.download_pass,
                       "tenure_id" => %tenure_tip.consensus_hash,
-                      "block.header.block_id" => %block.header.block_id(),
+                      "block.header.block_id" => %block_id,
                       "state" => %self.state,
                       "error" => %e);
                 return Err(NetError::InvalidMessage);
@@ -491,25 +616,28 @@ impl NakamotoUnconfirmedTenureDownloader {
             // do, make sure it's valid, and it's the last block we receive.
             let Ok(is_tenure_start) = block.is_wellformed_tenure_start_block() else {
                 warn!("Invalid tenure-start block";
+                      "download_pass" => self.download_pass,
                       "tenure_id" => %tenure_tip.consensus_hash,
-                      "block.header.block_id" => %block.header.block_id(),
+                      "block.header.block_id" => %block_id,
                       "state" => %self.state);
                 return Err(NetError::InvalidMessage);
             };
             if is_tenure_start {
                 // this is the tenure-start block, so make sure it matches our /v3/tenure/info
-                if block.header.block_id() != tenure_tip.tenure_start_block_id {
+                if block_id != tenure_tip.tenure_start_block_id {
                     warn!("Unexpected tenure-start block";
+                          "download_pass" => self.download_pass,
                           "tenure_id" => %tenure_tip.consensus_hash,
-                          "block.header.block_id" => %block.header.block_id(),
+                          "block.header.block_id" => %block_id,
                           "tenure_tip.tenure_start_block_id" => %tenure_tip.tenure_start_block_id);
                     return Err(NetError::InvalidMessage);
                 }
 
                 if cnt.saturating_add(1) != tenure_blocks.len() {
                     warn!("Invalid tenure stream -- got tenure-start before end of tenure";
+                          "download_pass" => self.download_pass,
                           "tenure_id" => %tenure_tip.consensus_hash,
-                          "block.header.block_id" => %block.header.block_id(),
+                          "block.header.block_id" => %block_id,
                           "cnt" => cnt,
                           "len" => tenure_blocks.len(),
                           "state" => %self.state);
@@ -521,14 +649,18 @@ impl NakamotoUnconfirmedTenureDownloader {
                 break;
             }
 
-            debug!("Got unconfirmed tenure block {}", &block.header.block_id());
+            debug!("Got unconfirmed tenure block {}", &block_id;
+                "download_pass" => self.download_pass,
+            );
 
             // NOTE: this field can get updated by the downloader while this state-machine is in
             // this state.
             if let Some(highest_processed_block_id) = self.highest_processed_block_id.as_ref() {
                 if expected_block_id == highest_processed_block_id {
                     // got all the blocks we asked for
-                    debug!("Cancelling unconfirmed tenure download to {}: have processed block up to block {} already", &self.naddr, highest_processed_block_id);
+                    debug!("Cancelling unconfirmed tenure download to {}: have processed block up to block {} already", &self.naddr, highest_processed_block_id;
+                        "download_pass" => self.download_pass,
+                    );
                     finished_download = true;
                     last_block_index = Some(cnt);
                     break;
@@ -542,7 +674,9 @@ impl NakamotoUnconfirmedTenureDownloader {
             {
                 if &block.header.chain_length <= highest_processed_block_height {
                     // no need to continue this download
-                    debug!("Cancelling unconfirmed tenure download to {}: have processed block at height {} already", &self.naddr, highest_processed_block_height);
+                    debug!("Cancelling unconfirmed tenure download to {}: have processed block at height {} already", &self.naddr, highest_processed_block_height;
+                        "download_pass" => self.download_pass,
+                    );
                     finished_download = true;
                     last_block_index = Some(cnt);
                     break;
@@ -571,7 +705,9 @@ impl NakamotoUnconfirmedTenureDownloader {
             let highest_processed_block_height =
                 *self.highest_processed_block_height.as_ref().unwrap_or(&0);
 
-            debug!("Finished receiving unconfirmed tenure");
+            debug!("Finished receiving unconfirmed tenure";
+                "download_pass" => self.download_pass,
+            );
             return Ok(self.unconfirmed_tenure_blocks.take().map(|blocks| {
                 blocks
                     .into_iter()
@@ -583,21 +719,26 @@ impl NakamotoUnconfirmedTenureDownloader {
 
         let Some(blocks) = self.unconfirmed_tenure_blocks.as_ref() else {
             // unreachable but be defensive
-            warn!("Invalid state: no blocks (infallible -- got empty vec)");
+            warn!("Invalid state: no blocks (infallible -- got empty vec)";
+                "download_pass" => self.download_pass,
+            );
             return Err(NetError::InvalidState);
         };
 
         // still have more to get
         let Some(earliest_block) = blocks.last() else {
             // unreachable but be defensive
-            warn!("Invalid state: no blocks (infallible -- got empty vec)");
+            warn!("Invalid state: no blocks (infallible -- got empty vec)";
+                "download_pass" => self.download_pass,
+            );
             return Err(NetError::InvalidState);
         };
         let next_block_id = earliest_block.header.parent_block_id.clone();
 
         debug!(
             "Will resume fetching unconfirmed tenure blocks starting at {}",
-            &next_block_id
+            &next_block_id;
+            "download_pass" => self.download_pass,
         );
         self.state = NakamotoUnconfirmedDownloadState::GetUnconfirmedTenureBlocks(next_block_id);
         Ok(None)
@@ -670,6 +811,7 @@ impl NakamotoUnconfirmedTenureDownloader {
             || tip_tenure.epoch_id < StacksEpochId::Epoch30
         {
             debug!("Cannot make highest complete tenure: start and/or end block is not a Nakamoto block";
+                   "download_pass" => self.download_pass,
                    "start_tenure" => %tenure_tip.parent_consensus_hash,
                    "end_tenure" => %tenure_tip.consensus_hash,
                    "start_tenure_epoch" => %parent_tenure.epoch_id,
@@ -705,11 +847,12 @@ impl NakamotoUnconfirmedTenureDownloader {
 
         info!(
             "Create highest confirmed downloader from unconfirmed";
+            "download_pass" => self.download_pass,
             "confirmed_tenure" => %tenure_tip.parent_consensus_hash,
             "neighbor" => %self.naddr,
         );
 
-        let ntd = NakamotoTenureDownloader::new(
+        let mut ntd = NakamotoTenureDownloader::new(
             tenure_tip.parent_consensus_hash.clone(),
             tenure_tip.consensus_hash.clone(),
             tenure_tip.parent_tenure_start_block_id.clone(),
@@ -719,7 +862,9 @@ impl NakamotoUnconfirmedTenureDownloader {
             confirmed_signer_keys.clone(),
             unconfirmed_signer_keys.clone(),
             true,
-        );
+        )
+        .with_signature_cache(self.signature_cache.clone());
+        ntd.download_pass = self.download_pass;
 
         Ok(ntd)
     }
@@ -753,6 +898,10 @@ impl NakamotoUnconfirmedTenureDownloader {
                 // tenure downloader using the earliest unconfirmed tenure block.
                 return None;
             }
+            NakamotoUnconfirmedDownloadState::Aborted => {
+                // nothing more to fetch
+                return None;
+            }
         }
     }
 
@@ -786,7 +935,8 @@ impl NakamotoUnconfirmedTenureDownloader {
                     // TODO: look at the chainstate and find out what we don't have to download
                     break;
                 }
-                NakamotoUnconfirmedDownloadState::Done => {
+                NakamotoUnconfirmedDownloadState::Done
+                | NakamotoUnconfirmedDownloadState::Aborted => {
                     break;
                 }
             }
@@ -806,7 +956,9 @@ impl NakamotoUnconfirmedTenureDownloader {
         neighbor_rpc: &mut NeighborRPC,
     ) -> Result<(), NetError> {
         if neighbor_rpc.has_inflight(&self.naddr) {
-            debug!("Peer {} has an inflight request", &self.naddr);
+            debug!("Peer {} has an inflight request", &self.naddr;
+                "download_pass" => self.download_pass,
+            );
             return Ok(());
         }
         if neighbor_rpc.is_dead_or_broken(network, &self.naddr) {
@@ -843,7 +995,8 @@ impl NakamotoUnconfirmedTenureDownloader {
     ///
     /// Returns Ok(Some(blocks)) if we finished downloading the unconfirmed tenure
     /// Returns Ok(None) if we're still working, in which case the caller should call
-    /// `send_next_download_request()`
+    /// `send_next_download_request()`.  This includes the case where the tenure-info response
+    /// could not be decoded, but may be requested again.
     /// Returns Err(..) on unrecoverable failure to advance state
     pub fn handle_next_download_response(
         &mut self,
@@ -855,9 +1008,27 @@ impl NakamotoUnconfirmedTenureDownloader {
     ) -> Result<Option<Vec<NakamotoBlock>>, NetError> {
         match &self.state {
             NakamotoUnconfirmedDownloadState::GetTenureInfo => {
-                debug!("Got tenure-info response");
-                let remote_tenure_info = response.decode_nakamoto_tenure_info()?;
-                debug!("Got tenure-info response: {:?}", &remote_tenure_info);
+                debug!("Got tenure-info response";
+                    "download_pass" => self.download_pass,
+                );
+                let remote_tenure_info = match response.decode_nakamoto_tenure_info() {
+                    Ok(remote_tenure_info) => remote_tenure_info,
+                    Err(e) if self.tenure_info_retries < MAX_TENURE_INFO_RETRIES => {
+                        // this request is cheap, so ask again before giving up on the peer
+                        self.tenure_info_retries += 1;
+                        info!(
+                            "Failed to decode tenure-info response from {}; will retry", &self.naddr;
+                            "download_pass" => self.download_pass,
+                            "error" => ?e,
+                            "tenure_info_retries" => self.tenure_info_retries,
+                        );
+                        return Ok(None);
+                    }
+                    Err(e) => return Err(e),
+                };
+                debug!("Got tenure-info response: {:?}", &remote_tenure_info;
+                    "download_pass" => self.download_pass,
+                );
                 self.try_accept_tenure_info(
                     sortdb,
                     local_sort_tip,
@@ -868,19 +1039,26 @@ impl NakamotoUnconfirmedTenureDownloader {
                 Ok(None)
             }
             NakamotoUnconfirmedDownloadState::GetTenureStartBlock(..) => {
-                debug!("Got tenure start-block response");
+                debug!("Got tenure start-block response";
+                    "download_pass" => self.download_pass,
+                );
                 let block = response.decode_nakamoto_block()?;
                 self.try_accept_unconfirmed_tenure_start_block(block)?;
                 Ok(None)
             }
             NakamotoUnconfirmedDownloadState::GetUnconfirmedTenureBlocks(..) => {
-                debug!("Got unconfirmed tenure blocks response");
+                debug!("Got unconfirmed tenure blocks response";
+                    "download_pass" => self.download_pass,
+                );
                 let blocks = response.decode_nakamoto_tenure()?;
                 let accepted_opt = self.try_accept_unconfirmed_tenure_blocks(blocks)?;
-                debug!("Got unconfirmed tenure blocks"; "complete" => accepted_opt.is_some());
+                debug!("Got unconfirmed tenure blocks";
+                    "download_pass" => self.download_pass,
+                    "complete" => accepted_opt.is_some(),
+                );
                 Ok(accepted_opt)
             }
-            NakamotoUnconfirmedDownloadState::Done => {
+            NakamotoUnconfirmedDownloadState::Done | NakamotoUnconfirmedDownloadState::Aborted => {
                 return Err(NetError::InvalidState);
             }
         }
@@ -890,4 +1068,9 @@ impl NakamotoUnconfirmedTenureDownloader {
     pub fn is_done(&self) -> bool {
         self.state == NakamotoUnconfirmedDownloadState::Done
     }
+
+    /// Did this machine give up because its tenure was reorged out?
+    pub fn is_aborted(&self) -> bool {
+        self.state == NakamotoUnconfirmedDownloadState::Aborted
+    }
 }
@@ -16,10 +16,11 @@
 
 use std::io::Read;
 
+use libflate::gzip;
 use stacks_common::codec::{read_next, StacksMessageCodec};
 use stacks_common::types::net::PeerHost;
 
-use crate::net::http::{Error, HttpContentType, HttpResponsePreamble};
+use crate::net::http::{Error, HttpContentType, HttpRequestPreamble, HttpResponsePreamble};
 
 /// HTTP version (1.0 or 1.1)
 #[derive(Debug, Clone, PartialEq, Copy, Hash)]
@@ -106,7 +107,51 @@ pub fn parse_json<T: serde::de::DeserializeOwned>(
     })
 }
 
-/// Helper function to read a raw bytestream
+/// Does the request advertise that the client will accept a gzip-encoded response body?
+pub fn accepts_gzip(preamble: &HttpRequestPreamble) -> bool {
+    let Some(accept_encoding) = preamble.get_header("accept-encoding".into()) else {
+        return false;
+    };
+    accept_encoding.split(',').any(|coding| {
+        let mut params = coding.split(';');
+        let name = params.next().unwrap_or("").trim();
+        if !name.eq_ignore_ascii_case("gzip") {
+            return false;
+        }
+        // `gzip;q=0` means the client does *not* accept gzip
+        !params.any(|param| {
+            param
+                .trim()
+                .strip_prefix("q=")
+                .and_then(|q| q.trim().parse::<f64>().ok())
+                .map(|q| q <= 0.0)
+                .unwrap_or(false)
+        })
+    })
+}
+
+/// Is the response body gzip-encoded?
+pub fn is_gzip_encoded(preamble: &HttpResponsePreamble) -> bool {
+    preamble
+        .get_header("content-encoding".into())
+        .is_some_and(|encoding| encoding.trim().eq_ignore_ascii_case("gzip"))
+}
+
+/// Helper function to decompress a gzip-encoded body.  At most `max_len` bytes of decompressed
+/// data will be returned.
+pub fn gunzip_bytes(body: &[u8], max_len: u64) -> Result<Vec<u8>, Error> {
+    let decoder = gzip::Decoder::new(body)
+        .map_err(|e| Error::DecodeError(format!("Invalid gzip header: {:?}", &e)))?;
+    let mut out_bytes = vec![];
+    decoder
+        .take(max_len)
+        .read_to_end(&mut out_bytes)
+        .map_err(|e| Error::DecodeError(format!("Failed to decompress gzip body: {:?}", &e)))?;
+    Ok(out_bytes)
+}
+
+/// Helper function to read a raw bytestream.
+/// If the body is gzip-encoded, then it is decompressed first.
 pub fn parse_raw_bytes(
     preamble: &HttpResponsePreamble,
     body: &[u8],
@@ -119,6 +164,9 @@ pub fn parse_raw_bytes(
             expected_content_type
         )));
     }
+    if is_gzip_encoded(preamble) {
+        return gunzip_bytes(body, max_len);
+    }
     let out_len = usize::try_from(max_len).unwrap().min(body.len());
     let out_bytes = body
         .get(..out_len)
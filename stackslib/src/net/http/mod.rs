@@ -32,7 +32,7 @@ use serde_json;
 use stacks_common::codec::Error as CodecError;
 
 pub use crate::net::http::common::{
-    parse_bytes, parse_bytestream, parse_json, HttpReservedHeader, HttpVersion,
+    accepts_gzip, parse_bytes, parse_bytestream, parse_json, HttpReservedHeader, HttpVersion,
     HTTP_PREAMBLE_MAX_NUM_HEADERS,
 };
 pub use crate::net::http::error::{
@@ -46,7 +46,7 @@ pub use crate::net::http::request::{
 pub use crate::net::http::response::{
     HttpResponse, HttpResponseContents, HttpResponsePayload, HttpResponsePreamble,
 };
-pub use crate::net::http::stream::HttpChunkGenerator;
+pub use crate::net::http::stream::{GzipChunkGenerator, HttpChunkGenerator};
 
 #[derive(Debug)]
 pub enum Error {
@@ -17,6 +17,7 @@
 use std::io;
 use std::io::Write;
 
+use libflate::gzip;
 use stacks_common::util::chunked_encoding::{
     HttpChunkedTransferWriter, HttpChunkedTransferWriterState,
 };
@@ -57,6 +58,61 @@ pub trait HttpChunkGenerator: Send {
     }
 }
 
+/// Chunk generator which gzip-compresses the chunks of another chunk generator.  The
+/// concatenation of its chunks is a single gzip stream.
+pub struct GzipChunkGenerator {
+    /// generator of the uncompressed data
+    inner: Box<dyn HttpChunkGenerator>,
+    /// compressor, which writes to an in-RAM buffer.  Will be None once the stream is finished.
+    encoder: Option<gzip::Encoder<Vec<u8>>>,
+}
+
+impl GzipChunkGenerator {
+    pub fn new(inner: Box<dyn HttpChunkGenerator>) -> Self {
+        let encoder =
+            gzip::Encoder::new(vec![]).expect("FATAL: failed to write gzip header to RAM");
+        Self {
+            inner,
+            encoder: Some(encoder),
+        }
+    }
+}
+
+impl HttpChunkGenerator for GzipChunkGenerator {
+    fn hint_chunk_size(&self) -> usize {
+        self.inner.hint_chunk_size()
+    }
+
+    /// Pull chunks from the inner generator until the compressor has some output to send.
+    /// Returns an empty chunk once the gzip stream has been finished.
+    fn generate_next_chunk(&mut self) -> Result<Vec<u8>, String> {
+        loop {
+            let Some(encoder) = self.encoder.as_mut() else {
+                return Ok(vec![]);
+            };
+            let chunk = self.inner.generate_next_chunk()?;
+            if chunk.is_empty() {
+                // no more data, so flush what's left along with the gzip trailer
+                let Some(encoder) = self.encoder.take() else {
+                    return Ok(vec![]);
+                };
+                return encoder
+                    .finish()
+                    .into_result()
+                    .map_err(|e| format!("Failed to finish gzip stream: {:?}", &e));
+            }
+            encoder
+                .write_all(&chunk)
+                .map_err(|e| format!("Failed to gzip chunk: {:?}", &e))?;
+
+            let compressed = std::mem::take(encoder.as_inner_mut());
+            if !compressed.is_empty() {
+                return Ok(compressed);
+            }
+        }
+    }
+}
+
 /// Interface for streaming data
 pub trait Streamer {
     /// Return the offset into the stream at which this Streamer points.  This value is equivalent
@@ -454,6 +454,7 @@ fn test_http_parse_host_header_value() {
         "1.2.3.4:5678",
         "[1:203:405:607:809:a0b:c0d:e0f]",
         "[1:203:405:607:809:a0b:c0d:e0f]:12345",
+        "1:203:405:607:809:a0b:c0d:e0f",
         "www.foo.com",
         "www.foo.com:12345",
         // invalid IP addresses will be parsed to DNS names
@@ -486,6 +487,10 @@ fn test_http_parse_host_header_value() {
             PeerAddress([0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15]),
             12345,
         )),
+        Some(PeerHost::IP(
+            PeerAddress([0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15]),
+            80,
+        )),
         Some(PeerHost::DNS("www.foo.com".to_string(), 80)),
         Some(PeerHost::DNS("www.foo.com".to_string(), 12345)),
         Some(PeerHost::DNS("1.2.3.4.5".to_string(), 80)),
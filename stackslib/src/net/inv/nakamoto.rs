@@ -463,6 +463,9 @@ pub struct NakamotoTenureInv {
     pub tenures_inv: BTreeMap<u64, BitVec<2100>>,
     /// Time of last update, in seconds
     pub last_updated_at: u64,
+    /// Number of times `tenures_inv` has changed.  Consumers can compare this against the value
+    /// they last saw to cheaply tell whether this inventory has changed.
+    pub generation: u64,
     /// Burn block height of first sortition
     pub first_block_height: u64,
     /// Length of reward cycle
@@ -490,6 +493,7 @@ impl NakamotoTenureInv {
         Self {
             tenures_inv: BTreeMap::new(),
             last_updated_at: 0,
+            generation: 0,
             first_block_height,
             reward_cycle_len,
             neighbor_address,
@@ -556,6 +560,9 @@ impl NakamotoTenureInv {
 
         self.tenures_inv.insert(reward_cycle, tenure_inv);
         self.last_updated_at = get_epoch_time_secs();
+        if learned {
+            self.generation = self.generation.wrapping_add(1);
+        }
         learned
     }
 
@@ -254,6 +254,8 @@ pub enum Error {
     WaitingForDNS,
     /// No reward set for given reward cycle
     NoPoXRewardSet(u64),
+    /// Peer sent the same block more than once in a single response
+    DuplicateBlock(StacksBlockId),
 }
 
 impl From<libstackerdb_error> for Error {
@@ -405,6 +407,9 @@ impl fmt::Display for Error {
             Error::InvalidState => write!(f, "Invalid state-machine state reached"),
             Error::WaitingForDNS => write!(f, "Waiting for DNS resolution"),
             Error::NoPoXRewardSet(rc) => write!(f, "No PoX reward set for cycle {}", rc),
+            Error::DuplicateBlock(ref block_id) => {
+                write!(f, "Duplicate block {} in response", block_id)
+            }
         }
     }
 }
@@ -479,6 +484,7 @@ impl error::Error for Error {
             Error::InvalidState => None,
             Error::WaitingForDNS => None,
             Error::NoPoXRewardSet(..) => None,
+            Error::DuplicateBlock(..) => None,
         }
     }
 }
@@ -33,6 +33,17 @@ pub struct NeighborRPC {
     state: HashMap<NeighborAddress, (usize, Option<StacksHttpRequest>)>,
     dead: HashSet<DropNeighbor>,
     broken: HashSet<DropNeighbor>,
+    /// The HTTP conversation (by event ID) last used to talk to each neighbor, so successive
+    /// requests to the same neighbor can reuse its keep-alive connection
+    connections: HashMap<NeighborAddress, usize>,
+    /// Number of requests for which we opened a new HTTP connection
+    num_connects: u64,
+    /// Number of requests which were sent on an already-open HTTP connection
+    num_reused_connections: u64,
+    /// Canned replies to in-flight requests, which are handed back instead of polling the
+    /// network.  `None` means that the reply has not arrived yet.
+    #[cfg(test)]
+    test_replies: HashMap<NeighborAddress, Option<StacksHttpResponse>>,
 }
 
 impl NeighborRPC {
@@ -41,9 +52,27 @@ impl NeighborRPC {
             state: HashMap::new(),
             dead: HashSet::new(),
             broken: HashSet::new(),
+            connections: HashMap::new(),
+            num_connects: 0,
+            num_reused_connections: 0,
+            #[cfg(test)]
+            test_replies: HashMap::new(),
         }
     }
 
+    /// Mark a request to `naddr` as in-flight without sending it.  If `reply` is given, then it
+    /// will be collected as the neighbor's reply; otherwise, the request remains in-flight until
+    /// this method is called again with a reply.
+    #[cfg(test)]
+    pub(crate) fn add_test_request(
+        &mut self,
+        naddr: NeighborAddress,
+        reply: Option<StacksHttpResponse>,
+    ) {
+        self.state.insert(naddr.clone(), (0, None));
+        self.test_replies.insert(naddr, reply);
+    }
+
     /// Add a dead neighbor -- a neighbor which failed to communicate with us.
     pub fn add_dead(
         &mut self,
@@ -121,11 +150,37 @@ impl NeighborRPC {
     pub fn collect_replies(
         &mut self,
         network: &mut PeerNetwork,
+    ) -> Vec<(NeighborAddress, StacksHttpResponse)> {
+        self.collect_replies_from(network, |_| true)
+    }
+
+    /// Collect the in-flight replies from the neighbors selected by `filter` into a vec.
+    /// Requests to all other neighbors are left untouched, so another client of this struct can
+    /// collect them.
+    pub fn collect_replies_from(
+        &mut self,
+        network: &mut PeerNetwork,
+        filter: impl Fn(&NeighborAddress) -> bool,
     ) -> Vec<(NeighborAddress, StacksHttpResponse)> {
         let mut inflight = HashMap::new();
         let mut dead = vec![];
         let mut ret = vec![];
         for (naddr, (event_id, mut request_opt)) in self.state.drain() {
+            if !filter(&naddr) {
+                inflight.insert(naddr, (event_id, request_opt));
+                continue;
+            }
+            #[cfg(test)]
+            if let Some(reply) = self.test_replies.remove(&naddr) {
+                match reply {
+                    Some(response) => ret.push((naddr, response)),
+                    None => {
+                        self.test_replies.insert(naddr.clone(), None);
+                        inflight.insert(naddr, (event_id, request_opt));
+                    }
+                }
+                continue;
+            }
             let response =
                 match NeighborRPC::poll_next_reply(network, &naddr, event_id, &mut request_opt) {
                     Ok(Some(response)) => response,
@@ -163,6 +218,7 @@ impl NeighborRPC {
             ret.push((naddr, response));
         }
         for (naddr, reason) in dead.into_iter() {
+            self.connections.remove(&naddr);
             self.add_dead(network, &naddr, reason, DropSource::NeighborRPC);
         }
         self.state.extend(inflight);
@@ -179,6 +235,16 @@ impl NeighborRPC {
         self.state.contains_key(naddr)
     }
 
+    /// How many requests have required opening a new HTTP connection?
+    pub fn num_connects(&self) -> u64 {
+        self.num_connects
+    }
+
+    /// How many requests have been sent on an already-open HTTP connection?
+    pub fn num_reused_connections(&self) -> u64 {
+        self.num_reused_connections
+    }
+
     /// Find the PeerHost to use when creating a Stacks HTTP request.
     /// Returns Some(host) if we're connected and authenticated to this peer
     /// Returns None otherwise.
@@ -190,6 +256,8 @@ impl NeighborRPC {
 
     /// Send an HTTP request to the given neighbor's HTTP endpoint.
     /// The peer must already be connected and authenticated via the p2p network.
+    /// The request is sent over an idle keep-alive HTTP connection to the peer if there is one,
+    /// preferring the one we last used for this neighbor.  Otherwise, a new connection is opened.
     /// Returns Ok(()) if we successfully queue the request.
     /// Returns Err(..) if we fail to connect to the remote peer for some reason.
     pub fn send_request(
@@ -227,12 +295,19 @@ impl NeighborRPC {
             )));
         };
 
-        let event_id =
+        let last_event_id = self.connections.get(&naddr).copied();
+        let (event_id, reused) =
             PeerNetwork::with_network_state(network, |ref mut network, ref mut network_state| {
                 PeerNetwork::with_http(network, |ref mut network, ref mut http| {
+                    // prefer the keep-alive connection we last used with this neighbor
+                    if let Some(event_id) = last_event_id {
+                        if http.is_free_conversation(event_id, &data_url) {
+                            return Ok((event_id, true));
+                        }
+                    }
                     match http.connect_http(network_state, network, data_url, data_addr, None) {
-                        Ok(event_id) => Ok(event_id),
-                        Err(NetError::AlreadyConnected(event_id, _)) => Ok(event_id),
+                        Ok(event_id) => Ok((event_id, false)),
+                        Err(NetError::AlreadyConnected(event_id, _)) => Ok((event_id, true)),
                         Err(e) => {
                             return Err(e);
                         }
@@ -240,10 +315,17 @@ impl NeighborRPC {
                 })
             })?;
 
+        if reused {
+            self.num_reused_connections = self.num_reused_connections.saturating_add(1);
+        } else {
+            self.num_connects = self.num_connects.saturating_add(1);
+        }
+
         debug!(
-            "Send request to {} on event {}: {:?}",
-            &naddr, event_id, &request
+            "Send request to {} on event {} (reused connection: {}): {:?}",
+            &naddr, event_id, reused, &request
         );
+        self.connections.insert(naddr.clone(), event_id);
         self.state.insert(naddr, (event_id, Some(request)));
         Ok(())
     }
@@ -91,6 +91,15 @@ impl HttpPeer {
         None
     }
 
+    /// Is the HTTP conversation with the given event ID open to this data_url and not in
+    /// progress?
+    pub fn is_free_conversation(&self, event_id: usize, data_url: &UrlString) -> bool {
+        self.peers
+            .get(&event_id)
+            .map(|convo| convo.get_url() == Some(data_url) && !convo.is_request_inflight())
+            .unwrap_or(false)
+    }
+
     /// Get a mut ref to a conversation
     #[cfg_attr(test, mutants::skip)]
     pub fn get_conversation(&mut self, event_id: usize) -> Option<&mut ConversationHttp> {
@@ -14,26 +14,39 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use std::collections::{HashMap, VecDeque};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use std::sync::mpsc::sync_channel;
+use std::sync::{Arc, Mutex};
 use std::thread;
+use std::time::Duration;
 
+use rand::{thread_rng, Rng};
+use rusqlite::params;
 use stacks_common::bitvec::BitVec;
+use stacks_common::codec::StacksMessageCodec;
 use stacks_common::types::chainstate::{
-    ConsensusHash, StacksAddress, StacksBlockId, StacksPrivateKey, TrieHash,
+    BlockHeaderHash, BurnchainHeaderHash, ConsensusHash, StacksAddress, StacksBlockId,
+    StacksPrivateKey, TrieHash,
 };
-use stacks_common::types::net::PeerAddress;
-use stacks_common::util::hash::{hex_bytes, Sha512Trunc256Sum};
+use stacks_common::types::net::{PeerAddress, PeerHost};
+use stacks_common::types::StacksEpochId;
+use stacks_common::util::hash::{hex_bytes, to_hex, Sha512Trunc256Sum};
 use stacks_common::util::secp256k1::MessageSignature;
 use stacks_common::util::vrf::VRFProof;
+use stacks_common::util::{get_epoch_time_ms, get_epoch_time_secs};
 
-use crate::burnchains::PoxConstants;
+use crate::burnchains::{PoxConstants, Txid};
+use crate::chainstate::burn::db::sortdb::tests::test_append_snapshot;
 use crate::chainstate::burn::db::sortdb::SortitionHandle;
 use crate::chainstate::burn::BlockSnapshot;
+use crate::chainstate::coordinator::{PoxAnchorBlockStatus, RewardCycleInfo};
+use crate::chainstate::nakamoto::staging_blocks::NakamotoBlockObtainMethod;
 use crate::chainstate::nakamoto::test_signers::TestSigners;
 use crate::chainstate::nakamoto::{
     NakamotoBlock, NakamotoBlockHeader, NakamotoChainState, NakamotoStagingBlocksConnRef,
 };
+use crate::chainstate::stacks::db::test::instantiate_chainstate;
+use crate::chainstate::stacks::db::StacksChainState;
 use crate::chainstate::stacks::{
     CoinbasePayload, Error as ChainstateError, StacksTransaction, TenureChangeCause,
     TenureChangePayload, TokenTransferMemo, TransactionAnchorMode, TransactionAuth,
@@ -41,9 +54,15 @@ use crate::chainstate::stacks::{
 };
 use crate::clarity::vm::types::StacksAddressExtensions;
 use crate::core::test_util::to_addr;
+use crate::core::{StacksEpoch, StacksEpochExtension};
 use crate::net::api::gettenureinfo::RPCGetTenureInfo;
+use crate::net::connection::ConnectionOptions;
 use crate::net::download::nakamoto::{TenureStartEnd, WantedTenure, *};
+use crate::net::http::{HttpContentType, HttpResponsePayload, HttpResponsePreamble, HttpVersion};
+use crate::net::httpcore::{StacksHttpRequest, StacksHttpResponse};
 use crate::net::inv::nakamoto::NakamotoTenureInv;
+use crate::net::neighbors::rpc::NeighborRPC;
+use crate::net::p2p::{CurrentRewardSet, PeerNetwork};
 use crate::net::test::{dns_thread_start, TestEventObserver};
 use crate::net::tests::inv::nakamoto::{
     make_nakamoto_peer_from_invs, make_nakamoto_peers_from_invs_ext, peer_get_nakamoto_invs,
@@ -341,6 +360,7 @@ fn test_nakamoto_tenure_downloader() {
     assert_eq!(td.tenure_length(), Some(11));
 
     let mut td_one_shot = td.clone();
+    let mut td_duplicate = td.clone();
 
     // advance state, one block at a time
     for block in blocks.iter().rev() {
@@ -384,34 +404,285 @@ fn test_nakamoto_tenure_downloader() {
     assert_eq!(res.unwrap().unwrap(), all_blocks);
     assert_eq!(td_one_shot.state, NakamotoTenureDownloadState::Done);
 
+    // a response which repeats a block is rejected as such, and doesn't move the tail pointer
+    let mut duplicate_blocks: Vec<_> = blocks.clone().into_iter().rev().collect();
+    let duplicate_block = duplicate_blocks[0].clone();
+    duplicate_blocks.insert(1, duplicate_block.clone());
+    match td_duplicate.try_accept_tenure_blocks(duplicate_blocks) {
+        Err(NetError::DuplicateBlock(block_id)) => {
+            assert_eq!(block_id, duplicate_block.header.block_id())
+        }
+        res => panic!("Expected a duplicate block error, but got {res:?}"),
+    }
+    assert_eq!(
+        td_duplicate.state,
+        NakamotoTenureDownloadState::GetTenureBlocks(
+            next_tenure_start_block.header.parent_block_id.clone(),
+            td_duplicate.state.request_time().unwrap(),
+        )
+    );
+
     // TODO:
     // * bad signature
     // * too many blocks
 }
 
 #[test]
-fn test_nakamoto_unconfirmed_tenure_downloader() {
-    let observer = TestEventObserver::new();
-    let bitvecs = vec![vec![
-        true, true, true, true, true, true, true, true, true, true,
-    ]];
+fn test_nakamoto_download_state_serde() {
+    for state in [
+        NakamotoDownloadState::Confirmed,
+        NakamotoDownloadState::Unconfirmed,
+    ] {
+        let json = serde_json::to_string(&state).unwrap();
+        assert_eq!(json, format!("\"{}\"", &state));
+        let decoded: NakamotoDownloadState = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, state);
+    }
+}
 
-    let rc_len = 10u64;
-    let peer = make_nakamoto_peer_from_invs(function_name!(), &observer, rc_len as u32, 3, bitvecs);
-    let (mut peer, reward_cycle_invs) =
-        peer_get_nakamoto_invs(peer, &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12]);
-    peer.chain.mine_malleablized_blocks = false;
+/// The inventory-derived view of which tenures each neighbor can serve survives a round-trip
+/// through JSON.
+#[test]
+fn test_nakamoto_download_tenure_block_ids_serde() {
+    let wanted_tenure =
+        WantedTenure::new(ConsensusHash([0x01; 20]), StacksBlockId([0x02; 32]), 123);
+    let json = serde_json::to_string(&wanted_tenure).unwrap();
+    let decoded: WantedTenure = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded, wanted_tenure);
+
+    let mut downloader =
+        NakamotoDownloadStateMachine::with_rpc(0, StacksBlockId([0x00; 32]), NeighborRPC::new());
+    for i in 0..3u8 {
+        let naddr = NeighborAddress {
+            addrbytes: PeerAddress([i; 16]),
+            port: 123 + u16::from(i),
+            public_key_hash: Hash160([i; 20]),
+        };
+        let available: AvailableTenures = (0..4u8)
+            .map(|j| {
+                let mut tse = TenureStartEnd::new(
+                    ConsensusHash([j; 20]),
+                    100 + u64::from(j),
+                    ConsensusHash([j; 20]),
+                    StacksBlockId([j; 32]),
+                    ConsensusHash([j + 1; 20]),
+                    StacksBlockId([j + 1; 32]),
+                    u64::from(j / 2),
+                    u64::from((j + 1) / 2),
+                    j % 2 == 0,
+                );
+                tse.fetch_end_block = i == j;
+                (ConsensusHash([j; 20]), tse)
+            })
+            .collect();
+        downloader.tenure_block_ids.insert(naddr, available);
+    }
 
-    let nakamoto_start = NakamotoBootPlan::nakamoto_first_tenure_height(
-        &peer.config.chain_config.burnchain.pox_constants,
+    let json = downloader.export_tenure_block_ids().unwrap();
+
+    // exports are deterministic
+    assert_eq!(downloader.export_tenure_block_ids().unwrap(), json);
+
+    let mut imported =
+        NakamotoDownloadStateMachine::with_rpc(0, StacksBlockId([0x00; 32]), NeighborRPC::new());
+    imported.import_tenure_block_ids(&json).unwrap();
+    assert_eq!(imported.tenure_block_ids, downloader.tenure_block_ids);
+
+    assert!(imported.import_tenure_block_ids("not json").is_err());
+    assert_eq!(imported.tenure_block_ids, downloader.tenure_block_ids);
+}
+
+/// Make a successful HTTP response carrying a Nakamoto block
+fn make_nakamoto_block_response(block: &NakamotoBlock) -> StacksHttpResponse {
+    StacksHttpResponse::new(
+        HttpResponsePreamble::new(
+            HttpVersion::Http11,
+            200,
+            "OK".into(),
+            None,
+            HttpContentType::Bytes,
+            true,
+        ),
+        HttpResponsePayload::Bytes(block.serialize_to_vec()),
+    )
+}
+
+fn make_nakamoto_tenure_response(blocks: &[NakamotoBlock]) -> StacksHttpResponse {
+    StacksHttpResponse::new(
+        HttpResponsePreamble::new(
+            HttpVersion::Http11,
+            200,
+            "OK".into(),
+            None,
+            HttpContentType::Bytes,
+            true,
+        ),
+        HttpResponsePayload::Bytes(blocks.iter().flat_map(|b| b.serialize_to_vec()).collect()),
+    )
+}
+
+/// Make a tenure consisting of a single signed tenure-start block.  Returns the tenure-start
+/// block, the next tenure's start block (which ends this tenure), and a variant of the latter
+/// whose tenure-change claims that this tenure has no blocks.
+fn make_one_block_tenure(
+    test_signers: &mut TestSigners,
+) -> (NakamotoBlock, NakamotoBlock, NakamotoBlock) {
+    let (mut blocks, next_tenure_start_block, empty_tenure_end_block) =
+        make_tenure(test_signers, 1);
+    (
+        blocks.remove(0),
+        next_tenure_start_block,
+        empty_tenure_end_block,
+    )
+}
+
+/// Make a tenure of `num_blocks` signed blocks, starting with its tenure-start block.  Returns the
+/// tenure's blocks in ascending order, the next tenure's start block (which ends this tenure), and
+/// a variant of the latter whose tenure-change claims that this tenure has no blocks.  Each
+/// block's tx Merkle root matches its transactions.
+fn make_tenure(
+    test_signers: &mut TestSigners,
+    num_blocks: u32,
+) -> (Vec<NakamotoBlock>, NakamotoBlock, NakamotoBlock) {
+    let private_key = StacksPrivateKey::random();
+
+    let proof_bytes = hex_bytes("9275df67a68c8745c0ff97b48201ee6db447f7c93b23ae24cdc2400f52fdb08a1a6ac7ec71bf9c9c76e96ee4675ebff60625af28718501047bfd87b810c2d2139b73c23bd69de66360953a642c2a330a").unwrap();
+    let proof = VRFProof::from_bytes(&proof_bytes[..]).unwrap();
+    let mut coinbase_tx = StacksTransaction::new(
+        TransactionVersion::Testnet,
+        TransactionAuth::from_p2pkh(&private_key).unwrap(),
+        TransactionPayload::Coinbase(CoinbasePayload([0x12; 32]), None, Some(proof)),
     );
+    coinbase_tx.chain_id = 0x80000000;
+    coinbase_tx.anchor_mode = TransactionAnchorMode::OnChainOnly;
 
-    let all_sortitions = peer.sortdb().get_all_snapshots().unwrap();
-    let tip = SortitionDB::get_canonical_burn_chain_tip(peer.sortdb().conn()).unwrap();
+    let make_tenure_change_tx = |payload: TenureChangePayload| {
+        let mut tenure_change_tx = StacksTransaction::new(
+            TransactionVersion::Testnet,
+            TransactionAuth::from_p2pkh(&private_key).unwrap(),
+            TransactionPayload::TenureChange(payload),
+        );
+        tenure_change_tx.chain_id = 0x80000000;
+        tenure_change_tx.anchor_mode = TransactionAnchorMode::OnChainOnly;
+        tenure_change_tx
+    };
 
-    assert_eq!(tip.block_height, 51);
+    let tenure_start_header = NakamotoBlockHeader {
+        version: 1,
+        chain_length: 2,
+        burn_spent: 3,
+        consensus_hash: ConsensusHash([0x04; 20]),
+        parent_block_id: StacksBlockId([0x05; 32]),
+        tx_merkle_root: Sha512Trunc256Sum([0x06; 32]),
+        state_index_root: TrieHash([0x07; 32]),
+        timestamp: 8,
+        miner_signature: MessageSignature::empty(),
+        signer_signature: vec![],
+        pox_treatment: BitVec::zeros(1).unwrap(),
+    };
+    let tenure_change_tx = make_tenure_change_tx(TenureChangePayload {
+        tenure_consensus_hash: ConsensusHash([0x04; 20]),
+        prev_tenure_consensus_hash: ConsensusHash([0x03; 20]),
+        burn_view_consensus_hash: ConsensusHash([0x04; 20]),
+        previous_tenure_end: tenure_start_header.parent_block_id.clone(),
+        previous_tenure_blocks: 1,
+        cause: TenureChangeCause::BlockFound,
+        pubkey_hash: Hash160([0x02; 20]),
+    });
+    let mut tenure_start_block = NakamotoBlock {
+        header: tenure_start_header,
+        txs: vec![tenure_change_tx, coinbase_tx.clone()],
+    };
+    tenure_start_block.header.tx_merkle_root = tenure_start_block.compute_tx_merkle_root();
+    test_signers.sign_nakamoto_block(&mut tenure_start_block, 0);
 
-    let test_signers = TestSigners::new(vec![]);
+    let recipient_addr =
+        StacksAddress::from_string("ST2YM3J4KQK09V670TD6ZZ1XYNYCNGCWCVTASN5VM").unwrap();
+    let mut stx_transfer = StacksTransaction::new(
+        TransactionVersion::Testnet,
+        TransactionAuth::from_p2pkh(&private_key).unwrap(),
+        TransactionPayload::TokenTransfer(
+            recipient_addr.to_account_principal(),
+            1,
+            TokenTransferMemo([0x00; 34]),
+        ),
+    );
+    stx_transfer.chain_id = 0x80000000;
+    stx_transfer.anchor_mode = TransactionAnchorMode::OnChainOnly;
+
+    let mut blocks = vec![tenure_start_block];
+    for _ in 1..num_blocks {
+        let last_block = blocks.last().unwrap();
+        let header = NakamotoBlockHeader {
+            version: 1,
+            chain_length: last_block.header.chain_length + 1,
+            burn_spent: last_block.header.burn_spent + 1,
+            consensus_hash: last_block.header.consensus_hash.clone(),
+            parent_block_id: last_block.header.block_id(),
+            tx_merkle_root: Sha512Trunc256Sum([0x06; 32]),
+            state_index_root: TrieHash([0x07; 32]),
+            timestamp: 8,
+            miner_signature: MessageSignature::empty(),
+            signer_signature: vec![],
+            pox_treatment: BitVec::zeros(1).unwrap(),
+        };
+        let mut block = NakamotoBlock {
+            header,
+            txs: vec![stx_transfer.clone()],
+        };
+        block.header.tx_merkle_root = block.compute_tx_merkle_root();
+        test_signers.sign_nakamoto_block(&mut block, 0);
+        blocks.push(block);
+    }
+
+    // the next tenure builds directly atop the last block of this tenure
+    let last_block = blocks.last().unwrap();
+    let make_next_tenure_start_block = |previous_tenure_blocks: u32| {
+        let header = NakamotoBlockHeader {
+            version: 1,
+            chain_length: last_block.header.chain_length + 1,
+            burn_spent: last_block.header.burn_spent + 1,
+            consensus_hash: ConsensusHash([0x05; 20]),
+            parent_block_id: last_block.header.block_id(),
+            tx_merkle_root: Sha512Trunc256Sum([0x07; 32]),
+            state_index_root: TrieHash([0x08; 32]),
+            timestamp: 9,
+            miner_signature: MessageSignature::empty(),
+            signer_signature: vec![],
+            pox_treatment: BitVec::zeros(1).unwrap(),
+        };
+        let tenure_change_tx = make_tenure_change_tx(TenureChangePayload {
+            tenure_consensus_hash: ConsensusHash([0x05; 20]),
+            prev_tenure_consensus_hash: ConsensusHash([0x04; 20]),
+            burn_view_consensus_hash: ConsensusHash([0x05; 20]),
+            previous_tenure_end: header.parent_block_id.clone(),
+            previous_tenure_blocks,
+            cause: TenureChangeCause::BlockFound,
+            pubkey_hash: Hash160([0x02; 20]),
+        });
+        let mut block = NakamotoBlock {
+            header,
+            txs: vec![tenure_change_tx, coinbase_tx.clone()],
+        };
+        block.header.tx_merkle_root = block.compute_tx_merkle_root();
+        block
+    };
+    let mut next_tenure_start_block = make_next_tenure_start_block(num_blocks);
+    test_signers.sign_nakamoto_block(&mut next_tenure_start_block, 0);
+    let mut empty_tenure_end_block = make_next_tenure_start_block(0);
+    test_signers.sign_nakamoto_block(&mut empty_tenure_end_block, 0);
+    (blocks, next_tenure_start_block, empty_tenure_end_block)
+}
+
+/// A tenure which consists only of its tenure-start block completes as soon as the tenure-end
+/// block arrives, without requesting the tenure's blocks.  A tenure-end block which claims that
+/// the tenure is empty is rejected.
+#[test]
+fn test_nakamoto_tenure_downloader_single_block_tenure() {
+    let mut test_signers = TestSigners::new(vec![]);
+    let reward_set = test_signers.synthesize_reward_set();
+    let (tenure_start_block, next_tenure_start_block, empty_tenure_end_block) =
+        make_one_block_tenure(&mut test_signers);
 
     let naddr = NeighborAddress {
         addrbytes: PeerAddress([0xff; 16]),
@@ -419,897 +690,5623 @@ fn test_nakamoto_unconfirmed_tenure_downloader() {
         public_key_hash: Hash160([0xff; 20]),
     };
 
-    peer.refresh_burnchain_view();
-    let tip_block_id = peer.network.stacks_tip.block_id();
-
-    let tip_ch = peer.network.stacks_tip.consensus_hash.clone();
-    let parent_tip_ch = peer.network.parent_stacks_tip.consensus_hash.clone();
-    let current_reward_sets = peer.network.current_reward_sets.clone();
-
-    let last_block_in_confirmed_tenure = NakamotoChainState::get_highest_block_header_in_tenure(
-        &mut peer.chainstate().index_conn(),
-        &tip_block_id,
-        &parent_tip_ch,
-    )
-    .unwrap()
-    .unwrap();
+    let mut td = NakamotoTenureDownloader::new(
+        tenure_start_block.header.consensus_hash.clone(),
+        tenure_start_block.header.consensus_hash.clone(),
+        tenure_start_block.header.block_id(),
+        next_tenure_start_block.header.consensus_hash.clone(),
+        next_tenure_start_block.header.block_id(),
+        naddr.clone(),
+        reward_set.clone(),
+        reward_set.clone(),
+        false,
+    );
 
-    // NOTE: we have to account for malleablized blocks!
-    let unconfirmed_tenure = peer
-        .chainstate()
-        .nakamoto_blocks_db()
-        .get_all_blocks_in_tenure(&tip_ch, &tip_block_id)
-        .unwrap();
-    let last_confirmed_tenure = peer
-        .chainstate()
-        .nakamoto_blocks_db()
-        .get_all_blocks_in_tenure(
-            &parent_tip_ch,
-            &last_block_in_confirmed_tenure.index_block_hash(),
-        )
+    let res = td
+        .handle_next_download_response(make_nakamoto_block_response(&tenure_start_block))
         .unwrap();
+    assert!(res.is_none());
+    assert!(matches!(
+        td.state,
+        NakamotoTenureDownloadState::GetTenureEndBlock(..)
+    ));
 
-    let parent_parent_header = NakamotoChainState::get_block_header_nakamoto(
-        peer.chainstate().db(),
-        &last_confirmed_tenure
-            .first()
-            .as_ref()
-            .unwrap()
-            .header
-            .parent_block_id,
-    )
-    .unwrap()
-    .unwrap();
-    let parent_parent_start_header = NakamotoChainState::get_nakamoto_tenure_start_block_header(
-        &mut peer.chainstate().index_conn(),
-        &tip_block_id,
-        &parent_parent_header.consensus_hash,
-    )
-    .unwrap()
-    .unwrap();
-
-    assert!(!unconfirmed_tenure.is_empty());
-    assert!(!last_confirmed_tenure.is_empty());
-
-    assert_eq!(
-        unconfirmed_tenure.first().as_ref().unwrap().block_id(),
-        peer.network.tenure_start_block_id
-    );
+    // tenure completes on receipt of the tenure-end block
+    let res = td
+        .handle_next_download_response(make_nakamoto_block_response(&next_tenure_start_block))
+        .unwrap()
+        .unwrap();
     assert_eq!(
-        unconfirmed_tenure
-            .first()
-            .as_ref()
-            .unwrap()
-            .header
-            .parent_block_id,
-        last_confirmed_tenure.last().as_ref().unwrap().block_id()
+        res,
+        vec![tenure_start_block.clone(), next_tenure_start_block.clone()]
     );
+    assert!(td.is_done());
 
-    let tip_rc = peer
-        .network
-        .burnchain
-        .block_height_to_reward_cycle(peer.network.burnchain_tip.block_height)
-        .expect("FATAL: burnchain tip before system start");
+    // a tenure-end block cannot claim that the tenure has no blocks
+    let mut td = NakamotoTenureDownloader::new(
+        tenure_start_block.header.consensus_hash.clone(),
+        tenure_start_block.header.consensus_hash.clone(),
+        tenure_start_block.header.block_id(),
+        empty_tenure_end_block.header.consensus_hash.clone(),
+        empty_tenure_end_block.header.block_id(),
+        naddr,
+        reward_set.clone(),
+        reward_set,
+        false,
+    );
+    td.try_accept_tenure_start_block(tenure_start_block.clone())
+        .unwrap();
+    assert!(matches!(
+        td.try_accept_tenure_end_block(&empty_tenure_end_block),
+        Err(NetError::InvalidMessage)
+    ));
+    assert!(matches!(
+        td.state,
+        NakamotoTenureDownloadState::GetTenureEndBlock(..)
+    ));
+}
 
-    let highest_confirmed_wanted_tenure = WantedTenure {
-        tenure_id_consensus_hash: peer.network.parent_stacks_tip.consensus_hash.clone(),
-        winning_block_id: parent_parent_start_header.index_block_hash(),
-        processed: false,
-        burn_height: peer.network.burnchain_tip.block_height - 1,
+/// A tenure-end block whose tenure-change reports a different number of blocks than the
+/// tenure-start and tenure-end blocks' chain lengths imply is rejected, even if its signer
+/// signatures are valid.
+#[test]
+fn test_nakamoto_tenure_downloader_wrong_tenure_length() {
+    let mut test_signers = TestSigners::new(vec![]);
+    let reward_set = test_signers.synthesize_reward_set();
+    let (blocks, next_tenure_start_block, _) = make_tenure(&mut test_signers, 3);
+    let tenure_start_block = blocks[0].clone();
+
+    // same block ID and signatures, but the tenure-change claims a different tenure length
+    let with_tenure_length = |previous_tenure_blocks: u32| {
+        let mut block = next_tenure_start_block.clone();
+        let TransactionPayload::TenureChange(ref mut payload) = block.txs[0].payload else {
+            panic!("Tenure-end block does not start with a tenure-change");
+        };
+        payload.previous_tenure_blocks = previous_tenure_blocks;
+        assert_eq!(block.block_id(), next_tenure_start_block.block_id());
+        block
     };
 
-    let unconfirmed_wanted_tenure = WantedTenure {
-        tenure_id_consensus_hash: peer.network.stacks_tip.consensus_hash.clone(),
-        winning_block_id: last_confirmed_tenure
-            .first()
-            .as_ref()
-            .unwrap()
-            .header
-            .block_id(),
-        processed: false,
-        burn_height: peer.network.burnchain_tip.block_height,
+    let naddr = NeighborAddress {
+        addrbytes: PeerAddress([0xff; 16]),
+        port: 123,
+        public_key_hash: Hash160([0xff; 20]),
     };
 
-    // we can make unconfirmed tenure downloaders
+    let mut td = NakamotoTenureDownloader::new(
+        tenure_start_block.header.consensus_hash.clone(),
+        tenure_start_block.header.consensus_hash.clone(),
+        tenure_start_block.header.block_id(),
+        next_tenure_start_block.header.consensus_hash.clone(),
+        next_tenure_start_block.header.block_id(),
+        naddr,
+        reward_set.clone(),
+        reward_set,
+        false,
+    );
+    td.try_accept_tenure_start_block(tenure_start_block)
+        .unwrap();
+
+    // a truncated tenure is rejected, as is an overlong one
+    for bad_tenure_length in [1, 2, 4] {
+        assert!(matches!(
+            td.try_accept_tenure_end_block(&with_tenure_length(bad_tenure_length)),
+            Err(NetError::InvalidMessage)
+        ));
+        assert!(matches!(
+            td.state,
+            NakamotoTenureDownloadState::GetTenureEndBlock(..)
+        ));
+        assert!(td.tenure_length().is_none());
+    }
+
+    // the honest tenure-end block is accepted
+    td.try_accept_tenure_end_block(&next_tenure_start_block)
+        .unwrap();
+    assert_eq!(td.tenure_length(), Some(3));
+    assert!(matches!(
+        td.state,
+        NakamotoTenureDownloadState::GetTenureBlocks(..)
+    ));
+}
+
+/// A tenure block whose transactions don't match its header's tx Merkle root (and thus has the
+/// same block ID and signer signatures as the honest block) is only rejected by a downloader which
+/// has been told to check Merkle roots.
+#[test]
+fn test_nakamoto_tenure_downloader_tx_merkle_root_check() {
+    let mut test_signers = TestSigners::new(vec![]);
+    let reward_set = test_signers.synthesize_reward_set();
+    let (blocks, next_tenure_start_block, _) = make_tenure(&mut test_signers, 3);
+    let tenure_start_block = blocks[0].clone();
+
+    // same header, but a different transaction
+    let mut tampered_block = blocks[2].clone();
+    let TransactionPayload::TokenTransfer(_, ref mut amount, _) = tampered_block.txs[0].payload
+    else {
+        panic!("Tenure block does not contain a token transfer");
+    };
+    *amount += 1;
+    assert_eq!(tampered_block.block_id(), blocks[2].block_id());
+    assert_eq!(
+        blocks[2].compute_tx_merkle_root(),
+        blocks[2].header.tx_merkle_root
+    );
+    assert_ne!(
+        tampered_block.compute_tx_merkle_root(),
+        tampered_block.header.tx_merkle_root
+    );
+
+    let naddr = NeighborAddress {
+        addrbytes: PeerAddress([0xff; 16]),
+        port: 123,
+        public_key_hash: Hash160([0xff; 20]),
+    };
+
+    for verify_tx_merkle_roots in [false, true] {
+        let mut td = NakamotoTenureDownloader::new(
+            tenure_start_block.header.consensus_hash.clone(),
+            tenure_start_block.header.consensus_hash.clone(),
+            tenure_start_block.header.block_id(),
+            next_tenure_start_block.header.consensus_hash.clone(),
+            next_tenure_start_block.header.block_id(),
+            naddr.clone(),
+            reward_set.clone(),
+            reward_set.clone(),
+            false,
+        )
+        .with_tx_merkle_root_check(verify_tx_merkle_roots);
+        td.try_accept_tenure_start_block(tenure_start_block.clone())
+            .unwrap();
+        td.try_accept_tenure_end_block(&next_tenure_start_block)
+            .unwrap();
+
+        // the honest tenure is accepted either way
+        let mut td_honest = td.clone();
+        let res = td_honest
+            .try_accept_tenure_blocks(blocks.clone().into_iter().rev().collect())
+            .unwrap();
+        assert!(res.is_some());
+        assert_eq!(td_honest.state, NakamotoTenureDownloadState::Done);
+
+        let tampered_blocks = vec![tampered_block.clone(), blocks[1].clone(), blocks[0].clone()];
+        let res = td.try_accept_tenure_blocks(tampered_blocks);
+        if verify_tx_merkle_roots {
+            assert!(matches!(res, Err(NetError::InvalidMessage)));
+            assert!(matches!(
+                td.state,
+                NakamotoTenureDownloadState::GetTenureBlocks(..)
+            ));
+        } else {
+            assert!(res.unwrap().unwrap().contains(&tampered_block));
+            assert_eq!(td.state, NakamotoTenureDownloadState::Done);
+        }
+    }
+}
+
+/// A downloader which has accumulated some of its tenure's blocks can checkpoint its progress to
+/// the staging DB, and a new downloader for the tenure (e.g. after a restart) resumes from the
+/// checkpoint's cursor instead of starting over.
+#[test]
+fn test_nakamoto_tenure_downloader_checkpoint() {
+    let mut test_signers = TestSigners::new(vec![]);
+    let reward_set = test_signers.synthesize_reward_set();
+    let (blocks, next_tenure_start_block, _) = make_tenure(&mut test_signers, 6);
+    let tenure_start_block = blocks[0].clone();
+    let tenure_id = tenure_start_block.header.consensus_hash.clone();
+    let mut chainstate = instantiate_chainstate(false, 0x80000000, function_name!());
+
+    let naddr = NeighborAddress {
+        addrbytes: PeerAddress([0xff; 16]),
+        port: 123,
+        public_key_hash: Hash160([0xff; 20]),
+    };
+    let peerhost = PeerHost::from_host_port("127.0.0.1".to_string(), 20443);
+    let make_downloader = || {
+        NakamotoTenureDownloader::new(
+            tenure_id.clone(),
+            tenure_id.clone(),
+            tenure_start_block.header.block_id(),
+            next_tenure_start_block.header.consensus_hash.clone(),
+            next_tenure_start_block.header.block_id(),
+            naddr.clone(),
+            reward_set.clone(),
+            reward_set.clone(),
+            false,
+        )
+    };
+
+    // nothing to checkpoint or resume from yet
+    let mut td = make_downloader();
+    assert!(td.make_checkpoint().is_none());
+    assert!(!td.store_checkpoint(&mut chainstate).unwrap());
+    assert!(!make_downloader()
+        .try_resume_from_stored_checkpoint(&mut chainstate)
+        .unwrap());
+
+    // accumulate the tenure's three highest blocks
+    td.try_accept_tenure_start_block(tenure_start_block.clone())
+        .unwrap();
+    td.try_accept_tenure_end_block(&next_tenure_start_block)
+        .unwrap();
+    let res = td
+        .try_accept_tenure_blocks(vec![
+            blocks[5].clone(),
+            blocks[4].clone(),
+            blocks[3].clone(),
+        ])
+        .unwrap();
+    assert!(res.is_none());
+
+    assert!(td.store_checkpoint(&mut chainstate).unwrap());
+    let checkpoint = chainstate
+        .nakamoto_blocks_db()
+        .get_tenure_download_checkpoint(&tenure_id)
+        .unwrap()
+        .unwrap();
+    assert_eq!(Some(checkpoint.clone()), td.make_checkpoint());
+    assert_eq!(checkpoint.block_cursor(), &blocks[2].block_id());
+
+    // a new downloader resumes by requesting the parent of the lowest block obtained so far
+    let mut resumed_td = make_downloader();
+    assert!(resumed_td
+        .try_resume_from_stored_checkpoint(&mut chainstate)
+        .unwrap());
+    assert_eq!(resumed_td.snapshot(), td.snapshot());
+    let NakamotoTenureDownloadState::GetTenureBlocks(block_cursor, _) = &resumed_td.state else {
+        panic!("Resumed downloader is not fetching tenure blocks");
+    };
+    assert_eq!(block_cursor, &blocks[2].block_id());
+
+    let request = resumed_td
+        .make_next_download_request(peerhost.clone())
+        .unwrap()
+        .unwrap();
+    let expected_request =
+        StacksHttpRequest::new_get_nakamoto_tenure(peerhost, blocks[2].block_id(), None);
+    assert_eq!(request.preamble(), expected_request.preamble());
+
+    // ...and finishes the tenure with the same blocks as the original
+    let remaining_blocks = vec![blocks[2].clone(), blocks[1].clone(), blocks[0].clone()];
+    let tenure_blocks = td
+        .try_accept_tenure_blocks(remaining_blocks.clone())
+        .unwrap()
+        .unwrap();
+    let resumed_tenure_blocks = resumed_td
+        .try_accept_tenure_blocks(remaining_blocks)
+        .unwrap()
+        .unwrap();
+    assert_eq!(tenure_blocks, resumed_tenure_blocks);
+    assert_eq!(resumed_td.state, NakamotoTenureDownloadState::Done);
+
+    // a downloader which has already started does not resume
+    let mut started_td = make_downloader();
+    started_td
+        .try_accept_tenure_start_block(tenure_start_block.clone())
+        .unwrap();
+    assert!(!started_td
+        .try_resume_from_stored_checkpoint(&mut chainstate)
+        .unwrap());
+    assert!(matches!(
+        started_td.state,
+        NakamotoTenureDownloadState::GetTenureEndBlock(..)
+    ));
+
+    // a checkpoint whose blocks skip one is unusable, and gets discarded
+    let mut bad_checkpoint = checkpoint.clone();
+    bad_checkpoint.tenure_blocks.remove(2);
+    let staging_tx = chainstate.staging_db_tx_begin().unwrap();
+    staging_tx
+        .store_tenure_download_checkpoint(&bad_checkpoint)
+        .unwrap();
+    staging_tx.commit().unwrap();
+
+    let mut fresh_td = make_downloader();
+    assert!(!fresh_td
+        .try_resume_from_stored_checkpoint(&mut chainstate)
+        .unwrap());
+    assert_eq!(fresh_td.snapshot(), make_downloader().snapshot());
+    assert!(chainstate
+        .nakamoto_blocks_db()
+        .get_tenure_download_checkpoint(&tenure_id)
+        .unwrap()
+        .is_none());
+}
+
+/// A downloader only writes the tenure blocks it obtained since its last checkpoint, and writes
+/// nothing if it has obtained none.  Checkpoints of cancelled tenures and of processed tenures are
+/// deleted by the downloader set.
+#[test]
+fn test_nakamoto_tenure_downloader_incremental_checkpoint() {
+    let mut test_signers = TestSigners::new(vec![]);
+    let reward_set = test_signers.synthesize_reward_set();
+    let (blocks, next_tenure_start_block, _) = make_tenure(&mut test_signers, 6);
+    let tenure_start_block = blocks[0].clone();
+    let tenure_id = tenure_start_block.header.consensus_hash.clone();
+    let mut chainstate = instantiate_chainstate(false, 0x80000000, function_name!());
+
+    let naddr = NeighborAddress {
+        addrbytes: PeerAddress([0xff; 16]),
+        port: 123,
+        public_key_hash: Hash160([0xff; 20]),
+    };
+    let make_downloader = || {
+        NakamotoTenureDownloader::new(
+            tenure_id.clone(),
+            tenure_id.clone(),
+            tenure_start_block.header.block_id(),
+            next_tenure_start_block.header.consensus_hash.clone(),
+            next_tenure_start_block.header.block_id(),
+            naddr.clone(),
+            reward_set.clone(),
+            reward_set.clone(),
+            false,
+        )
+    };
+    let get_checkpoint = |chainstate: &StacksChainState| {
+        chainstate
+            .nakamoto_blocks_db()
+            .get_tenure_download_checkpoint(&tenure_id)
+            .unwrap()
+    };
+
+    let mut td = make_downloader();
+    td.try_accept_tenure_start_block(tenure_start_block.clone())
+        .unwrap();
+    td.try_accept_tenure_end_block(&next_tenure_start_block)
+        .unwrap();
+    assert!(td
+        .try_accept_tenure_blocks(vec![blocks[5].clone(), blocks[4].clone()])
+        .unwrap()
+        .is_none());
+    assert!(td.store_checkpoint(&mut chainstate).unwrap());
+    assert_eq!(td.checkpoint_num_blocks, 3);
+
+    // nothing new to write, e.g. after a truncated response
+    assert!(!td.store_checkpoint(&mut chainstate).unwrap());
+    assert_eq!(get_checkpoint(&chainstate), td.make_checkpoint());
+
+    // only the new block is written
+    assert!(td
+        .try_accept_tenure_blocks(vec![blocks[3].clone()])
+        .unwrap()
+        .is_none());
+    assert!(td.store_checkpoint(&mut chainstate).unwrap());
+    assert_eq!(td.checkpoint_num_blocks, 4);
+    let checkpoint = get_checkpoint(&chainstate).unwrap();
+    assert_eq!(Some(checkpoint.clone()), td.make_checkpoint());
+    assert_eq!(checkpoint.block_cursor(), &blocks[2].block_id());
+
+    // a resumed downloader extends the stored checkpoint
+    let mut resumed_td = make_downloader();
+    assert!(resumed_td
+        .try_resume_from_stored_checkpoint(&mut chainstate)
+        .unwrap());
+    assert_eq!(resumed_td.checkpoint_num_blocks, 4);
+    assert!(resumed_td
+        .try_accept_tenure_blocks(vec![blocks[2].clone()])
+        .unwrap()
+        .is_none());
+    assert!(resumed_td.store_checkpoint(&mut chainstate).unwrap());
+    assert_eq!(get_checkpoint(&chainstate), resumed_td.make_checkpoint());
+    assert_eq!(
+        get_checkpoint(&chainstate).unwrap().block_cursor(),
+        &blocks[1].block_id()
+    );
+
+    // a fresh downloader's first checkpoint replaces the stored one
+    let mut fresh_td = make_downloader();
+    fresh_td
+        .try_accept_tenure_start_block(tenure_start_block.clone())
+        .unwrap();
+    fresh_td
+        .try_accept_tenure_end_block(&next_tenure_start_block)
+        .unwrap();
+    assert!(fresh_td
+        .try_accept_tenure_blocks(vec![blocks[5].clone()])
+        .unwrap()
+        .is_none());
+    assert!(fresh_td.store_checkpoint(&mut chainstate).unwrap());
+    assert_eq!(get_checkpoint(&chainstate), fresh_td.make_checkpoint());
+
+    // cancelling the tenure discards its checkpoint the next time the set runs
+    let mut downloader_set = NakamotoTenureDownloaderSet::new();
+    downloader_set.checkpoint_tenure_downloads = true;
+    downloader_set.cancel_tenure(&tenure_id);
+    assert!(downloader_set.stale_checkpoints.contains(&tenure_id));
+    downloader_set.delete_stale_checkpoints(&mut chainstate, 0);
+    assert!(downloader_set.stale_checkpoints.is_empty());
+    assert!(get_checkpoint(&chainstate).is_none());
+
+    // a checkpoint whose tenure-end block has been processed is pruned
+    let mut td = make_downloader();
+    td.try_accept_tenure_start_block(tenure_start_block.clone())
+        .unwrap();
+    td.try_accept_tenure_end_block(&next_tenure_start_block)
+        .unwrap();
+    assert!(td
+        .try_accept_tenure_blocks(vec![blocks[5].clone()])
+        .unwrap()
+        .is_none());
+    assert!(td.store_checkpoint(&mut chainstate).unwrap());
+
+    // not yet processed, so it's kept
+    downloader_set.delete_stale_checkpoints(&mut chainstate, CHECKPOINT_PRUNE_INTERVAL_SECS);
+    assert_eq!(
+        downloader_set.last_checkpoint_prune_secs,
+        CHECKPOINT_PRUNE_INTERVAL_SECS
+    );
+    assert!(get_checkpoint(&chainstate).is_some());
+
+    let staging_tx = chainstate.staging_db_tx_begin().unwrap();
+    staging_tx
+        .store_block(
+            &next_tenure_start_block,
+            true,
+            1,
+            NakamotoBlockObtainMethod::Downloaded,
+        )
+        .unwrap();
+    staging_tx
+        .set_block_processed(&next_tenure_start_block.block_id())
+        .unwrap();
+    staging_tx.commit().unwrap();
+
+    // pruning happens at most once per interval
+    downloader_set.delete_stale_checkpoints(&mut chainstate, CHECKPOINT_PRUNE_INTERVAL_SECS + 1);
+    assert!(get_checkpoint(&chainstate).is_some());
+
+    downloader_set.delete_stale_checkpoints(&mut chainstate, 2 * CHECKPOINT_PRUNE_INTERVAL_SECS);
+    assert!(get_checkpoint(&chainstate).is_none());
+}
+
+/// A downloader's snapshots track it through fetching the tenure-start block, the tenure-end
+/// block, and the tenure's remaining blocks (in two batches).
+#[test]
+fn test_nakamoto_tenure_downloader_snapshot() {
+    let mut test_signers = TestSigners::new(vec![]);
+    let reward_set = test_signers.synthesize_reward_set();
+    let (blocks, next_tenure_start_block, _) = make_tenure(&mut test_signers, 3);
+    let tenure_start_block = blocks[0].clone();
+
+    let naddr = NeighborAddress {
+        addrbytes: PeerAddress([0xff; 16]),
+        port: 123,
+        public_key_hash: Hash160([0xff; 20]),
+    };
+
+    let mut td = NakamotoTenureDownloader::new(
+        tenure_start_block.header.consensus_hash.clone(),
+        tenure_start_block.header.consensus_hash.clone(),
+        tenure_start_block.header.block_id(),
+        next_tenure_start_block.header.consensus_hash.clone(),
+        next_tenure_start_block.header.block_id(),
+        naddr,
+        reward_set.clone(),
+        reward_set,
+        false,
+    );
+
+    let mut snapshot = TenureDownloaderSnapshot {
+        state: NakamotoTenureDownloadState::GetTenureStartBlock(
+            tenure_start_block.header.block_id(),
+            0,
+        ),
+        has_tenure_start_block: false,
+        has_tenure_end_block: false,
+        num_tenure_blocks: 0,
+        tenure_length: None,
+        idle: false,
+        truncated_responses: 0,
+    };
+    assert_eq!(td.snapshot(), snapshot);
+
+    // start
+    assert!(td
+        .handle_next_download_response(make_nakamoto_block_response(&tenure_start_block))
+        .unwrap()
+        .is_none());
+    snapshot.state = NakamotoTenureDownloadState::GetTenureEndBlock(
+        next_tenure_start_block.header.block_id(),
+        0,
+    );
+    snapshot.has_tenure_start_block = true;
+    snapshot.idle = true;
+    assert_eq!(td.snapshot(), snapshot);
+
+    // end
+    assert!(td
+        .handle_next_download_response(make_nakamoto_block_response(&next_tenure_start_block))
+        .unwrap()
+        .is_none());
+    snapshot.state = NakamotoTenureDownloadState::GetTenureBlocks(blocks[2].header.block_id(), 0);
+    snapshot.has_tenure_end_block = true;
+    snapshot.tenure_length = Some(3);
+    assert_eq!(td.snapshot(), snapshot);
+
+    // blocks
+    assert!(td
+        .handle_next_download_response(make_nakamoto_tenure_response(&blocks[2..]))
+        .unwrap()
+        .is_none());
+    snapshot.state = NakamotoTenureDownloadState::GetTenureBlocks(blocks[1].header.block_id(), 0);
+    snapshot.num_tenure_blocks = 2;
+    assert_eq!(td.snapshot(), snapshot);
+
+    // done
+    let tenure_blocks = td
+        .handle_next_download_response(make_nakamoto_tenure_response(&[
+            blocks[1].clone(),
+            blocks[0].clone(),
+        ]))
+        .unwrap()
+        .unwrap();
+    let mut all_blocks = blocks.clone();
+    all_blocks.push(next_tenure_start_block);
+    assert_eq!(tenure_blocks, all_blocks);
+
+    snapshot.state = NakamotoTenureDownloadState::Done;
+    snapshot.num_tenure_blocks = 0;
+    assert_eq!(td.snapshot(), snapshot);
+}
+
+/// Tenure blocks are checked as they are decoded.  A response whose first block is invalid is
+/// rejected without decoding the rest of it, and a truncated response is retried without
+/// accepting the blocks that did decode.
+#[test]
+fn test_nakamoto_tenure_downloader_tenure_block_stream() {
+    let mut test_signers = TestSigners::new(vec![]);
+    let reward_set = test_signers.synthesize_reward_set();
+    let (blocks, next_tenure_start_block, _) = make_tenure(&mut test_signers, 3);
+    let tenure_start_block = blocks[0].clone();
+
+    let naddr = NeighborAddress {
+        addrbytes: PeerAddress([0xff; 16]),
+        port: 123,
+        public_key_hash: Hash160([0xff; 20]),
+    };
+    let make_downloader = || {
+        let mut td = NakamotoTenureDownloader::new(
+            tenure_start_block.header.consensus_hash.clone(),
+            tenure_start_block.header.consensus_hash.clone(),
+            tenure_start_block.header.block_id(),
+            next_tenure_start_block.header.consensus_hash.clone(),
+            next_tenure_start_block.header.block_id(),
+            naddr.clone(),
+            reward_set.clone(),
+            reward_set.clone(),
+            false,
+        );
+        td.try_accept_tenure_start_block(tenure_start_block.clone())
+            .unwrap();
+        td.try_accept_tenure_end_block(&next_tenure_start_block)
+            .unwrap();
+        assert_eq!(
+            td.snapshot().state,
+            NakamotoTenureDownloadState::GetTenureBlocks(blocks[2].header.block_id(), 0)
+        );
+        td
+    };
+    let make_response = |tenure_bytes: Vec<u8>| {
+        StacksHttpResponse::new(
+            HttpResponsePreamble::new(
+                HttpVersion::Http11,
+                200,
+                "OK".into(),
+                None,
+                HttpContentType::Bytes,
+                true,
+            ),
+            HttpResponsePayload::Bytes(tenure_bytes),
+        )
+    };
+    let truncated_block_bytes = {
+        let block_bytes = blocks[1].serialize_to_vec();
+        block_bytes[..block_bytes.len() / 2].to_vec()
+    };
+
+    // an out-of-order first block is rejected before the truncated remainder is decoded, so the
+    // response is not retried
+    let mut td = make_downloader();
+    let mut tenure_bytes = blocks[1].serialize_to_vec();
+    tenure_bytes.extend_from_slice(&truncated_block_bytes);
+    assert!(matches!(
+        td.handle_next_download_response(make_response(tenure_bytes)),
+        Err(NetError::InvalidMessage)
+    ));
+    assert_eq!(td.truncated_responses, 0);
+
+    // a valid prefix followed by a truncated block is retried, and the prefix is not accepted
+    let mut td = make_downloader();
+    let mut tenure_bytes = blocks[2].serialize_to_vec();
+    tenure_bytes.extend_from_slice(&truncated_block_bytes);
+    assert!(td
+        .handle_next_download_response(make_response(tenure_bytes))
+        .unwrap()
+        .is_none());
+    assert!(td.idle);
+    assert_eq!(td.truncated_responses, 1);
+    assert_eq!(td.snapshot().num_tenure_blocks, 0);
+    assert_eq!(
+        td.snapshot().state,
+        NakamotoTenureDownloadState::GetTenureBlocks(blocks[2].header.block_id(), 0)
+    );
+
+    // the retried response is accepted in full
+    let tenure_blocks = td
+        .handle_next_download_response(make_nakamoto_tenure_response(&[
+            blocks[2].clone(),
+            blocks[1].clone(),
+            blocks[0].clone(),
+        ]))
+        .unwrap()
+        .unwrap();
+    let mut all_blocks = blocks.clone();
+    all_blocks.push(next_tenure_start_block.clone());
+    assert_eq!(tenure_blocks, all_blocks);
+    assert_eq!(td.truncated_responses, 0);
+}
+
+/// Download requests to an IPv6 neighbor bracket its address in the `Host` header, and the
+/// header parses back to the same host.
+#[test]
+fn test_nakamoto_tenure_downloader_ipv6_host() {
+    let mut test_signers = TestSigners::new(vec![]);
+    let reward_set = test_signers.synthesize_reward_set();
+    let (tenure_start_block, next_tenure_start_block, _) = make_one_block_tenure(&mut test_signers);
+
+    let naddr = NeighborAddress {
+        addrbytes: PeerAddress([0xff; 16]),
+        port: 123,
+        public_key_hash: Hash160([0xff; 20]),
+    };
+    let td = NakamotoTenureDownloader::new(
+        tenure_start_block.header.consensus_hash.clone(),
+        tenure_start_block.header.consensus_hash.clone(),
+        tenure_start_block.header.block_id(),
+        next_tenure_start_block.header.consensus_hash.clone(),
+        next_tenure_start_block.header.block_id(),
+        naddr,
+        reward_set.clone(),
+        reward_set,
+        false,
+    );
+
+    for (peerhost, expected_host) in [
+        (
+            PeerHost::from_host_port("::1".to_string(), 20443),
+            "[::1]:20443",
+        ),
+        (
+            PeerHost::from_host_port("2001:db8::7".to_string(), 20443),
+            "[2001:db8::7]:20443",
+        ),
+        // not a valid `IpAddr`, so it's treated as a DNS name
+        (
+            PeerHost::from_host_port("fe80::1%eth0".to_string(), 20443),
+            "[fe80::1%eth0]:20443",
+        ),
+        (
+            PeerHost::from_host_port("127.0.0.1".to_string(), 20443),
+            "127.0.0.1:20443",
+        ),
+    ] {
+        let request = td
+            .make_next_download_request(peerhost.clone())
+            .unwrap()
+            .unwrap();
+        let bytes = request.try_serialize().unwrap();
+        let preamble = String::from_utf8_lossy(&bytes);
+        assert!(
+            preamble.contains(&format!("\r\nHost: {expected_host}\r\n")),
+            "{preamble}"
+        );
+        assert_eq!(
+            request.preamble().get_header("host".to_string()),
+            Some(expected_host.to_string())
+        );
+        if let PeerHost::IP(..) = &peerhost {
+            assert_eq!(expected_host.parse::<PeerHost>().unwrap(), peerhost);
+        }
+    }
+}
+
+/// Drive a confirmed tenure download from `GetTenureStartBlock` to `Done` inside a
+/// `NakamotoDownloadStateMachine`, feeding it canned responses instead of using the network.
+#[test]
+fn test_nakamoto_download_state_machine_with_rpc() {
+    let mut test_signers = TestSigners::new(vec![]);
+    let reward_set = test_signers.synthesize_reward_set();
+    let (tenure_start_block, next_tenure_start_block, _) = make_one_block_tenure(&mut test_signers);
+
+    let naddr = NeighborAddress {
+        addrbytes: PeerAddress([0xff; 16]),
+        port: 123,
+        public_key_hash: Hash160([0xff; 20]),
+    };
+
+    let mut downloader =
+        NakamotoDownloadStateMachine::with_rpc(0, StacksBlockId([0x00; 32]), NeighborRPC::new());
+    downloader.tenure_downloads.add_downloaders(vec![(
+        naddr.clone(),
+        NakamotoTenureDownloader::new(
+            tenure_start_block.header.consensus_hash.clone(),
+            tenure_start_block.header.consensus_hash.clone(),
+            tenure_start_block.header.block_id(),
+            next_tenure_start_block.header.consensus_hash.clone(),
+            next_tenure_start_block.header.block_id(),
+            naddr.clone(),
+            reward_set.clone(),
+            reward_set,
+            false,
+        ),
+    )]);
+    assert_eq!(downloader.metrics().num_downloaders, 1);
+
+    let idx = *downloader.tenure_downloads.peers.get(&naddr).unwrap();
+    let td = downloader.tenure_downloads.downloaders[idx]
+        .as_mut()
+        .unwrap();
+    assert!(matches!(
+        td.state,
+        NakamotoTenureDownloadState::GetTenureStartBlock(..)
+    ));
+
+    let mut blocks = None;
+    for response in [
+        make_nakamoto_block_response(&tenure_start_block),
+        make_nakamoto_block_response(&next_tenure_start_block),
+    ] {
+        assert!(blocks.is_none());
+        blocks = td.handle_next_download_response(response).unwrap();
+    }
+    assert_eq!(
+        blocks.unwrap(),
+        vec![tenure_start_block, next_tenure_start_block]
+    );
+    assert!(td.is_done());
+    assert_eq!(downloader.metrics().inflight, 0);
+}
+
+/// Seeded tenure-start blocks are validated, and let waiting downloaders advance without network
+/// requests.
+#[test]
+fn test_nakamoto_download_seed_tenure_start_blocks() {
+    let mut test_signers = TestSigners::new(vec![]);
+    let reward_set = test_signers.synthesize_reward_set();
+    let (tenure_start_block, next_tenure_start_block, _) = make_one_block_tenure(&mut test_signers);
+
+    // only well-formed tenure-start blocks, keyed by their block IDs, are accepted
+    let mut not_tenure_start_block = tenure_start_block.clone();
+    not_tenure_start_block.txs.clear();
+    let mut downloader =
+        NakamotoDownloadStateMachine::with_rpc(0, StacksBlockId([0x00; 32]), NeighborRPC::new());
+    assert_eq!(
+        downloader.seed_tenure_start_blocks(HashMap::from([
+            (
+                next_tenure_start_block.block_id(),
+                next_tenure_start_block.clone()
+            ),
+            (StacksBlockId([0x01; 32]), tenure_start_block.clone()),
+            (
+                not_tenure_start_block.block_id(),
+                not_tenure_start_block.clone()
+            ),
+        ])),
+        1
+    );
+
+    let naddr = NeighborAddress {
+        addrbytes: PeerAddress([0xff; 16]),
+        port: 123,
+        public_key_hash: Hash160([0xff; 20]),
+    };
+    let make_downloader = || {
+        NakamotoTenureDownloader::new(
+            tenure_start_block.header.consensus_hash.clone(),
+            tenure_start_block.header.consensus_hash.clone(),
+            tenure_start_block.header.block_id(),
+            next_tenure_start_block.header.consensus_hash.clone(),
+            next_tenure_start_block.header.block_id(),
+            naddr.clone(),
+            reward_set.clone(),
+            reward_set.clone(),
+            false,
+        )
+    };
+    let neighbor_rpc = NeighborRPC::new();
+
+    // a downloader waiting for its tenure-end block gets it from the known blocks
+    let mut td = make_downloader();
+    td.try_accept_tenure_start_block(tenure_start_block.clone())
+        .unwrap();
+    let mut downloaders = NakamotoTenureDownloaderSet::new();
+    downloaders.add_downloaders(vec![(naddr.clone(), td)]);
+    downloaders.use_known_tenure_start_blocks(
+        &neighbor_rpc,
+        &HashMap::from([(
+            next_tenure_start_block.block_id(),
+            next_tenure_start_block.clone(),
+        )]),
+    );
+    let td = downloaders.downloaders[0].as_ref().unwrap();
+    assert_eq!(td.tenure_end_block, Some(next_tenure_start_block.clone()));
+    assert!(matches!(
+        &td.state,
+        NakamotoTenureDownloadState::GetTenureBlocks(cursor, _)
+            if cursor == &tenure_start_block.block_id()
+    ));
+
+    // a downloader that hasn't started holds on to its tenure-end block until it gets its
+    // tenure-start block, and takes both if both are known
+    let mut downloaders = NakamotoTenureDownloaderSet::new();
+    downloaders.add_downloaders(vec![(naddr.clone(), make_downloader())]);
+    downloaders.use_known_tenure_start_blocks(
+        &neighbor_rpc,
+        &HashMap::from([(
+            next_tenure_start_block.block_id(),
+            next_tenure_start_block.clone(),
+        )]),
+    );
+    let td = downloaders.downloaders[0].as_ref().unwrap();
+    assert!(matches!(
+        td.state,
+        NakamotoTenureDownloadState::GetTenureStartBlock(..)
+    ));
+    assert_eq!(td.tenure_end_block, Some(next_tenure_start_block.clone()));
+
+    downloaders.use_known_tenure_start_blocks(
+        &neighbor_rpc,
+        &HashMap::from([(tenure_start_block.block_id(), tenure_start_block.clone())]),
+    );
+    let td = downloaders.downloaders[0].as_ref().unwrap();
+    assert_eq!(td.tenure_start_block, Some(tenure_start_block.clone()));
+    assert!(matches!(
+        td.state,
+        NakamotoTenureDownloadState::GetTenureBlocks(..)
+    ));
+}
+
+/// Two adjacent tenures share a block: the first tenure's end block is the second tenure's start
+/// block.  If their downloaders are scheduled on different peers, then only one of them fetches
+/// the shared block, and the other is handed the block once it arrives.
+#[test]
+fn test_nakamoto_tenure_downloader_set_coalesce_shared_block() {
+    let mut test_signers = TestSigners::new(vec![]);
+    let reward_set = test_signers.synthesize_reward_set();
+    let (tenure_start_block, shared_block, _) = make_one_block_tenure(&mut test_signers);
+
+    let first_naddr = NeighborAddress {
+        addrbytes: PeerAddress([0xff; 16]),
+        port: 123,
+        public_key_hash: Hash160([0xff; 20]),
+    };
+    let second_naddr = NeighborAddress {
+        addrbytes: PeerAddress([0xfe; 16]),
+        port: 456,
+        public_key_hash: Hash160([0xfe; 20]),
+    };
+
+    // the first tenure's downloader needs its tenure-end block, which is the shared block
+    let mut first_td = NakamotoTenureDownloader::new(
+        tenure_start_block.header.consensus_hash.clone(),
+        tenure_start_block.header.consensus_hash.clone(),
+        tenure_start_block.header.block_id(),
+        shared_block.header.consensus_hash.clone(),
+        shared_block.header.block_id(),
+        first_naddr.clone(),
+        reward_set.clone(),
+        reward_set.clone(),
+        false,
+    );
+    first_td
+        .try_accept_tenure_start_block(tenure_start_block.clone())
+        .unwrap();
+
+    // the second tenure's downloader needs its tenure-start block, which is the shared block
+    let second_td = NakamotoTenureDownloader::new(
+        shared_block.header.consensus_hash.clone(),
+        shared_block.header.consensus_hash.clone(),
+        shared_block.header.block_id(),
+        ConsensusHash([0x06; 20]),
+        StacksBlockId([0x06; 32]),
+        second_naddr.clone(),
+        reward_set.clone(),
+        reward_set,
+        false,
+    );
+    assert_eq!(
+        first_td.requested_boundary_block_id(),
+        Some(&shared_block.block_id())
+    );
+    assert_eq!(
+        second_td.requested_boundary_block_id(),
+        Some(&shared_block.block_id())
+    );
+
+    let mut downloaders = NakamotoTenureDownloaderSet::new();
+    downloaders.add_downloaders(vec![
+        (first_naddr.clone(), first_td),
+        (second_naddr.clone(), second_td),
+    ]);
+
+    // only one downloader may fetch the shared block, and that doesn't change on later passes
+    let waiting = downloaders.find_coalesced_requests();
+    assert_eq!(waiting.len(), 1);
+    assert_eq!(downloaders.find_coalesced_requests(), waiting);
+    assert_eq!(downloaders.block_requests.len(), 1);
+
+    let fetching_naddr = downloaders
+        .block_requests
+        .get(&shared_block.block_id())
+        .unwrap()
+        .clone();
+    assert!(!waiting.contains(&fetching_naddr));
+
+    // the shared block arrives for the fetching downloader, and is handed to the waiting one
+    let fetching_index = *downloaders.peers.get(&fetching_naddr).unwrap();
+    downloaders.downloaders[fetching_index]
+        .as_mut()
+        .unwrap()
+        .handle_next_download_response(make_nakamoto_block_response(&shared_block))
+        .unwrap();
+    downloaders.share_tenure_boundary_blocks(&NeighborRPC::new());
+
+    let first_td = downloaders.downloaders[0].as_ref().unwrap();
+    assert_eq!(first_td.tenure_end_block, Some(shared_block.clone()));
+    assert!(!matches!(
+        first_td.state,
+        NakamotoTenureDownloadState::GetTenureEndBlock(..)
+    ));
+
+    let second_td = downloaders.downloaders[1].as_ref().unwrap();
+    assert_eq!(second_td.tenure_start_block, Some(shared_block.clone()));
+    assert_eq!(
+        second_td.state,
+        NakamotoTenureDownloadState::GetTenureEndBlock(
+            StacksBlockId([0x06; 32]),
+            second_td.state.request_time().unwrap()
+        )
+    );
+
+    // neither downloader needs the shared block any longer
+    assert!(downloaders.find_coalesced_requests().is_empty());
+    assert_eq!(
+        downloaders.block_requests,
+        HashMap::from([(StacksBlockId([0x06; 32]), second_naddr)])
+    );
+}
+
+/// Only downloaders which haven't made progress within the timeout are reported as stuck, and
+/// reporting them doesn't change anything.
+#[test]
+fn test_nakamoto_tenure_downloader_set_stuck_downloaders() {
+    let mut test_signers = TestSigners::new(vec![]);
+    let reward_set = test_signers.synthesize_reward_set();
+    let (tenure_start_block, next_tenure_start_block, _) = make_one_block_tenure(&mut test_signers);
+
+    let make_downloader = |naddr: &NeighborAddress| {
+        NakamotoTenureDownloader::new(
+            tenure_start_block.header.consensus_hash.clone(),
+            tenure_start_block.header.consensus_hash.clone(),
+            tenure_start_block.header.block_id(),
+            next_tenure_start_block.header.consensus_hash.clone(),
+            next_tenure_start_block.header.block_id(),
+            naddr.clone(),
+            reward_set.clone(),
+            reward_set.clone(),
+            false,
+        )
+    };
+    let progressing_naddr = NeighborAddress {
+        addrbytes: PeerAddress([0xff; 16]),
+        port: 123,
+        public_key_hash: Hash160([0xff; 20]),
+    };
+    let stalled_naddr = NeighborAddress {
+        addrbytes: PeerAddress([0xff; 16]),
+        port: 456,
+        public_key_hash: Hash160([0xff; 20]),
+    };
+
+    // this one just got its tenure-start block
+    let mut progressing_td = make_downloader(&progressing_naddr);
+    progressing_td.last_progress_ms = 0;
+    progressing_td
+        .try_accept_tenure_start_block(tenure_start_block.clone())
+        .unwrap();
+    assert!(progressing_td.last_progress_ms > 0);
+
+    // this one hasn't gotten anything in a long time
+    let mut stalled_td = make_downloader(&stalled_naddr);
+    stalled_td.last_progress_ms = 0;
+
+    let mut downloaders = NakamotoTenureDownloaderSet::new();
+    downloaders.add_downloaders(vec![
+        (progressing_naddr.clone(), progressing_td),
+        (stalled_naddr.clone(), stalled_td.clone()),
+    ]);
+
+    let stuck = downloaders.stuck_downloaders(Duration::from_secs(60));
+    assert_eq!(
+        stuck,
+        vec![(
+            tenure_start_block.header.consensus_hash.clone(),
+            stalled_naddr.clone(),
+            stalled_td.state.clone(),
+        )]
+    );
+
+    // read-only
+    assert_eq!(downloaders.num_downloaders(), 2);
+    assert_eq!(downloaders.downloaders[1].as_ref(), Some(&stalled_td));
+}
+
+#[test]
+fn test_nakamoto_download_rate() {
+    let mut downloader =
+        NakamotoDownloadStateMachine::with_rpc(0, StacksBlockId([0x00; 32]), NeighborRPC::new());
+    assert_eq!(downloader.download_rate_at(1000), 0.0);
+
+    // 5 blocks per second for two minutes, delivered as tenures of 10 blocks every other second
+    for now_secs in 1000..1120 {
+        let num_blocks = if now_secs % 2 == 0 { 10 } else { 0 };
+        downloader.record_downloaded_blocks(now_secs, num_blocks);
+        if now_secs >= 1060 {
+            assert!((downloader.download_rate_at(now_secs) - 5.0).abs() < 0.2);
+        }
+    }
+
+    // several tenures in the same second are combined, and old seconds are forgotten
+    downloader.record_downloaded_blocks(1120, 30);
+    downloader.record_downloaded_blocks(1120, 30);
+    assert!(downloader.downloaded_blocks_per_sec.len() <= 60);
+    assert!((downloader.download_rate_at(1120) - 5.8).abs() < 0.2);
+
+    // the rate decays once downloads stop
+    downloader.record_downloaded_blocks(1150, 0);
+    assert!(downloader.download_rate_at(1150) < 5.0);
+    downloader.record_downloaded_blocks(1200, 0);
+    assert_eq!(downloader.download_rate_at(1200), 0.0);
+    assert!(downloader.downloaded_blocks_per_sec.is_empty());
+}
+
+/// A truncated response is retried once before the downloader gives up on the peer.  A response
+/// that is complete but unusable fails immediately.
+#[test]
+fn test_nakamoto_tenure_downloader_truncated_response_retry() {
+    let mut test_signers = TestSigners::new(vec![]);
+    let reward_set = test_signers.synthesize_reward_set();
+
+    let mut tenure_start_block = NakamotoBlock {
+        header: NakamotoBlockHeader {
+            version: 1,
+            chain_length: 2,
+            burn_spent: 3,
+            consensus_hash: ConsensusHash([0x04; 20]),
+            parent_block_id: StacksBlockId([0x05; 32]),
+            tx_merkle_root: Sha512Trunc256Sum([0x06; 32]),
+            state_index_root: TrieHash([0x07; 32]),
+            timestamp: 8,
+            miner_signature: MessageSignature::empty(),
+            signer_signature: vec![],
+            pox_treatment: BitVec::zeros(1).unwrap(),
+        },
+        txs: vec![],
+    };
+    test_signers.sign_nakamoto_block(&mut tenure_start_block, 0);
+
+    let make_truncated_response = || {
+        let block_bytes = tenure_start_block.serialize_to_vec();
+        StacksHttpResponse::new(
+            HttpResponsePreamble::new(
+                HttpVersion::Http11,
+                200,
+                "OK".into(),
+                None,
+                HttpContentType::Bytes,
+                true,
+            ),
+            HttpResponsePayload::Bytes(block_bytes[..block_bytes.len() / 2].to_vec()),
+        )
+    };
+
+    let naddr = NeighborAddress {
+        addrbytes: PeerAddress([0xff; 16]),
+        port: 123,
+        public_key_hash: Hash160([0xff; 20]),
+    };
+    let make_downloader = || {
+        NakamotoTenureDownloader::new(
+            tenure_start_block.header.consensus_hash.clone(),
+            tenure_start_block.header.consensus_hash.clone(),
+            tenure_start_block.header.block_id(),
+            ConsensusHash([0x05; 20]),
+            StacksBlockId([0x06; 32]),
+            naddr.clone(),
+            reward_set.clone(),
+            reward_set.clone(),
+            false,
+        )
+    };
+
+    // a truncated response followed by a complete one is fine
+    let mut td = make_downloader();
+    let res = td
+        .handle_next_download_response(make_truncated_response())
+        .unwrap();
+    assert!(res.is_none());
+    assert!(td.idle);
+    assert_eq!(td.truncated_responses, 1);
+    assert!(matches!(
+        td.state,
+        NakamotoTenureDownloadState::GetTenureStartBlock(..)
+    ));
+
+    let res = td
+        .handle_next_download_response(make_nakamoto_block_response(&tenure_start_block))
+        .unwrap();
+    assert!(res.is_none());
+    assert_eq!(td.truncated_responses, 0);
+    assert_eq!(td.tenure_start_block, Some(tenure_start_block.clone()));
+    assert!(matches!(
+        td.state,
+        NakamotoTenureDownloadState::GetTenureEndBlock(..)
+    ));
+
+    // two truncated responses in a row fail
+    let mut td = make_downloader();
+    assert!(td
+        .handle_next_download_response(make_truncated_response())
+        .unwrap()
+        .is_none());
+    assert!(td
+        .handle_next_download_response(make_truncated_response())
+        .is_err());
+
+    // a complete response that isn't a block fails immediately
+    let mut td = make_downloader();
+    let not_found = StacksHttpResponse::new(
+        HttpResponsePreamble::new(
+            HttpVersion::Http11,
+            404,
+            "Not Found".into(),
+            None,
+            HttpContentType::Bytes,
+            true,
+        ),
+        HttpResponsePayload::Bytes(vec![]),
+    );
+    assert!(matches!(
+        td.handle_next_download_response(not_found),
+        Err(NetError::NotFoundError)
+    ));
+    assert_eq!(td.truncated_responses, 0);
+}
+
+/// A block whose signer signatures all recover to keys in the reward set is still invalid if the
+/// total weight of those signers does not meet the approval threshold.
+#[test]
+fn test_nakamoto_tenure_downloader_insufficient_signer_weight() {
+    let signer_keys: Vec<_> = (0..5).map(|_| StacksPrivateKey::random()).collect();
+    let test_signers = TestSigners::new(signer_keys);
+    let reward_set = test_signers.synthesize_reward_set();
+
+    let tenure_start_header = NakamotoBlockHeader {
+        version: 1,
+        chain_length: 2,
+        burn_spent: 3,
+        consensus_hash: ConsensusHash([0x04; 20]),
+        parent_block_id: StacksBlockId([0x05; 32]),
+        tx_merkle_root: Sha512Trunc256Sum([0x06; 32]),
+        state_index_root: TrieHash([0x07; 32]),
+        timestamp: 8,
+        miner_signature: MessageSignature::empty(),
+        signer_signature: vec![],
+        pox_treatment: BitVec::zeros(1).unwrap(),
+    };
+
+    let mut tenure_start_block = NakamotoBlock {
+        header: tenure_start_header,
+        txs: vec![],
+    };
+    test_signers.sign_block_with_reward_set(&mut tenure_start_block, &reward_set);
+    assert_eq!(tenure_start_block.header.signer_signature.len(), 5);
+
+    // keep only one signer's signature.  It's a valid signature from a reward set member, but one
+    // signer's weight is below the 70% threshold.
+    let mut underweight_block = tenure_start_block.clone();
+    underweight_block.header.signer_signature.truncate(1);
+    assert_eq!(
+        underweight_block.header.block_id(),
+        tenure_start_block.header.block_id()
+    );
+    assert!(underweight_block
+        .header
+        .verify_signer_signatures(&reward_set)
+        .is_err());
+
+    let naddr = NeighborAddress {
+        addrbytes: PeerAddress([0xff; 16]),
+        port: 123,
+        public_key_hash: Hash160([0xff; 20]),
+    };
+
+    let mut td = NakamotoTenureDownloader::new(
+        tenure_start_block.header.consensus_hash.clone(),
+        tenure_start_block.header.consensus_hash.clone(),
+        tenure_start_block.header.block_id(),
+        ConsensusHash([0x05; 20]),
+        StacksBlockId([0x06; 32]),
+        naddr,
+        reward_set.clone(),
+        reward_set,
+        false,
+    );
+
+    assert!(matches!(
+        td.try_accept_tenure_start_block(underweight_block),
+        Err(NetError::InvalidMessage)
+    ));
+    assert!(matches!(
+        td.state,
+        NakamotoTenureDownloadState::GetTenureStartBlock(..)
+    ));
+
+    // the fully-signed block is accepted
+    td.try_accept_tenure_start_block(tenure_start_block.clone())
+        .unwrap();
+    assert!(matches!(
+        td.state,
+        NakamotoTenureDownloadState::GetTenureEndBlock(..)
+    ));
+    assert_eq!(td.tenure_start_block, Some(tenure_start_block));
+}
+
+#[test]
+fn test_nakamoto_unconfirmed_tenure_downloader() {
+    let observer = TestEventObserver::new();
+    let bitvecs = vec![vec![
+        true, true, true, true, true, true, true, true, true, true,
+    ]];
+
+    let rc_len = 10u64;
+    let peer = make_nakamoto_peer_from_invs(function_name!(), &observer, rc_len as u32, 3, bitvecs);
+    let (mut peer, reward_cycle_invs) =
+        peer_get_nakamoto_invs(peer, &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12]);
+    peer.chain.mine_malleablized_blocks = false;
+
+    let nakamoto_start = NakamotoBootPlan::nakamoto_first_tenure_height(
+        &peer.config.chain_config.burnchain.pox_constants,
+    );
+
+    let all_sortitions = peer.sortdb().get_all_snapshots().unwrap();
+    let tip = SortitionDB::get_canonical_burn_chain_tip(peer.sortdb().conn()).unwrap();
+
+    assert_eq!(tip.block_height, 51);
+
+    let test_signers = TestSigners::new(vec![]);
+
+    let naddr = NeighborAddress {
+        addrbytes: PeerAddress([0xff; 16]),
+        port: 123,
+        public_key_hash: Hash160([0xff; 20]),
+    };
+
+    peer.refresh_burnchain_view();
+    let tip_block_id = peer.network.stacks_tip.block_id();
+
+    let tip_ch = peer.network.stacks_tip.consensus_hash.clone();
+    let parent_tip_ch = peer.network.parent_stacks_tip.consensus_hash.clone();
+    let current_reward_sets = peer.network.current_reward_sets.clone();
+
+    let last_block_in_confirmed_tenure = NakamotoChainState::get_highest_block_header_in_tenure(
+        &mut peer.chainstate().index_conn(),
+        &tip_block_id,
+        &parent_tip_ch,
+    )
+    .unwrap()
+    .unwrap();
+
+    // NOTE: we have to account for malleablized blocks!
+    let unconfirmed_tenure = peer
+        .chainstate()
+        .nakamoto_blocks_db()
+        .get_all_blocks_in_tenure(&tip_ch, &tip_block_id)
+        .unwrap();
+    let last_confirmed_tenure = peer
+        .chainstate()
+        .nakamoto_blocks_db()
+        .get_all_blocks_in_tenure(
+            &parent_tip_ch,
+            &last_block_in_confirmed_tenure.index_block_hash(),
+        )
+        .unwrap();
+
+    let parent_parent_header = NakamotoChainState::get_block_header_nakamoto(
+        peer.chainstate().db(),
+        &last_confirmed_tenure
+            .first()
+            .as_ref()
+            .unwrap()
+            .header
+            .parent_block_id,
+    )
+    .unwrap()
+    .unwrap();
+    let parent_parent_start_header = NakamotoChainState::get_nakamoto_tenure_start_block_header(
+        &mut peer.chainstate().index_conn(),
+        &tip_block_id,
+        &parent_parent_header.consensus_hash,
+    )
+    .unwrap()
+    .unwrap();
+
+    assert!(!unconfirmed_tenure.is_empty());
+    assert!(!last_confirmed_tenure.is_empty());
+
+    assert_eq!(
+        unconfirmed_tenure.first().as_ref().unwrap().block_id(),
+        peer.network.tenure_start_block_id
+    );
+    assert_eq!(
+        unconfirmed_tenure
+            .first()
+            .as_ref()
+            .unwrap()
+            .header
+            .parent_block_id,
+        last_confirmed_tenure.last().as_ref().unwrap().block_id()
+    );
+
+    let tip_rc = peer
+        .network
+        .burnchain
+        .block_height_to_reward_cycle(peer.network.burnchain_tip.block_height)
+        .expect("FATAL: burnchain tip before system start");
+
+    let highest_confirmed_wanted_tenure = WantedTenure {
+        tenure_id_consensus_hash: peer.network.parent_stacks_tip.consensus_hash.clone(),
+        winning_block_id: parent_parent_start_header.index_block_hash(),
+        processed: false,
+        burn_height: peer.network.burnchain_tip.block_height - 1,
+    };
+
+    let unconfirmed_wanted_tenure = WantedTenure {
+        tenure_id_consensus_hash: peer.network.stacks_tip.consensus_hash.clone(),
+        winning_block_id: last_confirmed_tenure
+            .first()
+            .as_ref()
+            .unwrap()
+            .header
+            .block_id(),
+        processed: false,
+        burn_height: peer.network.burnchain_tip.block_height,
+    };
+
+    // we can make unconfirmed tenure downloaders
+    {
+        let mut empty_schedule = VecDeque::new();
+        let mut full_schedule = {
+            let mut sched = VecDeque::new();
+            sched.push_back(naddr.clone());
+            sched
+        };
+        let mut empty_downloaders = HashMap::new();
+        let mut full_downloaders = {
+            let mut dl = HashMap::new();
+            let utd =
+                NakamotoUnconfirmedTenureDownloader::new(naddr.clone(), Some(tip_block_id.clone()));
+            dl.insert(naddr.clone(), utd);
+            dl
+        };
+        assert_eq!(
+            NakamotoDownloadStateMachine::make_unconfirmed_tenure_downloaders(
+                &mut empty_schedule,
+                10,
+                &mut empty_downloaders,
+                None,
+                &SignerSignatureCache::default(),
+            ),
+            0
+        );
+        assert_eq!(
+            NakamotoDownloadStateMachine::make_unconfirmed_tenure_downloaders(
+                &mut empty_schedule,
+                10,
+                &mut full_downloaders,
+                None,
+                &SignerSignatureCache::default(),
+            ),
+            0
+        );
+        assert_eq!(
+            NakamotoDownloadStateMachine::make_unconfirmed_tenure_downloaders(
+                &mut full_schedule,
+                10,
+                &mut full_downloaders,
+                None,
+                &SignerSignatureCache::default(),
+            ),
+            0
+        );
+        assert_eq!(full_schedule.len(), 1);
+        assert_eq!(
+            NakamotoDownloadStateMachine::make_unconfirmed_tenure_downloaders(
+                &mut full_schedule,
+                10,
+                &mut empty_downloaders,
+                None,
+                &SignerSignatureCache::default(),
+            ),
+            1
+        );
+        assert!(full_schedule.is_empty());
+        assert_eq!(empty_downloaders.len(), 1);
+    }
+
+    // we've processed the tip already, so we transition straight to the Done state
+    {
+        let mut utd = NakamotoUnconfirmedTenureDownloader::new(naddr.clone(), Some(tip_block_id));
+        assert_eq!(utd.state, NakamotoUnconfirmedDownloadState::GetTenureInfo);
+
+        utd.confirmed_signer_keys = Some(
+            current_reward_sets
+                .get(&tip_rc)
+                .cloned()
+                .unwrap()
+                .reward_cycle_info
+                .known_selected_anchor_block_owned()
+                .unwrap(),
+        );
+        utd.unconfirmed_signer_keys = Some(
+            current_reward_sets
+                .get(&tip_rc)
+                .cloned()
+                .unwrap()
+                .reward_cycle_info
+                .known_selected_anchor_block_owned()
+                .unwrap(),
+        );
+
+        let tenure_tip = RPCGetTenureInfo {
+            consensus_hash: peer.network.stacks_tip.consensus_hash.clone(),
+            tenure_start_block_id: peer.network.tenure_start_block_id.clone(),
+            parent_consensus_hash: peer.network.parent_stacks_tip.consensus_hash.clone(),
+            parent_tenure_start_block_id: StacksBlockId::new(
+                &peer.network.parent_stacks_tip.consensus_hash,
+                &peer.network.parent_stacks_tip.block_hash,
+            ),
+            tip_block_id: StacksBlockId::new(
+                &peer.network.stacks_tip.consensus_hash,
+                &peer.network.stacks_tip.block_hash,
+            ),
+            tip_height: peer.network.stacks_tip.height,
+            reward_cycle: tip_rc,
+        };
+
+        let sortdb = peer.chain.sortdb.take().unwrap();
+        let sort_tip = SortitionDB::get_canonical_burn_chain_tip(sortdb.conn()).unwrap();
+        utd.try_accept_tenure_info(
+            &sortdb,
+            &sort_tip,
+            peer.chainstate(),
+            tenure_tip.clone(),
+            &current_reward_sets,
+        )
+        .unwrap();
+
+        peer.chain.sortdb = Some(sortdb);
+
+        assert!(utd.unconfirmed_tenure_start_block.is_some());
+
+        // because the highest processed block is the same as .tip_block_id, we're done
+        assert_eq!(utd.state, NakamotoUnconfirmedDownloadState::Done);
+
+        // we can request the highest-complete tenure
+        assert!(!utd.need_highest_complete_tenure(peer.chainstate()).unwrap());
+
+        let ntd = utd.make_highest_complete_tenure_downloader().unwrap();
+        assert_eq!(
+            ntd.state,
+            NakamotoTenureDownloadState::GetTenureStartBlock(
+                unconfirmed_wanted_tenure.winning_block_id.clone(),
+                ntd.state.request_time().unwrap()
+            )
+        );
+    }
+
+    // if we don't know the ongoing tenure's reward set yet, then the downloader reports a
+    // retryable error and stays put, instead of treating the peer as broken
+    {
+        let mid_tip_block_id = unconfirmed_tenure.first().as_ref().unwrap().block_id();
+        let mut utd =
+            NakamotoUnconfirmedTenureDownloader::new(naddr.clone(), Some(mid_tip_block_id));
+
+        let tenure_tip = RPCGetTenureInfo {
+            consensus_hash: peer.network.stacks_tip.consensus_hash.clone(),
+            tenure_start_block_id: peer.network.tenure_start_block_id.clone(),
+            parent_consensus_hash: peer.network.parent_stacks_tip.consensus_hash.clone(),
+            parent_tenure_start_block_id: StacksBlockId::new(
+                &peer.network.parent_stacks_tip.consensus_hash,
+                &peer.network.parent_stacks_tip.block_hash,
+            ),
+            tip_block_id: StacksBlockId::new(
+                &peer.network.stacks_tip.consensus_hash,
+                &peer.network.stacks_tip.block_hash,
+            ),
+            tip_height: peer.network.stacks_tip.height,
+            reward_cycle: tip_rc,
+        };
+
+        let mut missing_reward_sets = current_reward_sets.clone();
+        missing_reward_sets.remove(&tip_rc);
+
+        let sortdb = peer.chain.sortdb.take().unwrap();
+        let sort_tip = SortitionDB::get_canonical_burn_chain_tip(sortdb.conn()).unwrap();
+        let res = utd.try_accept_tenure_info(
+            &sortdb,
+            &sort_tip,
+            peer.chainstate(),
+            tenure_tip.clone(),
+            &missing_reward_sets,
+        );
+        assert!(matches!(res, Err(NetError::Transient(_))), "{res:?}");
+        assert_eq!(utd.state, NakamotoUnconfirmedDownloadState::GetTenureInfo);
+        assert!(utd.tenure_tip.is_none());
+
+        // once the reward set is known, the same tenure info is accepted
+        utd.try_accept_tenure_info(
+            &sortdb,
+            &sort_tip,
+            peer.chainstate(),
+            tenure_tip.clone(),
+            &current_reward_sets,
+        )
+        .unwrap();
+        assert_eq!(
+            utd.state,
+            NakamotoUnconfirmedDownloadState::GetUnconfirmedTenureBlocks(
+                tenure_tip.tip_block_id.clone(),
+            )
+        );
+
+        peer.chain.sortdb = Some(sortdb);
+    }
+
+    // a tenure tip which names its own tenure as its parent tenure is rejected
+    {
+        let mut utd = NakamotoUnconfirmedTenureDownloader::new(naddr.clone(), None);
+
+        let tenure_tip = RPCGetTenureInfo {
+            consensus_hash: peer.network.stacks_tip.consensus_hash.clone(),
+            tenure_start_block_id: peer.network.tenure_start_block_id.clone(),
+            parent_consensus_hash: peer.network.stacks_tip.consensus_hash.clone(),
+            parent_tenure_start_block_id: peer.network.tenure_start_block_id.clone(),
+            tip_block_id: StacksBlockId::new(
+                &peer.network.stacks_tip.consensus_hash,
+                &peer.network.stacks_tip.block_hash,
+            ),
+            tip_height: peer.network.stacks_tip.height,
+            reward_cycle: tip_rc,
+        };
+
+        let sortdb = peer.chain.sortdb.take().unwrap();
+        let sort_tip = SortitionDB::get_canonical_burn_chain_tip(sortdb.conn()).unwrap();
+        let res = utd.try_accept_tenure_info(
+            &sortdb,
+            &sort_tip,
+            peer.chainstate(),
+            tenure_tip,
+            &current_reward_sets,
+        );
+        assert!(matches!(res, Err(NetError::InvalidMessage)), "{res:?}");
+        assert_eq!(utd.state, NakamotoUnconfirmedDownloadState::GetTenureInfo);
+        assert!(utd.tenure_tip.is_none());
+
+        peer.chain.sortdb = Some(sortdb);
+    }
+
+    // if the sortition history is reorged after we accept the tenure info, such that the ongoing
+    // tenure is no longer canonical, then the downloader aborts instead of fetching more blocks.
+    {
+        let mid_tip_block_id = unconfirmed_tenure.first().as_ref().unwrap().block_id();
+        let mut utd =
+            NakamotoUnconfirmedTenureDownloader::new(naddr.clone(), Some(mid_tip_block_id));
+
+        let tenure_tip = RPCGetTenureInfo {
+            consensus_hash: peer.network.stacks_tip.consensus_hash.clone(),
+            tenure_start_block_id: peer.network.tenure_start_block_id.clone(),
+            parent_consensus_hash: peer.network.parent_stacks_tip.consensus_hash.clone(),
+            parent_tenure_start_block_id: StacksBlockId::new(
+                &peer.network.parent_stacks_tip.consensus_hash,
+                &peer.network.parent_stacks_tip.block_hash,
+            ),
+            tip_block_id: StacksBlockId::new(
+                &peer.network.stacks_tip.consensus_hash,
+                &peer.network.stacks_tip.block_hash,
+            ),
+            tip_height: peer.network.stacks_tip.height,
+            reward_cycle: tip_rc,
+        };
+
+        let sortdb = peer.chain.sortdb.take().unwrap();
+        let sort_tip = SortitionDB::get_canonical_burn_chain_tip(sortdb.conn()).unwrap();
+
+        // nothing to check before we know the tenure tip
+        assert!(!utd.check_reorg(&sortdb, &sort_tip).unwrap());
+
+        utd.try_accept_tenure_info(
+            &sortdb,
+            &sort_tip,
+            peer.chainstate(),
+            tenure_tip.clone(),
+            &current_reward_sets,
+        )
+        .unwrap();
+        assert_eq!(
+            utd.state,
+            NakamotoUnconfirmedDownloadState::GetUnconfirmedTenureBlocks(
+                tenure_tip.tip_block_id.clone(),
+            )
+        );
+
+        // still canonical
+        assert!(!utd.check_reorg(&sortdb, &sort_tip).unwrap());
+        assert!(!utd.is_aborted());
+
+        // the parent tenure's sortition does not descend from the ongoing tenure's sortition, so
+        // from its point of view the ongoing tenure is not canonical
+        let reorged_sort_tip =
+            SortitionDB::get_block_snapshot_consensus(sortdb.conn(), &parent_tip_ch)
+                .unwrap()
+                .unwrap();
+        assert!(reorged_sort_tip.block_height < sort_tip.block_height);
+        assert!(utd.check_reorg(&sortdb, &reorged_sort_tip).unwrap());
+        assert!(utd.is_aborted());
+        assert!(!utd.is_done());
+        assert_eq!(utd.state, NakamotoUnconfirmedDownloadState::Aborted);
+
+        // no more requests, and no more responses accepted
+        let peerhost = PeerHost::from_host_port("127.0.0.1".into(), 20443);
+        assert!(utd.make_next_download_request(peerhost).is_none());
+        assert!(utd
+            .try_accept_unconfirmed_tenure_blocks(vec![unconfirmed_tenure.last().cloned().unwrap()])
+            .is_err());
+
+        peer.chain.sortdb = Some(sortdb);
+    }
+
+    // we've processed the first block in the unconfirmed tenure, but not the tip, so we transition to
+    // the GetUnconfirmedTenureBlocks(..) state.
+    {
+        let mid_tip_block_id = unconfirmed_tenure.first().as_ref().unwrap().block_id();
+
+        let mut utd =
+            NakamotoUnconfirmedTenureDownloader::new(naddr.clone(), Some(mid_tip_block_id));
+        utd.confirmed_signer_keys = Some(
+            current_reward_sets
+                .get(&tip_rc)
+                .cloned()
+                .unwrap()
+                .reward_cycle_info
+                .known_selected_anchor_block_owned()
+                .unwrap(),
+        );
+        utd.unconfirmed_signer_keys = Some(
+            current_reward_sets
+                .get(&tip_rc)
+                .cloned()
+                .unwrap()
+                .reward_cycle_info
+                .known_selected_anchor_block_owned()
+                .unwrap(),
+        );
+
+        assert_eq!(utd.state, NakamotoUnconfirmedDownloadState::GetTenureInfo);
+
+        let tenure_tip = RPCGetTenureInfo {
+            consensus_hash: peer.network.stacks_tip.consensus_hash.clone(),
+            tenure_start_block_id: peer.network.tenure_start_block_id.clone(),
+            parent_consensus_hash: peer.network.parent_stacks_tip.consensus_hash.clone(),
+            parent_tenure_start_block_id: StacksBlockId::new(
+                &peer.network.parent_stacks_tip.consensus_hash,
+                &peer.network.parent_stacks_tip.block_hash,
+            ),
+            tip_block_id: StacksBlockId::new(
+                &peer.network.stacks_tip.consensus_hash,
+                &peer.network.stacks_tip.block_hash,
+            ),
+            tip_height: peer.network.stacks_tip.height,
+            reward_cycle: tip_rc,
+        };
+
+        let sortdb = peer.chain.sortdb.take().unwrap();
+        let sort_tip = SortitionDB::get_canonical_burn_chain_tip(sortdb.conn()).unwrap();
+        utd.try_accept_tenure_info(
+            &sortdb,
+            &sort_tip,
+            peer.chainstate(),
+            tenure_tip.clone(),
+            &current_reward_sets,
+        )
+        .unwrap();
+
+        peer.chain.sortdb = Some(sortdb);
+
+        assert!(utd.unconfirmed_tenure_start_block.is_some());
+
+        // because we already have processed the start-block of this unconfirmed tenure, we'll
+        // advance straight to getting more unconfirmed tenure blocks
+        assert_eq!(
+            utd.state,
+            NakamotoUnconfirmedDownloadState::GetUnconfirmedTenureBlocks(
+                tenure_tip.tip_block_id.clone(),
+            )
+        );
+        assert_eq!(utd.tenure_tip, Some(tenure_tip));
+
+        // fill in blocks
+        for (i, block) in unconfirmed_tenure.iter().enumerate().rev() {
+            let res = utd
+                .try_accept_unconfirmed_tenure_blocks(vec![block.clone()])
+                .unwrap();
+            if i == 0 {
+                // res won't contain the first block because it stopped processing once it reached
+                // a block that the node knew
+                assert_eq!(res.unwrap(), unconfirmed_tenure[1..].to_vec());
+                break;
+            } else {
+                assert!(res.is_none());
+            }
+        }
+
+        assert_eq!(utd.state, NakamotoUnconfirmedDownloadState::Done);
+
+        // we can request the highest-complete tenure
+        assert!(!utd.need_highest_complete_tenure(peer.chainstate()).unwrap());
+
+        let ntd = utd.make_highest_complete_tenure_downloader().unwrap();
+        assert_eq!(
+            ntd.state,
+            NakamotoTenureDownloadState::GetTenureStartBlock(
+                unconfirmed_wanted_tenure.winning_block_id.clone(),
+                ntd.state.request_time().unwrap()
+            )
+        );
+    }
+
+    // we've processed the middle block in the unconfirmed tenure, but not the tip, so we transition to
+    // the GetUnconfirmedTenureBlocks(..) state.
+    {
+        let mid_tip_block_id = unconfirmed_tenure.get(5).unwrap().block_id();
+
+        let mut utd =
+            NakamotoUnconfirmedTenureDownloader::new(naddr.clone(), Some(mid_tip_block_id));
+        utd.confirmed_signer_keys = Some(
+            current_reward_sets
+                .get(&tip_rc)
+                .cloned()
+                .unwrap()
+                .reward_cycle_info
+                .known_selected_anchor_block_owned()
+                .unwrap(),
+        );
+        utd.unconfirmed_signer_keys = Some(
+            current_reward_sets
+                .get(&tip_rc)
+                .cloned()
+                .unwrap()
+                .reward_cycle_info
+                .known_selected_anchor_block_owned()
+                .unwrap(),
+        );
+
+        assert_eq!(utd.state, NakamotoUnconfirmedDownloadState::GetTenureInfo);
+
+        let tenure_tip = RPCGetTenureInfo {
+            consensus_hash: peer.network.stacks_tip.consensus_hash.clone(),
+            tenure_start_block_id: peer.network.tenure_start_block_id.clone(),
+            parent_consensus_hash: peer.network.parent_stacks_tip.consensus_hash.clone(),
+            parent_tenure_start_block_id: StacksBlockId::new(
+                &peer.network.parent_stacks_tip.consensus_hash,
+                &peer.network.parent_stacks_tip.block_hash,
+            ),
+            tip_block_id: StacksBlockId::new(
+                &peer.network.stacks_tip.consensus_hash,
+                &peer.network.stacks_tip.block_hash,
+            ),
+            tip_height: peer.network.stacks_tip.height,
+            reward_cycle: tip_rc,
+        };
+
+        let sortdb = peer.chain.sortdb.take().unwrap();
+        let sort_tip = SortitionDB::get_canonical_burn_chain_tip(sortdb.conn()).unwrap();
+        utd.try_accept_tenure_info(
+            &sortdb,
+            &sort_tip,
+            peer.chainstate(),
+            tenure_tip.clone(),
+            &current_reward_sets,
+        )
+        .unwrap();
+
+        peer.chain.sortdb = Some(sortdb);
+
+        assert!(utd.unconfirmed_tenure_start_block.is_some());
+
+        // because we already have processed the start-block of this unconfirmed tenure, we'll
+        // advance straight to getting more unconfirmed tenure blocks
+        assert_eq!(
+            utd.state,
+            NakamotoUnconfirmedDownloadState::GetUnconfirmedTenureBlocks(
+                tenure_tip.tip_block_id.clone(),
+            )
+        );
+        assert_eq!(utd.tenure_tip, Some(tenure_tip));
+
+        // fill in blocks
+        for (i, block) in unconfirmed_tenure.iter().enumerate().rev() {
+            let res = utd
+                .try_accept_unconfirmed_tenure_blocks(vec![block.clone()])
+                .unwrap();
+            if i == unconfirmed_tenure.len() - 5 {
+                // got back only the blocks we were missing
+                assert_eq!(
+                    res.unwrap(),
+                    unconfirmed_tenure[(unconfirmed_tenure.len() - 4)..].to_vec()
+                );
+                break;
+            } else {
+                assert!(res.is_none());
+            }
+        }
+
+        assert_eq!(utd.state, NakamotoUnconfirmedDownloadState::Done);
+
+        // we can request the highest-complete tenure
+        assert!(!utd.need_highest_complete_tenure(peer.chainstate()).unwrap());
+
+        let ntd = utd.make_highest_complete_tenure_downloader().unwrap();
+        assert_eq!(
+            ntd.state,
+            NakamotoTenureDownloadState::GetTenureStartBlock(
+                unconfirmed_wanted_tenure.winning_block_id.clone(),
+                ntd.state.request_time().unwrap()
+            )
+        );
+    }
+
+    // we haven't processed anything yet.
+    // serve all of the unconfirmed blocks in one shot.
+    {
+        let mut utd = NakamotoUnconfirmedTenureDownloader::new(naddr.clone(), None);
+        utd.confirmed_signer_keys = Some(
+            current_reward_sets
+                .get(&tip_rc)
+                .cloned()
+                .unwrap()
+                .reward_cycle_info
+                .known_selected_anchor_block_owned()
+                .unwrap(),
+        );
+        utd.unconfirmed_signer_keys = Some(
+            current_reward_sets
+                .get(&tip_rc)
+                .cloned()
+                .unwrap()
+                .reward_cycle_info
+                .known_selected_anchor_block_owned()
+                .unwrap(),
+        );
+
+        assert_eq!(utd.state, NakamotoUnconfirmedDownloadState::GetTenureInfo);
+
+        let tenure_tip = RPCGetTenureInfo {
+            consensus_hash: peer.network.stacks_tip.consensus_hash.clone(),
+            tenure_start_block_id: peer.network.tenure_start_block_id.clone(),
+            parent_consensus_hash: peer.network.parent_stacks_tip.consensus_hash.clone(),
+            parent_tenure_start_block_id: StacksBlockId::new(
+                &peer.network.parent_stacks_tip.consensus_hash,
+                &peer.network.parent_stacks_tip.block_hash,
+            ),
+            tip_block_id: StacksBlockId::new(
+                &peer.network.stacks_tip.consensus_hash,
+                &peer.network.stacks_tip.block_hash,
+            ),
+            tip_height: peer.network.stacks_tip.height,
+            reward_cycle: tip_rc,
+        };
+
+        let sortdb = peer.chain.sortdb.take().unwrap();
+        let sort_tip = SortitionDB::get_canonical_burn_chain_tip(sortdb.conn()).unwrap();
+        utd.try_accept_tenure_info(
+            &sortdb,
+            &sort_tip,
+            peer.chainstate(),
+            tenure_tip,
+            &current_reward_sets,
+        )
+        .unwrap();
+
+        peer.chain.sortdb = Some(sortdb);
+
+        assert!(utd.unconfirmed_tenure_start_block.is_some());
+
+        let res = utd
+            .try_accept_unconfirmed_tenure_blocks(
+                unconfirmed_tenure.clone().into_iter().rev().collect(),
+            )
+            .unwrap();
+        assert_eq!(res.unwrap(), unconfirmed_tenure);
+
+        assert_eq!(utd.state, NakamotoUnconfirmedDownloadState::Done);
+
+        // we can request the highest-complete tenure
+        assert!(!utd.need_highest_complete_tenure(peer.chainstate()).unwrap());
+
+        let ntd = utd.make_highest_complete_tenure_downloader().unwrap();
+        assert_eq!(
+            ntd.state,
+            NakamotoTenureDownloadState::GetTenureStartBlock(
+                unconfirmed_wanted_tenure.winning_block_id.clone(),
+                ntd.state.request_time().unwrap()
+            )
+        );
+    }
+
+    // bad block signature
+    {
+        let mut utd = NakamotoUnconfirmedTenureDownloader::new(naddr.clone(), None);
+        utd.confirmed_signer_keys = Some(
+            current_reward_sets
+                .get(&tip_rc)
+                .cloned()
+                .unwrap()
+                .reward_cycle_info
+                .known_selected_anchor_block_owned()
+                .unwrap(),
+        );
+        utd.unconfirmed_signer_keys = Some(
+            current_reward_sets
+                .get(&tip_rc)
+                .cloned()
+                .unwrap()
+                .reward_cycle_info
+                .known_selected_anchor_block_owned()
+                .unwrap(),
+        );
+
+        assert_eq!(utd.state, NakamotoUnconfirmedDownloadState::GetTenureInfo);
+
+        let tenure_tip = RPCGetTenureInfo {
+            consensus_hash: peer.network.stacks_tip.consensus_hash.clone(),
+            tenure_start_block_id: peer.network.tenure_start_block_id.clone(),
+            parent_consensus_hash: peer.network.parent_stacks_tip.consensus_hash.clone(),
+            parent_tenure_start_block_id: StacksBlockId::new(
+                &peer.network.parent_stacks_tip.consensus_hash,
+                &peer.network.parent_stacks_tip.block_hash,
+            ),
+            tip_block_id: StacksBlockId::new(
+                &peer.network.stacks_tip.consensus_hash,
+                &peer.network.stacks_tip.block_hash,
+            ),
+            tip_height: peer.network.stacks_tip.height,
+            reward_cycle: tip_rc,
+        };
+
+        let sortdb = peer.chain.sortdb.take().unwrap();
+        let sort_tip = SortitionDB::get_canonical_burn_chain_tip(sortdb.conn()).unwrap();
+        utd.try_accept_tenure_info(
+            &sortdb,
+            &sort_tip,
+            peer.chainstate(),
+            tenure_tip,
+            &current_reward_sets,
+        )
+        .unwrap();
+
+        peer.chain.sortdb = Some(sortdb);
+
+        assert!(utd.unconfirmed_tenure_start_block.is_some());
+
+        let mut bad_block = unconfirmed_tenure.last().cloned().unwrap();
+        bad_block.header.version += 1;
+
+        assert!(utd
+            .try_accept_unconfirmed_tenure_blocks(vec![bad_block])
+            .is_err());
+    }
+
+    // Does not consume blocks beyond the highest processed block ID
+    {
+        let mut utd = NakamotoUnconfirmedTenureDownloader::new(naddr.clone(), None);
+        utd.confirmed_signer_keys = Some(
+            current_reward_sets
+                .get(&tip_rc)
+                .cloned()
+                .unwrap()
+                .reward_cycle_info
+                .known_selected_anchor_block_owned()
+                .unwrap(),
+        );
+        utd.unconfirmed_signer_keys = Some(
+            current_reward_sets
+                .get(&tip_rc)
+                .cloned()
+                .unwrap()
+                .reward_cycle_info
+                .known_selected_anchor_block_owned()
+                .unwrap(),
+        );
+
+        assert_eq!(utd.state, NakamotoUnconfirmedDownloadState::GetTenureInfo);
+
+        let tenure_tip = RPCGetTenureInfo {
+            consensus_hash: peer.network.stacks_tip.consensus_hash.clone(),
+            tenure_start_block_id: peer.network.tenure_start_block_id.clone(),
+            parent_consensus_hash: peer.network.parent_stacks_tip.consensus_hash.clone(),
+            parent_tenure_start_block_id: StacksBlockId::new(
+                &peer.network.parent_stacks_tip.consensus_hash,
+                &peer.network.parent_stacks_tip.block_hash,
+            ),
+            tip_block_id: StacksBlockId::new(
+                &peer.network.stacks_tip.consensus_hash,
+                &peer.network.stacks_tip.block_hash,
+            ),
+            tip_height: peer.network.stacks_tip.height,
+            reward_cycle: tip_rc,
+        };
+
+        let sortdb = peer.chain.sortdb.take().unwrap();
+        let sort_tip = SortitionDB::get_canonical_burn_chain_tip(sortdb.conn()).unwrap();
+        utd.try_accept_tenure_info(
+            &sortdb,
+            &sort_tip,
+            peer.chainstate(),
+            tenure_tip,
+            &current_reward_sets,
+        )
+        .unwrap();
+
+        peer.chain.sortdb = Some(sortdb);
+
+        assert!(utd.unconfirmed_tenure_start_block.is_some());
+
+        utd.highest_processed_block_id = Some(unconfirmed_tenure[1].header.block_id());
+        let res = utd
+            .try_accept_unconfirmed_tenure_blocks(
+                unconfirmed_tenure.clone().into_iter().rev().collect(),
+            )
+            .unwrap();
+        assert_eq!(res.unwrap().as_slice(), &unconfirmed_tenure[1..]);
+
+        assert_eq!(utd.state, NakamotoUnconfirmedDownloadState::Done);
+
+        // we can request the highest-complete tenure
+        assert!(!utd.need_highest_complete_tenure(peer.chainstate()).unwrap());
+
+        let ntd = utd.make_highest_complete_tenure_downloader().unwrap();
+        assert_eq!(
+            ntd.state,
+            NakamotoTenureDownloadState::GetTenureStartBlock(
+                unconfirmed_wanted_tenure.winning_block_id.clone(),
+                ntd.state.request_time().unwrap()
+            )
+        );
+    }
+
+    // If some of the ongoing tenure's blocks below the tip are missing, then the highest
+    // processed block is the one just below the gap, and the downloader fetches the gap's blocks.
+    {
+        let gap_block_id = unconfirmed_tenure[5].block_id();
+        let staging_tx = peer.chainstate().staging_db_tx_begin().unwrap();
+        staging_tx
+            .execute(
+                "DELETE FROM nakamoto_staging_blocks WHERE index_block_hash = ?1",
+                params![gap_block_id],
+            )
+            .unwrap();
+        staging_tx.commit().unwrap();
+
+        let (highest_processed_block_id, highest_processed_block_height) =
+            NakamotoChainState::highest_contiguous_processed_height(
+                peer.chainstate(),
+                &tip_block_id,
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(highest_processed_block_id, unconfirmed_tenure[4].block_id());
+        assert_eq!(
+            highest_processed_block_height,
+            unconfirmed_tenure[4].header.chain_length
+        );
+
+        let mut utd =
+            NakamotoUnconfirmedTenureDownloader::new(naddr, Some(highest_processed_block_id));
+        utd.confirmed_signer_keys = Some(
+            current_reward_sets
+                .get(&tip_rc)
+                .cloned()
+                .unwrap()
+                .reward_cycle_info
+                .known_selected_anchor_block_owned()
+                .unwrap(),
+        );
+        utd.unconfirmed_signer_keys = Some(
+            current_reward_sets
+                .get(&tip_rc)
+                .cloned()
+                .unwrap()
+                .reward_cycle_info
+                .known_selected_anchor_block_owned()
+                .unwrap(),
+        );
+
+        let tenure_tip = RPCGetTenureInfo {
+            consensus_hash: peer.network.stacks_tip.consensus_hash.clone(),
+            tenure_start_block_id: peer.network.tenure_start_block_id.clone(),
+            parent_consensus_hash: peer.network.parent_stacks_tip.consensus_hash.clone(),
+            parent_tenure_start_block_id: StacksBlockId::new(
+                &peer.network.parent_stacks_tip.consensus_hash,
+                &peer.network.parent_stacks_tip.block_hash,
+            ),
+            tip_block_id: StacksBlockId::new(
+                &peer.network.stacks_tip.consensus_hash,
+                &peer.network.stacks_tip.block_hash,
+            ),
+            tip_height: peer.network.stacks_tip.height,
+            reward_cycle: tip_rc,
+        };
+
+        let sortdb = peer.chain.sortdb.take().unwrap();
+        let sort_tip = SortitionDB::get_canonical_burn_chain_tip(sortdb.conn()).unwrap();
+        utd.try_accept_tenure_info(
+            &sortdb,
+            &sort_tip,
+            peer.chainstate(),
+            tenure_tip.clone(),
+            &current_reward_sets,
+        )
+        .unwrap();
+
+        peer.chain.sortdb = Some(sortdb);
+
+        assert_eq!(
+            utd.state,
+            NakamotoUnconfirmedDownloadState::GetUnconfirmedTenureBlocks(
+                tenure_tip.tip_block_id.clone(),
+            )
+        );
+
+        let res = utd
+            .try_accept_unconfirmed_tenure_blocks(
+                unconfirmed_tenure.clone().into_iter().rev().collect(),
+            )
+            .unwrap();
+        assert_eq!(res.unwrap().as_slice(), &unconfirmed_tenure[5..]);
+        assert_eq!(utd.state, NakamotoUnconfirmedDownloadState::Done);
+    }
+
+    // a malformed tenure-info response is retried once before the downloader gives up
     {
-        let mut empty_schedule = VecDeque::new();
-        let mut full_schedule = {
-            let mut sched = VecDeque::new();
-            sched.push_back(naddr.clone());
-            sched
+        let mid_tip_block_id = unconfirmed_tenure.first().as_ref().unwrap().block_id();
+        let tenure_tip = RPCGetTenureInfo {
+            consensus_hash: peer.network.stacks_tip.consensus_hash.clone(),
+            tenure_start_block_id: peer.network.tenure_start_block_id.clone(),
+            parent_consensus_hash: peer.network.parent_stacks_tip.consensus_hash.clone(),
+            parent_tenure_start_block_id: StacksBlockId::new(
+                &peer.network.parent_stacks_tip.consensus_hash,
+                &peer.network.parent_stacks_tip.block_hash,
+            ),
+            tip_block_id: StacksBlockId::new(
+                &peer.network.stacks_tip.consensus_hash,
+                &peer.network.stacks_tip.block_hash,
+            ),
+            tip_height: peer.network.stacks_tip.height,
+            reward_cycle: tip_rc,
+        };
+        let make_json_response = |payload: serde_json::Value| {
+            StacksHttpResponse::new(
+                HttpResponsePreamble::new(
+                    HttpVersion::Http11,
+                    200,
+                    "OK".into(),
+                    None,
+                    HttpContentType::JSON,
+                    true,
+                ),
+                HttpResponsePayload::JSON(payload),
+            )
+        };
+        let garbage = serde_json::json!({ "garbage": true });
+
+        let sortdb = peer.chain.sortdb.take().unwrap();
+        let sort_tip = SortitionDB::get_canonical_burn_chain_tip(sortdb.conn()).unwrap();
+
+        // garbage, then a valid tenure-info
+        let mut utd =
+            NakamotoUnconfirmedTenureDownloader::new(naddr.clone(), Some(mid_tip_block_id.clone()));
+        let res = utd
+            .handle_next_download_response(
+                make_json_response(garbage.clone()),
+                &sortdb,
+                &sort_tip,
+                peer.chainstate(),
+                &current_reward_sets,
+            )
+            .unwrap();
+        assert!(res.is_none());
+        assert_eq!(utd.tenure_info_retries, 1);
+        assert_eq!(utd.state, NakamotoUnconfirmedDownloadState::GetTenureInfo);
+        assert!(utd
+            .make_next_download_request(PeerHost::from_host_port("127.0.0.1".into(), 20443))
+            .is_some());
+
+        let res = utd
+            .handle_next_download_response(
+                make_json_response(serde_json::to_value(&tenure_tip).unwrap()),
+                &sortdb,
+                &sort_tip,
+                peer.chainstate(),
+                &current_reward_sets,
+            )
+            .unwrap();
+        assert!(res.is_none());
+        assert_eq!(utd.tenure_tip, Some(tenure_tip.clone()));
+
+        // we already have the tenure-start block, so we skip straight to fetching the
+        // unconfirmed blocks
+        assert_eq!(
+            utd.state,
+            NakamotoUnconfirmedDownloadState::GetUnconfirmedTenureBlocks(
+                tenure_tip.tip_block_id.clone(),
+            )
+        );
+
+        // garbage twice in a row is fatal
+        let mut utd =
+            NakamotoUnconfirmedTenureDownloader::new(naddr.clone(), Some(mid_tip_block_id));
+        assert!(utd
+            .handle_next_download_response(
+                make_json_response(garbage.clone()),
+                &sortdb,
+                &sort_tip,
+                peer.chainstate(),
+                &current_reward_sets,
+            )
+            .unwrap()
+            .is_none());
+        assert!(utd
+            .handle_next_download_response(
+                make_json_response(garbage),
+                &sortdb,
+                &sort_tip,
+                peer.chainstate(),
+                &current_reward_sets,
+            )
+            .is_err());
+
+        peer.chain.sortdb = Some(sortdb);
+    }
+}
+
+#[test]
+fn test_tenure_start_end_from_inventory() {
+    let naddr = NeighborAddress {
+        addrbytes: PeerAddress([0xff; 16]),
+        port: 123,
+        public_key_hash: Hash160([0xff; 20]),
+    };
+    let rc_len = 12u16;
+    let mut invs = NakamotoTenureInv::new(0, u64::from(rc_len), 0, naddr);
+    let pox_constants = PoxConstants::new(
+        rc_len.into(),
+        5,
+        3,
+        0,
+        25,
+        u64::MAX,
+        u64::MAX,
+        u32::MAX,
+        u32::MAX,
+        u32::MAX,
+        u32::MAX,
+    );
+    let first_burn_height = 100u64;
+
+    // make some invs
+    let num_rcs = 6;
+    invs.merge_tenure_inv(
+        BitVec::<2100>::try_from(
+            vec![
+                true, true, true, true, true, true, true, true, true, true, true, true,
+            ]
+            .as_slice(),
+        )
+        .unwrap(),
+        0,
+    );
+    invs.merge_tenure_inv(
+        BitVec::<2100>::try_from(
+            vec![
+                false, false, false, false, false, false, false, true, true, true, false, false,
+            ]
+            .as_slice(),
+        )
+        .unwrap(),
+        1,
+    );
+    invs.merge_tenure_inv(
+        BitVec::<2100>::try_from(
+            vec![
+                true, false, false, false, false, false, false, true, true, true, false, true,
+            ]
+            .as_slice(),
+        )
+        .unwrap(),
+        2,
+    );
+    invs.merge_tenure_inv(
+        BitVec::<2100>::try_from(
+            vec![
+                true, false, true, false, true, false, true, true, true, true, true, false,
+            ]
+            .as_slice(),
+        )
+        .unwrap(),
+        3,
+    );
+    invs.merge_tenure_inv(
+        BitVec::<2100>::try_from(
+            vec![
+                false, true, false, true, false, true, true, true, true, true, false, true,
+            ]
+            .as_slice(),
+        )
+        .unwrap(),
+        4,
+    );
+    invs.merge_tenure_inv(
+        BitVec::<2100>::try_from(
+            vec![
+                false, false, true, true, true, true, true, true, true, true, true, true,
+            ]
+            .as_slice(),
+        )
+        .unwrap(),
+        5,
+    );
+
+    // wanted tenures for a reward cycle start at its mod 0 burnchain block.  Block IDs start at 1,
+    // since the all-zero block ID means that the sortition had no winner.
+    let make_wanted_tenures = |rc: u64, id_offset: u16| -> Vec<WantedTenure> {
+        (0..rc_len)
+            .map(|i| {
+                WantedTenure::new(
+                    ConsensusHash([(i + id_offset) as u8; 20]),
+                    StacksBlockId([(i + id_offset + 1) as u8; 32]),
+                    pox_constants.nakamoto_first_block_of_cycle(first_burn_height, rc)
+                        + u64::from(i),
+                )
+            })
+            .collect()
+    };
+
+    // check the case where we only have one Nakamoto rewrad cycle
+    for rc in 0..num_rcs {
+        let wanted_tenures = make_wanted_tenures(rc, 0);
+        let available = TenureStartEnd::from_inventory(
+            rc,
+            &wanted_tenures,
+            None,
+            &pox_constants,
+            first_burn_height,
+            &invs,
+        )
+        .unwrap();
+        let bits = invs.tenures_inv.get(&rc).unwrap();
+        for (i, wt) in wanted_tenures.iter().enumerate() {
+            if i >= usize::from(rc_len - 1) {
+                // nothing here
+                assert!(!available.contains_key(&wt.tenure_id_consensus_hash));
+                continue;
+            }
+
+            let tenure_start_end_opt = available.get(&wt.tenure_id_consensus_hash);
+            if bits.get(i as u16).unwrap() {
+                // this sortition had a tenure
+                let mut j = (i + 1) as u16;
+                let mut tenure_start_index = None;
+                let mut tenure_end_index = None;
+
+                while j < bits.len() {
+                    if bits.get(j).unwrap() {
+                        tenure_start_index = Some(j);
+                        j += 1;
+                        break;
+                    }
+                    j += 1;
+                }
+
+                while j < bits.len() {
+                    if bits.get(j).unwrap() {
+                        tenure_end_index = Some(j);
+                        break;
+                    }
+                    j += 1;
+                }
+
+                if tenure_start_index.is_some() && tenure_end_index.is_some() {
+                    let tenure_start_end = tenure_start_end_opt.unwrap();
+                    assert_eq!(
+                        wanted_tenures[tenure_start_index.unwrap() as usize].winning_block_id,
+                        tenure_start_end.start_block_id
+                    );
+                    assert_eq!(
+                        wanted_tenures[tenure_end_index.unwrap() as usize].winning_block_id,
+                        tenure_start_end.end_block_id
+                    );
+                } else {
+                    assert!(tenure_start_end_opt.is_none());
+                }
+            } else {
+                // no tenure here
+                assert!(
+                    tenure_start_end_opt.is_none(),
+                    "tenure_start_end = {tenure_start_end_opt:?}, rc = {rc}, i = {i}, wt = {wt:?}"
+                );
+            }
+        }
+    }
+
+    // check the case where we have at least two Nakamoto rewrad cycles.
+    // the available tenures should straddle the reward cycle boundary.
+    for rc in 0..(num_rcs - 1) {
+        debug!("rc = {}", rc);
+        let wanted_tenures = make_wanted_tenures(rc, 0);
+        let next_wanted_tenures = make_wanted_tenures(rc + 1, 128);
+        let mut all_tenures = wanted_tenures.clone();
+        all_tenures.append(&mut next_wanted_tenures.clone());
+
+        let available = TenureStartEnd::from_inventory(
+            rc,
+            &wanted_tenures,
+            Some(&next_wanted_tenures),
+            &pox_constants,
+            first_burn_height,
+            &invs,
+        )
+        .unwrap();
+
+        // need to check across two reward cycles
+        let bits_cur_rc = invs.tenures_inv.get(&rc).unwrap();
+        let bits_next_rc = invs.tenures_inv.get(&(rc + 1)).unwrap();
+        let mut bits = BitVec::<2100>::zeros(rc_len * 2).unwrap();
+        for i in 0..rc_len {
+            if bits_cur_rc.get(i).unwrap() {
+                bits.set(i, true).unwrap();
+            }
+            if bits_next_rc.get(i).unwrap() {
+                bits.set(i + rc_len, true).unwrap();
+            }
+        }
+
+        for (i, wt) in wanted_tenures.iter().enumerate() {
+            let tenure_start_end_opt = available.get(&wt.tenure_id_consensus_hash);
+            if bits
+                .get(i as u16)
+                .unwrap_or_else(|| panic!("failed to get bit {i}: {wt:?}"))
+            {
+                // this sortition had a tenure
+                let mut j = (i + 1) as u16;
+                let mut tenure_start_index = None;
+                let mut tenure_end_index = None;
+
+                while j < bits.len() {
+                    if bits.get(j).unwrap() {
+                        tenure_start_index = Some(j);
+                        j += 1;
+                        break;
+                    }
+                    j += 1;
+                }
+
+                while j < bits.len() {
+                    if bits.get(j).unwrap() {
+                        tenure_end_index = Some(j);
+                        break;
+                    }
+                    j += 1;
+                }
+
+                if tenure_start_index.is_some() && tenure_end_index.is_some() {
+                    debug!(
+                        "rc = {rc}, i = {i}, tenure_start_index = {tenure_start_index:?}, tenure_end_index = {tenure_end_index:?}"
+                    );
+                    let tenure_start_end = tenure_start_end_opt.unwrap_or_else(|| {
+                        panic!("failed to get tenure_start_end_opt: i = {i}, wt = {wt:?}")
+                    });
+                    assert_eq!(
+                        all_tenures[tenure_start_index.unwrap() as usize].winning_block_id,
+                        tenure_start_end.start_block_id
+                    );
+                    assert_eq!(
+                        all_tenures[tenure_end_index.unwrap() as usize].winning_block_id,
+                        tenure_start_end.end_block_id
+                    );
+                } else {
+                    assert!(tenure_start_end_opt.is_none());
+                }
+            } else {
+                // no tenure here
+                assert!(
+                    tenure_start_end_opt.is_none(),
+                    "tenure_start_end = {tenure_start_end_opt:?}, rc = {rc}, i = {i}, wt = {wt:?}"
+                );
+            }
+        }
+    }
+}
+
+/// `TenureStartEnd::from_inventory()` refuses wanted tenure lists which do not begin on a reward
+/// cycle boundary, since it would otherwise assign the wrong reward cycles (and thus signer sets)
+/// to the tenures' blocks.
+#[test]
+fn test_tenure_start_end_from_inventory_misaligned() {
+    let naddr = NeighborAddress {
+        addrbytes: PeerAddress([0xff; 16]),
+        port: 123,
+        public_key_hash: Hash160([0xff; 20]),
+    };
+    let rc_len = 12u16;
+    let mut invs = NakamotoTenureInv::new(0, u64::from(rc_len), 0, naddr);
+    let pox_constants = PoxConstants::new(
+        rc_len.into(),
+        5,
+        3,
+        0,
+        25,
+        u64::MAX,
+        u64::MAX,
+        u32::MAX,
+        u32::MAX,
+        u32::MAX,
+        u32::MAX,
+    );
+    let first_burn_height = 100u64;
+
+    for rc in [1, 2] {
+        invs.merge_tenure_inv(
+            BitVec::<2100>::try_from(
+                vec![
+                    true, true, true, true, true, true, true, true, true, true, true, true,
+                ]
+                .as_slice(),
+            )
+            .unwrap(),
+            rc,
+        );
+    }
+
+    let make_wanted_tenures = |start_height: u64, id_offset: u16| -> Vec<WantedTenure> {
+        (0..rc_len)
+            .map(|i| {
+                WantedTenure::new(
+                    ConsensusHash([(i + id_offset) as u8; 20]),
+                    StacksBlockId([(i + id_offset) as u8; 32]),
+                    start_height + u64::from(i),
+                )
+            })
+            .collect()
+    };
+
+    let rc = 1;
+    let rc_start = pox_constants.nakamoto_first_block_of_cycle(first_burn_height, rc);
+    let next_rc_start = pox_constants.nakamoto_first_block_of_cycle(first_burn_height, rc + 1);
+
+    let wanted_tenures = make_wanted_tenures(rc_start, 0);
+    let next_wanted_tenures = make_wanted_tenures(next_rc_start, 128);
+
+    // aligned
+    let available = TenureStartEnd::from_inventory(
+        rc,
+        &wanted_tenures,
+        Some(&next_wanted_tenures),
+        &pox_constants,
+        first_burn_height,
+        &invs,
+    )
+    .unwrap();
+    assert!(!available.is_empty());
+
+    // wanted tenures start at the mod 1 block instead of the mod 0 block
+    let misaligned_wanted_tenures = make_wanted_tenures(rc_start + 1, 0);
+    assert!(TenureStartEnd::from_inventory(
+        rc,
+        &misaligned_wanted_tenures,
+        None,
+        &pox_constants,
+        first_burn_height,
+        &invs,
+    )
+    .is_none());
+    assert!(TenureStartEnd::from_inventory(
+        rc,
+        &misaligned_wanted_tenures,
+        Some(&next_wanted_tenures),
+        &pox_constants,
+        first_burn_height,
+        &invs,
+    )
+    .is_none());
+
+    // next wanted tenures are in the wrong reward cycle
+    assert!(TenureStartEnd::from_inventory(
+        rc,
+        &wanted_tenures,
+        Some(&wanted_tenures),
+        &pox_constants,
+        first_burn_height,
+        &invs,
+    )
+    .is_none());
+
+    // next wanted tenures are off by one
+    let misaligned_next_wanted_tenures = make_wanted_tenures(next_rc_start - 1, 128);
+    assert!(TenureStartEnd::from_inventory(
+        rc,
+        &wanted_tenures,
+        Some(&misaligned_next_wanted_tenures),
+        &pox_constants,
+        first_burn_height,
+        &invs,
+    )
+    .is_none());
+}
+
+/// Generate the wanted tenures for reward cycle `rc`, of which there are `num_tenures`
+fn make_wanted_tenures_for_cycle(
+    pox_constants: &PoxConstants,
+    first_burn_height: u64,
+    rc: u64,
+    num_tenures: u64,
+) -> Vec<WantedTenure> {
+    let rc_start = pox_constants.nakamoto_first_block_of_cycle(first_burn_height, rc);
+    (0..num_tenures)
+        .map(|i| {
+            let burn_height = rc_start + i;
+            let mut bytes = [0u8; 20];
+            bytes[0..8].copy_from_slice(&burn_height.to_be_bytes());
+            let mut wt = WantedTenure::new(
+                ConsensusHash(bytes),
+                StacksBlockId(Sha512Trunc256Sum::from_data(&bytes).0),
+                burn_height,
+            );
+            wt.processed = i % 3 == 0;
+            wt
+        })
+        .collect()
+}
+
+/// `TenureStartEnd::from_inventory_bits()` upholds the invariants checked by
+/// `TenureStartEnd::check_available_tenures()` for arbitrary inventory bits, with and without the
+/// next reward cycle's wanted tenures and inventory bits.
+#[test]
+fn test_tenure_start_end_from_inventory_bits_invariants() {
+    let rc_len = 12u16;
+    let pox_constants = PoxConstants::new(
+        rc_len.into(),
+        5,
+        3,
+        0,
+        25,
+        u64::MAX,
+        u64::MAX,
+        u32::MAX,
+        u32::MAX,
+        u32::MAX,
+        u32::MAX,
+    );
+    let first_burn_height = 100u64;
+    let mut rng = thread_rng();
+
+    for _ in 0..1000 {
+        let rc = rng.gen_range(0..10);
+        let wanted_tenures = make_wanted_tenures_for_cycle(
+            &pox_constants,
+            first_burn_height,
+            rc,
+            rng.gen_range(0..=u64::from(rc_len)),
+        );
+        let next_wanted_tenures = make_wanted_tenures_for_cycle(
+            &pox_constants,
+            first_burn_height,
+            rc + 1,
+            rng.gen_range(0..=u64::from(rc_len)),
+        );
+        let random_bits = |rng: &mut rand::rngs::ThreadRng| {
+            let bits: Vec<bool> = (0..rc_len).map(|_| rng.gen_bool(0.5)).collect();
+            BitVec::<2100>::try_from(bits.as_slice()).unwrap()
+        };
+        let invbits = random_bits(&mut rng);
+        let next_invbits = random_bits(&mut rng);
+
+        let next_wanted_tenures_opt = rng.gen_bool(0.75).then_some(next_wanted_tenures.as_slice());
+        let next_invbits_opt = rng.gen_bool(0.75).then_some(&next_invbits);
+
+        let available = TenureStartEnd::from_inventory_bits(
+            rc,
+            &wanted_tenures,
+            &invbits,
+            next_wanted_tenures_opt,
+            next_invbits_opt,
+            &pox_constants,
+            first_burn_height,
+        );
+        assert_eq!(
+            TenureStartEnd::check_available_tenures(
+                rc,
+                &wanted_tenures,
+                &invbits,
+                next_wanted_tenures_opt,
+                next_invbits_opt,
+                &available,
+            ),
+            Ok(()),
+            "rc = {rc}, invbits = {invbits:?}, next_invbits = {next_invbits_opt:?}, wanted_tenures = {wanted_tenures:?}, next_wanted_tenures = {next_wanted_tenures_opt:?}"
+        );
+
+        for tse in available.values() {
+            assert_eq!(tse.start_reward_cycle, rc);
+            assert!(tse.end_reward_cycle == rc || tse.end_reward_cycle == rc + 1);
+            assert_ne!(tse.start_block_id, tse.end_block_id);
+        }
+
+        // the checker notices if a tenure goes missing
+        if let Some(ch) = available.keys().next().cloned() {
+            let mut broken = available.clone();
+            broken.remove(&ch);
+            assert!(TenureStartEnd::check_available_tenures(
+                rc,
+                &wanted_tenures,
+                &invbits,
+                next_wanted_tenures_opt,
+                next_invbits_opt,
+                &broken,
+            )
+            .is_err());
+        }
+    }
+}
+
+/// A wanted tenure whose sortition had no winner (i.e. whose winning block ID is the all-zero
+/// sentinel) is never turned into a `TenureStartEnd`, never used as another tenure's start or end
+/// block, and never scheduled for download, even if a peer's inventory claims to have it.
+#[test]
+fn test_wanted_tenure_without_winner_is_never_scheduled() {
+    let naddr = NeighborAddress {
+        addrbytes: PeerAddress([0xff; 16]),
+        port: 123,
+        public_key_hash: Hash160([0xff; 20]),
+    };
+    let rc_len = 12u16;
+    let pox_constants = PoxConstants::new(
+        rc_len.into(),
+        5,
+        3,
+        0,
+        25,
+        u64::MAX,
+        u64::MAX,
+        u32::MAX,
+        u32::MAX,
+        u32::MAX,
+        u32::MAX,
+    );
+    let first_burn_height = 100u64;
+    let rc = 1;
+
+    let mut wanted_tenures =
+        make_wanted_tenures_for_cycle(&pox_constants, first_burn_height, rc, rc_len.into());
+    for wt in wanted_tenures.iter_mut() {
+        wt.processed = false;
+    }
+    let no_winner_idx = 4;
+    wanted_tenures[no_winner_idx].winning_block_id = StacksBlockId([0x00; 32]);
+    let no_winner_ch = wanted_tenures[no_winner_idx]
+        .tenure_id_consensus_hash
+        .clone();
+    assert!(!wanted_tenures[no_winner_idx].has_winner());
+    assert!(wanted_tenures
+        .iter()
+        .enumerate()
+        .all(|(i, wt)| i == no_winner_idx || wt.has_winner()));
+
+    // the peer claims to have every tenure, including the one without a winner
+    let mut invs = NakamotoTenureInv::new(0, u64::from(rc_len), 0, naddr.clone());
+    invs.merge_tenure_inv(BitVec::<2100>::ones(rc_len).unwrap(), rc);
+
+    let available_tenures = TenureStartEnd::from_inventory(
+        rc,
+        &wanted_tenures,
+        None,
+        &pox_constants,
+        first_burn_height,
+        &invs,
+    )
+    .unwrap();
+    assert!(!available_tenures.is_empty());
+    assert!(!available_tenures.contains_key(&no_winner_ch));
+    for tse in available_tenures.values() {
+        assert_ne!(tse.start_block_id, StacksBlockId([0x00; 32]));
+        assert_ne!(tse.end_block_id, StacksBlockId([0x00; 32]));
+        assert_ne!(tse.start_block_snapshot_consensus_hash, no_winner_ch);
+        assert_ne!(tse.end_block_snapshot_consensus_hash, no_winner_ch);
+    }
+
+    // the tenure before it skips over it to find its start block
+    let prev_ch = &wanted_tenures[no_winner_idx - 1].tenure_id_consensus_hash;
+    assert_eq!(
+        available_tenures.get(prev_ch).unwrap().start_block_id,
+        wanted_tenures[no_winner_idx + 1].winning_block_id
+    );
+
+    // the tenure is not available from any peer
+    let inventories = HashMap::from([(naddr.clone(), invs)]);
+    let available = NakamotoDownloadStateMachine::find_available_tenures(
+        rc,
+        &wanted_tenures,
+        &HashSet::new(),
+        inventories.iter(),
+    );
+    assert!(!available.contains_key(&no_winner_ch));
+
+    // ...and even if it was, it would not be scheduled
+    let mut available = available;
+    available.insert(no_winner_ch.clone(), vec![naddr]);
+    let ibd_schedule =
+        NakamotoDownloadStateMachine::make_ibd_download_schedule(0, &wanted_tenures, &available);
+    assert_eq!(ibd_schedule.len(), available.len() - 1);
+    assert!(!ibd_schedule.contains(&no_winner_ch));
+
+    let rarest_first_schedule = NakamotoDownloadStateMachine::make_rarest_first_download_schedule(
+        0,
+        &wanted_tenures,
+        &available,
+    );
+    assert_eq!(rarest_first_schedule.len(), available.len() - 1);
+    assert!(!rarest_first_schedule.contains(&no_winner_ch));
+}
+
+/// The downloader stays in Confirmed mode while the sortition tip and the burnchain height
+/// disagree by more than the configured tolerance.
+#[test]
+fn test_need_unconfirmed_tenures_burnchain_divergence() {
+    let wanted_tenures = vec![WantedTenure {
+        tenure_id_consensus_hash: ConsensusHash([0x01; 20]),
+        winning_block_id: StacksBlockId([0x01; 32]),
+        burn_height: 100,
+        processed: true,
+    }];
+    let prev_wanted_tenures = vec![WantedTenure {
+        tenure_id_consensus_hash: ConsensusHash([0x02; 20]),
+        winning_block_id: StacksBlockId([0x02; 32]),
+        burn_height: 99,
+        processed: true,
+    }];
+    let naddr = NeighborAddress {
+        addrbytes: PeerAddress([0xff; 16]),
+        port: 123,
+        public_key_hash: Hash160([0xff; 20]),
+    };
+    let tenure_block_ids = HashMap::from([(naddr, AvailableTenures::new())]);
+    let available_tenures = HashMap::new();
+    let default_divergence =
+        ConnectionOptions::default().nakamoto_unconfirmed_downloader_max_burnchain_divergence;
+    assert_eq!(default_divergence, 0);
+
+    let need_unconfirmed_tenures =
+        |burnchain_height: u64, sort_tip_height: u64, max_divergence: u64| {
+            let mut sort_tip = BlockSnapshot::empty();
+            sort_tip.block_height = sort_tip_height;
+            NakamotoDownloadStateMachine::find_unconfirmed_tenures_blocker(
+                burnchain_height,
+                &sort_tip,
+                max_divergence,
+                &wanted_tenures,
+                &prev_wanted_tenures,
+                &tenure_block_ids,
+                &available_tenures,
+            )
+            .is_none()
+        };
+
+    // by default, the sortition tip must be at the burnchain tip
+    assert!(need_unconfirmed_tenures(100, 100, default_divergence));
+    assert!(!need_unconfirmed_tenures(101, 100, default_divergence));
+    assert!(!need_unconfirmed_tenures(100, 101, default_divergence));
+
+    // in sync
+    assert!(need_unconfirmed_tenures(100, 100, 1));
+    assert!(need_unconfirmed_tenures(100, 101, 1));
+
+    // sortition tip lags the burnchain by 1, which is tolerated
+    assert!(need_unconfirmed_tenures(101, 100, 1));
+
+    // sortition tip lags the burnchain by 2
+    assert!(!need_unconfirmed_tenures(102, 100, 1));
+
+    // sortition tip is ahead of our burnchain view by 2
+    assert!(!need_unconfirmed_tenures(100, 102, 1));
+}
+
+/// With the default tolerance, the downloader stays in Confirmed mode while the sortition tip lags
+/// the burnchain at all.  With a tolerance of 1, it may fetch unconfirmed tenures while the
+/// sortition tip lags the burnchain by 1 block, but stays in Confirmed mode if it lags by 2.
+#[test]
+fn test_sortition_lag_keeps_downloader_confirmed() {
+    let observer = TestEventObserver::new();
+    let bitvecs = vec![vec![
+        true, true, true, true, true, true, true, true, true, true,
+    ]];
+
+    let rc_len = 10u64;
+    let peer = make_nakamoto_peer_from_invs(function_name!(), &observer, rc_len as u32, 3, bitvecs);
+    let (mut peer, _reward_cycle_invs) =
+        peer_get_nakamoto_invs(peer, &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12]);
+    assert_eq!(
+        peer.network
+            .get_connection_opts()
+            .nakamoto_unconfirmed_downloader_max_burnchain_divergence,
+        0
+    );
+
+    let nakamoto_start = NakamotoBootPlan::nakamoto_first_tenure_height(
+        &peer.config.chain_config.burnchain.pox_constants,
+    );
+    let sortdb = peer.chain.sortdb.take().unwrap();
+    let sort_tip_height = peer.network.burnchain_tip.block_height;
+
+    let mut run_pass = |burnchain_height: u64, max_divergence: u64| {
+        peer.network
+            .connection_opts
+            .nakamoto_unconfirmed_downloader_max_burnchain_divergence = max_divergence;
+        let mut downloader =
+            NakamotoDownloadStateMachine::new(nakamoto_start, peer.network.stacks_tip.block_id());
+        downloader
+            .run(
+                burnchain_height,
+                &mut peer.network,
+                &sortdb,
+                &mut peer.chain.stacks_node.as_mut().unwrap().chainstate,
+                Some(false),
+            )
+            .unwrap();
+        downloader
+    };
+
+    // by default, a lag of 1 blocks unconfirmed tenure downloads
+    let downloader = run_pass(sort_tip_height + 1, 0);
+    assert_eq!(
+        downloader.unconfirmed_tenures_blocker(),
+        Some(&UnconfirmedTenuresBlocker::SortitionBehindBurnchain {
+            sort_tip_height,
+            burnchain_height: sort_tip_height + 1,
+        })
+    );
+    assert_eq!(downloader.metrics().state, NakamotoDownloadState::Confirmed);
+
+    // with a tolerance of 1, a lag of 1 doesn't block unconfirmed tenure downloads
+    let downloader = run_pass(sort_tip_height + 1, 1);
+    assert!(!matches!(
+        downloader.unconfirmed_tenures_blocker(),
+        Some(UnconfirmedTenuresBlocker::SortitionBehindBurnchain { .. })
+            | Some(UnconfirmedTenuresBlocker::BurnchainDivergence { .. })
+    ));
+
+    // but a lag of 2 does
+    let downloader = run_pass(sort_tip_height + 2, 1);
+    assert_eq!(
+        downloader.unconfirmed_tenures_blocker(),
+        Some(&UnconfirmedTenuresBlocker::SortitionBehindBurnchain {
+            sort_tip_height,
+            burnchain_height: sort_tip_height + 2,
+        })
+    );
+    assert_eq!(downloader.metrics().state, NakamotoDownloadState::Confirmed);
+}
+
+/// Each condition which keeps the downloader from fetching unconfirmed tenures is reported as
+/// such.
+#[test]
+fn test_find_unconfirmed_tenures_blocker() {
+    let wanted_tenures = vec![WantedTenure {
+        tenure_id_consensus_hash: ConsensusHash([0x01; 20]),
+        winning_block_id: StacksBlockId([0x01; 32]),
+        burn_height: 100,
+        processed: true,
+    }];
+    let prev_wanted_tenures = vec![WantedTenure {
+        tenure_id_consensus_hash: ConsensusHash([0x02; 20]),
+        winning_block_id: StacksBlockId([0x02; 32]),
+        burn_height: 99,
+        processed: true,
+    }];
+    let naddr = NeighborAddress {
+        addrbytes: PeerAddress([0xff; 16]),
+        port: 123,
+        public_key_hash: Hash160([0xff; 20]),
+    };
+    let tenure_block_ids = HashMap::from([(naddr.clone(), AvailableTenures::new())]);
+    let available_tenures = HashMap::new();
+    let mut sort_tip = BlockSnapshot::empty();
+    sort_tip.block_height = 100;
+
+    let find_blocker =
+        |burnchain_height: u64,
+         sort_tip: &BlockSnapshot,
+         wanted_tenures: &[WantedTenure],
+         prev_wanted_tenures: &[WantedTenure],
+         tenure_block_ids: &HashMap<NeighborAddress, AvailableTenures>| {
+            NakamotoDownloadStateMachine::find_unconfirmed_tenures_blocker(
+                burnchain_height,
+                sort_tip,
+                1,
+                wanted_tenures,
+                prev_wanted_tenures,
+                tenure_block_ids,
+                &available_tenures,
+            )
         };
-        let mut empty_downloaders = HashMap::new();
-        let mut full_downloaders = {
-            let mut dl = HashMap::new();
-            let utd =
-                NakamotoUnconfirmedTenureDownloader::new(naddr.clone(), Some(tip_block_id.clone()));
-            dl.insert(naddr.clone(), utd);
-            dl
+
+    assert_eq!(
+        find_blocker(
+            100,
+            &sort_tip,
+            &wanted_tenures,
+            &prev_wanted_tenures,
+            &tenure_block_ids
+        ),
+        None
+    );
+
+    assert_eq!(
+        find_blocker(
+            101,
+            &sort_tip,
+            &wanted_tenures,
+            &prev_wanted_tenures,
+            &tenure_block_ids
+        ),
+        None
+    );
+
+    let blocker = find_blocker(
+        102,
+        &sort_tip,
+        &wanted_tenures,
+        &prev_wanted_tenures,
+        &tenure_block_ids,
+    )
+    .unwrap();
+    assert_eq!(
+        blocker,
+        UnconfirmedTenuresBlocker::SortitionBehindBurnchain {
+            sort_tip_height: 100,
+            burnchain_height: 102
+        }
+    );
+    assert_eq!(blocker.reason(), "sortition_behind_burnchain");
+
+    let blocker = find_blocker(
+        98,
+        &sort_tip,
+        &wanted_tenures,
+        &prev_wanted_tenures,
+        &tenure_block_ids,
+    )
+    .unwrap();
+    assert_eq!(
+        blocker,
+        UnconfirmedTenuresBlocker::BurnchainDivergence {
+            sort_tip_height: 100,
+            burnchain_height: 98
+        }
+    );
+    assert_eq!(blocker.reason(), "burnchain_divergence");
+
+    let blocker =
+        find_blocker(100, &sort_tip, &[], &prev_wanted_tenures, &tenure_block_ids).unwrap();
+    assert_eq!(blocker, UnconfirmedTenuresBlocker::NoWantedTenures);
+    assert_eq!(blocker.reason(), "no_wanted_tenures");
+
+    let blocker = find_blocker(100, &sort_tip, &wanted_tenures, &[], &tenure_block_ids).unwrap();
+    assert_eq!(blocker, UnconfirmedTenuresBlocker::NoPrevWantedTenures);
+    assert_eq!(blocker.reason(), "no_prev_wanted_tenures");
+
+    let blocker = find_blocker(
+        100,
+        &sort_tip,
+        &wanted_tenures,
+        &prev_wanted_tenures,
+        &HashMap::new(),
+    )
+    .unwrap();
+    assert_eq!(blocker, UnconfirmedTenuresBlocker::NoTenureAvailability);
+    assert_eq!(blocker.reason(), "no_tenure_availability");
+
+    // the neighbor can serve the previous reward cycle's tenure, but we haven't processed it, and
+    // it isn't one of the tenures that only the unconfirmed downloader can get
+    let unprocessed_tenure = TenureStartEnd::new(
+        ConsensusHash([0x02; 20]),
+        99,
+        ConsensusHash([0x02; 20]),
+        StacksBlockId([0x02; 32]),
+        ConsensusHash([0x01; 20]),
+        StacksBlockId([0x01; 32]),
+        0,
+        0,
+        false,
+    );
+    let tenure_block_ids = HashMap::from([(
+        naddr,
+        AvailableTenures::from([(ConsensusHash([0x02; 20]), unprocessed_tenure)]),
+    )]);
+    let blocker = find_blocker(
+        100,
+        &sort_tip,
+        &wanted_tenures,
+        &prev_wanted_tenures,
+        &tenure_block_ids,
+    )
+    .unwrap();
+    assert_eq!(
+        blocker,
+        UnconfirmedTenuresBlocker::UnprocessedTenure(ConsensusHash([0x02; 20]))
+    );
+    assert_eq!(blocker.reason(), "unprocessed_tenure");
+}
+
+/// Wanted tenures which no neighbor advertises are not reported as available, and do not
+/// generate download schedule entries.
+#[test]
+fn test_find_available_tenures_skips_unservable_tenures() {
+    let rc_len = 6u16;
+    let wanted_tenures: Vec<_> = (0..rc_len)
+        .map(|i| WantedTenure {
+            tenure_id_consensus_hash: ConsensusHash([u8::try_from(i + 1).unwrap(); 20]),
+            winning_block_id: StacksBlockId([u8::try_from(i + 1).unwrap(); 32]),
+            burn_height: 100 + u64::from(i),
+            processed: false,
+        })
+        .collect();
+
+    // two neighbors, which both lack the last three tenures
+    let mut inventories = HashMap::new();
+    for port in [123, 456] {
+        let naddr = NeighborAddress {
+            addrbytes: PeerAddress([0xff; 16]),
+            port,
+            public_key_hash: Hash160([0xff; 20]),
         };
-        assert_eq!(
-            NakamotoDownloadStateMachine::make_unconfirmed_tenure_downloaders(
-                &mut empty_schedule,
-                10,
-                &mut empty_downloaders,
-                None
-            ),
-            0
+        let mut invs = NakamotoTenureInv::new(0, u64::from(rc_len), 0, naddr.clone());
+        invs.merge_tenure_inv(
+            BitVec::<2100>::try_from(vec![true, true, true, false, false, false].as_slice())
+                .unwrap(),
+            1,
         );
-        assert_eq!(
-            NakamotoDownloadStateMachine::make_unconfirmed_tenure_downloaders(
-                &mut empty_schedule,
-                10,
-                &mut full_downloaders,
-                None
-            ),
-            0
+        inventories.insert(naddr, invs);
+    }
+
+    let available = NakamotoDownloadStateMachine::find_available_tenures(
+        1,
+        &wanted_tenures,
+        &HashSet::new(),
+        inventories.iter(),
+    );
+    assert_eq!(available.len(), 3);
+    for (i, wt) in wanted_tenures.iter().enumerate() {
+        if i < 3 {
+            assert_eq!(
+                available.get(&wt.tenure_id_consensus_hash).unwrap().len(),
+                2
+            );
+        } else {
+            assert!(available.get(&wt.tenure_id_consensus_hash).is_none());
+        }
+    }
+
+    let ibd_schedule =
+        NakamotoDownloadStateMachine::make_ibd_download_schedule(0, &wanted_tenures, &available);
+    let rarest_first_schedule = NakamotoDownloadStateMachine::make_rarest_first_download_schedule(
+        0,
+        &wanted_tenures,
+        &available,
+    );
+    for schedule in [ibd_schedule, rarest_first_schedule] {
+        assert_eq!(schedule.len(), 3);
+        for ch in schedule.iter() {
+            assert!(!available.get(ch).unwrap().is_empty());
+        }
+    }
+
+    // no neighbor has any tenure in this reward cycle
+    let available = NakamotoDownloadStateMachine::find_available_tenures(
+        2,
+        &wanted_tenures,
+        &HashSet::new(),
+        inventories.iter(),
+    );
+    assert!(available.is_empty());
+    assert!(NakamotoDownloadStateMachine::make_ibd_download_schedule(
+        0,
+        &wanted_tenures,
+        &available
+    )
+    .is_empty());
+}
+
+/// If the caller doesn't say whether or not the node is in IBD, a large download backlog yields
+/// IBD (sortition-order) scheduling, and a small one yields steady-state (rarest-first)
+/// scheduling.
+#[test]
+fn test_infer_ibd_from_download_backlog() {
+    let rc_len = 6u16;
+    let mut wanted_tenures: Vec<_> = (0..rc_len)
+        .map(|i| WantedTenure {
+            tenure_id_consensus_hash: ConsensusHash([u8::try_from(i + 1).unwrap(); 20]),
+            winning_block_id: StacksBlockId([u8::try_from(i + 1).unwrap(); 32]),
+            burn_height: 100 + u64::from(i),
+            processed: false,
+        })
+        .collect();
+
+    // later tenures are rarer
+    let mut available = HashMap::new();
+    for (i, wt) in wanted_tenures.iter().enumerate() {
+        let naddrs: Vec<_> = (0..(wanted_tenures.len() - i))
+            .map(|port| NeighborAddress {
+                addrbytes: PeerAddress([0xff; 16]),
+                port: u16::try_from(port).unwrap(),
+                public_key_hash: Hash160([0xff; 20]),
+            })
+            .collect();
+        available.insert(wt.tenure_id_consensus_hash.clone(), naddrs);
+    }
+
+    let threshold = 4;
+    let mut sort_tip = BlockSnapshot::empty();
+    sort_tip.block_height = 105;
+
+    // large backlog: all six tenures are unprocessed
+    let ibd =
+        NakamotoDownloadStateMachine::infer_ibd(105, &sort_tip, &wanted_tenures, &[], threshold);
+    assert!(ibd);
+    let schedule =
+        NakamotoDownloadStateMachine::make_download_schedule(0, &wanted_tenures, &available, ibd);
+    let expected: Vec<_> = wanted_tenures
+        .iter()
+        .map(|wt| wt.tenure_id_consensus_hash.clone())
+        .collect();
+    assert_eq!(schedule, expected);
+
+    // backlog counts tenures in the previous reward cycle too
+    assert!(NakamotoDownloadStateMachine::infer_ibd(
+        105,
+        &sort_tip,
+        &wanted_tenures[3..],
+        &wanted_tenures[..3],
+        threshold
+    ));
+
+    // small backlog: only the last three tenures are unprocessed
+    for wt in wanted_tenures.iter_mut().take(3) {
+        wt.processed = true;
+    }
+    let ibd =
+        NakamotoDownloadStateMachine::infer_ibd(105, &sort_tip, &wanted_tenures, &[], threshold);
+    assert!(!ibd);
+    let schedule =
+        NakamotoDownloadStateMachine::make_download_schedule(0, &wanted_tenures, &available, ibd);
+    let expected: Vec<_> = wanted_tenures
+        .iter()
+        .rev()
+        .take(3)
+        .map(|wt| wt.tenure_id_consensus_hash.clone())
+        .collect();
+    assert_eq!(schedule, expected);
+
+    // burnchain blocks the sortition DB hasn't processed yet count towards the backlog
+    assert!(NakamotoDownloadStateMachine::infer_ibd(
+        106,
+        &sort_tip,
+        &wanted_tenures,
+        &[],
+        threshold
+    ));
+
+    // sortitions without a winner don't
+    wanted_tenures[0].processed = false;
+    wanted_tenures[0].winning_block_id = StacksBlockId([0x00; 32]);
+    assert!(!NakamotoDownloadStateMachine::infer_ibd(
+        105,
+        &sort_tip,
+        &wanted_tenures,
+        &[],
+        threshold
+    ));
+}
+
+/// The reward cycle completion handler is told exactly once when every tenure with a winner in
+/// the previous reward cycle has been processed.
+#[test]
+fn test_reward_cycle_complete_handler() {
+    let rc = 5;
+    let mut prev_wanted_tenures: Vec<_> = (0..4u8)
+        .map(|i| {
+            WantedTenure::new(
+                ConsensusHash([i; 20]),
+                StacksBlockId([i + 1; 32]),
+                100 + u64::from(i),
+            )
+        })
+        .collect();
+    // this sortition had no winner, so there is no tenure to process
+    prev_wanted_tenures[2].winning_block_id = StacksBlockId([0x00; 32]);
+
+    let completed = Arc::new(Mutex::new(vec![]));
+    let handler_completed = completed.clone();
+    let mut downloader = NakamotoDownloadStateMachine::new(0, StacksBlockId([0x00; 32]));
+    downloader.set_reward_cycle_complete_handler(Box::new(move |reward_cycle: u64| {
+        handler_completed.lock().unwrap().push(reward_cycle);
+    }));
+    downloader.reward_cycle = rc;
+    downloader.wanted_tenures = vec![WantedTenure::new(
+        ConsensusHash([0x10; 20]),
+        StacksBlockId([0x10; 32]),
+        104,
+    )];
+    downloader.prev_wanted_tenures = Some(prev_wanted_tenures);
+
+    // nothing processed yet
+    downloader.check_reward_cycle_complete();
+    assert!(completed.lock().unwrap().is_empty());
+
+    // process the tenures one at a time
+    for i in [0, 1, 3] {
+        downloader.prev_wanted_tenures.as_mut().unwrap()[i].processed = true;
+        downloader.check_reward_cycle_complete();
+    }
+    assert_eq!(*completed.lock().unwrap(), vec![rc - 1]);
+    assert_eq!(downloader.last_complete_reward_cycle, Some(rc - 1));
+
+    // not told again
+    downloader.check_reward_cycle_complete();
+    assert_eq!(*completed.lock().unwrap(), vec![rc - 1]);
+
+    // the ongoing reward cycle isn't reported while its tenures are still coming in
+    downloader.wanted_tenures[0].processed = true;
+    downloader.check_reward_cycle_complete();
+    assert_eq!(*completed.lock().unwrap(), vec![rc - 1]);
+}
+
+/// Once several download passes in a row find no tenures to download, the downloader stops
+/// recomputing the available tenures until its peers' inventories or its wanted tenures change, or
+/// a peer's blocklisting expires.
+#[test]
+fn test_empty_pass_backoff() {
+    let naddr = NeighborAddress {
+        addrbytes: PeerAddress([0xff; 16]),
+        port: 123,
+        public_key_hash: Hash160([0xff; 20]),
+    };
+    let rc_len = 12u16;
+    let pox_constants = PoxConstants::new(
+        rc_len.into(),
+        5,
+        3,
+        0,
+        25,
+        u64::MAX,
+        u64::MAX,
+        u32::MAX,
+        u32::MAX,
+        u32::MAX,
+        u32::MAX,
+    );
+    let first_burn_height = 100u64;
+    let rc = 1;
+
+    let wanted_tenures: Vec<_> = (0..rc_len)
+        .map(|i| {
+            WantedTenure::new(
+                ConsensusHash([u8::try_from(i).unwrap(); 20]),
+                StacksBlockId([u8::try_from(i + 1).unwrap(); 32]),
+                pox_constants.nakamoto_first_block_of_cycle(first_burn_height, rc) + u64::from(i),
+            )
+        })
+        .collect();
+
+    let mut downloader =
+        NakamotoDownloadStateMachine::with_rpc(0, StacksBlockId([0x00; 32]), NeighborRPC::new());
+    downloader.reward_cycle = rc;
+    downloader.wanted_tenures = wanted_tenures;
+
+    // our only peer has none of the tenures
+    let mut invs = NakamotoTenureInv::new(0, u64::from(rc_len), 0, naddr.clone());
+    invs.merge_tenure_inv(BitVec::<2100>::zeros(rc_len).unwrap(), rc);
+    let mut inventories = HashMap::from([(naddr.clone(), invs)]);
+
+    // the backoff engages once enough passes in a row find nothing to download
+    for i in 1..=3 {
+        downloader.update_available_tenures(&inventories, &pox_constants, first_burn_height, true);
+        assert!(downloader.tenure_download_schedule.is_empty());
+        assert_eq!(downloader.num_empty_passes, i);
+    }
+    assert!(downloader.empty_pass_backoff_deadline_ms > get_epoch_time_ms());
+
+    // while it's engaged, the available tenures aren't recomputed
+    for _ in 0..3 {
+        downloader.update_available_tenures(&inventories, &pox_constants, first_burn_height, true);
+        assert!(downloader.tenure_download_schedule.is_empty());
+        assert_eq!(downloader.num_empty_passes, 3);
+    }
+
+    // the peer sends the same inventory again, which doesn't change its generation, so the
+    // backoff stays engaged
+    let generation = inventories[&naddr].generation;
+    assert!(!inventories
+        .get_mut(&naddr)
+        .unwrap()
+        .merge_tenure_inv(BitVec::<2100>::zeros(rc_len).unwrap(), rc));
+    assert_eq!(inventories[&naddr].generation, generation);
+    downloader.update_available_tenures(&inventories, &pox_constants, first_burn_height, true);
+    assert_eq!(downloader.num_empty_passes, 3);
+    assert!(downloader.empty_pass_backoff_deadline_ms > get_epoch_time_ms());
+
+    // a wanted tenure gets processed, so the available tenures are recomputed right away (and
+    // there's still nothing to download)
+    downloader.wanted_tenures.last_mut().unwrap().processed = true;
+    downloader.update_available_tenures(&inventories, &pox_constants, first_burn_height, true);
+    assert!(downloader.tenure_download_schedule.is_empty());
+    assert_eq!(downloader.num_empty_passes, 1);
+    assert_eq!(downloader.empty_pass_backoff_deadline_ms, 0);
+
+    // engage the backoff again, with the peer blocklisted
+    downloader.blocklist_peer(naddr.clone(), get_epoch_time_secs());
+    for i in 2..=3 {
+        downloader.update_available_tenures(&inventories, &pox_constants, first_burn_height, true);
+        assert_eq!(downloader.num_empty_passes, i);
+    }
+    assert!(downloader.empty_pass_backoff_deadline_ms > get_epoch_time_ms());
+
+    // the peer's blocklisting expires, so the available tenures are recomputed right away
+    downloader.blocklisted_peers.insert(naddr.clone(), 0);
+    downloader.update_available_tenures(&inventories, &pox_constants, first_burn_height, true);
+    assert!(downloader.blocklisted_peers.is_empty());
+    assert_eq!(downloader.num_empty_passes, 1);
+    assert_eq!(downloader.empty_pass_backoff_deadline_ms, 0);
+
+    // the peer learns about some tenures, so the available tenures are recomputed right away
+    let mut bits = vec![false; usize::from(rc_len)];
+    bits[..4].fill(true);
+    inventories
+        .get_mut(&naddr)
+        .unwrap()
+        .merge_tenure_inv(BitVec::<2100>::try_from(bits.as_slice()).unwrap(), rc);
+    downloader.update_available_tenures(&inventories, &pox_constants, first_burn_height, true);
+    assert!(!downloader.tenure_download_schedule.is_empty());
+    assert_eq!(downloader.num_empty_passes, 0);
+    assert_eq!(downloader.empty_pass_backoff_deadline_ms, 0);
+}
+
+/// While the empty-pass backoff is engaged, the last-computed tenure availability is kept, so a
+/// node which has processed every tenure can still decide to fetch unconfirmed tenures.
+#[test]
+fn test_empty_pass_backoff_keeps_unconfirmed_tenures() {
+    let naddr = NeighborAddress {
+        addrbytes: PeerAddress([0xff; 16]),
+        port: 123,
+        public_key_hash: Hash160([0xff; 20]),
+    };
+    let rc_len = 12u16;
+    let pox_constants = PoxConstants::new(
+        rc_len.into(),
+        5,
+        3,
+        0,
+        25,
+        u64::MAX,
+        u64::MAX,
+        u32::MAX,
+        u32::MAX,
+        u32::MAX,
+        u32::MAX,
+    );
+    let first_burn_height = 100u64;
+    let rc = 1;
+
+    // every wanted tenure has been processed
+    let make_wanted_tenures = |rc: u64, offset: u16| -> Vec<_> {
+        (0..rc_len)
+            .map(|i| {
+                let mut wt = WantedTenure::new(
+                    ConsensusHash([u8::try_from(i + offset).unwrap(); 20]),
+                    StacksBlockId([u8::try_from(i + offset + 1).unwrap(); 32]),
+                    pox_constants.nakamoto_first_block_of_cycle(first_burn_height, rc)
+                        + u64::from(i),
+                );
+                wt.processed = true;
+                wt
+            })
+            .collect()
+    };
+
+    let mut downloader =
+        NakamotoDownloadStateMachine::with_rpc(0, StacksBlockId([0x00; 32]), NeighborRPC::new());
+    downloader.reward_cycle = rc;
+    downloader.prev_wanted_tenures = Some(make_wanted_tenures(rc - 1, 0x80));
+    downloader.wanted_tenures = make_wanted_tenures(rc, 0);
+
+    // our only peer has all of the tenures
+    let mut invs = NakamotoTenureInv::new(0, u64::from(rc_len), 0, naddr.clone());
+    invs.merge_tenure_inv(
+        BitVec::<2100>::try_from(vec![true; usize::from(rc_len)].as_slice()).unwrap(),
+        rc,
+    );
+    let inventories = HashMap::from([(naddr.clone(), invs)]);
+
+    // there's nothing to download, so the backoff engages
+    for _ in 0..3 {
+        downloader.update_available_tenures(&inventories, &pox_constants, first_burn_height, true);
+        assert!(downloader.tenure_download_schedule.is_empty());
+    }
+    assert!(downloader.empty_pass_backoff_deadline_ms > get_epoch_time_ms());
+
+    // while it's engaged, the tenure availability from before the backoff is kept
+    downloader.update_available_tenures(&inventories, &pox_constants, first_burn_height, true);
+    assert!(downloader.empty_pass_backoff_deadline_ms > get_epoch_time_ms());
+    assert!(downloader.tenure_block_ids.contains_key(&naddr));
+
+    // so the downloader can still switch to fetching unconfirmed tenures
+    let mut sort_tip = BlockSnapshot::empty();
+    sort_tip.block_height = 200;
+    downloader.update_fetch_unconfirmed_tenures(
+        sort_tip.block_height,
+        &sort_tip,
+        &ConnectionOptions::default(),
+        get_epoch_time_ms(),
+    );
+    assert_eq!(downloader.unconfirmed_tenures_blocker(), None);
+    assert!(downloader.fetch_unconfirmed_tenures);
+}
+
+/// A neighbor which was marked dead or broken is not reported as able to serve any tenure until
+/// its blocklisting expires, even though its inventory still advertises the tenures.
+#[test]
+fn test_find_available_tenures_skips_blocklisted_peers() {
+    let rc_len = 6u16;
+    let wanted_tenures: Vec<_> = (0..rc_len)
+        .map(|i| WantedTenure {
+            tenure_id_consensus_hash: ConsensusHash([u8::try_from(i + 1).unwrap(); 20]),
+            winning_block_id: StacksBlockId([u8::try_from(i + 1).unwrap(); 32]),
+            burn_height: 100 + u64::from(i),
+            processed: false,
+        })
+        .collect();
+
+    // two neighbors, which both have all tenures
+    let mut inventories = HashMap::new();
+    let mut naddrs = vec![];
+    for port in [123, 456] {
+        let naddr = NeighborAddress {
+            addrbytes: PeerAddress([0xff; 16]),
+            port,
+            public_key_hash: Hash160([0xff; 20]),
+        };
+        let mut invs = NakamotoTenureInv::new(0, u64::from(rc_len), 0, naddr.clone());
+        invs.merge_tenure_inv(
+            BitVec::<2100>::try_from(vec![true; usize::from(rc_len)].as_slice()).unwrap(),
+            1,
         );
-        assert_eq!(
-            NakamotoDownloadStateMachine::make_unconfirmed_tenure_downloaders(
-                &mut full_schedule,
-                10,
-                &mut full_downloaders,
-                None
-            ),
-            0
+        inventories.insert(naddr.clone(), invs);
+        naddrs.push(naddr);
+    }
+
+    let mut downloader = NakamotoDownloadStateMachine::new(0, StacksBlockId([0x00; 32]));
+    let now_secs = 1_000_000;
+    downloader.blocklist_peer(naddrs[0].clone(), now_secs);
+
+    // blocklisted for the whole TTL window
+    for secs in [now_secs, now_secs + PEER_BLOCKLIST_TIME_SECS - 1] {
+        let blocklisted_peers = downloader.expire_blocklisted_peers(secs);
+        assert_eq!(blocklisted_peers, HashSet::from([naddrs[0].clone()]));
+
+        let available = NakamotoDownloadStateMachine::find_available_tenures(
+            1,
+            &wanted_tenures,
+            &blocklisted_peers,
+            inventories.iter(),
+        );
+        assert_eq!(available.len(), usize::from(rc_len));
+        for wt in wanted_tenures.iter() {
+            assert_eq!(
+                available.get(&wt.tenure_id_consensus_hash).unwrap(),
+                &vec![naddrs[1].clone()]
+            );
+        }
+    }
+
+    // blocklisting expires
+    let blocklisted_peers =
+        downloader.expire_blocklisted_peers(now_secs + PEER_BLOCKLIST_TIME_SECS);
+    assert!(blocklisted_peers.is_empty());
+    assert!(downloader.blocklisted_peers.is_empty());
+
+    let available = NakamotoDownloadStateMachine::find_available_tenures(
+        1,
+        &wanted_tenures,
+        &blocklisted_peers,
+        inventories.iter(),
+    );
+    assert_eq!(available.len(), usize::from(rc_len));
+    for wt in wanted_tenures.iter() {
+        let neighbors: HashSet<_> = available
+            .get(&wt.tenure_id_consensus_hash)
+            .unwrap()
+            .iter()
+            .cloned()
+            .collect();
+        assert_eq!(neighbors, naddrs.iter().cloned().collect::<HashSet<_>>());
+    }
+}
+
+fn make_empty_test_block(
+    tenure_id: u8,
+    parent_block_id: &StacksBlockId,
+    chain_length: u64,
+) -> NakamotoBlock {
+    let mut header = NakamotoBlockHeader::empty();
+    header.consensus_hash = ConsensusHash([tenure_id; 20]);
+    header.parent_block_id = parent_block_id.clone();
+    header.chain_length = chain_length;
+    NakamotoBlock {
+        header,
+        txs: vec![],
+    }
+}
+
+/// Downloaded tenures whose first block's parent is unknown are held back until the parent shows
+/// up, and are discarded if it never does.
+#[test]
+fn test_quarantine_disconnected_tenures() {
+    assert!(!ConnectionOptions::default().nakamoto_download_quarantine_disconnected_tenures);
+
+    let stored_block_id = StacksBlockId([0xaa; 32]);
+    let missing_block_id = StacksBlockId([0xbb; 32]);
+
+    // tenure 1 builds on a stored block, tenure 2 builds on tenure 1, and tenure 3 builds on a
+    // block we don't have
+    let a1 = make_empty_test_block(1, &stored_block_id, 1);
+    let a2 = make_empty_test_block(1, &a1.block_id(), 2);
+    let b1 = make_empty_test_block(2, &a2.block_id(), 3);
+    let c1 = make_empty_test_block(3, &missing_block_id, 4);
+    let new_blocks = HashMap::from([
+        (ConsensusHash([1; 20]), vec![a2.clone(), a1.clone()]),
+        (ConsensusHash([2; 20]), vec![b1.clone()]),
+        (ConsensusHash([3; 20]), vec![c1.clone()]),
+    ]);
+
+    let mut downloader = NakamotoDownloadStateMachine::new(0, StacksBlockId([0x00; 32]));
+    let now_secs = 1_000;
+    let connected = downloader
+        .quarantine_disconnected_tenures(new_blocks, now_secs, |block_id| {
+            Ok(block_id == &stored_block_id)
+        })
+        .unwrap();
+    assert_eq!(connected.len(), 2);
+    assert_eq!(
+        connected.get(&ConsensusHash([1; 20])).unwrap(),
+        &vec![a1, a2]
+    );
+    assert_eq!(connected.get(&ConsensusHash([2; 20])).unwrap(), &vec![b1]);
+    assert_eq!(
+        downloader.quarantined_tenures.get(&ConsensusHash([3; 20])),
+        Some(&(now_secs, vec![c1.clone()]))
+    );
+
+    // still disconnected on a later pass, so it stays quarantined from the same time
+    let connected = downloader
+        .quarantine_disconnected_tenures(HashMap::new(), now_secs + 1, |block_id| {
+            Ok(block_id == &stored_block_id)
+        })
+        .unwrap();
+    assert!(connected.is_empty());
+    assert_eq!(
+        downloader.quarantined_tenures.get(&ConsensusHash([3; 20])),
+        Some(&(now_secs, vec![c1.clone()]))
+    );
+
+    // the parent arrives, so the tenure is released
+    let connected = downloader
+        .quarantine_disconnected_tenures(HashMap::new(), now_secs + 2, |block_id| {
+            Ok(block_id == &stored_block_id || block_id == &missing_block_id)
+        })
+        .unwrap();
+    assert_eq!(
+        connected.get(&ConsensusHash([3; 20])).unwrap(),
+        &vec![c1.clone()]
+    );
+    assert!(downloader.quarantined_tenures.is_empty());
+
+    // the parent never arrives, so the tenure is eventually discarded
+    let connected = downloader
+        .quarantine_disconnected_tenures(
+            HashMap::from([(ConsensusHash([3; 20]), vec![c1.clone()])]),
+            now_secs,
+            |_| Ok(false),
+        )
+        .unwrap();
+    assert!(connected.is_empty());
+    assert_eq!(downloader.quarantined_tenures.len(), 1);
+
+    let connected = downloader
+        .quarantine_disconnected_tenures(
+            HashMap::new(),
+            now_secs + TENURE_QUARANTINE_TIME_SECS,
+            |_| Ok(false),
+        )
+        .unwrap();
+    assert!(connected.is_empty());
+    assert!(downloader.quarantined_tenures.is_empty());
+}
+
+/// A block sink receives the tenures left over once a pass's disconnected tenures have been
+/// quarantined, and receives a quarantined tenure once its parent arrives.
+#[test]
+fn test_block_sink_receives_quarantined_tenures() {
+    let stored_block_id = StacksBlockId([0xaa; 32]);
+    let missing_block_id = StacksBlockId([0xbb; 32]);
+
+    // tenure 1 builds on a stored block, and tenure 3 builds on a block we don't have
+    let a1 = make_empty_test_block(1, &stored_block_id, 1);
+    let c1 = make_empty_test_block(3, &missing_block_id, 4);
+    let new_blocks = HashMap::from([
+        (ConsensusHash([1; 20]), vec![a1.clone()]),
+        (ConsensusHash([3; 20]), vec![c1.clone()]),
+    ]);
+
+    let pushes = Arc::new(Mutex::new(vec![]));
+    let mut downloader = NakamotoDownloadStateMachine::new(0, StacksBlockId([0x00; 32]));
+    downloader.set_block_sink(Box::new(CountingBlockSink {
+        pushes: pushes.clone(),
+    }));
+
+    let now_secs = 1_000;
+    let returned = downloader
+        .finish_pass(new_blocks, true, now_secs, |block_id| {
+            Ok(block_id == &stored_block_id)
+        })
+        .unwrap();
+    assert!(returned.is_empty());
+    assert_eq!(
+        *pushes.lock().unwrap(),
+        vec![(ConsensusHash([1; 20]), vec![a1.clone()])]
+    );
+    assert_eq!(
+        downloader.quarantined_tenures.get(&ConsensusHash([3; 20])),
+        Some(&(now_secs, vec![c1.clone()]))
+    );
+
+    // the parent arrives, so the tenure is released to the sink
+    let returned = downloader
+        .finish_pass(HashMap::new(), true, now_secs + 1, |block_id| {
+            Ok(block_id == &stored_block_id || block_id == &missing_block_id)
+        })
+        .unwrap();
+    assert!(returned.is_empty());
+    assert_eq!(
+        *pushes.lock().unwrap(),
+        vec![
+            (ConsensusHash([1; 20]), vec![a1]),
+            (ConsensusHash([3; 20]), vec![c1]),
+        ]
+    );
+    assert!(downloader.quarantined_tenures.is_empty());
+}
+
+/// Flushing the downloader hands back blocks it is still holding, and resets its scheduling state
+/// but not its record of completed tenures.
+#[test]
+fn test_nakamoto_download_state_machine_flush() {
+    let mut test_signers = TestSigners::new(vec![]);
+    let reward_set = test_signers.synthesize_reward_set();
+    let (tenure_start_block, next_tenure_start_block, _) = make_one_block_tenure(&mut test_signers);
+
+    let naddr = NeighborAddress {
+        addrbytes: PeerAddress([0xff; 16]),
+        port: 123,
+        public_key_hash: Hash160([0xff; 20]),
+    };
+    let make_downloader = || {
+        NakamotoTenureDownloader::new(
+            tenure_start_block.header.consensus_hash.clone(),
+            tenure_start_block.header.consensus_hash.clone(),
+            tenure_start_block.header.block_id(),
+            next_tenure_start_block.header.consensus_hash.clone(),
+            next_tenure_start_block.header.block_id(),
+            naddr.clone(),
+            reward_set.clone(),
+            reward_set.clone(),
+            false,
+        )
+    };
+
+    let mut downloader = NakamotoDownloadStateMachine::new(0, StacksBlockId([0x00; 32]));
+
+    // a downloader which has its tenure-start block, but still needs its tenure-end block
+    let mut td = make_downloader();
+    td.try_accept_tenure_start_block(tenure_start_block.clone())
+        .unwrap();
+    downloader
+        .tenure_downloads
+        .add_downloaders(vec![(naddr.clone(), td)]);
+
+    // a quarantined tenure
+    let quarantined_block = make_empty_test_block(0xee, &StacksBlockId([0xbb; 32]), 1);
+    downloader.quarantined_tenures.insert(
+        ConsensusHash([0xee; 20]),
+        (1_000, vec![quarantined_block.clone()]),
+    );
+
+    // a tenure we already downloaded, plus scheduling state for the current reward cycle
+    let completed_tenure = TenureStartEnd::new(
+        ConsensusHash([0x01; 20]),
+        1,
+        ConsensusHash([0x01; 20]),
+        StacksBlockId([0x01; 32]),
+        ConsensusHash([0x02; 20]),
+        StacksBlockId([0x02; 32]),
+        0,
+        0,
+        false,
+    );
+    downloader
+        .tenure_downloads
+        .completed_tenures
+        .insert((&completed_tenure).into());
+    downloader.wanted_tenures = vec![WantedTenure {
+        tenure_id_consensus_hash: ConsensusHash([0x03; 20]),
+        winning_block_id: StacksBlockId([0x03; 32]),
+        burn_height: 100,
+        processed: false,
+    }];
+    downloader.prev_wanted_tenures = Some(vec![]);
+    downloader
+        .available_tenures
+        .insert(ConsensusHash([0x03; 20]), vec![naddr.clone()]);
+    downloader
+        .tenure_download_schedule
+        .push_back(ConsensusHash([0x03; 20]));
+
+    let flushed = downloader.flush();
+    assert_eq!(flushed.len(), 2);
+    assert_eq!(
+        flushed
+            .get(&tenure_start_block.header.consensus_hash)
+            .unwrap(),
+        &vec![tenure_start_block.clone()]
+    );
+    assert_eq!(
+        flushed.get(&ConsensusHash([0xee; 20])).unwrap(),
+        &vec![quarantined_block]
+    );
+
+    // scheduling state is reset, but completed tenures are remembered
+    assert!(downloader.wanted_tenures.is_empty());
+    assert!(downloader.prev_wanted_tenures.is_none());
+    assert!(downloader.available_tenures.is_empty());
+    assert!(downloader.tenure_download_schedule.is_empty());
+    assert!(downloader.quarantined_tenures.is_empty());
+    assert!(downloader.tenure_downloads.peers.is_empty());
+    assert_eq!(downloader.tenure_downloads.completed_tenures.len(), 1);
+    let metrics = downloader.metrics();
+    assert_eq!(metrics.state, NakamotoDownloadState::Confirmed);
+    assert_eq!(metrics.num_downloaders, 0);
+    assert_eq!(metrics.num_unconfirmed_downloaders, 0);
+    assert_eq!(metrics.inflight, 0);
+    assert_eq!(metrics.scheduled_tenures, 0);
+    assert_eq!(metrics.completed_tenures, 1);
+
+    // nothing left to flush
+    assert!(downloader.flush().is_empty());
+
+    // and the next pass can schedule downloaders for the same peer afresh
+    downloader
+        .tenure_downloads
+        .add_downloaders(vec![(naddr.clone(), make_downloader())]);
+    assert!(downloader.tenure_downloads.has_downloader(&naddr));
+    assert_eq!(downloader.metrics().num_downloaders, 1);
+}
+
+/// The tenure status summary reports each wanted tenure's availability and known block IDs.
+#[test]
+fn test_nakamoto_download_state_machine_tenure_status() {
+    let naddrs: Vec<_> = [123, 456]
+        .into_iter()
+        .map(|port| NeighborAddress {
+            addrbytes: PeerAddress([0xff; 16]),
+            port,
+            public_key_hash: Hash160([0xff; 20]),
+        })
+        .collect();
+    let make_wanted_tenure = |i: u8, processed: bool| WantedTenure {
+        tenure_id_consensus_hash: ConsensusHash([i; 20]),
+        winning_block_id: StacksBlockId([i; 32]),
+        burn_height: 100 + u64::from(i),
+        processed,
+    };
+
+    let mut downloader = NakamotoDownloadStateMachine::new(0, StacksBlockId([0x00; 32]));
+    assert!(downloader.tenure_status().is_empty());
+
+    downloader.prev_wanted_tenures = Some(vec![make_wanted_tenure(1, true)]);
+    downloader.wanted_tenures = vec![make_wanted_tenure(2, false), make_wanted_tenure(3, false)];
+
+    // tenure 2 is available from both neighbors, and we know its start and end blocks
+    downloader
+        .available_tenures
+        .insert(ConsensusHash([2; 20]), naddrs.clone());
+    let tenure_start_end = TenureStartEnd::new(
+        ConsensusHash([2; 20]),
+        102,
+        ConsensusHash([2; 20]),
+        StacksBlockId([0x22; 32]),
+        ConsensusHash([3; 20]),
+        StacksBlockId([0x33; 32]),
+        0,
+        0,
+        false,
+    );
+    downloader.tenure_block_ids.insert(
+        naddrs[0].clone(),
+        AvailableTenures::from([(ConsensusHash([2; 20]), tenure_start_end)]),
+    );
+
+    // tenure 3 is being downloaded
+    let reward_set = TestSigners::new(vec![]).synthesize_reward_set();
+    downloader.tenure_downloads.add_downloaders(vec![(
+        naddrs[1].clone(),
+        NakamotoTenureDownloader::new(
+            ConsensusHash([3; 20]),
+            ConsensusHash([3; 20]),
+            StacksBlockId([0x33; 32]),
+            ConsensusHash([4; 20]),
+            StacksBlockId([0x44; 32]),
+            naddrs[1].clone(),
+            reward_set.clone(),
+            reward_set,
+            false,
+        ),
+    )]);
+
+    let status = downloader.tenure_status();
+    assert_eq!(
+        status,
+        vec![
+            TenureStatus {
+                tenure_id: ConsensusHash([1; 20]),
+                burn_height: 101,
+                processed: true,
+                num_available_peers: 0,
+                start_block_id: None,
+                end_block_id: None,
+                downloading: false,
+            },
+            TenureStatus {
+                tenure_id: ConsensusHash([2; 20]),
+                burn_height: 102,
+                processed: false,
+                num_available_peers: 2,
+                start_block_id: Some(StacksBlockId([0x22; 32])),
+                end_block_id: Some(StacksBlockId([0x33; 32])),
+                downloading: false,
+            },
+            TenureStatus {
+                tenure_id: ConsensusHash([3; 20]),
+                burn_height: 103,
+                processed: false,
+                num_available_peers: 0,
+                start_block_id: None,
+                end_block_id: None,
+                downloading: true,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_nakamoto_tenure_downloader_set_cancel_tenure() {
+    let test_signers = TestSigners::new(vec![StacksPrivateKey::random()]);
+    let reward_set = test_signers.synthesize_reward_set();
+
+    let make_naddr = |port| NeighborAddress {
+        addrbytes: PeerAddress([0xff; 16]),
+        port,
+        public_key_hash: Hash160([0xff; 20]),
+    };
+    let make_downloader = |ch: &ConsensusHash, naddr: &NeighborAddress| {
+        NakamotoTenureDownloader::new(
+            ch.clone(),
+            ch.clone(),
+            StacksBlockId([0x01; 32]),
+            ConsensusHash([0x02; 20]),
+            StacksBlockId([0x03; 32]),
+            naddr.clone(),
+            reward_set.clone(),
+            reward_set.clone(),
+            false,
+        )
+    };
+
+    let cancelled_ch = ConsensusHash([0x11; 20]);
+    let other_ch = ConsensusHash([0x22; 20]);
+    let naddr_1 = make_naddr(123);
+    let naddr_2 = make_naddr(456);
+
+    let mut downloaders = NakamotoTenureDownloaderSet::new();
+    downloaders.add_downloaders(vec![
+        (naddr_1.clone(), make_downloader(&cancelled_ch, &naddr_1)),
+        (naddr_2.clone(), make_downloader(&other_ch, &naddr_2)),
+    ]);
+
+    // pretend we already downloaded the tenure once before
+    let tenure_start_end = TenureStartEnd::new(
+        cancelled_ch.clone(),
+        1,
+        cancelled_ch.clone(),
+        StacksBlockId([0x01; 32]),
+        ConsensusHash([0x02; 20]),
+        StacksBlockId([0x03; 32]),
+        0,
+        0,
+        false,
+    );
+    downloaders
+        .completed_tenures
+        .insert((&tenure_start_end).into());
+
+    assert_eq!(downloaders.num_downloaders(), 2);
+    assert!(downloaders.has_downloader_for_tenure(&cancelled_ch));
+
+    assert!(downloaders.cancel_tenure(&cancelled_ch));
+
+    // slot and peer are freed, and the tenure is forgotten
+    assert_eq!(downloaders.num_downloaders(), 1);
+    assert_eq!(downloaders.num_scheduled_downloaders(), 1);
+    assert!(!downloaders.has_downloader(&naddr_1));
+    assert!(!downloaders.peers.contains_key(&naddr_1));
+    assert!(!downloaders.has_downloader_for_tenure(&cancelled_ch));
+    assert!(downloaders.completed_tenures.is_empty());
+
+    // the other tenure is untouched
+    assert!(downloaders.has_downloader(&naddr_2));
+    assert!(downloaders.has_downloader_for_tenure(&other_ch));
+
+    // nothing left to cancel
+    assert!(!downloaders.cancel_tenure(&cancelled_ch));
+
+    // the tenure can be rescheduled on the freed peer
+    downloaders.add_downloaders(vec![(
+        naddr_1.clone(),
+        make_downloader(&cancelled_ch, &naddr_1),
+    )]);
+    assert!(downloaders.has_downloader(&naddr_1));
+    assert!(downloaders.has_downloader_for_tenure(&cancelled_ch));
+    assert_eq!(downloaders.num_scheduled_downloaders(), 2);
+}
+
+#[test]
+fn test_nakamoto_tenure_downloader_set_assignments() {
+    let test_signers = TestSigners::new(vec![StacksPrivateKey::random()]);
+    let reward_set = test_signers.synthesize_reward_set();
+
+    let make_naddr = |port| NeighborAddress {
+        addrbytes: PeerAddress([0xff; 16]),
+        port,
+        public_key_hash: Hash160([0xff; 20]),
+    };
+    let make_downloader = |ch: &ConsensusHash, start_id: u8, naddr: &NeighborAddress| {
+        NakamotoTenureDownloader::new(
+            ch.clone(),
+            ch.clone(),
+            StacksBlockId([start_id; 32]),
+            ConsensusHash([0x02; 20]),
+            StacksBlockId([0x03; 32]),
+            naddr.clone(),
+            reward_set.clone(),
+            reward_set.clone(),
+            false,
+        )
+    };
+
+    let ch_1 = ConsensusHash([0x11; 20]);
+    let ch_2 = ConsensusHash([0x22; 20]);
+    let naddr_1 = make_naddr(123);
+    let naddr_2 = make_naddr(456);
+
+    let mut downloaders = NakamotoTenureDownloaderSet::new();
+    assert!(downloaders.assignments().is_empty());
+
+    // pin the request times so the states can be compared
+    let mut downloader_1 = make_downloader(&ch_1, 0x01, &naddr_1);
+    downloader_1.state =
+        NakamotoTenureDownloadState::GetTenureStartBlock(StacksBlockId([0x01; 32]), 0);
+    let mut downloader_2 = make_downloader(&ch_2, 0x04, &naddr_2);
+    downloader_2.state =
+        NakamotoTenureDownloadState::GetTenureEndBlock(StacksBlockId([0x03; 32]), 1);
+
+    downloaders.add_downloaders(vec![
+        (naddr_1.clone(), downloader_1),
+        (naddr_2.clone(), downloader_2),
+    ]);
+
+    let assignments = downloaders.assignments();
+    assert_eq!(assignments.len(), 2);
+    assert_eq!(
+        assignments.get(&ch_1).unwrap(),
+        &vec![(
+            naddr_1.clone(),
+            NakamotoTenureDownloadState::GetTenureStartBlock(StacksBlockId([0x01; 32]), 0)
+        )]
+    );
+    assert_eq!(
+        assignments.get(&ch_2).unwrap(),
+        &vec![(
+            naddr_2.clone(),
+            NakamotoTenureDownloadState::GetTenureEndBlock(StacksBlockId([0x03; 32]), 1)
+        )]
+    );
+
+    // once a peer is unbound, its tenure is still reported, but with no peers
+    downloaders.peers.remove(&naddr_2);
+    let assignments = downloaders.assignments();
+    assert_eq!(assignments.len(), 2);
+    assert_eq!(assignments.get(&ch_1).unwrap().len(), 1);
+    assert!(assignments.get(&ch_2).unwrap().is_empty());
+}
+
+#[test]
+fn test_nakamoto_download_metrics() {
+    let observer = TestEventObserver::new();
+    let bitvecs = vec![vec![
+        true, true, true, true, true, true, true, true, true, true,
+    ]];
+
+    let rc_len = 10u64;
+    let mut peer =
+        make_nakamoto_peer_from_invs(function_name!(), &observer, rc_len as u32, 3, bitvecs);
+    assert!(peer.network.nakamoto_download_metrics().is_none());
+
+    peer.network.init_nakamoto_block_downloader();
+    let metrics = peer.network.nakamoto_download_metrics().unwrap();
+    assert_eq!(metrics.state, NakamotoDownloadState::Confirmed);
+    assert_eq!(metrics.num_downloaders, 0);
+    assert_eq!(metrics.inflight, 0);
+    assert_eq!(metrics.completed_tenures, 0);
+    assert_eq!(metrics.scheduled_tenures, 0);
+
+    let test_signers = TestSigners::new(vec![StacksPrivateKey::random()]);
+    let reward_set = test_signers.synthesize_reward_set();
+    let make_naddr = |port| NeighborAddress {
+        addrbytes: PeerAddress([0xff; 16]),
+        port,
+        public_key_hash: Hash160([0xff; 20]),
+    };
+    let make_downloader = |ch: ConsensusHash, naddr: &NeighborAddress| {
+        NakamotoTenureDownloader::new(
+            ch.clone(),
+            ch,
+            StacksBlockId([0x01; 32]),
+            ConsensusHash([0x02; 20]),
+            StacksBlockId([0x03; 32]),
+            naddr.clone(),
+            reward_set.clone(),
+            reward_set.clone(),
+            false,
+        )
+    };
+
+    // three downloaders, one of which is waiting on a reply and one of which is finished
+    let naddrs: Vec<_> = (0..3).map(|i| make_naddr(100 + i)).collect();
+    let mut busy = make_downloader(ConsensusHash([0x11; 20]), &naddrs[0]);
+    busy.idle = false;
+    let mut idle = make_downloader(ConsensusHash([0x22; 20]), &naddrs[1]);
+    idle.idle = true;
+    let mut done = make_downloader(ConsensusHash([0x33; 20]), &naddrs[2]);
+    done.idle = false;
+    done.state = NakamotoTenureDownloadState::Done;
+
+    let downloader = peer.network.block_downloader_nakamoto.as_mut().unwrap();
+    downloader.tenure_downloads.add_downloaders(vec![
+        (naddrs[0].clone(), busy),
+        (naddrs[1].clone(), idle),
+        (naddrs[2].clone(), done),
+    ]);
+    downloader
+        .tenure_download_schedule
+        .extend([ConsensusHash([0x44; 20]), ConsensusHash([0x55; 20])]);
+
+    let metrics = peer.network.nakamoto_download_metrics().unwrap();
+    assert_eq!(metrics.num_downloaders, 3);
+    assert_eq!(metrics.inflight, 1);
+    assert_eq!(metrics.scheduled_tenures, 2);
+    assert_eq!(metrics.num_unconfirmed_downloaders, 0);
+    assert_eq!(metrics.dead_peers, 0);
+    assert_eq!(metrics.broken_peers, 0);
+}
+
+/// Unconfirmed tenure downloaders may run alongside the highest complete tenure's downloaders, but
+/// only on neighbors that aren't serving a confirmed tenure, and only up to the reservation.
+#[test]
+fn test_find_reserved_unconfirmed_downloaders() {
+    assert_eq!(
+        ConnectionOptions::default().nakamoto_unconfirmed_downloader_reserved_inflight,
+        0
+    );
+
+    let test_signers = TestSigners::new(vec![StacksPrivateKey::random()]);
+    let reward_set = test_signers.synthesize_reward_set();
+    let make_naddr = |port| NeighborAddress {
+        addrbytes: PeerAddress([0xff; 16]),
+        port,
+        public_key_hash: Hash160([0xff; 20]),
+    };
+    let naddrs: Vec<_> = (0..3).map(|i| make_naddr(100 + i)).collect();
+
+    // the first neighbor is busy serving the highest complete tenure
+    let mut tenure_downloads = NakamotoTenureDownloaderSet::new();
+    let mut busy = NakamotoTenureDownloader::new(
+        ConsensusHash([0x11; 20]),
+        ConsensusHash([0x11; 20]),
+        StacksBlockId([0x01; 32]),
+        ConsensusHash([0x02; 20]),
+        StacksBlockId([0x03; 32]),
+        naddrs[0].clone(),
+        reward_set.clone(),
+        reward_set,
+        false,
+    );
+    busy.idle = false;
+    tenure_downloads.add_downloaders(vec![(naddrs[0].clone(), busy)]);
+    assert_eq!(tenure_downloads.inflight(), 1);
+
+    let unconfirmed_tenure_downloads: HashMap<_, _> = naddrs
+        .iter()
+        .map(|naddr| {
+            (
+                naddr.clone(),
+                NakamotoUnconfirmedTenureDownloader::new(naddr.clone(), None),
+            )
+        })
+        .collect();
+
+    let find_reserved = |count| {
+        NakamotoDownloadStateMachine::find_reserved_unconfirmed_downloaders(
+            &unconfirmed_tenure_downloads,
+            &tenure_downloads,
+            count,
+        )
+    };
+    assert!(find_reserved(0).is_empty());
+
+    let reserved = find_reserved(1);
+    assert_eq!(reserved.len(), 1);
+    assert_ne!(reserved[0], naddrs[0]);
+
+    let reserved: HashSet<_> = find_reserved(10).into_iter().collect();
+    assert_eq!(
+        reserved,
+        HashSet::from([naddrs[1].clone(), naddrs[2].clone()])
+    );
+}
+
+/// Reserved unconfirmed tenure downloaders run alongside a confirmed tenure downloader on a shared
+/// `NeighborRPC`.  Each side only collects the replies from its own neighbors, so neither side
+/// gets a reply meant for the other (which it would drop as a rogue response).
+#[test]
+fn test_reserved_unconfirmed_downloaders_share_neighbor_rpc() {
+    let observer = TestEventObserver::new();
+    let bitvecs = vec![vec![
+        true, true, true, true, true, true, true, true, true, true,
+    ]];
+
+    let rc_len = 10u64;
+    let peer = make_nakamoto_peer_from_invs(function_name!(), &observer, rc_len as u32, 3, bitvecs);
+    let (mut peer, _reward_cycle_invs) =
+        peer_get_nakamoto_invs(peer, &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12]);
+
+    let mut test_signers = TestSigners::new(vec![]);
+    let reward_set = test_signers.synthesize_reward_set();
+    let (tenure_start_block, next_tenure_start_block, _) = make_one_block_tenure(&mut test_signers);
+
+    let make_naddr = |port| NeighborAddress {
+        addrbytes: PeerAddress([0xff; 16]),
+        port,
+        public_key_hash: Hash160([0xff; 20]),
+    };
+    let confirmed_naddr = make_naddr(100);
+    let unconfirmed_naddr = make_naddr(101);
+
+    let mut tenure_downloads = NakamotoTenureDownloaderSet::new();
+    tenure_downloads.own_replies_only = true;
+    tenure_downloads.add_downloaders(vec![(
+        confirmed_naddr.clone(),
+        NakamotoTenureDownloader::new(
+            tenure_start_block.header.consensus_hash.clone(),
+            tenure_start_block.header.consensus_hash.clone(),
+            tenure_start_block.header.block_id(),
+            next_tenure_start_block.header.consensus_hash.clone(),
+            next_tenure_start_block.header.block_id(),
+            confirmed_naddr.clone(),
+            reward_set.clone(),
+            reward_set,
+            false,
+        ),
+    )]);
+    let mut reserved_downloaders = HashMap::from([(
+        unconfirmed_naddr.clone(),
+        NakamotoUnconfirmedTenureDownloader::new(unconfirmed_naddr.clone(), None),
+    )]);
+
+    // the confirmed downloader's reply has arrived, but the unconfirmed downloader's has not
+    let mut neighbor_rpc = NeighborRPC::new();
+    neighbor_rpc.add_test_request(
+        confirmed_naddr.clone(),
+        Some(make_nakamoto_block_response(&tenure_start_block)),
+    );
+    neighbor_rpc.add_test_request(unconfirmed_naddr.clone(), None);
+
+    let sortdb = peer.chain.sortdb.take().unwrap();
+    let sort_tip = SortitionDB::get_canonical_burn_chain_tip(sortdb.conn()).unwrap();
+    let chainstate = &mut peer.chain.stacks_node.as_mut().unwrap().chainstate;
+
+    let mut run_reserved = |network: &mut PeerNetwork,
+                            neighbor_rpc: &mut NeighborRPC,
+                            chainstate: &StacksChainState| {
+        NakamotoDownloadStateMachine::run_unconfirmed_downloaders(
+            &mut reserved_downloaders,
+            network,
+            neighbor_rpc,
+            &sortdb,
+            &sort_tip,
+            chainstate,
+            true,
+            0,
         );
-        assert_eq!(full_schedule.len(), 1);
-        assert_eq!(
-            NakamotoDownloadStateMachine::make_unconfirmed_tenure_downloaders(
-                &mut full_schedule,
-                10,
-                &mut empty_downloaders,
-                None
+    };
+
+    // the reserved downloaders run first, and leave the confirmed downloader's reply alone
+    run_reserved(&mut peer.network, &mut neighbor_rpc, chainstate);
+    assert!(neighbor_rpc.has_inflight(&confirmed_naddr));
+    assert!(neighbor_rpc.has_inflight(&unconfirmed_naddr));
+
+    // the unconfirmed downloader's reply arrives before the confirmed downloaders run, and the
+    // confirmed downloaders leave it alone
+    neighbor_rpc.add_test_request(
+        unconfirmed_naddr.clone(),
+        Some(make_nakamoto_block_response(&next_tenure_start_block)),
+    );
+    tenure_downloads.run(&mut peer.network, &mut neighbor_rpc, chainstate);
+    assert!(neighbor_rpc.has_inflight(&unconfirmed_naddr));
+    let idx = *tenure_downloads.peers.get(&confirmed_naddr).unwrap();
+    let td = tenure_downloads.downloaders[idx].as_ref().unwrap();
+    assert_eq!(td.tenure_start_block, Some(tenure_start_block.clone()));
+    assert!(matches!(
+        td.state,
+        NakamotoTenureDownloadState::GetTenureEndBlock(..)
+    ));
+
+    // the next reserved pass hands the reply to the unconfirmed downloader it was meant for.  It
+    // isn't a tenure-info reply, so the downloader will ask again.
+    run_reserved(&mut peer.network, &mut neighbor_rpc, chainstate);
+    assert!(!neighbor_rpc.has_inflight(&unconfirmed_naddr));
+    assert!(!neighbor_rpc.is_dead_or_broken(&peer.network, &unconfirmed_naddr));
+    assert_eq!(
+        reserved_downloaders
+            .get(&unconfirmed_naddr)
+            .unwrap()
+            .tenure_info_retries,
+        1
+    );
+}
+
+/// No more than `nakamoto_max_unconfirmed_downloaders` unconfirmed tenure downloaders are created,
+/// no matter how large `max_inflight_blocks` is.
+#[test]
+fn test_max_unconfirmed_downloaders() {
+    let mut connection_opts = ConnectionOptions::default();
+    assert_eq!(connection_opts.nakamoto_max_unconfirmed_downloaders, 3);
+    connection_opts.max_inflight_blocks = 100;
+
+    let mut schedule: VecDeque<_> = (0..10)
+        .map(|port| NeighborAddress {
+            addrbytes: PeerAddress([0xff; 16]),
+            port,
+            public_key_hash: Hash160([0xff; 20]),
+        })
+        .collect();
+    let mut downloaders = HashMap::new();
+
+    // repeated passes don't exceed the cap
+    for _ in 0..3 {
+        NakamotoDownloadStateMachine::make_unconfirmed_tenure_downloaders(
+            &mut schedule,
+            NakamotoDownloadStateMachine::unconfirmed_downloader_budget(
+                &connection_opts,
+                downloaders.len(),
             ),
-            1
+            &mut downloaders,
+            None,
+            &SignerSignatureCache::default(),
         );
-        assert!(full_schedule.is_empty());
-        assert_eq!(empty_downloaders.len(), 1);
+        assert_eq!(downloaders.len(), 3);
+        assert_eq!(schedule.len(), 7);
     }
 
-    // we've processed the tip already, so we transition straight to the Done state
-    {
-        let mut utd = NakamotoUnconfirmedTenureDownloader::new(naddr.clone(), Some(tip_block_id));
-        assert_eq!(utd.state, NakamotoUnconfirmedDownloadState::GetTenureInfo);
+    // max_inflight_blocks still applies if it's lower
+    connection_opts.max_inflight_blocks = 2;
+    assert_eq!(
+        NakamotoDownloadStateMachine::unconfirmed_downloader_budget(&connection_opts, 0),
+        2
+    );
+    assert_eq!(
+        NakamotoDownloadStateMachine::unconfirmed_downloader_budget(&connection_opts, 3),
+        0
+    );
+}
 
-        utd.confirmed_signer_keys = Some(
-            current_reward_sets
-                .get(&tip_rc)
-                .cloned()
-                .unwrap()
-                .reward_cycle_info
-                .known_selected_anchor_block_owned()
-                .unwrap(),
-        );
-        utd.unconfirmed_signer_keys = Some(
-            current_reward_sets
-                .get(&tip_rc)
-                .cloned()
-                .unwrap()
-                .reward_cycle_info
-                .known_selected_anchor_block_owned()
-                .unwrap(),
-        );
+/// During IBD, confirmed tenure downloaders are only scheduled once enough peers have provided
+/// their inventories for the target reward cycle.
+#[test]
+fn test_min_peers_for_ibd() {
+    let mut test_signers = TestSigners::new(vec![]);
+    let reward_set = test_signers.synthesize_reward_set();
+    let (tenure_start_block, next_tenure_start_block, _) = make_one_block_tenure(&mut test_signers);
+    let tenure_id = tenure_start_block.header.consensus_hash.clone();
 
-        let tenure_tip = RPCGetTenureInfo {
-            consensus_hash: peer.network.stacks_tip.consensus_hash.clone(),
-            tenure_start_block_id: peer.network.tenure_start_block_id.clone(),
-            parent_consensus_hash: peer.network.parent_stacks_tip.consensus_hash.clone(),
-            parent_tenure_start_block_id: StacksBlockId::new(
-                &peer.network.parent_stacks_tip.consensus_hash,
-                &peer.network.parent_stacks_tip.block_hash,
-            ),
-            tip_block_id: StacksBlockId::new(
-                &peer.network.stacks_tip.consensus_hash,
-                &peer.network.stacks_tip.block_hash,
-            ),
-            tip_height: peer.network.stacks_tip.height,
-            reward_cycle: tip_rc,
-        };
+    let current_reward_sets = BTreeMap::from([(
+        0,
+        CurrentRewardSet {
+            reward_cycle: 0,
+            reward_cycle_info: RewardCycleInfo {
+                reward_cycle: 0,
+                anchor_status: PoxAnchorBlockStatus::SelectedAndKnown(
+                    BlockHeaderHash([0x00; 32]),
+                    Txid([0x00; 32]),
+                    reward_set,
+                ),
+            },
+            anchor_block_consensus_hash: ConsensusHash([0x00; 20]),
+            anchor_block_hash: BlockHeaderHash([0x00; 32]),
+        },
+    )]);
+    let tenure_info = TenureStartEnd::new(
+        tenure_id.clone(),
+        1,
+        tenure_id.clone(),
+        tenure_start_block.block_id(),
+        next_tenure_start_block.header.consensus_hash.clone(),
+        next_tenure_start_block.block_id(),
+        0,
+        0,
+        false,
+    );
 
-        let sortdb = peer.chain.sortdb.take().unwrap();
-        let sort_tip = SortitionDB::get_canonical_burn_chain_tip(sortdb.conn()).unwrap();
-        utd.try_accept_tenure_info(
-            &sortdb,
-            &sort_tip,
-            peer.chainstate(),
-            tenure_tip.clone(),
-            &current_reward_sets,
-        )
-        .unwrap();
+    let naddrs: Vec<_> = (0..3)
+        .map(|i| NeighborAddress {
+            addrbytes: PeerAddress([0xff; 16]),
+            port: 123 + i,
+            public_key_hash: Hash160([0xff; 20]),
+        })
+        .collect();
+
+    // every given peer has an inventory for reward cycle 0, and can serve the tenure
+    let make_downloader = |naddrs: &[NeighborAddress]| {
+        let mut downloader = NakamotoDownloadStateMachine::with_rpc(
+            0,
+            StacksBlockId([0x00; 32]),
+            NeighborRPC::new(),
+        );
+        downloader.tenure_download_schedule = VecDeque::from([tenure_id.clone()]);
+        downloader.available_tenures = HashMap::from([(tenure_id.clone(), naddrs.to_vec())]);
+        downloader.tenure_block_ids = naddrs
+            .iter()
+            .map(|naddr| {
+                (
+                    naddr.clone(),
+                    AvailableTenures::from([(tenure_id.clone(), tenure_info.clone())]),
+                )
+            })
+            .collect();
+
+        let inventories: HashMap<_, _> = naddrs
+            .iter()
+            .map(|naddr| {
+                let mut inv = NakamotoTenureInv::new(0, 10, 0, naddr.clone());
+                inv.merge_tenure_inv(
+                    BitVec::<2100>::try_from(vec![true; 10].as_slice()).unwrap(),
+                    0,
+                );
+                (naddr.clone(), inv)
+            })
+            .collect();
+        (downloader, inventories)
+    };
+
+    // one peer is not enough
+    let (mut downloader, inventories) = make_downloader(&naddrs[..1]);
+    assert_eq!(
+        NakamotoDownloadStateMachine::count_inventory_peers(&inventories, 0),
+        1
+    );
+    assert_eq!(
+        NakamotoDownloadStateMachine::count_inventory_peers(&inventories, 1),
+        0
+    );
+    assert!(!downloader.try_update_tenure_downloaders(
+        6,
+        &current_reward_sets,
+        &inventories,
+        3,
+        true
+    ));
+    assert_eq!(downloader.tenure_downloads.num_downloaders(), 0);
+    assert_eq!(downloader.tenure_download_schedule.len(), 1);
+
+    // ...unless we're not in IBD
+    assert!(downloader.try_update_tenure_downloaders(
+        6,
+        &current_reward_sets,
+        &inventories,
+        3,
+        false
+    ));
+    assert_eq!(downloader.tenure_downloads.num_downloaders(), 1);
+
+    // three peers are enough
+    let (mut downloader, inventories) = make_downloader(&naddrs);
+    assert!(downloader.try_update_tenure_downloaders(
+        6,
+        &current_reward_sets,
+        &inventories,
+        3,
+        true
+    ));
+    assert_eq!(downloader.tenure_downloads.num_downloaders(), 1);
+    assert!(downloader
+        .tenure_downloads
+        .has_downloader_for_tenure(&tenure_id));
+}
 
-        peer.chain.sortdb = Some(sortdb);
+/// No new confirmed tenure downloaders are scheduled while there are too many unprocessed blocks
+/// in the staging DB, and scheduling resumes once enough of them have been processed.
+#[test]
+fn test_staging_backpressure() {
+    let mut test_signers = TestSigners::new(vec![]);
+    let reward_set = test_signers.synthesize_reward_set();
+    let (tenure_start_block, next_tenure_start_block, _) = make_one_block_tenure(&mut test_signers);
+    let tenure_id = tenure_start_block.header.consensus_hash.clone();
 
-        assert!(utd.unconfirmed_tenure_start_block.is_some());
+    let current_reward_sets = BTreeMap::from([(
+        0,
+        CurrentRewardSet {
+            reward_cycle: 0,
+            reward_cycle_info: RewardCycleInfo {
+                reward_cycle: 0,
+                anchor_status: PoxAnchorBlockStatus::SelectedAndKnown(
+                    BlockHeaderHash([0x00; 32]),
+                    Txid([0x00; 32]),
+                    reward_set,
+                ),
+            },
+            anchor_block_consensus_hash: ConsensusHash([0x00; 20]),
+            anchor_block_hash: BlockHeaderHash([0x00; 32]),
+        },
+    )]);
+    let tenure_info = TenureStartEnd::new(
+        tenure_id.clone(),
+        1,
+        tenure_id.clone(),
+        tenure_start_block.block_id(),
+        next_tenure_start_block.header.consensus_hash.clone(),
+        next_tenure_start_block.block_id(),
+        0,
+        0,
+        false,
+    );
 
-        // because the highest processed block is the same as .tip_block_id, we're done
-        assert_eq!(utd.state, NakamotoUnconfirmedDownloadState::Done);
+    let naddr = NeighborAddress {
+        addrbytes: PeerAddress([0xff; 16]),
+        port: 123,
+        public_key_hash: Hash160([0xff; 20]),
+    };
+    let mut inv = NakamotoTenureInv::new(0, 10, 0, naddr.clone());
+    inv.merge_tenure_inv(
+        BitVec::<2100>::try_from(vec![true; 10].as_slice()).unwrap(),
+        0,
+    );
+    let inventories = HashMap::from([(naddr.clone(), inv)]);
+
+    let mut downloader =
+        NakamotoDownloadStateMachine::with_rpc(0, StacksBlockId([0x00; 32]), NeighborRPC::new());
+    downloader.tenure_download_schedule = VecDeque::from([tenure_id.clone()]);
+    downloader.available_tenures = HashMap::from([(tenure_id.clone(), vec![naddr.clone()])]);
+    downloader.tenure_block_ids = HashMap::from([(
+        naddr.clone(),
+        AvailableTenures::from([(tenure_id.clone(), tenure_info)]),
+    )]);
+
+    let high_water_mark = 100;
+    let low_water_mark = 50;
+
+    // below the high-water mark, so not backpressured
+    assert!(!downloader.update_staging_backpressure(99, high_water_mark, low_water_mark));
+
+    // at or above the high-water mark, so no new downloaders get scheduled
+    assert!(downloader.update_staging_backpressure(100, high_water_mark, low_water_mark));
+    assert!(!downloader.try_update_tenure_downloaders(
+        6,
+        &current_reward_sets,
+        &inventories,
+        1,
+        false
+    ));
+    assert_eq!(downloader.tenure_downloads.num_downloaders(), 0);
+    assert_eq!(downloader.tenure_download_schedule.len(), 1);
+
+    // still backpressured until we drop to the low-water mark
+    assert!(downloader.update_staging_backpressure(99, high_water_mark, low_water_mark));
+    assert!(downloader.update_staging_backpressure(51, high_water_mark, low_water_mark));
+    assert!(!downloader.try_update_tenure_downloaders(
+        6,
+        &current_reward_sets,
+        &inventories,
+        1,
+        false
+    ));
+    assert_eq!(downloader.tenure_downloads.num_downloaders(), 0);
+
+    // resumes at the low-water mark
+    assert!(!downloader.update_staging_backpressure(50, high_water_mark, low_water_mark));
+    assert!(downloader.try_update_tenure_downloaders(
+        6,
+        &current_reward_sets,
+        &inventories,
+        1,
+        false
+    ));
+    assert_eq!(downloader.tenure_downloads.num_downloaders(), 1);
+    assert!(downloader
+        .tenure_downloads
+        .has_downloader_for_tenure(&tenure_id));
+
+    // a high-water mark of 0 disables backpressure altogether
+    assert!(downloader.update_staging_backpressure(1000, high_water_mark, low_water_mark));
+    assert!(!downloader.update_staging_backpressure(1000, 0, low_water_mark));
+}
 
-        // we can request the highest-complete tenure
-        assert!(!utd.need_highest_complete_tenure(peer.chainstate()).unwrap());
+/// Trusted download peers are asked for a tenure before untrusted peers that can also serve it.
+#[test]
+fn test_trusted_download_peers() {
+    let mut test_signers = TestSigners::new(vec![]);
+    let reward_set = test_signers.synthesize_reward_set();
+    let (tenure_start_block, next_tenure_start_block, _) = make_one_block_tenure(&mut test_signers);
+    let tenure_id = tenure_start_block.header.consensus_hash.clone();
 
-        let ntd = utd.make_highest_complete_tenure_downloader().unwrap();
-        assert_eq!(
-            ntd.state,
-            NakamotoTenureDownloadState::GetTenureStartBlock(
-                unconfirmed_wanted_tenure.winning_block_id.clone(),
-                ntd.state.request_time().unwrap()
-            )
-        );
-    }
+    let current_reward_sets = BTreeMap::from([(
+        0,
+        CurrentRewardSet {
+            reward_cycle: 0,
+            reward_cycle_info: RewardCycleInfo {
+                reward_cycle: 0,
+                anchor_status: PoxAnchorBlockStatus::SelectedAndKnown(
+                    BlockHeaderHash([0x00; 32]),
+                    Txid([0x00; 32]),
+                    reward_set,
+                ),
+            },
+            anchor_block_consensus_hash: ConsensusHash([0x00; 20]),
+            anchor_block_hash: BlockHeaderHash([0x00; 32]),
+        },
+    )]);
+    let tenure_info = TenureStartEnd::new(
+        tenure_id.clone(),
+        1,
+        tenure_id.clone(),
+        tenure_start_block.block_id(),
+        next_tenure_start_block.header.consensus_hash.clone(),
+        next_tenure_start_block.block_id(),
+        0,
+        0,
+        false,
+    );
 
-    // we've processed the first block in the unconfirmed tenure, but not the tip, so we transition to
-    // the GetUnconfirmedTenureBlocks(..) state.
-    {
-        let mid_tip_block_id = unconfirmed_tenure.first().as_ref().unwrap().block_id();
+    let trusted_naddr = NeighborAddress {
+        addrbytes: PeerAddress([0xff; 16]),
+        port: 123,
+        public_key_hash: Hash160([0x11; 20]),
+    };
+    let untrusted_naddr = NeighborAddress {
+        addrbytes: PeerAddress([0xff; 16]),
+        port: 456,
+        public_key_hash: Hash160([0x22; 20]),
+    };
 
-        let mut utd =
-            NakamotoUnconfirmedTenureDownloader::new(naddr.clone(), Some(mid_tip_block_id));
-        utd.confirmed_signer_keys = Some(
-            current_reward_sets
-                .get(&tip_rc)
-                .cloned()
-                .unwrap()
-                .reward_cycle_info
-                .known_selected_anchor_block_owned()
-                .unwrap(),
+    // both peers serve the tenure, and the untrusted peer would be asked first
+    let make_downloader = |trusted_download_peers: HashSet<NeighborAddress>| {
+        let mut downloader = NakamotoDownloadStateMachine::with_rpc(
+            0,
+            StacksBlockId([0x00; 32]),
+            NeighborRPC::new(),
         );
-        utd.unconfirmed_signer_keys = Some(
-            current_reward_sets
-                .get(&tip_rc)
-                .cloned()
-                .unwrap()
-                .reward_cycle_info
-                .known_selected_anchor_block_owned()
-                .unwrap(),
+        let mut available = HashMap::from([(
+            tenure_id.clone(),
+            vec![trusted_naddr.clone(), untrusted_naddr.clone()],
+        )]);
+        NakamotoDownloadStateMachine::prioritize_trusted_peers(
+            &mut available,
+            &trusted_download_peers,
         );
+        downloader.trusted_download_peers = trusted_download_peers;
+        downloader.tenure_download_schedule = VecDeque::from([tenure_id.clone()]);
+        downloader.available_tenures = available;
+        downloader.tenure_block_ids = [&trusted_naddr, &untrusted_naddr]
+            .into_iter()
+            .map(|naddr| {
+                (
+                    naddr.clone(),
+                    AvailableTenures::from([(tenure_id.clone(), tenure_info.clone())]),
+                )
+            })
+            .collect();
+        downloader
+    };
 
-        assert_eq!(utd.state, NakamotoUnconfirmedDownloadState::GetTenureInfo);
+    // without any trusted peers, the untrusted peer gets the download
+    let mut downloader = make_downloader(HashSet::new());
+    assert!(downloader.try_update_tenure_downloaders(
+        1,
+        &current_reward_sets,
+        &HashMap::new(),
+        0,
+        false
+    ));
+    assert!(downloader.tenure_downloads.has_downloader(&untrusted_naddr));
+    assert!(!downloader.tenure_downloads.has_downloader(&trusted_naddr));
 
-        let tenure_tip = RPCGetTenureInfo {
-            consensus_hash: peer.network.stacks_tip.consensus_hash.clone(),
-            tenure_start_block_id: peer.network.tenure_start_block_id.clone(),
-            parent_consensus_hash: peer.network.parent_stacks_tip.consensus_hash.clone(),
-            parent_tenure_start_block_id: StacksBlockId::new(
-                &peer.network.parent_stacks_tip.consensus_hash,
-                &peer.network.parent_stacks_tip.block_hash,
-            ),
-            tip_block_id: StacksBlockId::new(
-                &peer.network.stacks_tip.consensus_hash,
-                &peer.network.stacks_tip.block_hash,
-            ),
-            tip_height: peer.network.stacks_tip.height,
-            reward_cycle: tip_rc,
-        };
+    // the trusted peer is scheduled first
+    let mut downloader = make_downloader(HashSet::from([trusted_naddr.clone()]));
+    assert_eq!(
+        downloader.available_tenures.get(&tenure_id).unwrap(),
+        &vec![untrusted_naddr.clone(), trusted_naddr.clone()]
+    );
+    assert!(downloader.try_update_tenure_downloaders(
+        1,
+        &current_reward_sets,
+        &HashMap::new(),
+        0,
+        false
+    ));
+    assert!(downloader.tenure_downloads.has_downloader(&trusted_naddr));
+    assert!(!downloader.tenure_downloads.has_downloader(&untrusted_naddr));
 
-        let sortdb = peer.chain.sortdb.take().unwrap();
-        let sort_tip = SortitionDB::get_canonical_burn_chain_tip(sortdb.conn()).unwrap();
-        utd.try_accept_tenure_info(
-            &sortdb,
-            &sort_tip,
-            peer.chainstate(),
-            tenure_tip.clone(),
-            &current_reward_sets,
-        )
-        .unwrap();
+    // the untrusted peer is still available as a fallback
+    assert_eq!(
+        downloader.available_tenures.get(&tenure_id).unwrap(),
+        &vec![untrusted_naddr]
+    );
+}
 
-        peer.chain.sortdb = Some(sortdb);
+/// A peer selector which always chooses a given neighbor, if it can serve the tenure
+struct PreferredPeerSelector {
+    preferred: NeighborAddress,
+}
 
-        assert!(utd.unconfirmed_tenure_start_block.is_some());
+impl DownloadPeerSelector for PreferredPeerSelector {
+    fn select_peer(
+        &mut self,
+        _tenure_id: &ConsensusHash,
+        neighbors: &[NeighborAddress],
+    ) -> Option<usize> {
+        neighbors.iter().position(|naddr| naddr == &self.preferred)
+    }
+}
 
-        // because we already have processed the start-block of this unconfirmed tenure, we'll
-        // advance straight to getting more unconfirmed tenure blocks
-        assert_eq!(
-            utd.state,
-            NakamotoUnconfirmedDownloadState::GetUnconfirmedTenureBlocks(
-                tenure_tip.tip_block_id.clone(),
-            )
+/// A custom peer selector decides which neighbor gets a scheduled tenure's download.
+#[test]
+fn test_download_peer_selector() {
+    let mut test_signers = TestSigners::new(vec![]);
+    let reward_set = test_signers.synthesize_reward_set();
+    let (tenure_start_block, next_tenure_start_block, _) = make_one_block_tenure(&mut test_signers);
+    let tenure_id = tenure_start_block.header.consensus_hash.clone();
+
+    let current_reward_sets = BTreeMap::from([(
+        0,
+        CurrentRewardSet {
+            reward_cycle: 0,
+            reward_cycle_info: RewardCycleInfo {
+                reward_cycle: 0,
+                anchor_status: PoxAnchorBlockStatus::SelectedAndKnown(
+                    BlockHeaderHash([0x00; 32]),
+                    Txid([0x00; 32]),
+                    reward_set,
+                ),
+            },
+            anchor_block_consensus_hash: ConsensusHash([0x00; 20]),
+            anchor_block_hash: BlockHeaderHash([0x00; 32]),
+        },
+    )]);
+    let tenure_info = TenureStartEnd::new(
+        tenure_id.clone(),
+        1,
+        tenure_id.clone(),
+        tenure_start_block.block_id(),
+        next_tenure_start_block.header.consensus_hash.clone(),
+        next_tenure_start_block.block_id(),
+        0,
+        0,
+        false,
+    );
+
+    let naddrs: Vec<_> = [123, 456, 789]
+        .into_iter()
+        .map(|port| NeighborAddress {
+            addrbytes: PeerAddress([0xff; 16]),
+            port,
+            public_key_hash: Hash160([0x11; 20]),
+        })
+        .collect();
+
+    let make_downloader = || {
+        let mut downloader = NakamotoDownloadStateMachine::with_rpc(
+            0,
+            StacksBlockId([0x00; 32]),
+            NeighborRPC::new(),
         );
-        assert_eq!(utd.tenure_tip, Some(tenure_tip));
+        downloader.tenure_download_schedule = VecDeque::from([tenure_id.clone()]);
+        downloader.available_tenures = HashMap::from([(tenure_id.clone(), naddrs.clone())]);
+        downloader.tenure_block_ids = naddrs
+            .iter()
+            .map(|naddr| {
+                (
+                    naddr.clone(),
+                    AvailableTenures::from([(tenure_id.clone(), tenure_info.clone())]),
+                )
+            })
+            .collect();
+        downloader
+    };
 
-        // fill in blocks
-        for (i, block) in unconfirmed_tenure.iter().enumerate().rev() {
-            let res = utd
-                .try_accept_unconfirmed_tenure_blocks(vec![block.clone()])
-                .unwrap();
-            if i == 0 {
-                // res won't contain the first block because it stopped processing once it reached
-                // a block that the node knew
-                assert_eq!(res.unwrap(), unconfirmed_tenure[1..].to_vec());
-                break;
-            } else {
-                assert!(res.is_none());
-            }
-        }
+    // by default, the last neighbor gets the download
+    let mut downloader = make_downloader();
+    assert!(downloader.try_update_tenure_downloaders(
+        1,
+        &current_reward_sets,
+        &HashMap::new(),
+        0,
+        false
+    ));
+    assert!(downloader.tenure_downloads.has_downloader(&naddrs[2]));
+    assert!(!downloader.tenure_downloads.has_downloader(&naddrs[0]));
+    assert!(!downloader.tenure_downloads.has_downloader(&naddrs[1]));
+
+    // the custom selector's neighbor gets the download, and the others remain available
+    let mut downloader = make_downloader();
+    downloader.set_peer_selector(Box::new(PreferredPeerSelector {
+        preferred: naddrs[1].clone(),
+    }));
+    assert!(downloader.try_update_tenure_downloaders(
+        1,
+        &current_reward_sets,
+        &HashMap::new(),
+        0,
+        false
+    ));
+    assert!(downloader.tenure_downloads.has_downloader(&naddrs[1]));
+    assert!(!downloader.tenure_downloads.has_downloader(&naddrs[0]));
+    assert!(!downloader.tenure_downloads.has_downloader(&naddrs[2]));
+    assert_eq!(
+        downloader.available_tenures.get(&tenure_id).unwrap(),
+        &vec![naddrs[0].clone(), naddrs[2].clone()]
+    );
 
-        assert_eq!(utd.state, NakamotoUnconfirmedDownloadState::Done);
+    // if the selector declines every neighbor, then the tenure is abandoned
+    let mut downloader = make_downloader();
+    downloader.set_peer_selector(Box::new(PreferredPeerSelector {
+        preferred: NeighborAddress {
+            addrbytes: PeerAddress([0xff; 16]),
+            port: 1000,
+            public_key_hash: Hash160([0x11; 20]),
+        },
+    }));
+    downloader.try_update_tenure_downloaders(1, &current_reward_sets, &HashMap::new(), 0, false);
+    assert_eq!(downloader.tenure_downloads.num_downloaders(), 0);
+    assert_eq!(
+        downloader.abandoned_tenures().get(&tenure_id),
+        Some(&AbandonedTenureReason::NoNeighbors)
+    );
+}
 
-        // we can request the highest-complete tenure
-        assert!(!utd.need_highest_complete_tenure(peer.chainstate()).unwrap());
+/// When the confirmed and unconfirmed tenure downloaders both obtain a block, the confirmed
+/// downloader's copy is kept.
+#[test]
+fn test_coalesce_tenure_blocks_prefers_confirmed() {
+    let naddr = NeighborAddress {
+        addrbytes: PeerAddress([0xff; 16]),
+        port: 123,
+        public_key_hash: Hash160([0xff; 20]),
+    };
 
-        let ntd = utd.make_highest_complete_tenure_downloader().unwrap();
-        assert_eq!(
-            ntd.state,
-            NakamotoTenureDownloadState::GetTenureStartBlock(
-                unconfirmed_wanted_tenure.winning_block_id.clone(),
-                ntd.state.request_time().unwrap()
-            )
-        );
-    }
+    let block_1 = make_empty_test_block(1, &StacksBlockId([0xaa; 32]), 1);
+    let block_2 = make_empty_test_block(1, &block_1.block_id(), 2);
+    let block_3 = make_empty_test_block(1, &block_2.block_id(), 3);
+
+    // same block ID (signatures aren't committed to), but a different instance
+    let mut confirmed_block_2 = block_2.clone();
+    confirmed_block_2.header.signer_signature = vec![MessageSignature([0x01; 65])];
+    let mut unconfirmed_block_2 = block_2.clone();
+    unconfirmed_block_2.header.signer_signature = vec![MessageSignature([0x02; 65])];
+    assert_eq!(confirmed_block_2.block_id(), unconfirmed_block_2.block_id());
+    assert_ne!(confirmed_block_2, unconfirmed_block_2);
+
+    let confirmed_blocks = HashMap::from([(
+        ConsensusHash([1; 20]),
+        vec![block_1.clone(), confirmed_block_2.clone()],
+    )]);
+    let unconfirmed_blocks =
+        HashMap::from([(naddr, vec![block_3.clone(), unconfirmed_block_2.clone()])]);
+
+    let coalesced =
+        NakamotoDownloadStateMachine::coalesce_tenure_blocks(confirmed_blocks, unconfirmed_blocks);
+    assert_eq!(coalesced.len(), 1);
+    assert_eq!(
+        coalesced.get(&ConsensusHash([1; 20])).unwrap(),
+        &vec![block_1, confirmed_block_2, block_3]
+    );
+}
 
-    // we've processed the middle block in the unconfirmed tenure, but not the tip, so we transition to
-    // the GetUnconfirmedTenureBlocks(..) state.
-    {
-        let mid_tip_block_id = unconfirmed_tenure.get(5).unwrap().block_id();
+/// Blocks obtained from several peers in excess of the per-pass limit are carried over to the
+/// next pass, lowest tenure and lowest height first, instead of all being returned at once.
+#[test]
+fn test_unconfirmed_pass_blocks_deferred_past_limit() {
+    assert_eq!(
+        ConnectionOptions::default().nakamoto_unconfirmed_download_max_coalesced_blocks,
+        4096
+    );
 
-        let mut utd =
-            NakamotoUnconfirmedTenureDownloader::new(naddr.clone(), Some(mid_tip_block_id));
-        utd.confirmed_signer_keys = Some(
-            current_reward_sets
-                .get(&tip_rc)
-                .cloned()
-                .unwrap()
-                .reward_cycle_info
-                .known_selected_anchor_block_owned()
-                .unwrap(),
-        );
-        utd.unconfirmed_signer_keys = Some(
-            current_reward_sets
-                .get(&tip_rc)
-                .cloned()
-                .unwrap()
-                .reward_cycle_info
-                .known_selected_anchor_block_owned()
-                .unwrap(),
-        );
+    let naddrs: Vec<_> = (0..3u8)
+        .map(|i| NeighborAddress {
+            addrbytes: PeerAddress([i; 16]),
+            port: 123,
+            public_key_hash: Hash160([i; 20]),
+        })
+        .collect();
+
+    // tenure 1 has six blocks, and tenure 2 builds on it with two more
+    let mut blocks: Vec<NakamotoBlock> = vec![];
+    for height in 1..=8 {
+        let parent_block_id = blocks
+            .last()
+            .map(|blk| blk.block_id())
+            .unwrap_or(StacksBlockId([0xaa; 32]));
+        let tenure_id = if height <= 6 { 1 } else { 2 };
+        blocks.push(make_empty_test_block(tenure_id, &parent_block_id, height));
+    }
 
-        assert_eq!(utd.state, NakamotoUnconfirmedDownloadState::GetTenureInfo);
+    // each peer serves part of the chain, and they overlap
+    let unconfirmed_blocks = HashMap::from([
+        (naddrs[0].clone(), blocks[0..4].to_vec()),
+        (naddrs[1].clone(), blocks[2..6].to_vec()),
+        (naddrs[2].clone(), blocks[5..8].to_vec()),
+    ]);
 
-        let tenure_tip = RPCGetTenureInfo {
-            consensus_hash: peer.network.stacks_tip.consensus_hash.clone(),
-            tenure_start_block_id: peer.network.tenure_start_block_id.clone(),
-            parent_consensus_hash: peer.network.parent_stacks_tip.consensus_hash.clone(),
-            parent_tenure_start_block_id: StacksBlockId::new(
-                &peer.network.parent_stacks_tip.consensus_hash,
-                &peer.network.parent_stacks_tip.block_hash,
-            ),
-            tip_block_id: StacksBlockId::new(
-                &peer.network.stacks_tip.consensus_hash,
-                &peer.network.stacks_tip.block_hash,
-            ),
-            tip_height: peer.network.stacks_tip.height,
-            reward_cycle: tip_rc,
-        };
+    let mut downloader = NakamotoDownloadStateMachine::new(0, StacksBlockId([0x00; 32]));
 
-        let sortdb = peer.chain.sortdb.take().unwrap();
-        let sort_tip = SortitionDB::get_canonical_burn_chain_tip(sortdb.conn()).unwrap();
-        utd.try_accept_tenure_info(
-            &sortdb,
-            &sort_tip,
-            peer.chainstate(),
-            tenure_tip.clone(),
-            &current_reward_sets,
-        )
-        .unwrap();
+    // first pass: only the first three blocks of tenure 1 are returned
+    let returned =
+        downloader.coalesce_unconfirmed_pass_blocks(HashMap::new(), unconfirmed_blocks, 3);
+    assert_eq!(
+        returned,
+        HashMap::from([(ConsensusHash([1; 20]), blocks[0..3].to_vec())])
+    );
+    assert_eq!(
+        downloader.deferred_unconfirmed_blocks,
+        HashMap::from([
+            (ConsensusHash([1; 20]), blocks[3..6].to_vec()),
+            (ConsensusHash([2; 20]), blocks[6..8].to_vec()),
+        ])
+    );
 
-        peer.chain.sortdb = Some(sortdb);
+    // second pass: a peer serves a block again, but it's only returned once, and the rest of
+    // tenure 1 comes before tenure 2
+    let unconfirmed_blocks = HashMap::from([(naddrs[2].clone(), blocks[7..8].to_vec())]);
+    let returned =
+        downloader.coalesce_unconfirmed_pass_blocks(HashMap::new(), unconfirmed_blocks, 3);
+    assert_eq!(
+        returned,
+        HashMap::from([(ConsensusHash([1; 20]), blocks[3..6].to_vec())])
+    );
+    assert_eq!(
+        downloader.deferred_unconfirmed_blocks,
+        HashMap::from([(ConsensusHash([2; 20]), blocks[6..8].to_vec())])
+    );
 
-        assert!(utd.unconfirmed_tenure_start_block.is_some());
+    // third pass: the overflow is drained
+    let returned = downloader.coalesce_unconfirmed_pass_blocks(HashMap::new(), HashMap::new(), 3);
+    assert_eq!(
+        returned,
+        HashMap::from([(ConsensusHash([2; 20]), blocks[6..8].to_vec())])
+    );
+    assert!(downloader.deferred_unconfirmed_blocks.is_empty());
+
+    // with no limit, everything is returned at once
+    let unconfirmed_blocks = HashMap::from([(naddrs[0].clone(), blocks.clone())]);
+    let returned =
+        downloader.coalesce_unconfirmed_pass_blocks(HashMap::new(), unconfirmed_blocks, 0);
+    assert_eq!(
+        returned,
+        HashMap::from([
+            (ConsensusHash([1; 20]), blocks[0..6].to_vec()),
+            (ConsensusHash([2; 20]), blocks[6..8].to_vec()),
+        ])
+    );
+    assert!(downloader.deferred_unconfirmed_blocks.is_empty());
+}
 
-        // because we already have processed the start-block of this unconfirmed tenure, we'll
-        // advance straight to getting more unconfirmed tenure blocks
-        assert_eq!(
-            utd.state,
-            NakamotoUnconfirmedDownloadState::GetUnconfirmedTenureBlocks(
-                tenure_tip.tip_block_id.clone(),
-            )
-        );
-        assert_eq!(utd.tenure_tip, Some(tenure_tip));
+/// The unconfirmed tenure-start block, once verified by the unconfirmed downloader, is not
+/// verified again when it is handed to the highest complete tenure downloader as its tenure-end
+/// block.  A copy of that block with different signatures is still checked.
+#[test]
+fn test_signer_signature_cache_shared_across_downloaders() {
+    let mut test_signers = TestSigners::default();
+    let reward_set = test_signers.synthesize_reward_set();
+    let (mut tenure_start_block, mut next_tenure_start_block, _) =
+        make_one_block_tenure(&mut test_signers);
 
-        // fill in blocks
-        for (i, block) in unconfirmed_tenure.iter().enumerate().rev() {
-            let res = utd
-                .try_accept_unconfirmed_tenure_blocks(vec![block.clone()])
-                .unwrap();
-            if i == unconfirmed_tenure.len() - 5 {
-                // got back only the blocks we were missing
-                assert_eq!(
-                    res.unwrap(),
-                    unconfirmed_tenure[(unconfirmed_tenure.len() - 4)..].to_vec()
-                );
-                break;
-            } else {
-                assert!(res.is_none());
-            }
-        }
+    // re-sign so that the signatures are in reward set order (this doesn't change block IDs)
+    test_signers.sign_block_with_reward_set(&mut tenure_start_block, &reward_set);
+    test_signers.sign_block_with_reward_set(&mut next_tenure_start_block, &reward_set);
 
-        assert_eq!(utd.state, NakamotoUnconfirmedDownloadState::Done);
+    let naddr = NeighborAddress {
+        addrbytes: PeerAddress([0xff; 16]),
+        port: 123,
+        public_key_hash: Hash160([0xff; 20]),
+    };
 
-        // we can request the highest-complete tenure
-        assert!(!utd.need_highest_complete_tenure(peer.chainstate()).unwrap());
+    let signature_cache = SignerSignatureCache::default();
+    let mut utd = NakamotoUnconfirmedTenureDownloader::new(naddr, None)
+        .with_signature_cache(signature_cache.clone());
+    utd.confirmed_signer_keys = Some(reward_set.clone());
+    utd.unconfirmed_signer_keys = Some(reward_set);
+    utd.tenure_tip = Some(RPCGetTenureInfo {
+        consensus_hash: next_tenure_start_block.header.consensus_hash.clone(),
+        tenure_start_block_id: next_tenure_start_block.block_id(),
+        parent_consensus_hash: tenure_start_block.header.consensus_hash.clone(),
+        parent_tenure_start_block_id: tenure_start_block.block_id(),
+        tip_block_id: next_tenure_start_block.block_id(),
+        tip_height: next_tenure_start_block.header.chain_length,
+        reward_cycle: 0,
+    });
+    utd.state =
+        NakamotoUnconfirmedDownloadState::GetTenureStartBlock(next_tenure_start_block.block_id());
 
-        let ntd = utd.make_highest_complete_tenure_downloader().unwrap();
-        assert_eq!(
-            ntd.state,
-            NakamotoTenureDownloadState::GetTenureStartBlock(
-                unconfirmed_wanted_tenure.winning_block_id.clone(),
-                ntd.state.request_time().unwrap()
-            )
-        );
-    }
+    utd.try_accept_unconfirmed_tenure_start_block(next_tenure_start_block.clone())
+        .unwrap();
+    assert_eq!(signature_cache.hits(), 0);
 
-    // we haven't processed anything yet.
-    // serve all of the unconfirmed blocks in one shot.
-    {
-        let mut utd = NakamotoUnconfirmedTenureDownloader::new(naddr.clone(), None);
-        utd.confirmed_signer_keys = Some(
-            current_reward_sets
-                .get(&tip_rc)
-                .cloned()
-                .unwrap()
-                .reward_cycle_info
-                .known_selected_anchor_block_owned()
-                .unwrap(),
-        );
-        utd.unconfirmed_signer_keys = Some(
-            current_reward_sets
-                .get(&tip_rc)
-                .cloned()
-                .unwrap()
-                .reward_cycle_info
-                .known_selected_anchor_block_owned()
-                .unwrap(),
-        );
+    utd.state = NakamotoUnconfirmedDownloadState::Done;
+    let mut ntd = utd.make_highest_complete_tenure_downloader().unwrap();
 
-        assert_eq!(utd.state, NakamotoUnconfirmedDownloadState::GetTenureInfo);
+    // the tenure-start block hasn't been seen before
+    ntd.try_accept_tenure_start_block(tenure_start_block.clone())
+        .unwrap();
+    assert_eq!(signature_cache.hits(), 0);
 
-        let tenure_tip = RPCGetTenureInfo {
-            consensus_hash: peer.network.stacks_tip.consensus_hash.clone(),
-            tenure_start_block_id: peer.network.tenure_start_block_id.clone(),
-            parent_consensus_hash: peer.network.parent_stacks_tip.consensus_hash.clone(),
-            parent_tenure_start_block_id: StacksBlockId::new(
-                &peer.network.parent_stacks_tip.consensus_hash,
-                &peer.network.parent_stacks_tip.block_hash,
-            ),
-            tip_block_id: StacksBlockId::new(
-                &peer.network.stacks_tip.consensus_hash,
-                &peer.network.stacks_tip.block_hash,
-            ),
-            tip_height: peer.network.stacks_tip.height,
-            reward_cycle: tip_rc,
-        };
+    // the tenure-end block was already verified by the unconfirmed downloader
+    ntd.try_accept_tenure_end_block(&next_tenure_start_block)
+        .unwrap();
+    assert_eq!(signature_cache.hits(), 1);
 
-        let sortdb = peer.chain.sortdb.take().unwrap();
-        let sort_tip = SortitionDB::get_canonical_burn_chain_tip(sortdb.conn()).unwrap();
-        utd.try_accept_tenure_info(
-            &sortdb,
-            &sort_tip,
-            peer.chainstate(),
-            tenure_tip,
-            &current_reward_sets,
-        )
+    // same block ID, but not enough signatures, so it must be verified (and rejected)
+    let mut ntd = utd.make_highest_complete_tenure_downloader().unwrap();
+    ntd.try_accept_tenure_start_block(tenure_start_block)
         .unwrap();
+    assert_eq!(signature_cache.hits(), 2);
 
-        peer.chain.sortdb = Some(sortdb);
+    let mut bad_tenure_end_block = next_tenure_start_block.clone();
+    bad_tenure_end_block.header.signer_signature.truncate(1);
+    assert_eq!(
+        bad_tenure_end_block.block_id(),
+        next_tenure_start_block.block_id()
+    );
+    assert!(matches!(
+        ntd.try_accept_tenure_end_block(&bad_tenure_end_block),
+        Err(NetError::InvalidMessage)
+    ));
+    assert_eq!(signature_cache.hits(), 2);
+}
 
-        assert!(utd.unconfirmed_tenure_start_block.is_some());
+/// Verifying a header's signatures with a precomputed block ID uses the same cache entry as
+/// verifying it without one.
+#[test]
+fn test_signer_signature_cache_with_block_id() {
+    let mut test_signers = TestSigners::default();
+    let reward_set = test_signers.synthesize_reward_set();
+    let (mut block, ..) = make_one_block_tenure(&mut test_signers);
+    test_signers.sign_block_with_reward_set(&mut block, &reward_set);
+    let block_id = block.header.block_id();
 
-        let res = utd
-            .try_accept_unconfirmed_tenure_blocks(
-                unconfirmed_tenure.clone().into_iter().rev().collect(),
-            )
-            .unwrap();
-        assert_eq!(res.unwrap(), unconfirmed_tenure);
+    let signature_cache = SignerSignatureCache::default();
+    signature_cache
+        .verify_signer_signatures_with_id(&block_id, &block.header, &reward_set)
+        .unwrap();
+    assert_eq!(signature_cache.hits(), 0);
 
-        assert_eq!(utd.state, NakamotoUnconfirmedDownloadState::Done);
+    signature_cache
+        .verify_signer_signatures(&block.header, &reward_set)
+        .unwrap();
+    assert_eq!(signature_cache.hits(), 1);
 
-        // we can request the highest-complete tenure
-        assert!(!utd.need_highest_complete_tenure(peer.chainstate()).unwrap());
+    signature_cache
+        .verify_signer_signatures_with_id(&block_id, &block.header, &reward_set)
+        .unwrap();
+    assert_eq!(signature_cache.hits(), 2);
 
-        let ntd = utd.make_highest_complete_tenure_downloader().unwrap();
-        assert_eq!(
-            ntd.state,
-            NakamotoTenureDownloadState::GetTenureStartBlock(
-                unconfirmed_wanted_tenure.winning_block_id.clone(),
-                ntd.state.request_time().unwrap()
-            )
-        );
-    }
+    // a different signature set is not a cache hit
+    let mut bad_block = block.clone();
+    bad_block.header.signer_signature.truncate(1);
+    assert!(signature_cache
+        .verify_signer_signatures_with_id(&block_id, &bad_block.header, &reward_set)
+        .is_err());
+    assert_eq!(signature_cache.hits(), 2);
+}
 
-    // bad block signature
-    {
-        let mut utd = NakamotoUnconfirmedTenureDownloader::new(naddr.clone(), None);
-        utd.confirmed_signer_keys = Some(
-            current_reward_sets
-                .get(&tip_rc)
-                .cloned()
-                .unwrap()
-                .reward_cycle_info
-                .known_selected_anchor_block_owned()
-                .unwrap(),
-        );
-        utd.unconfirmed_signer_keys = Some(
-            current_reward_sets
-                .get(&tip_rc)
-                .cloned()
-                .unwrap()
-                .reward_cycle_info
-                .known_selected_anchor_block_owned()
-                .unwrap(),
-        );
+/// Tenures written to a tenure archive are read back intact and in order, and tenures whose
+/// blocks were not signed by the expected signers are rejected.
+#[test]
+fn test_tenure_archive_round_trip() {
+    let mut test_signers = TestSigners::default();
+    let reward_set = test_signers.synthesize_reward_set();
+    let (mut first_tenure, mut second_tenure_start_block, _) = make_tenure(&mut test_signers, 3);
+    for block in first_tenure.iter_mut() {
+        test_signers.sign_block_with_reward_set(block, &reward_set);
+    }
+    test_signers.sign_block_with_reward_set(&mut second_tenure_start_block, &reward_set);
+    let second_tenure = vec![second_tenure_start_block];
+
+    // a third tenure builds on the second
+    let mut third_tenure: Vec<NakamotoBlock> = vec![];
+    for _ in 0..2 {
+        let parent = third_tenure.last().unwrap_or(&second_tenure[0]);
+        let mut block = parent.clone();
+        block.header.consensus_hash = ConsensusHash([0x33; 20]);
+        block.header.parent_block_id = parent.block_id();
+        block.header.chain_length = parent.header.chain_length + 1;
+        test_signers.sign_block_with_reward_set(&mut block, &reward_set);
+        third_tenure.push(block);
+    }
 
-        assert_eq!(utd.state, NakamotoUnconfirmedDownloadState::GetTenureInfo);
+    let tenures = HashMap::from([
+        (
+            third_tenure[0].header.consensus_hash.clone(),
+            third_tenure.clone(),
+        ),
+        (
+            first_tenure[0].header.consensus_hash.clone(),
+            first_tenure.clone(),
+        ),
+        (
+            second_tenure[0].header.consensus_hash.clone(),
+            second_tenure.clone(),
+        ),
+    ]);
 
-        let tenure_tip = RPCGetTenureInfo {
-            consensus_hash: peer.network.stacks_tip.consensus_hash.clone(),
-            tenure_start_block_id: peer.network.tenure_start_block_id.clone(),
-            parent_consensus_hash: peer.network.parent_stacks_tip.consensus_hash.clone(),
-            parent_tenure_start_block_id: StacksBlockId::new(
-                &peer.network.parent_stacks_tip.consensus_hash,
-                &peer.network.parent_stacks_tip.block_hash,
-            ),
-            tip_block_id: StacksBlockId::new(
-                &peer.network.stacks_tip.consensus_hash,
-                &peer.network.stacks_tip.block_hash,
-            ),
-            tip_height: peer.network.stacks_tip.height,
-            reward_cycle: tip_rc,
-        };
+    let archive_dir = "/tmp/stacks-node-tests/tenure-archive";
+    std::fs::create_dir_all(archive_dir).unwrap();
+    let archive_path = format!("{archive_dir}/{}.bin", function_name!());
+    export_tenure_archive(&archive_path, &tenures).unwrap();
 
-        let sortdb = peer.chain.sortdb.take().unwrap();
-        let sort_tip = SortitionDB::get_canonical_burn_chain_tip(sortdb.conn()).unwrap();
-        utd.try_accept_tenure_info(
-            &sortdb,
-            &sort_tip,
-            peer.chainstate(),
-            tenure_tip,
-            &current_reward_sets,
-        )
+    let lookup_reward_set = reward_set.clone();
+    let read_tenures: Vec<_> = TenureArchiveReader::open(&archive_path)
+        .unwrap()
+        .with_reward_set_lookup(move |_| Ok(lookup_reward_set.clone()))
+        .collect::<Result<_, _>>()
         .unwrap();
+    assert_eq!(
+        read_tenures,
+        vec![
+            (first_tenure[0].header.consensus_hash.clone(), first_tenure),
+            (
+                second_tenure[0].header.consensus_hash.clone(),
+                second_tenure
+            ),
+            (third_tenure[0].header.consensus_hash.clone(), third_tenure),
+        ]
+    );
 
-        peer.chain.sortdb = Some(sortdb);
-
-        assert!(utd.unconfirmed_tenure_start_block.is_some());
+    // signed by someone else's signers
+    let other_signers = TestSigners::default();
+    let other_reward_set = other_signers.synthesize_reward_set();
+    let mut reader = TenureArchiveReader::open(&archive_path)
+        .unwrap()
+        .with_reward_set_lookup(move |_| Ok(other_reward_set.clone()));
+    assert!(matches!(reader.next(), Some(Err(NetError::InvalidMessage))));
+    assert!(reader.next().is_none());
+
+    // not an archive
+    let mut bytes = vec![];
+    write_tenure_archive(&mut bytes, &tenures).unwrap();
+    bytes[0] ^= 0xff;
+    assert!(matches!(
+        TenureArchiveReader::new(&bytes[..]),
+        Err(NetError::DeserializeError(..))
+    ));
+}
 
-        let mut bad_block = unconfirmed_tenure.last().cloned().unwrap();
-        bad_block.header.version += 1;
+/// Tenures as the downloader produces them end with their tenure-end block, which is the next
+/// tenure's start block.  These can be archived and read back, with the tenure-end block checked
+/// against its own tenure's reward set.  A tenure-end block which does not follow the tenure, or
+/// which is followed by more blocks, is rejected.
+#[test]
+fn test_tenure_archive_downloaded_tenures() {
+    let mut test_signers = TestSigners::default();
+    let reward_set = test_signers.synthesize_reward_set();
+    let next_test_signers = TestSigners::default();
+    let next_reward_set = next_test_signers.synthesize_reward_set();
 
-        assert!(utd
-            .try_accept_unconfirmed_tenure_blocks(vec![bad_block])
-            .is_err());
+    let (mut blocks, mut tenure_end_block, _) = make_tenure(&mut test_signers, 3);
+    for block in blocks.iter_mut() {
+        test_signers.sign_block_with_reward_set(block, &reward_set);
     }
+    // the next tenure is in the next reward cycle
+    next_test_signers.sign_block_with_reward_set(&mut tenure_end_block, &next_reward_set);
+
+    let tenure_id = blocks[0].header.consensus_hash.clone();
+    let next_tenure_id = tenure_end_block.header.consensus_hash.clone();
+    let mut downloaded_tenure = blocks.clone();
+    downloaded_tenure.push(tenure_end_block.clone());
+
+    let read_archive = |tenures: &HashMap<ConsensusHash, Vec<NakamotoBlock>>| {
+        let mut bytes = vec![];
+        write_tenure_archive(&mut bytes, tenures).unwrap();
+        let (tenure_id, reward_set) = (tenure_id.clone(), reward_set.clone());
+        let (next_tenure_id, next_reward_set) = (next_tenure_id.clone(), next_reward_set.clone());
+        TenureArchiveReader::new(std::io::Cursor::new(bytes))
+            .unwrap()
+            .with_reward_set_lookup(move |consensus_hash| {
+                if consensus_hash == &tenure_id {
+                    Ok(reward_set.clone())
+                } else if consensus_hash == &next_tenure_id {
+                    Ok(next_reward_set.clone())
+                } else {
+                    Err(NetError::NotFoundError)
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()
+    };
 
-    // Does not consume blocks beyond the highest processed block ID
-    {
-        let mut utd = NakamotoUnconfirmedTenureDownloader::new(naddr, None);
-        utd.confirmed_signer_keys = Some(
-            current_reward_sets
-                .get(&tip_rc)
-                .cloned()
-                .unwrap()
-                .reward_cycle_info
-                .known_selected_anchor_block_owned()
-                .unwrap(),
-        );
-        utd.unconfirmed_signer_keys = Some(
-            current_reward_sets
-                .get(&tip_rc)
-                .cloned()
-                .unwrap()
-                .reward_cycle_info
-                .known_selected_anchor_block_owned()
-                .unwrap(),
-        );
+    let tenures = HashMap::from([
+        (tenure_id.clone(), downloaded_tenure.clone()),
+        (next_tenure_id.clone(), vec![tenure_end_block.clone()]),
+    ]);
+    assert_eq!(
+        read_archive(&tenures).unwrap(),
+        vec![
+            (tenure_id.clone(), downloaded_tenure.clone()),
+            (next_tenure_id.clone(), vec![tenure_end_block.clone()]),
+        ]
+    );
 
-        assert_eq!(utd.state, NakamotoUnconfirmedDownloadState::GetTenureInfo);
+    // the tenure-end block must be signed by its own tenure's signers
+    let mut bad_tenure = downloaded_tenure.clone();
+    test_signers.sign_block_with_reward_set(bad_tenure.last_mut().unwrap(), &reward_set);
+    assert!(matches!(
+        read_archive(&HashMap::from([(tenure_id.clone(), bad_tenure)])),
+        Err(NetError::InvalidMessage)
+    ));
+
+    // the tenure-end block must build on the tenure's last block
+    let mut bad_tenure = downloaded_tenure.clone();
+    bad_tenure.remove(2);
+    assert!(matches!(
+        read_archive(&HashMap::from([(tenure_id.clone(), bad_tenure)])),
+        Err(NetError::InvalidMessage)
+    ));
+
+    // only the last block may be from another tenure
+    let mut bad_tenure = downloaded_tenure.clone();
+    let mut next_block = tenure_end_block.clone();
+    next_block.header.parent_block_id = tenure_end_block.block_id();
+    next_block.header.chain_length += 1;
+    next_block.txs.clear();
+    next_block.header.tx_merkle_root = next_block.compute_tx_merkle_root();
+    next_test_signers.sign_block_with_reward_set(&mut next_block, &next_reward_set);
+    bad_tenure.push(next_block);
+    assert!(matches!(
+        read_archive(&HashMap::from([(tenure_id.clone(), bad_tenure)])),
+        Err(NetError::InvalidMessage)
+    ));
+
+    // a block from another tenure which isn't a tenure-start block can't end the tenure
+    let mut bad_tenure = downloaded_tenure.clone();
+    let mut not_tenure_end_block = blocks[1].clone();
+    not_tenure_end_block.header.consensus_hash = next_tenure_id.clone();
+    not_tenure_end_block.header.parent_block_id = blocks[2].block_id();
+    not_tenure_end_block.header.chain_length = blocks[2].header.chain_length + 1;
+    next_test_signers.sign_block_with_reward_set(&mut not_tenure_end_block, &next_reward_set);
+    *bad_tenure.last_mut().unwrap() = not_tenure_end_block;
+    assert!(matches!(
+        read_archive(&HashMap::from([(tenure_id.clone(), bad_tenure)])),
+        Err(NetError::InvalidMessage)
+    ));
+}
 
-        let tenure_tip = RPCGetTenureInfo {
-            consensus_hash: peer.network.stacks_tip.consensus_hash.clone(),
-            tenure_start_block_id: peer.network.tenure_start_block_id.clone(),
-            parent_consensus_hash: peer.network.parent_stacks_tip.consensus_hash.clone(),
-            parent_tenure_start_block_id: StacksBlockId::new(
-                &peer.network.parent_stacks_tip.consensus_hash,
-                &peer.network.parent_stacks_tip.block_hash,
-            ),
-            tip_block_id: StacksBlockId::new(
-                &peer.network.stacks_tip.consensus_hash,
-                &peer.network.stacks_tip.block_hash,
-            ),
-            tip_height: peer.network.stacks_tip.height,
-            reward_cycle: tip_rc,
-        };
+/// The reward cycle and sortition tip accessors reflect what the downloader has processed.
+#[test]
+fn test_nakamoto_download_state_machine_reward_cycle_and_sort_tip() {
+    let observer = TestEventObserver::new();
+    let bitvecs = vec![vec![
+        true, true, true, true, true, true, true, true, true, true,
+    ]];
 
-        let sortdb = peer.chain.sortdb.take().unwrap();
-        let sort_tip = SortitionDB::get_canonical_burn_chain_tip(sortdb.conn()).unwrap();
-        utd.try_accept_tenure_info(
-            &sortdb,
-            &sort_tip,
-            peer.chainstate(),
-            tenure_tip,
-            &current_reward_sets,
-        )
+    let rc_len = 10u64;
+    let peer = make_nakamoto_peer_from_invs(function_name!(), &observer, rc_len as u32, 3, bitvecs);
+    let (mut peer, _reward_cycle_invs) =
+        peer_get_nakamoto_invs(peer, &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12]);
+
+    let nakamoto_start = NakamotoBootPlan::nakamoto_first_tenure_height(
+        &peer.config.chain_config.burnchain.pox_constants,
+    );
+
+    let mut downloader =
+        NakamotoDownloadStateMachine::new(nakamoto_start, peer.network.stacks_tip.block_id());
+    assert_eq!(downloader.current_reward_cycle(), 0);
+    assert!(downloader.last_processed_sort_tip().is_none());
+
+    let sortdb = peer.chain.sortdb.take().unwrap();
+    let tip = peer.network.burnchain_tip.clone();
+
+    // tenures which start at the tip are confirmed by commits in the tip's reward cycle
+    let expected_rc = sortdb
+        .pox_constants
+        .block_height_to_reward_cycle(sortdb.first_block_height, tip.block_height - 1)
         .unwrap();
 
-        peer.chain.sortdb = Some(sortdb);
+    downloader
+        .update_wanted_tenures(&peer.network, &sortdb)
+        .unwrap();
+    assert_eq!(downloader.current_reward_cycle(), expected_rc);
+    assert_eq!(downloader.current_reward_cycle(), downloader.reward_cycle);
 
-        assert!(utd.unconfirmed_tenure_start_block.is_some());
+    // the sortition tip is only recorded once a download pass completes
+    assert!(downloader.last_processed_sort_tip().is_none());
 
-        utd.highest_processed_block_id = Some(unconfirmed_tenure[1].header.block_id());
-        let res = utd
-            .try_accept_unconfirmed_tenure_blocks(
-                unconfirmed_tenure.clone().into_iter().rev().collect(),
-            )
-            .unwrap();
-        assert_eq!(res.unwrap().as_slice(), &unconfirmed_tenure[1..]);
+    downloader
+        .run(
+            tip.block_height,
+            &mut peer.network,
+            &sortdb,
+            &mut peer.chain.stacks_node.as_mut().unwrap().chainstate,
+            Some(false),
+        )
+        .unwrap();
+    assert_eq!(downloader.current_reward_cycle(), expected_rc);
+    assert_eq!(downloader.last_processed_sort_tip(), Some(&tip));
 
-        assert_eq!(utd.state, NakamotoUnconfirmedDownloadState::Done);
+    peer.chain.sortdb = Some(sortdb);
+}
 
-        // we can request the highest-complete tenure
-        assert!(!utd.need_highest_complete_tenure(peer.chainstate()).unwrap());
+/// Collects the key/value pairs that a loggable value attaches to a log line
+#[derive(Default)]
+struct LogFieldCollector(HashMap<String, String>);
 
-        let ntd = utd.make_highest_complete_tenure_downloader().unwrap();
-        assert_eq!(
-            ntd.state,
-            NakamotoTenureDownloadState::GetTenureStartBlock(
-                unconfirmed_wanted_tenure.winning_block_id.clone(),
-                ntd.state.request_time().unwrap()
-            )
-        );
+impl slog::Serializer for LogFieldCollector {
+    fn emit_arguments(&mut self, key: slog::Key, val: &std::fmt::Arguments) -> slog::Result {
+        self.0.insert(key.to_string(), val.to_string());
+        Ok(())
     }
 }
 
+/// Each call to `run()` is a new download pass, which is counted in the downloader's metrics and
+/// attached to the log lines at the start and end of the pass.
 #[test]
-fn test_tenure_start_end_from_inventory() {
-    let naddr = NeighborAddress {
-        addrbytes: PeerAddress([0xff; 16]),
-        port: 123,
-        public_key_hash: Hash160([0xff; 20]),
-    };
-    let rc_len = 12u16;
-    let mut invs = NakamotoTenureInv::new(0, u64::from(rc_len), 0, naddr);
-    let pox_constants = PoxConstants::new(
-        rc_len.into(),
-        5,
-        3,
-        0,
-        25,
-        u64::MAX,
-        u64::MAX,
-        u32::MAX,
-        u32::MAX,
-        u32::MAX,
-        u32::MAX,
-    );
-    let first_burn_height = 100u64;
+fn test_nakamoto_download_pass_counter() {
+    let observer = TestEventObserver::new();
+    let bitvecs = vec![vec![
+        true, true, true, true, true, true, true, true, true, true,
+    ]];
 
-    // make some invs
-    let num_rcs = 6;
-    invs.merge_tenure_inv(
-        BitVec::<2100>::try_from(
-            vec![
-                true, true, true, true, true, true, true, true, true, true, true, true,
-            ]
-            .as_slice(),
-        )
-        .unwrap(),
-        0,
-    );
-    invs.merge_tenure_inv(
-        BitVec::<2100>::try_from(
-            vec![
-                false, false, false, false, false, false, false, true, true, true, false, false,
-            ]
-            .as_slice(),
-        )
-        .unwrap(),
-        1,
-    );
-    invs.merge_tenure_inv(
-        BitVec::<2100>::try_from(
-            vec![
-                true, false, false, false, false, false, false, true, true, true, false, true,
-            ]
-            .as_slice(),
-        )
-        .unwrap(),
-        2,
-    );
-    invs.merge_tenure_inv(
-        BitVec::<2100>::try_from(
-            vec![
-                true, false, true, false, true, false, true, true, true, true, true, false,
-            ]
-            .as_slice(),
-        )
-        .unwrap(),
-        3,
-    );
-    invs.merge_tenure_inv(
-        BitVec::<2100>::try_from(
-            vec![
-                false, true, false, true, false, true, true, true, true, true, false, true,
-            ]
-            .as_slice(),
-        )
-        .unwrap(),
-        4,
-    );
-    invs.merge_tenure_inv(
-        BitVec::<2100>::try_from(
-            vec![
-                false, false, true, true, true, true, true, true, true, true, true, true,
-            ]
-            .as_slice(),
-        )
-        .unwrap(),
-        5,
+    let rc_len = 10u64;
+    let peer = make_nakamoto_peer_from_invs(function_name!(), &observer, rc_len as u32, 3, bitvecs);
+    let (mut peer, _reward_cycle_invs) =
+        peer_get_nakamoto_invs(peer, &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12]);
+
+    let nakamoto_start = NakamotoBootPlan::nakamoto_first_tenure_height(
+        &peer.config.chain_config.burnchain.pox_constants,
     );
 
-    let mut wanted_tenures = vec![];
-    let mut next_wanted_tenures = vec![];
-    for i in 0..rc_len {
-        wanted_tenures.push(WantedTenure::new(
-            ConsensusHash([i as u8; 20]),
-            StacksBlockId([i as u8; 32]),
-            u64::from(i) + first_burn_height + 1,
-        ));
-        next_wanted_tenures.push(WantedTenure::new(
-            ConsensusHash([(i + 128) as u8; 20]),
-            StacksBlockId([(i + 128) as u8; 32]),
-            u64::from(i) + first_burn_height + 1,
-        ));
-    }
-    let mut all_tenures = wanted_tenures.clone();
-    all_tenures.append(&mut next_wanted_tenures.clone());
+    let mut downloader =
+        NakamotoDownloadStateMachine::new(nakamoto_start, peer.network.stacks_tip.block_id());
+    assert_eq!(downloader.metrics().download_pass, 0);
+
+    let sortdb = peer.chain.sortdb.take().unwrap();
+    let tip = peer.network.burnchain_tip.clone();
+    for expected_pass in 1..=3 {
+        downloader
+            .run(
+                tip.block_height,
+                &mut peer.network,
+                &sortdb,
+                &mut peer.chain.stacks_node.as_mut().unwrap().chainstate,
+                Some(false),
+            )
+            .unwrap();
 
-    // check the case where we only have one Nakamoto rewrad cycle
-    for rc in 0..num_rcs {
-        let available = TenureStartEnd::from_inventory(
-            rc,
-            &wanted_tenures,
-            None,
-            &pox_constants,
-            first_burn_height,
-            &invs,
+        let metrics = downloader.metrics();
+        assert_eq!(metrics.download_pass, expected_pass);
+
+        let mut log_fields = LogFieldCollector::default();
+        slog::KV::serialize(
+            &metrics,
+            &slog::Record::new(
+                &slog::record_static!(slog::Level::Debug, ""),
+                &format_args!("Downloader: end pass"),
+                slog::b!(),
+            ),
+            &mut log_fields,
         )
         .unwrap();
-        let bits = invs.tenures_inv.get(&rc).unwrap();
-        for (i, wt) in wanted_tenures.iter().enumerate() {
-            if i >= usize::from(rc_len - 1) {
-                // nothing here
-                assert!(!available.contains_key(&wt.tenure_id_consensus_hash));
-                continue;
-            }
-
-            let tenure_start_end_opt = available.get(&wt.tenure_id_consensus_hash);
-            if bits.get(i as u16).unwrap() {
-                // this sortition had a tenure
-                let mut j = (i + 1) as u16;
-                let mut tenure_start_index = None;
-                let mut tenure_end_index = None;
-
-                while j < bits.len() {
-                    if bits.get(j).unwrap() {
-                        tenure_start_index = Some(j);
-                        j += 1;
-                        break;
-                    }
-                    j += 1;
-                }
-
-                while j < bits.len() {
-                    if bits.get(j).unwrap() {
-                        tenure_end_index = Some(j);
-                        break;
-                    }
-                    j += 1;
-                }
-
-                if tenure_start_index.is_some() && tenure_end_index.is_some() {
-                    let tenure_start_end = tenure_start_end_opt.unwrap();
-                    assert_eq!(
-                        wanted_tenures[tenure_start_index.unwrap() as usize].winning_block_id,
-                        tenure_start_end.start_block_id
-                    );
-                    assert_eq!(
-                        wanted_tenures[tenure_end_index.unwrap() as usize].winning_block_id,
-                        tenure_start_end.end_block_id
-                    );
-                } else {
-                    assert!(tenure_start_end_opt.is_none());
-                }
-            } else {
-                // no tenure here
-                assert!(
-                    tenure_start_end_opt.is_none(),
-                    "tenure_start_end = {tenure_start_end_opt:?}, rc = {rc}, i = {i}, wt = {wt:?}"
-                );
-            }
-        }
+        assert_eq!(
+            log_fields.0.get("download_pass"),
+            Some(&expected_pass.to_string())
+        );
+        assert_eq!(
+            log_fields.0.get("reward_cycle"),
+            Some(&metrics.reward_cycle.to_string())
+        );
+        assert_eq!(log_fields.0.get("state"), Some(&metrics.state.to_string()));
+        assert_eq!(
+            log_fields.0.get("inflight"),
+            Some(&metrics.inflight.to_string())
+        );
     }
 
-    // check the case where we have at least two Nakamoto rewrad cycles.
-    // the available tenures should straddle the reward cycle boundary.
-    for rc in 0..(num_rcs - 1) {
-        debug!("rc = {}", rc);
-        let available = TenureStartEnd::from_inventory(
-            rc,
-            &wanted_tenures,
-            Some(&next_wanted_tenures),
-            &pox_constants,
-            first_burn_height,
-            &invs,
-        )
-        .unwrap();
+    peer.chain.sortdb = Some(sortdb);
+}
 
-        // need to check across two reward cycles
-        let bits_cur_rc = invs.tenures_inv.get(&rc).unwrap();
-        let bits_next_rc = invs.tenures_inv.get(&(rc + 1)).unwrap();
-        let mut bits = BitVec::<2100>::zeros(rc_len * 2).unwrap();
-        for i in 0..rc_len {
-            if bits_cur_rc.get(i).unwrap() {
-                bits.set(i, true).unwrap();
-            }
-            if bits_next_rc.get(i).unwrap() {
-                bits.set(i + rc_len, true).unwrap();
-            }
-        }
+/// Build a sortition DB with `num_cycles` full reward cycles of sortitions on top of its first
+/// block (at height 0), using the given PoX constants.  There is no Stacks chain behind it; the
+/// snapshots only carry the burnchain data the downloader needs to compute its wanted tenures.
+pub fn build_mock_sortition_history(pox_constants: PoxConstants, num_cycles: u64) -> SortitionDB {
+    let mut buf = [0u8; 32];
+    thread_rng().fill(&mut buf);
+    let db_path = format!(
+        "/tmp/stacks-node-tests/mock-sortition-history/db-{}",
+        to_hex(&buf)
+    );
+    let num_blocks = num_cycles * u64::from(pox_constants.reward_cycle_length);
+    let mut sortdb = SortitionDB::connect(
+        &db_path,
+        0,
+        &BurnchainHeaderHash([0x00; 32]),
+        get_epoch_time_secs(),
+        &StacksEpoch::unit_test(StacksEpochId::Epoch20, 0),
+        pox_constants,
+        None,
+        true,
+    )
+    .unwrap();
+    for _ in 0..num_blocks {
+        step_mock_sortition_tip(&mut sortdb);
+    }
+    sortdb
+}
 
-        for (i, wt) in wanted_tenures.iter().enumerate() {
-            let tenure_start_end_opt = available.get(&wt.tenure_id_consensus_hash);
-            if bits
-                .get(i as u16)
-                .unwrap_or_else(|| panic!("failed to get bit {i}: {wt:?}"))
-            {
-                // this sortition had a tenure
-                let mut j = (i + 1) as u16;
-                let mut tenure_start_index = None;
-                let mut tenure_end_index = None;
+/// Append one sortition to a sortition DB built by `build_mock_sortition_history()`, and return
+/// the new tip.
+pub fn step_mock_sortition_tip(sortdb: &mut SortitionDB) -> BlockSnapshot {
+    let tip = SortitionDB::get_canonical_burn_chain_tip(sortdb.conn()).unwrap();
+    let next_height = tip.block_height + 1;
+    let mut next_hash = [0xff; 32];
+    next_hash[24..].copy_from_slice(&next_height.to_be_bytes());
+    test_append_snapshot(sortdb, BurnchainHeaderHash(next_hash), &[])
+}
 
-                while j < bits.len() {
-                    if bits.get(j).unwrap() {
-                        tenure_start_index = Some(j);
-                        j += 1;
-                        break;
-                    }
-                    j += 1;
-                }
+/// Verify that `prev_wanted_tenures` and `wanted_tenures` together cover a contiguous run of
+/// sortitions, with no sortition in both lists.
+fn check_wanted_tenures_contiguous(downloader: &NakamotoDownloadStateMachine) {
+    let prev_wanted_tenures = downloader.prev_wanted_tenures.as_ref().unwrap();
+    let heights: Vec<_> = prev_wanted_tenures
+        .iter()
+        .chain(downloader.wanted_tenures.iter())
+        .map(|wt| wt.burn_height)
+        .collect();
+    for (prev_height, height) in heights.iter().zip(heights.iter().skip(1)) {
+        assert_eq!(
+            prev_height + 1,
+            *height,
+            "wanted tenures are not contiguous: {:?}",
+            &heights
+        );
+    }
+}
 
-                while j < bits.len() {
-                    if bits.get(j).unwrap() {
-                        tenure_end_index = Some(j);
-                        break;
-                    }
-                    j += 1;
-                }
+/// Drive `update_wanted_tenures` one sortition at a time across a reward cycle boundary, and
+/// verify that the downloader's reward cycle advances exactly once, and that when it does,
+/// `prev_wanted_tenures` and `wanted_tenures` hold exactly the previous and current reward
+/// cycles' sortitions.
+#[test]
+fn test_update_wanted_tenures_reward_cycle_boundary() {
+    let pox_constants = PoxConstants::test_default();
+    let rc_len = u64::from(pox_constants.reward_cycle_length);
+    let mut sortdb = build_mock_sortition_history(pox_constants, 3);
+    let tip = SortitionDB::get_canonical_burn_chain_tip(sortdb.conn()).unwrap();
+    assert_eq!(tip.block_height, 3 * rc_len);
+
+    let check_partition = |downloader: &NakamotoDownloadStateMachine, sortdb: &SortitionDB| {
+        let rc = downloader.reward_cycle;
+        let prev_wanted_tenures = downloader.prev_wanted_tenures.as_ref().unwrap();
+        assert_eq!(prev_wanted_tenures.len(), usize::try_from(rc_len).unwrap());
+        for wt in prev_wanted_tenures.iter() {
+            assert_eq!(
+                sortdb
+                    .pox_constants
+                    .block_height_to_reward_cycle(sortdb.first_block_height, wt.burn_height),
+                Some(rc - 1)
+            );
+        }
+        for wt in downloader.wanted_tenures.iter() {
+            assert_eq!(
+                sortdb
+                    .pox_constants
+                    .block_height_to_reward_cycle(sortdb.first_block_height, wt.burn_height),
+                Some(rc)
+            );
+        }
+        check_wanted_tenures_contiguous(downloader);
+    };
 
-                if tenure_start_index.is_some() && tenure_end_index.is_some() {
-                    debug!(
-                        "rc = {rc}, i = {i}, tenure_start_index = {tenure_start_index:?}, tenure_end_index = {tenure_end_index:?}"
-                    );
-                    let tenure_start_end = tenure_start_end_opt.unwrap_or_else(|| {
-                        panic!("failed to get tenure_start_end_opt: i = {i}, wt = {wt:?}")
-                    });
-                    assert_eq!(
-                        all_tenures[tenure_start_index.unwrap() as usize].winning_block_id,
-                        tenure_start_end.start_block_id
-                    );
-                    assert_eq!(
-                        all_tenures[tenure_end_index.unwrap() as usize].winning_block_id,
-                        tenure_start_end.end_block_id
-                    );
-                } else {
-                    assert!(tenure_start_end_opt.is_none());
-                }
-            } else {
-                // no tenure here
-                assert!(
-                    tenure_start_end_opt.is_none(),
-                    "tenure_start_end = {tenure_start_end_opt:?}, rc = {rc}, i = {i}, wt = {wt:?}"
-                );
-            }
+    // the tip is the first sortition of reward cycle 3, so it only confirms tenures in reward
+    // cycle 2
+    let mut downloader = NakamotoDownloadStateMachine::new(0, StacksBlockId([0x00; 32]));
+    downloader.update_wanted_tenures_at(&tip, &sortdb).unwrap();
+    downloader.last_sort_tip = Some(tip);
+    assert_eq!(downloader.reward_cycle, 2);
+    assert_eq!(
+        downloader.wanted_tenures.len(),
+        usize::try_from(rc_len).unwrap()
+    );
+    check_partition(&downloader, &sortdb);
+
+    let mut num_rc_changes = 0;
+    for _ in 0..rc_len {
+        let last_rc = downloader.reward_cycle;
+        let tip = step_mock_sortition_tip(&mut sortdb);
+        downloader.update_wanted_tenures_at(&tip, &sortdb).unwrap();
+        downloader.last_sort_tip = Some(tip);
+
+        check_wanted_tenures_contiguous(&downloader);
+        if downloader.reward_cycle != last_rc {
+            assert_eq!(downloader.reward_cycle, last_rc + 1);
+            num_rc_changes += 1;
+            check_partition(&downloader, &sortdb);
         }
     }
+    assert_eq!(num_rc_changes, 1);
+    assert_eq!(downloader.reward_cycle, 3);
+    check_partition(&downloader, &sortdb);
 }
 
 /// Test all of the functionality needed to transform a peer's reported tenure inventory into a
@@ -1546,6 +6543,7 @@ fn test_make_tenure_downloaders() {
         let available = NakamotoDownloadStateMachine::find_available_tenures(
             rc,
             &rc_wanted_tenures,
+            &HashSet::new(),
             full_inventories.iter(),
         );
         assert_eq!(available.len(), rc_len as usize);
@@ -1575,15 +6573,13 @@ fn test_make_tenure_downloaders() {
         let available = NakamotoDownloadStateMachine::find_available_tenures(
             rc,
             &rc_wanted_tenures,
+            &HashSet::new(),
             sparse_inventories.iter(),
         );
-        assert_eq!(available.len(), rc_len as usize);
+        assert_eq!(available.len(), (rc_len / 2) as usize);
         for (i, wt) in rc_wanted_tenures.iter().enumerate() {
             if i % 2 == 0 {
-                assert_eq!(
-                    available.get(&wt.tenure_id_consensus_hash).unwrap(),
-                    &vec![]
-                );
+                assert!(available.get(&wt.tenure_id_consensus_hash).is_none());
             } else {
                 assert_eq!(
                     available.get(&wt.tenure_id_consensus_hash).unwrap(),
@@ -1596,9 +6592,10 @@ fn test_make_tenure_downloaders() {
         let available = NakamotoDownloadStateMachine::find_available_tenures(
             rc + 1,
             &rc_wanted_tenures,
+            &HashSet::new(),
             full_inventories.iter(),
         );
-        assert_eq!(available.len(), rc_len as usize);
+        assert!(available.is_empty());
 
         // tip full invs
         full_invs.merge_tenure_inv(
@@ -1616,6 +6613,7 @@ fn test_make_tenure_downloaders() {
         let available = NakamotoDownloadStateMachine::find_available_tenures(
             rc + 1,
             &tip_wanted_tenures,
+            &HashSet::new(),
             full_inventories.iter(),
         );
         assert_eq!(available.len(), tip_wanted_tenures.len());
@@ -1642,15 +6640,13 @@ fn test_make_tenure_downloaders() {
         let available = NakamotoDownloadStateMachine::find_available_tenures(
             rc + 1,
             &tip_wanted_tenures,
+            &HashSet::new(),
             sparse_inventories.iter(),
         );
-        assert_eq!(available.len(), tip_wanted_tenures.len());
+        assert_eq!(available.len(), tip_wanted_tenures.len() / 2);
         for (i, wt) in tip_wanted_tenures.iter().enumerate() {
             if i % 2 == 0 {
-                assert_eq!(
-                    available.get(&wt.tenure_id_consensus_hash).unwrap(),
-                    &vec![]
-                );
+                assert!(available.get(&wt.tenure_id_consensus_hash).is_none());
             } else {
                 assert_eq!(
                     available.get(&wt.tenure_id_consensus_hash).unwrap(),
@@ -1982,89 +6978,382 @@ fn test_make_tenure_downloaders() {
             );
         }
 
-        // sanity check -- the ith wanted tenure is available from rc_len - i neighbors
-        for (i, wt) in rc_wanted_tenures.iter().enumerate() {
-            let addrs = available.get(&wt.tenure_id_consensus_hash).unwrap();
-            assert_eq!(addrs.len(), (rc_len as usize) - i + 1);
-        }
+        // sanity check -- the ith wanted tenure is available from rc_len - i neighbors
+        for (i, wt) in rc_wanted_tenures.iter().enumerate() {
+            let addrs = available.get(&wt.tenure_id_consensus_hash).unwrap();
+            assert_eq!(addrs.len(), (rc_len as usize) - i + 1);
+        }
+
+        // pretend nakamoto_start is 0 for now, so we can treat this like a full reward cycle
+        let mut ibd_schedule = NakamotoDownloadStateMachine::make_ibd_download_schedule(
+            0,
+            &rc_wanted_tenures,
+            &available,
+        );
+
+        let old_schedule = ibd_schedule.clone();
+        let sched_len = ibd_schedule.len();
+
+        // make 6 downloaders
+        downloaders.make_tenure_downloaders(
+            &mut ibd_schedule,
+            &mut available,
+            &tenure_block_ids,
+            6,
+            &current_reward_sets,
+        );
+
+        // made all 6 downloaders
+        assert_eq!(ibd_schedule.len() + 6, sched_len);
+        assert_eq!(downloaders.downloaders.len(), 6);
+        for (i, wt) in rc_wanted_tenures.iter().enumerate() {
+            let naddrs = available.get(&wt.tenure_id_consensus_hash).unwrap();
+            if i < 6 {
+                assert_eq!(naddrs.len(), (rc_len as usize) - i);
+            } else {
+                assert_eq!(naddrs.len(), (rc_len as usize) - i + 1);
+            }
+        }
+
+        for (i, wt) in rc_wanted_tenures.iter().enumerate() {
+            let possible_addrs = available_by_index.get(i).unwrap();
+            let mut found = false;
+            for addr in possible_addrs.iter() {
+                if downloaders.has_downloader(addr) {
+                    found = true;
+                    break;
+                }
+            }
+
+            if i < 6 {
+                assert!(found);
+            } else {
+                assert!(!found);
+            }
+        }
+
+        // make 6 more downloaders
+        downloaders.make_tenure_downloaders(
+            &mut ibd_schedule,
+            &mut available,
+            &tenure_block_ids,
+            12,
+            &current_reward_sets,
+        );
+
+        // only made 4 downloaders got created
+        assert!(ibd_schedule.is_empty());
+        assert_eq!(downloaders.downloaders.len(), 10);
+        for (i, wt) in rc_wanted_tenures.iter().enumerate() {
+            let naddrs = available.get(&wt.tenure_id_consensus_hash).unwrap();
+            assert_eq!(naddrs.len(), (rc_len as usize) - i);
+        }
+
+        for (i, wt) in rc_wanted_tenures.iter().enumerate() {
+            let possible_addrs = available_by_index.get(i).unwrap();
+            let mut found = false;
+            for addr in possible_addrs.iter() {
+                if downloaders.has_downloader(addr) {
+                    found = true;
+                    break;
+                }
+            }
+
+            assert!(found);
+        }
+    }
+
+    // test that a single peer which serves every tenure is only ever assigned one downloader
+    {
+        let mut downloaders = NakamotoTenureDownloaderSet::new();
+
+        let sortdb = peer.sortdb();
+        let rc = sortdb
+            .pox_constants
+            .block_height_to_reward_cycle(sortdb.first_block_height, tip.block_height)
+            .unwrap()
+            - 1;
+        let rc_wanted_tenures =
+            NakamotoDownloadStateMachine::load_wanted_tenures_for_reward_cycle(rc, &tip, sortdb)
+                .unwrap();
+        let tip_wanted_tenures =
+            NakamotoDownloadStateMachine::load_wanted_tenures_at_tip(None, &tip, sortdb, &[])
+                .unwrap();
+
+        let naddr = NeighborAddress {
+            addrbytes: PeerAddress([0xff; 16]),
+            port: 123,
+            public_key_hash: Hash160([0xff; 20]),
+        };
+
+        let mut full_invs = NakamotoTenureInv::new(0, rc_len, 0, naddr.clone());
+        for inv_rc in [rc, rc + 1] {
+            full_invs.merge_tenure_inv(
+                BitVec::<2100>::try_from(
+                    vec![
+                        true, true, true, true, true, true, true, true, true, true, true, true,
+                    ]
+                    .as_slice(),
+                )
+                .unwrap(),
+                inv_rc,
+            );
+        }
+
+        let mut full_inventories = HashMap::new();
+        full_inventories.insert(naddr.clone(), full_invs);
+
+        let tenure_block_ids = NakamotoDownloadStateMachine::find_tenure_block_ids(
+            rc,
+            &rc_wanted_tenures,
+            Some(&tip_wanted_tenures),
+            &sortdb.pox_constants,
+            sortdb.first_block_height,
+            full_inventories.iter(),
+        );
+        let mut available = NakamotoDownloadStateMachine::find_available_tenures(
+            rc,
+            &rc_wanted_tenures,
+            &HashSet::new(),
+            full_inventories.iter(),
+        );
+        assert_eq!(available.len(), rc_len as usize);
+
+        let mut ibd_schedule = NakamotoDownloadStateMachine::make_ibd_download_schedule(
+            0,
+            &rc_wanted_tenures,
+            &available,
+        );
+
+        downloaders.make_tenure_downloaders(
+            &mut ibd_schedule,
+            &mut available,
+            &tenure_block_ids,
+            rc_len as usize,
+            &current_reward_sets,
+        );
+
+        // the lone peer drives exactly one downloader, even though it serves all tenures and we
+        // asked for up to rc_len downloaders
+        assert_eq!(downloaders.num_downloaders(), 1);
+        assert_eq!(downloaders.num_scheduled_downloaders(), 1);
+        assert_eq!(downloaders.peers.len(), 1);
+        assert!(downloaders.has_downloader(&naddr));
+
+        // re-binding the same peer does not give it a second downloader
+        assert!(downloaders.try_resume_peer(naddr.clone()));
+        assert_eq!(downloaders.num_scheduled_downloaders(), 1);
+        assert_eq!(downloaders.peers.len(), 1);
+    }
+
+    // test that tenures from the previous reward cycle get at least `prev_cycle_download_slots`
+    // downloaders, even if the schedule puts the current reward cycle's tenures first
+    {
+        let sortdb = peer.sortdb();
+        let rc = sortdb
+            .pox_constants
+            .block_height_to_reward_cycle(sortdb.first_block_height, tip.block_height)
+            .unwrap()
+            - 1;
+        let rc_wanted_tenures =
+            NakamotoDownloadStateMachine::load_wanted_tenures_for_reward_cycle(rc, &tip, sortdb)
+                .unwrap();
+        let tip_wanted_tenures =
+            NakamotoDownloadStateMachine::load_wanted_tenures_at_tip(None, &tip, sortdb, &[])
+                .unwrap();
+
+        let naddrs: Vec<_> = (0..rc_len)
+            .map(|i| NeighborAddress {
+                addrbytes: PeerAddress([0xff; 16]),
+                port: (i + 123) as u16,
+                public_key_hash: Hash160([0xff; 20]),
+            })
+            .collect();
+
+        let mut full_inventories = HashMap::new();
+        for naddr in naddrs.iter() {
+            let mut full_invs = NakamotoTenureInv::new(0, rc_len, 0, naddr.clone());
+            for inv_rc in [rc, rc + 1] {
+                full_invs.merge_tenure_inv(
+                    BitVec::<2100>::try_from(
+                        vec![
+                            true, true, true, true, true, true, true, true, true, true, true, true,
+                        ]
+                        .as_slice(),
+                    )
+                    .unwrap(),
+                    inv_rc,
+                );
+            }
+            full_inventories.insert(naddr.clone(), full_invs);
+        }
+
+        let mut tenure_block_ids = NakamotoDownloadStateMachine::find_tenure_block_ids(
+            rc,
+            &rc_wanted_tenures,
+            Some(&tip_wanted_tenures),
+            &sortdb.pox_constants,
+            sortdb.first_block_height,
+            full_inventories.iter(),
+        );
+
+        // pretend that the first half of the tenures are in the previous reward cycle
+        let tenure_ids: Vec<_> = rc_wanted_tenures
+            .iter()
+            .map(|wt| wt.tenure_id_consensus_hash.clone())
+            .filter(|ch| tenure_block_ids.get(&naddrs[0]).unwrap().contains_key(ch))
+            .collect();
+        assert!(tenure_ids.len() >= 6);
+        let (prev_tenure_ids, cur_tenure_ids) = tenure_ids.split_at(tenure_ids.len() / 2);
+        for availability in tenure_block_ids.values_mut() {
+            for ch in prev_tenure_ids.iter() {
+                availability.get_mut(ch).unwrap().start_reward_cycle = rc - 1;
+            }
+        }
+
+        let mut reward_sets = current_reward_sets.clone();
+        let rc_reward_set = reward_sets.get(&rc).cloned().unwrap();
+        reward_sets.insert(rc - 1, rc_reward_set);
+
+        // current reward cycle's tenures first
+        let schedule: VecDeque<_> = cur_tenure_ids
+            .iter()
+            .chain(prev_tenure_ids.iter())
+            .cloned()
+            .collect();
+        let available: HashMap<_, _> = tenure_ids
+            .iter()
+            .map(|ch| (ch.clone(), naddrs.clone()))
+            .collect();
+
+        let count = cur_tenure_ids.len() - 1;
+        for prev_cycle_download_slots in [0, 2] {
+            let mut downloaders = NakamotoTenureDownloaderSet::new();
+            downloaders.prev_cycle_download_slots = prev_cycle_download_slots;
+
+            let mut schedule = schedule.clone();
+            let mut available = available.clone();
+            downloaders.make_tenure_downloaders(
+                &mut schedule,
+                &mut available,
+                &tenure_block_ids,
+                count,
+                &reward_sets,
+            );
+            assert_eq!(downloaders.num_scheduled_downloaders(), count);
+
+            let num_prev_cycle_downloaders = downloaders
+                .downloaders
+                .iter()
+                .flatten()
+                .filter(|d| prev_tenure_ids.contains(&d.tenure_id_consensus_hash))
+                .count();
+
+            if prev_cycle_download_slots == 0 {
+                // strictly in schedule order
+                assert_eq!(num_prev_cycle_downloaders, 0);
+            } else {
+                assert!(num_prev_cycle_downloaders >= prev_cycle_download_slots);
+            }
+        }
+    }
+    // test that tenures which can't be downloaded are reported as abandoned, along with why
+    {
+        let sortdb = peer.sortdb();
+        let rc = sortdb
+            .pox_constants
+            .block_height_to_reward_cycle(sortdb.first_block_height, tip.block_height)
+            .unwrap()
+            - 1;
+        let rc_wanted_tenures =
+            NakamotoDownloadStateMachine::load_wanted_tenures_for_reward_cycle(rc, &tip, sortdb)
+                .unwrap();
+        let tip_wanted_tenures =
+            NakamotoDownloadStateMachine::load_wanted_tenures_at_tip(None, &tip, sortdb, &[])
+                .unwrap();
+
+        let naddr = NeighborAddress {
+            addrbytes: PeerAddress([0xff; 16]),
+            port: 123,
+            public_key_hash: Hash160([0xff; 20]),
+        };
+
+        let mut full_invs = NakamotoTenureInv::new(0, rc_len, 0, naddr.clone());
+        for inv_rc in [rc, rc + 1] {
+            full_invs.merge_tenure_inv(
+                BitVec::<2100>::try_from(
+                    vec![
+                        true, true, true, true, true, true, true, true, true, true, true, true,
+                    ]
+                    .as_slice(),
+                )
+                .unwrap(),
+                inv_rc,
+            );
+        }
+
+        let mut full_inventories = HashMap::new();
+        full_inventories.insert(naddr.clone(), full_invs);
 
-        // pretend nakamoto_start is 0 for now, so we can treat this like a full reward cycle
-        let mut ibd_schedule = NakamotoDownloadStateMachine::make_ibd_download_schedule(
-            0,
+        let tenure_block_ids = NakamotoDownloadStateMachine::find_tenure_block_ids(
+            rc,
             &rc_wanted_tenures,
-            &available,
+            Some(&tip_wanted_tenures),
+            &sortdb.pox_constants,
+            sortdb.first_block_height,
+            full_inventories.iter(),
         );
+        let tenure_id = rc_wanted_tenures
+            .iter()
+            .map(|wt| wt.tenure_id_consensus_hash.clone())
+            .find(|ch| tenure_block_ids.get(&naddr).unwrap().contains_key(ch))
+            .unwrap();
 
-        let old_schedule = ibd_schedule.clone();
-        let sched_len = ibd_schedule.len();
+        // no neighbor has this tenure
+        let unavailable_tenure_id = ConsensusHash([0x33; 20]);
 
-        // make 6 downloaders
+        let available: HashMap<_, _> = [(tenure_id.clone(), vec![naddr.clone()])]
+            .into_iter()
+            .collect();
+        let schedule: VecDeque<_> = [tenure_id.clone(), unavailable_tenure_id.clone()]
+            .into_iter()
+            .collect();
+
+        // no reward sets are known, so the available tenure can't be validated
+        let mut downloaders = NakamotoTenureDownloaderSet::new();
         downloaders.make_tenure_downloaders(
-            &mut ibd_schedule,
-            &mut available,
+            &mut schedule.clone(),
+            &mut available.clone(),
             &tenure_block_ids,
-            6,
-            &current_reward_sets,
+            2,
+            &BTreeMap::new(),
+        );
+        assert_eq!(downloaders.num_downloaders(), 0);
+        assert_eq!(downloaders.abandoned_tenures().len(), 2);
+        assert_eq!(
+            downloaders.abandoned_tenures().get(&tenure_id),
+            Some(&AbandonedTenureReason::MissingRewardSet)
+        );
+        assert_eq!(
+            downloaders.abandoned_tenures().get(&unavailable_tenure_id),
+            Some(&AbandonedTenureReason::NoNeighbors)
         );
 
-        // made all 6 downloaders
-        assert_eq!(ibd_schedule.len() + 6, sched_len);
-        assert_eq!(downloaders.downloaders.len(), 6);
-        for (i, wt) in rc_wanted_tenures.iter().enumerate() {
-            let naddrs = available.get(&wt.tenure_id_consensus_hash).unwrap();
-            if i < 6 {
-                assert_eq!(naddrs.len(), (rc_len as usize) - i);
-            } else {
-                assert_eq!(naddrs.len(), (rc_len as usize) - i + 1);
-            }
-        }
-
-        for (i, wt) in rc_wanted_tenures.iter().enumerate() {
-            let possible_addrs = available_by_index.get(i).unwrap();
-            let mut found = false;
-            for addr in possible_addrs.iter() {
-                if downloaders.has_downloader(addr) {
-                    found = true;
-                    break;
-                }
-            }
-
-            if i < 6 {
-                assert!(found);
-            } else {
-                assert!(!found);
-            }
-        }
-
-        // make 6 more downloaders
+        // once the reward set is known, the tenure is downloaded and no longer abandoned
         downloaders.make_tenure_downloaders(
-            &mut ibd_schedule,
-            &mut available,
+            &mut schedule.clone(),
+            &mut available.clone(),
             &tenure_block_ids,
-            12,
+            2,
             &current_reward_sets,
         );
-
-        // only made 4 downloaders got created
-        assert!(ibd_schedule.is_empty());
-        assert_eq!(downloaders.downloaders.len(), 10);
-        for (i, wt) in rc_wanted_tenures.iter().enumerate() {
-            let naddrs = available.get(&wt.tenure_id_consensus_hash).unwrap();
-            assert_eq!(naddrs.len(), (rc_len as usize) - i);
-        }
-
-        for (i, wt) in rc_wanted_tenures.iter().enumerate() {
-            let possible_addrs = available_by_index.get(i).unwrap();
-            let mut found = false;
-            for addr in possible_addrs.iter() {
-                if downloaders.has_downloader(addr) {
-                    found = true;
-                    break;
-                }
-            }
-
-            assert!(found);
-        }
+        assert_eq!(downloaders.num_downloaders(), 1);
+        assert!(downloaders.has_downloader_for_tenure(&tenure_id));
+        assert_eq!(downloaders.abandoned_tenures().len(), 1);
+        assert_eq!(
+            downloaders.abandoned_tenures().get(&unavailable_tenure_id),
+            Some(&AbandonedTenureReason::NoNeighbors)
+        );
     }
 }
 
@@ -2210,6 +7499,252 @@ fn test_nakamoto_download_run_2_peers() {
     boot_dns_thread_handle.join().unwrap();
 }
 
+/// Block sink which records each tenure it is given, in order
+struct CountingBlockSink {
+    pushes: Arc<Mutex<Vec<(ConsensusHash, Vec<NakamotoBlock>)>>>,
+}
+
+impl BlockSink for CountingBlockSink {
+    fn push_tenure_blocks(&mut self, tenure_id: &ConsensusHash, blocks: Vec<NakamotoBlock>) {
+        self.pushes
+            .lock()
+            .unwrap()
+            .push((tenure_id.clone(), blocks));
+    }
+}
+
+/// Verify that a block sink installed on the downloader receives each tenure's blocks at the end
+/// of the pass in which that tenure finishes downloading, and not all at once.
+#[test]
+fn test_nakamoto_download_run_2_peers_block_sink() {
+    let observer = TestEventObserver::new();
+    let bitvecs = vec![
+        // full reward cycle
+        vec![true, true, true, true, true, true, true, true, true, true],
+        // full reward cycle
+        vec![true, true, true, true, true, true, true, true, true, true],
+    ];
+
+    let rc_len = 10u64;
+    let peer = make_nakamoto_peer_from_invs(
+        function_name!(),
+        &observer,
+        rc_len as u32,
+        5,
+        bitvecs.clone(),
+    );
+    let (mut peer, _reward_cycle_invs) = peer_get_nakamoto_invs(peer, &[0, 1, 2, 3, 4, 5, 6]);
+
+    let tip = SortitionDB::get_canonical_burn_chain_tip(peer.sortdb().conn()).unwrap();
+
+    // make a neighbor from this peer
+    let boot_observer = TestEventObserver::new();
+    let privk = StacksPrivateKey::from_seed(&[0, 1, 2, 3, 4]);
+    let mut boot_peer = peer.neighbor_with_observer(privk, Some(&boot_observer));
+
+    // boot up the boot peer's burnchain
+    for height in 25..tip.block_height {
+        let ops = peer
+            .get_burnchain_block_ops_at_height(height + 1)
+            .unwrap_or_default();
+        let sn = {
+            let ih = peer.sortdb().index_handle(&tip.sortition_id);
+            let sn = ih.get_block_snapshot_by_height(height).unwrap().unwrap();
+            sn
+        };
+        let block_header = TestPeer::make_next_burnchain_block(
+            &boot_peer.config.chain_config.burnchain,
+            sn.block_height,
+            &sn.burn_header_hash,
+            ops.len() as u64,
+            false,
+        );
+        TestPeer::add_burnchain_block(
+            &boot_peer.config.chain_config.burnchain,
+            &block_header,
+            ops.clone(),
+        );
+    }
+
+    // send downloaded tenures to our sink
+    let pushes = Arc::new(Mutex::new(vec![]));
+    boot_peer.network.init_nakamoto_block_downloader();
+    boot_peer
+        .network
+        .block_downloader_nakamoto
+        .as_mut()
+        .unwrap()
+        .set_block_sink(Box::new(CountingBlockSink {
+            pushes: pushes.clone(),
+        }));
+
+    let (mut boot_dns_client, boot_dns_thread_handle) = dns_thread_start(100);
+
+    // run the boot peer until its sink has received a few tenures.  Record the pass in which
+    // each tenure was delivered.
+    let (term_sx, term_rx) = sync_channel(1);
+    let delivery_passes = thread::scope(|s| {
+        let boot_pushes = pushes.clone();
+        let handle = s.spawn(move || {
+            let mut delivery_passes = vec![];
+            let mut num_pushes = 0;
+            for pass in 0..10_000 {
+                boot_peer
+                    .run_with_ibd(true, Some(&mut boot_dns_client))
+                    .unwrap();
+
+                let cur_num_pushes = boot_pushes.lock().unwrap().len();
+                for _ in num_pushes..cur_num_pushes {
+                    delivery_passes.push(pass);
+                }
+                num_pushes = cur_num_pushes;
+                if num_pushes >= 3 {
+                    break;
+                }
+            }
+
+            term_sx.send(()).unwrap();
+            delivery_passes
+        });
+
+        loop {
+            if term_rx.try_recv().is_ok() {
+                break;
+            }
+            peer.step_with_ibd(false).unwrap();
+        }
+        handle.join().unwrap()
+    });
+
+    boot_dns_thread_handle.join().unwrap();
+
+    let pushes = pushes.lock().unwrap();
+    assert!(pushes.len() >= 3);
+
+    // each push is one tenure's blocks, which end with the tenure-end block (the next tenure's
+    // start block)
+    for (tenure_id, blocks) in pushes.iter() {
+        assert!(!blocks.is_empty());
+        for block in blocks[..blocks.len() - 1].iter() {
+            assert_eq!(&block.header.consensus_hash, tenure_id);
+        }
+    }
+
+    // tenures arrived across multiple passes, as each finished
+    assert_eq!(delivery_passes.len(), pushes.len());
+    assert!(delivery_passes.first().unwrap() < delivery_passes.last().unwrap());
+}
+
+/// In confirmed-only mode, a booting peer downloads every confirmed tenure without ever running
+/// unconfirmed tenure downloaders.
+#[test]
+fn test_nakamoto_download_run_2_peers_confirmed_only() {
+    let observer = TestEventObserver::new();
+    let bitvecs = vec![
+        // full reward cycle
+        vec![true, true, true, true, true, true, true, true, true, true],
+        // full reward cycle
+        vec![true, true, true, true, true, true, true, true, true, true],
+    ];
+
+    let rc_len = 10u64;
+    let peer = make_nakamoto_peer_from_invs(
+        function_name!(),
+        &observer,
+        rc_len as u32,
+        5,
+        bitvecs.clone(),
+    );
+    let (mut peer, _reward_cycle_invs) = peer_get_nakamoto_invs(peer, &[0, 1, 2, 3, 4, 5, 6]);
+
+    let tip = SortitionDB::get_canonical_burn_chain_tip(peer.sortdb().conn()).unwrap();
+
+    // make a neighbor from this peer
+    let boot_observer = TestEventObserver::new();
+    let privk = StacksPrivateKey::from_seed(&[0, 1, 2, 3, 4]);
+    let mut boot_peer = peer.neighbor_with_observer(privk, Some(&boot_observer));
+    boot_peer
+        .network
+        .connection_opts
+        .nakamoto_download_confirmed_only = true;
+
+    // boot up the boot peer's burnchain
+    for height in 25..tip.block_height {
+        let ops = peer
+            .get_burnchain_block_ops_at_height(height + 1)
+            .unwrap_or_default();
+        let sn = {
+            let ih = peer.sortdb().index_handle(&tip.sortition_id);
+            let sn = ih.get_block_snapshot_by_height(height).unwrap().unwrap();
+            sn
+        };
+        let block_header = TestPeer::make_next_burnchain_block(
+            &boot_peer.config.chain_config.burnchain,
+            sn.block_height,
+            &sn.burn_header_hash,
+            ops.len() as u64,
+            false,
+        );
+        TestPeer::add_burnchain_block(
+            &boot_peer.config.chain_config.burnchain,
+            &block_header,
+            ops.clone(),
+        );
+    }
+
+    let (mut boot_dns_client, boot_dns_thread_handle) = dns_thread_start(100);
+
+    // Run the boot peer until it has processed every wanted tenure except for the two highest
+    // ones, which only the unconfirmed tenure downloaders can fetch.  Check the downloader's
+    // state after every pass.
+    let (term_sx, term_rx) = sync_channel(1);
+    let (saw_unconfirmed, num_unprocessed) = thread::scope(|s| {
+        let handle = s.spawn(move || {
+            let mut saw_unconfirmed = false;
+            let mut num_unprocessed = usize::MAX;
+            for _ in 0..10_000 {
+                boot_peer
+                    .run_with_ibd(true, Some(&mut boot_dns_client))
+                    .unwrap();
+
+                let Some(downloader) = boot_peer.network.block_downloader_nakamoto.as_ref() else {
+                    continue;
+                };
+                if downloader.metrics().state == NakamotoDownloadState::Unconfirmed {
+                    saw_unconfirmed = true;
+                }
+
+                num_unprocessed = downloader
+                    .prev_wanted_tenures
+                    .iter()
+                    .flatten()
+                    .chain(downloader.wanted_tenures.iter())
+                    .filter(|wt| !wt.processed && wt.winning_block_id != StacksBlockId([0x00; 32]))
+                    .count();
+                if downloader.prev_wanted_tenures.is_some() && num_unprocessed <= 2 {
+                    break;
+                }
+            }
+
+            term_sx.send(()).unwrap();
+            (saw_unconfirmed, num_unprocessed)
+        });
+
+        loop {
+            if term_rx.try_recv().is_ok() {
+                break;
+            }
+            peer.step_with_ibd(false).unwrap();
+        }
+        handle.join().unwrap()
+    });
+
+    boot_dns_thread_handle.join().unwrap();
+
+    assert!(num_unprocessed <= 2);
+    assert!(!saw_unconfirmed);
+}
+
 #[test]
 fn test_nakamoto_unconfirmed_download_run_2_peers() {
     let observer = TestEventObserver::new();
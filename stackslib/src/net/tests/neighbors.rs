@@ -1669,3 +1669,89 @@ fn test_issue_concurrent_requests_in_different_state_machines() {
         t.join().unwrap();
     })
 }
+
+/// Verify that successive requests from a state machine to the same neighbor reuse one keep-alive
+/// HTTP connection instead of reconnecting each time.
+#[test]
+fn test_neighbor_rpc_reuses_http_connection() {
+    with_timeout(600, || {
+        let peer_config = TestPeerConfig::new(function_name!(), 0, 0);
+        let mut peer = TestPeer::new(peer_config);
+
+        let peer_addr = NeighborAddress::from_neighbor(&peer.to_neighbor());
+        let peer_host = peer.to_peer_host();
+
+        let peer_client_config = TestPeerConfig::new(function_name!(), 0, 0);
+        let mut peer_client = TestPeer::new(peer_client_config);
+
+        let run = Arc::new(AtomicBool::new(true));
+        let run_thread = run.clone();
+
+        let t = thread::spawn(move || {
+            while run_thread.load(Ordering::SeqCst) {
+                let _ = peer.step();
+            }
+        });
+
+        // handshake with remote peer
+        let mut comms = PeerNetworkComms::new();
+        let mut connected = false;
+        let now = get_epoch_time_secs();
+        while !connected && get_epoch_time_secs() < now + 60 {
+            if !comms.is_neighbor_connecting(&mut peer_client.network, &peer_addr)
+                || !comms.has_neighbor_session(&mut peer_client.network, &peer_addr)
+            {
+                let _ = comms
+                    .neighbor_session_begin(&mut peer_client.network, &peer_addr)
+                    .unwrap();
+            }
+            let _ = peer_client.step();
+            for (_, reply) in comms.collect_replies(&mut peer_client.network) {
+                match reply.payload {
+                    StacksMessageType::HandshakeAccept(..)
+                    | StacksMessageType::StackerDBHandshakeAccept(..) => {
+                        connected = true;
+                        break;
+                    }
+                    _ => {
+                        panic!("Did not get handshake accept, but got {:?}", &reply);
+                    }
+                }
+            }
+        }
+        assert!(connected, "Failed to connect -- timed out");
+
+        // carry out several RPCs with the neighbor, one after the other
+        let mut rpc_comms = NeighborRPC::new();
+        let num_requests = 3;
+        for i in 0..num_requests {
+            rpc_comms
+                .send_request(
+                    &mut peer_client.network,
+                    peer_addr.clone(),
+                    StacksHttpRequest::new_getinfo(peer_host.clone(), None),
+                )
+                .unwrap();
+
+            let mut rpc_comms_reply = None;
+            let now = get_epoch_time_secs();
+            while get_epoch_time_secs() < now + 60 && rpc_comms_reply.is_none() {
+                let _ = peer_client.step();
+                for (_, reply) in rpc_comms.collect_replies(&mut peer_client.network) {
+                    rpc_comms_reply = Some(reply);
+                }
+            }
+            let rpc_comms_reply =
+                rpc_comms_reply.unwrap_or_else(|| panic!("timed out waiting for RPC reply {i}"));
+            let _ = rpc_comms_reply.decode_peer_info().unwrap();
+            assert!(!rpc_comms.is_dead_or_broken(&peer_client.network, &peer_addr));
+        }
+
+        // only the first request opened a connection
+        assert_eq!(rpc_comms.num_connects(), 1);
+        assert_eq!(rpc_comms.num_reused_connections(), num_requests - 1);
+
+        run.store(false, Ordering::SeqCst);
+        t.join().unwrap();
+    })
+}